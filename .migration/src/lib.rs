@@ -12,6 +12,28 @@ mod m20250307_195832_add_category_to_session;
 mod m20250308_154930_user_question_answer;
 mod m20250309_072200_add_image_url_to_questions_and_options;
 mod m20250316_055046_add_discussion_image_url;
+mod m20250321_080000_add_two_factor_auth;
+mod m20250322_090000_create_user_identities;
+mod m20250323_100000_create_jobs;
+mod m20250324_110000_create_referrals;
+mod m20250325_120000_add_soft_delete;
+mod m20250326_130000_create_audit_logs;
+mod m20250327_140000_add_question_versions;
+mod m20250328_150000_create_question_bank;
+mod m20250329_160000_create_test_blueprints;
+mod m20250330_170000_add_answer_created_at;
+mod m20250331_180000_add_results_release;
+mod m20250401_190000_create_session_enrollments;
+mod m20250402_200000_create_groups;
+mod m20250403_210000_create_packages_orders;
+mod m20250404_220000_create_vouchers;
+mod m20250405_230000_create_notifications;
+mod m20250406_000000_create_attempt_integrity_events;
+mod m20250407_010000_add_full_text_search;
+mod m20250408_020000_add_unique_purge_job;
+mod m20250409_030000_add_answer_session;
+mod m20250410_040000_create_attempt_answers;
+mod m20250411_050000_add_answer_scores;
 
 pub struct Migrator;
 
@@ -33,6 +55,28 @@ impl MigratorTrait for Migrator {
 				m20250309_072200_add_image_url_to_questions_and_options::Migration,
 			),
 			Box::new(m20250316_055046_add_discussion_image_url::Migration),
+			Box::new(m20250321_080000_add_two_factor_auth::Migration),
+			Box::new(m20250322_090000_create_user_identities::Migration),
+			Box::new(m20250323_100000_create_jobs::Migration),
+			Box::new(m20250324_110000_create_referrals::Migration),
+			Box::new(m20250325_120000_add_soft_delete::Migration),
+			Box::new(m20250326_130000_create_audit_logs::Migration),
+			Box::new(m20250327_140000_add_question_versions::Migration),
+			Box::new(m20250328_150000_create_question_bank::Migration),
+			Box::new(m20250329_160000_create_test_blueprints::Migration),
+			Box::new(m20250330_170000_add_answer_created_at::Migration),
+			Box::new(m20250331_180000_add_results_release::Migration),
+			Box::new(m20250401_190000_create_session_enrollments::Migration),
+			Box::new(m20250402_200000_create_groups::Migration),
+			Box::new(m20250403_210000_create_packages_orders::Migration),
+			Box::new(m20250404_220000_create_vouchers::Migration),
+			Box::new(m20250405_230000_create_notifications::Migration),
+			Box::new(m20250406_000000_create_attempt_integrity_events::Migration),
+			Box::new(m20250407_010000_add_full_text_search::Migration),
+			Box::new(m20250408_020000_add_unique_purge_job::Migration),
+			Box::new(m20250409_030000_add_answer_session::Migration),
+			Box::new(m20250410_040000_create_attempt_answers::Migration),
+			Box::new(m20250411_050000_add_answer_scores::Migration),
 		]
 	}
 }
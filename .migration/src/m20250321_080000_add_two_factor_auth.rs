@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUsers::Table)
+					.add_column(ColumnDef::new(AppUsers::TotpSecret).string().null())
+					.add_column(
+						ColumnDef::new(AppUsers::TotpEnabledAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.add_column(
+						ColumnDef::new(AppUsers::TotpRecoveryCodes)
+							.json_binary()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppRoles::Table)
+					.add_column(
+						ColumnDef::new(AppRoles::RequireTwoFactor)
+							.boolean()
+							.not_null()
+							.default(false),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppRoles::Table)
+					.drop_column(AppRoles::RequireTwoFactor)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUsers::Table)
+					.drop_column(AppUsers::TotpSecret)
+					.drop_column(AppUsers::TotpEnabledAt)
+					.drop_column(AppUsers::TotpRecoveryCodes)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	TotpSecret,
+	TotpEnabledAt,
+	TotpRecoveryCodes,
+}
+
+#[derive(Iden)]
+enum AppRoles {
+	Table,
+	RequireTwoFactor,
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppUserIdentities::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppUserIdentities::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppUserIdentities::UserId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppUserIdentities::Provider)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppUserIdentities::Subject)
+							.string()
+							.not_null(),
+					)
+					.col(ColumnDef::new(AppUserIdentities::Email).string().not_null())
+					.col(
+						ColumnDef::new(AppUserIdentities::CreatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppUserIdentities::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_user_identities_user")
+							.from(AppUserIdentities::Table, AppUserIdentities::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_user_identities_provider_subject")
+					.table(AppUserIdentities::Table)
+					.col(AppUserIdentities::Provider)
+					.col(AppUserIdentities::Subject)
+					.unique()
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppUserIdentities::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+pub enum AppUserIdentities {
+	Table,
+	Id,
+	UserId,
+	Provider,
+	Subject,
+	Email,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum AppUsers {
+	Table,
+	Id,
+}
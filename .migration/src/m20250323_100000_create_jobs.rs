@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppJobs::Table)
+					.if_not_exists()
+					.col(ColumnDef::new(AppJobs::Id).uuid().not_null().primary_key())
+					.col(ColumnDef::new(AppJobs::Kind).string().not_null())
+					.col(ColumnDef::new(AppJobs::Payload).json_binary().not_null())
+					.col(
+						ColumnDef::new(AppJobs::Status)
+							.string()
+							.not_null()
+							.default("pending"),
+					)
+					.col(
+						ColumnDef::new(AppJobs::Attempts)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.col(
+						ColumnDef::new(AppJobs::MaxAttempts)
+							.integer()
+							.not_null()
+							.default(5),
+					)
+					.col(
+						ColumnDef::new(AppJobs::RunAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppJobs::LockedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(ColumnDef::new(AppJobs::LastError).text().null())
+					.col(
+						ColumnDef::new(AppJobs::CreatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppJobs::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_jobs_status_run_at")
+					.table(AppJobs::Table)
+					.col(AppJobs::Status)
+					.col(AppJobs::RunAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppJobs::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+pub enum AppJobs {
+	Table,
+	Id,
+	Kind,
+	Payload,
+	Status,
+	Attempts,
+	MaxAttempts,
+	RunAt,
+	LockedAt,
+	LastError,
+	CreatedAt,
+	UpdatedAt,
+}
@@ -0,0 +1,180 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		let db = manager.get_connection();
+
+		// Client-supplied codes were never unique; keep the oldest owner of
+		// each code and issue fresh codes to everyone else.
+		db.execute_unprepared(
+			r#"
+			UPDATE app_users SET referral_code = NULL
+			WHERE id IN (
+				SELECT id FROM (
+					SELECT id, row_number() OVER (
+						PARTITION BY upper(referral_code) ORDER BY created_at, id
+					) AS rn
+					FROM app_users
+					WHERE referral_code IS NOT NULL
+				) ranked
+				WHERE rn > 1
+			);
+			UPDATE app_users SET referral_code = upper(referral_code)
+			WHERE referral_code IS NOT NULL;
+			UPDATE app_users
+			SET referral_code = upper(substr(md5(random()::text || id::text), 1, 8))
+			WHERE referral_code IS NULL OR referral_code = '';
+			"#,
+		)
+		.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_users_referral_code")
+					.table(AppUsers::Table)
+					.col(AppUsers::ReferralCode)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppReferrals::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppReferrals::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppReferrals::ReferrerId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppReferrals::ReferredId)
+							.uuid()
+							.not_null()
+							.unique_key(),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::ReferralCode)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::Status)
+							.string()
+							.not_null()
+							.default("registered"),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::RewardPoints)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::ActivatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::ConvertedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::CreatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppReferrals::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_referrals_referrer")
+							.from(AppReferrals::Table, AppReferrals::ReferrerId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_referrals_referred")
+							.from(AppReferrals::Table, AppReferrals::ReferredId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		db.execute_unprepared(
+			r#"
+			INSERT INTO app_referrals (
+				id, referrer_id, referred_id, referral_code, status,
+				activated_at, created_at, updated_at
+			)
+			SELECT
+				gen_random_uuid(), referrer.id, referred.id, referrer.referral_code,
+				CASE WHEN referred.is_active THEN 'activated' ELSE 'registered' END,
+				CASE WHEN referred.is_active THEN referred.updated_at ELSE NULL END,
+				referred.created_at, now()
+			FROM app_users referred
+			JOIN app_users referrer
+				ON upper(referred.referred_by) = referrer.referral_code
+			WHERE referred.id <> referrer.id
+			ON CONFLICT DO NOTHING;
+			"#,
+		)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppReferrals::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_index(
+				Index::drop()
+					.name("idx_users_referral_code")
+					.table(AppUsers::Table)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+pub enum AppReferrals {
+	Table,
+	Id,
+	ReferrerId,
+	ReferredId,
+	ReferralCode,
+	Status,
+	RewardPoints,
+	ActivatedAt,
+	ConvertedAt,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum AppUsers {
+	Table,
+	Id,
+	ReferralCode,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		for table in SoftDeleteTable::all() {
+			manager
+				.alter_table(
+					Table::alter()
+						.table(table)
+						.add_column(
+							ColumnDef::new(DeletedAt::DeletedAt)
+								.timestamp_with_time_zone()
+								.null(),
+						)
+						.to_owned(),
+				)
+				.await?;
+
+			manager
+				.create_index(
+					Index::create()
+						.name(format!("idx_{}_deleted_at", table.to_string()))
+						.table(table)
+						.col(DeletedAt::DeletedAt)
+						.to_owned(),
+				)
+				.await?;
+		}
+
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				UPDATE app_users SET deleted_at = COALESCE(updated_at, now())
+				WHERE is_deleted = true;
+				"#,
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		for table in SoftDeleteTable::all() {
+			manager
+				.alter_table(
+					Table::alter()
+						.table(table)
+						.drop_column(DeletedAt::DeletedAt)
+						.to_owned(),
+				)
+				.await?;
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Iden, Clone, Copy)]
+enum SoftDeleteTable {
+	#[iden = "app_users"]
+	Users,
+	#[iden = "app_tests"]
+	Tests,
+	#[iden = "app_test_sessions"]
+	TestSessions,
+	#[iden = "app_roles"]
+	Roles,
+}
+
+impl SoftDeleteTable {
+	fn all() -> [SoftDeleteTable; 4] {
+		[
+			SoftDeleteTable::Users,
+			SoftDeleteTable::Tests,
+			SoftDeleteTable::TestSessions,
+			SoftDeleteTable::Roles,
+		]
+	}
+}
+
+#[derive(Iden)]
+enum DeletedAt {
+	DeletedAt,
+}
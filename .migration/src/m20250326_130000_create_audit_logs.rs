@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// Entries outlive the users and rows they describe, so nothing here
+		// references other tables.
+		manager
+			.create_table(
+				Table::create()
+					.table(AppAuditLogs::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppAuditLogs::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppAuditLogs::ActorId).uuid().null())
+					.col(ColumnDef::new(AppAuditLogs::ActorEmail).string().null())
+					.col(ColumnDef::new(AppAuditLogs::Action).string().not_null())
+					.col(
+						ColumnDef::new(AppAuditLogs::EntityType).string().not_null(),
+					)
+					.col(ColumnDef::new(AppAuditLogs::EntityId).string().null())
+					.col(ColumnDef::new(AppAuditLogs::Before).json_binary().null())
+					.col(ColumnDef::new(AppAuditLogs::After).json_binary().null())
+					.col(ColumnDef::new(AppAuditLogs::Changes).json_binary().null())
+					.col(ColumnDef::new(AppAuditLogs::IpAddress).string().null())
+					.col(
+						ColumnDef::new(AppAuditLogs::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_audit_logs_entity")
+					.table(AppAuditLogs::Table)
+					.col(AppAuditLogs::EntityType)
+					.col(AppAuditLogs::EntityId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_audit_logs_actor_id")
+					.table(AppAuditLogs::Table)
+					.col(AppAuditLogs::ActorId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_audit_logs_created_at")
+					.table(AppAuditLogs::Table)
+					.col(AppAuditLogs::CreatedAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppAuditLogs::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+pub enum AppAuditLogs {
+	Table,
+	Id,
+	ActorId,
+	ActorEmail,
+	Action,
+	EntityType,
+	EntityId,
+	Before,
+	After,
+	Changes,
+	IpAddress,
+	CreatedAt,
+}
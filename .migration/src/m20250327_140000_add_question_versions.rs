@@ -0,0 +1,147 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Questions become immutable once answered: editing one archives the row and
+/// inserts a new version, so answers keep pointing at what the student saw.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.add_column(
+						ColumnDef::new(AppQuestions::Version)
+							.integer()
+							.not_null()
+							.default(1),
+					)
+					.add_column(
+						ColumnDef::new(AppQuestions::PreviousVersionId)
+							.uuid()
+							.null(),
+					)
+					.add_column(
+						ColumnDef::new(AppQuestions::ArchivedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_questions_test_id_archived_at")
+					.table(AppQuestions::Table)
+					.col(AppQuestions::TestId)
+					.col(AppQuestions::ArchivedAt)
+					.to_owned(),
+			)
+			.await?;
+
+		// Answers must never disappear because a question or option was removed.
+		// NO ACTION (rather than RESTRICT) still lets a whole test be purged,
+		// since the answers are removed by the same statement.
+		replace_answer_foreign_keys(manager, ForeignKeyAction::NoAction).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		replace_answer_foreign_keys(manager, ForeignKeyAction::Cascade).await?;
+
+		manager
+			.drop_index(
+				Index::drop()
+					.name("idx_app_questions_test_id_archived_at")
+					.table(AppQuestions::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.drop_column(AppQuestions::Version)
+					.drop_column(AppQuestions::PreviousVersionId)
+					.drop_column(AppQuestions::ArchivedAt)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+async fn replace_answer_foreign_keys(
+	manager: &SchemaManager<'_>,
+	on_delete: ForeignKeyAction,
+) -> Result<(), DbErr> {
+	for name in [
+		"fk-app_user_question_answers-question_id",
+		"fk-app_user_question_answers-option_id",
+	] {
+		manager
+			.drop_foreign_key(
+				ForeignKey::drop()
+					.name(name)
+					.table(AppUserQuestionAnswers::Table)
+					.to_owned(),
+			)
+			.await?;
+	}
+
+	manager
+		.create_foreign_key(
+			ForeignKey::create()
+				.name("fk-app_user_question_answers-question_id")
+				.from(
+					AppUserQuestionAnswers::Table,
+					AppUserQuestionAnswers::QuestionId,
+				)
+				.to(AppQuestions::Table, AppQuestions::Id)
+				.on_delete(on_delete)
+				.on_update(ForeignKeyAction::Cascade)
+				.to_owned(),
+		)
+		.await?;
+
+	manager
+		.create_foreign_key(
+			ForeignKey::create()
+				.name("fk-app_user_question_answers-option_id")
+				.from(
+					AppUserQuestionAnswers::Table,
+					AppUserQuestionAnswers::OptionId,
+				)
+				.to(AppOptions::Table, AppOptions::Id)
+				.on_delete(on_delete)
+				.on_update(ForeignKeyAction::Cascade)
+				.to_owned(),
+		)
+		.await
+}
+
+#[derive(Iden)]
+enum AppQuestions {
+	Table,
+	Id,
+	TestId,
+	Version,
+	PreviousVersionId,
+	ArchivedAt,
+}
+
+#[derive(Iden)]
+enum AppOptions {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUserQuestionAnswers {
+	Table,
+	QuestionId,
+	OptionId,
+}
@@ -0,0 +1,288 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Turns questions into a reusable bank. Tests link to bank questions through
+/// `app_tests_has_questions` instead of owning them via `app_questions.test_id`.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.add_column(ColumnDef::new(AppQuestions::Topic).string().null())
+					.add_column(
+						ColumnDef::new(AppQuestions::Subtopic).string().null(),
+					)
+					.add_column(
+						ColumnDef::new(AppQuestions::Difficulty).string().null(),
+					)
+					.add_column(ColumnDef::new(AppQuestions::AuthorId).uuid().null())
+					.add_column(
+						ColumnDef::new(AppQuestions::CreatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.add_column(
+						ColumnDef::new(AppQuestions::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.add_foreign_key(
+						TableForeignKey::new()
+							.name("fk_app_questions_author")
+							.from_tbl(AppQuestions::Table)
+							.from_col(AppQuestions::AuthorId)
+							.to_tbl(AppUsers::Table)
+							.to_col(AppUsers::Id)
+							.on_delete(ForeignKeyAction::SetNull)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		for (name, column) in [
+			("idx_app_questions_topic", AppQuestions::Topic),
+			("idx_app_questions_difficulty", AppQuestions::Difficulty),
+			("idx_app_questions_archived_at", AppQuestions::ArchivedAt),
+		] {
+			manager
+				.create_index(
+					Index::create()
+						.name(name)
+						.table(AppQuestions::Table)
+						.col(column)
+						.to_owned(),
+				)
+				.await?;
+		}
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppTestsHasQuestions::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppTestsHasQuestions::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppTestsHasQuestions::TestId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppTestsHasQuestions::QuestionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppTestsHasQuestions::Position)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_tests_has_questions_test")
+							.from(
+								AppTestsHasQuestions::Table,
+								AppTestsHasQuestions::TestId,
+							)
+							.to(AppTests::Table, AppTests::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_tests_has_questions_question")
+							.from(
+								AppTestsHasQuestions::Table,
+								AppTestsHasQuestions::QuestionId,
+							)
+							.to(AppQuestions::Table, AppQuestions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_tests_has_questions_unique")
+					.table(AppTestsHasQuestions::Table)
+					.col(AppTestsHasQuestions::TestId)
+					.col(AppTestsHasQuestions::QuestionId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_tests_has_questions_question_id")
+					.table(AppTestsHasQuestions::Table)
+					.col(AppTestsHasQuestions::QuestionId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				INSERT INTO app_tests_has_questions (id, test_id, question_id, position)
+				SELECT gen_random_uuid(), test_id, id,
+					(ROW_NUMBER() OVER (PARTITION BY test_id ORDER BY id) - 1)::int
+				FROM app_questions;
+				"#,
+			)
+			.await?;
+
+		// Dropping the column also drops its foreign key and indexes.
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.drop_column(AppQuestions::TestId)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.add_column(ColumnDef::new(AppQuestions::TestId).uuid().null())
+					.to_owned(),
+			)
+			.await?;
+
+		// A bank question used by several tests goes back to one of them, and
+		// questions used by none cannot be represented in the old layout.
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				UPDATE app_questions q SET test_id = (
+					SELECT l.test_id FROM app_tests_has_questions l
+					WHERE l.question_id = q.id
+					ORDER BY l.test_id LIMIT 1
+				);
+				DELETE FROM app_questions WHERE test_id IS NULL;
+				"#,
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.modify_column(
+						ColumnDef::new(AppQuestions::TestId).uuid().not_null(),
+					)
+					.add_foreign_key(
+						TableForeignKey::new()
+							.name("fk_app_questions_test")
+							.from_tbl(AppQuestions::Table)
+							.from_col(AppQuestions::TestId)
+							.to_tbl(AppTests::Table)
+							.to_col(AppTests::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_questions_test_id_archived_at")
+					.table(AppQuestions::Table)
+					.col(AppQuestions::TestId)
+					.col(AppQuestions::ArchivedAt)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppTestsHasQuestions::Table).to_owned())
+			.await?;
+
+		for name in [
+			"idx_app_questions_topic",
+			"idx_app_questions_difficulty",
+			"idx_app_questions_archived_at",
+		] {
+			manager
+				.drop_index(
+					Index::drop()
+						.name(name)
+						.table(AppQuestions::Table)
+						.to_owned(),
+				)
+				.await?;
+		}
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppQuestions::Table)
+					.drop_foreign_key(Alias::new("fk_app_questions_author"))
+					.drop_column(AppQuestions::Topic)
+					.drop_column(AppQuestions::Subtopic)
+					.drop_column(AppQuestions::Difficulty)
+					.drop_column(AppQuestions::AuthorId)
+					.drop_column(AppQuestions::CreatedAt)
+					.drop_column(AppQuestions::UpdatedAt)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppQuestions {
+	Table,
+	Id,
+	TestId,
+	Topic,
+	Subtopic,
+	Difficulty,
+	AuthorId,
+	ArchivedAt,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestsHasQuestions {
+	Table,
+	Id,
+	TestId,
+	QuestionId,
+	Position,
+}
+
+#[derive(Iden)]
+enum AppTests {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
@@ -0,0 +1,299 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Blueprint rules let a test draw its questions from the bank per attempt.
+/// Every attempt records the questions it received so answers are graded and
+/// reviewed against exactly that set.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppTestBlueprints::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppTestBlueprints::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppTestBlueprints::TestId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppTestBlueprints::Topic).string().not_null(),
+					)
+					.col(ColumnDef::new(AppTestBlueprints::Subtopic).string().null())
+					.col(
+						ColumnDef::new(AppTestBlueprints::Difficulty)
+							.string()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppTestBlueprints::QuestionCount)
+							.integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppTestBlueprints::Position)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_test_blueprints_test")
+							.from(
+								AppTestBlueprints::Table,
+								AppTestBlueprints::TestId,
+							)
+							.to(AppTests::Table, AppTests::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_test_blueprints_test_id")
+					.table(AppTestBlueprints::Table)
+					.col(AppTestBlueprints::TestId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppTestAttempts::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppTestAttempts::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppTestAttempts::TestId).uuid().not_null())
+					.col(ColumnDef::new(AppTestAttempts::UserId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppTestAttempts::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_test_attempts_test")
+							.from(AppTestAttempts::Table, AppTestAttempts::TestId)
+							.to(AppTests::Table, AppTests::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_test_attempts_user")
+							.from(AppTestAttempts::Table, AppTestAttempts::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_test_attempts_test_id_user_id")
+					.table(AppTestAttempts::Table)
+					.col(AppTestAttempts::TestId)
+					.col(AppTestAttempts::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		// Drawn questions are protected like answered ones: a question that
+		// was handed out can only be archived, never deleted.
+		manager
+			.create_table(
+				Table::create()
+					.table(AppTestAttemptQuestions::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppTestAttemptQuestions::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppTestAttemptQuestions::AttemptId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppTestAttemptQuestions::QuestionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppTestAttemptQuestions::Position)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_test_attempt_questions_attempt")
+							.from(
+								AppTestAttemptQuestions::Table,
+								AppTestAttemptQuestions::AttemptId,
+							)
+							.to(AppTestAttempts::Table, AppTestAttempts::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_test_attempt_questions_question")
+							.from(
+								AppTestAttemptQuestions::Table,
+								AppTestAttemptQuestions::QuestionId,
+							)
+							.to(AppQuestions::Table, AppQuestions::Id)
+							.on_delete(ForeignKeyAction::NoAction)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_test_attempt_questions_unique")
+					.table(AppTestAttemptQuestions::Table)
+					.col(AppTestAttemptQuestions::AttemptId)
+					.col(AppTestAttemptQuestions::QuestionId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.add_column(
+						ColumnDef::new(AppUserAnswers::AttemptId).uuid().null(),
+					)
+					.add_foreign_key(
+						TableForeignKey::new()
+							.name("fk_app_user_answers_attempt")
+							.from_tbl(AppUserAnswers::Table)
+							.from_col(AppUserAnswers::AttemptId)
+							.to_tbl(AppTestAttempts::Table)
+							.to_col(AppTestAttempts::Id)
+							.on_delete(ForeignKeyAction::SetNull)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_user_answers_attempt_id")
+					.table(AppUserAnswers::Table)
+					.col(AppUserAnswers::AttemptId)
+					.unique()
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.drop_column(AppUserAnswers::AttemptId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(
+				Table::drop()
+					.table(AppTestAttemptQuestions::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppTestAttempts::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppTestBlueprints::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppTestBlueprints {
+	Table,
+	Id,
+	TestId,
+	Topic,
+	Subtopic,
+	Difficulty,
+	QuestionCount,
+	Position,
+}
+
+#[derive(Iden)]
+enum AppTestAttempts {
+	Table,
+	Id,
+	TestId,
+	UserId,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestAttemptQuestions {
+	Table,
+	Id,
+	AttemptId,
+	QuestionId,
+	Position,
+}
+
+#[derive(Iden)]
+enum AppUserAnswers {
+	Table,
+	AttemptId,
+}
+
+#[derive(Iden)]
+enum AppQuestions {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppTests {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
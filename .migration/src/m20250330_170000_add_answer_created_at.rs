@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Answers get a submission time so progress can be shown over time. Older
+/// answers only know it when they came from an attempt.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.add_column(
+						ColumnDef::new(AppUserAnswers::CreatedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				UPDATE app_user_answers a SET created_at = t.created_at
+				FROM app_test_attempts t
+				WHERE t.id = a.attempt_id;
+				ALTER TABLE app_user_answers
+					ALTER COLUMN created_at SET DEFAULT CURRENT_TIMESTAMP;
+				"#,
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_user_answers_user_id_created_at")
+					.table(AppUserAnswers::Table)
+					.col(AppUserAnswers::UserId)
+					.col(AppUserAnswers::CreatedAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.drop_column(AppUserAnswers::CreatedAt)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppUserAnswers {
+	Table,
+	UserId,
+	CreatedAt,
+}
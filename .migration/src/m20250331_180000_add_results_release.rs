@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Sessions decide when students may see correct answers and discussions:
+/// right after submitting, once the test's end date has passed, or only after
+/// an admin publishes the results.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestSessions::Table)
+					.add_column(
+						ColumnDef::new(AppTestSessions::ResultsRelease)
+							.string()
+							.not_null()
+							.default("immediately"),
+					)
+					.add_column(
+						ColumnDef::new(AppTestSessions::ResultsPublishedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestSessions::Table)
+					.drop_column(AppTestSessions::ResultsRelease)
+					.drop_column(AppTestSessions::ResultsPublishedAt)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppTestSessions {
+	Table,
+	ResultsRelease,
+	ResultsPublishedAt,
+}
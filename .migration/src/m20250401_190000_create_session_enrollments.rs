@@ -0,0 +1,137 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Sessions decide who may take their tests: everyone, students of the
+/// session's student type, or an explicit roster of users.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestSessions::Table)
+					.add_column(
+						ColumnDef::new(AppTestSessions::Enrollment)
+							.string()
+							.not_null()
+							.default("open"),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppSessionEnrollments::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppSessionEnrollments::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppSessionEnrollments::SessionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppSessionEnrollments::UserId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppSessionEnrollments::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_session_enrollments_session")
+							.from(
+								AppSessionEnrollments::Table,
+								AppSessionEnrollments::SessionId,
+							)
+							.to(AppTestSessions::Table, AppTestSessions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_session_enrollments_user")
+							.from(
+								AppSessionEnrollments::Table,
+								AppSessionEnrollments::UserId,
+							)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_session_enrollments_unique")
+					.table(AppSessionEnrollments::Table)
+					.col(AppSessionEnrollments::SessionId)
+					.col(AppSessionEnrollments::UserId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_session_enrollments_user_id")
+					.table(AppSessionEnrollments::Table)
+					.col(AppSessionEnrollments::UserId)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppSessionEnrollments::Table).to_owned())
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestSessions::Table)
+					.drop_column(AppTestSessions::Enrollment)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppSessionEnrollments {
+	Table,
+	Id,
+	SessionId,
+	UserId,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestSessions {
+	Table,
+	Id,
+	Enrollment,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
@@ -0,0 +1,214 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Groups are classes of students with their instructors. Sessions can be
+/// assigned to groups so their members are enrolled, and instructors only see
+/// results of the groups they teach.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppGroups::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppGroups::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppGroups::Name).string().not_null())
+					.col(ColumnDef::new(AppGroups::Description).string().null())
+					.col(
+						ColumnDef::new(AppGroups::CreatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.col(
+						ColumnDef::new(AppGroups::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppGroupMembers::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppGroupMembers::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppGroupMembers::GroupId).uuid().not_null())
+					.col(ColumnDef::new(AppGroupMembers::UserId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppGroupMembers::Role)
+							.string()
+							.not_null()
+							.default("student"),
+					)
+					.col(
+						ColumnDef::new(AppGroupMembers::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_group_members_group")
+							.from(AppGroupMembers::Table, AppGroupMembers::GroupId)
+							.to(AppGroups::Table, AppGroups::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_group_members_user")
+							.from(AppGroupMembers::Table, AppGroupMembers::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_group_members_unique")
+					.table(AppGroupMembers::Table)
+					.col(AppGroupMembers::GroupId)
+					.col(AppGroupMembers::UserId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_group_members_user_id")
+					.table(AppGroupMembers::Table)
+					.col(AppGroupMembers::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppSessionGroups::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppSessionGroups::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppSessionGroups::SessionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(ColumnDef::new(AppSessionGroups::GroupId).uuid().not_null())
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_session_groups_session")
+							.from(
+								AppSessionGroups::Table,
+								AppSessionGroups::SessionId,
+							)
+							.to(AppTestSessions::Table, AppTestSessions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_session_groups_group")
+							.from(AppSessionGroups::Table, AppSessionGroups::GroupId)
+							.to(AppGroups::Table, AppGroups::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_session_groups_unique")
+					.table(AppSessionGroups::Table)
+					.col(AppSessionGroups::SessionId)
+					.col(AppSessionGroups::GroupId)
+					.unique()
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppSessionGroups::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppGroupMembers::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppGroups::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppGroups {
+	Table,
+	Id,
+	Name,
+	Description,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppGroupMembers {
+	Table,
+	Id,
+	GroupId,
+	UserId,
+	Role,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppSessionGroups {
+	Table,
+	Id,
+	SessionId,
+	GroupId,
+}
+
+#[derive(Iden)]
+enum AppTestSessions {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
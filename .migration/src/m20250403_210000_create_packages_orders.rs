@@ -0,0 +1,325 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Packages bundle sessions for sale. An order is a student's purchase of one
+/// package, settled through the payment gateway; once paid it grants an
+/// entitlement to every session in the package.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppPackages::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppPackages::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppPackages::Name).string().not_null())
+					.col(ColumnDef::new(AppPackages::Description).string().null())
+					.col(ColumnDef::new(AppPackages::Price).big_integer().not_null())
+					.col(
+						ColumnDef::new(AppPackages::IsActive)
+							.boolean()
+							.not_null()
+							.default(true),
+					)
+					.col(
+						ColumnDef::new(AppPackages::CreatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.col(
+						ColumnDef::new(AppPackages::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppPackageSessions::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppPackageSessions::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppPackageSessions::PackageId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppPackageSessions::SessionId)
+							.uuid()
+							.not_null(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_package_sessions_package")
+							.from(
+								AppPackageSessions::Table,
+								AppPackageSessions::PackageId,
+							)
+							.to(AppPackages::Table, AppPackages::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_package_sessions_session")
+							.from(
+								AppPackageSessions::Table,
+								AppPackageSessions::SessionId,
+							)
+							.to(AppTestSessions::Table, AppTestSessions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_package_sessions_unique")
+					.table(AppPackageSessions::Table)
+					.col(AppPackageSessions::PackageId)
+					.col(AppPackageSessions::SessionId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		// Orders keep the amount they were charged, so later price changes
+		// don't rewrite history. A package with orders can't be deleted.
+		manager
+			.create_table(
+				Table::create()
+					.table(AppOrders::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppOrders::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppOrders::UserId).uuid().not_null())
+					.col(ColumnDef::new(AppOrders::PackageId).uuid().not_null())
+					.col(ColumnDef::new(AppOrders::Amount).big_integer().not_null())
+					.col(
+						ColumnDef::new(AppOrders::Status)
+							.string()
+							.not_null()
+							.default("pending"),
+					)
+					.col(ColumnDef::new(AppOrders::Provider).string().not_null())
+					.col(
+						ColumnDef::new(AppOrders::ProviderReference).string().null(),
+					)
+					.col(ColumnDef::new(AppOrders::PaymentUrl).string().null())
+					.col(
+						ColumnDef::new(AppOrders::PaidAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppOrders::ExpiresAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppOrders::CreatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.col(
+						ColumnDef::new(AppOrders::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_orders_user")
+							.from(AppOrders::Table, AppOrders::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_orders_package")
+							.from(AppOrders::Table, AppOrders::PackageId)
+							.to(AppPackages::Table, AppPackages::Id)
+							.on_delete(ForeignKeyAction::Restrict)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_orders_user_id")
+					.table(AppOrders::Table)
+					.col(AppOrders::UserId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(AppEntitlements::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppEntitlements::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppEntitlements::UserId).uuid().not_null())
+					.col(
+						ColumnDef::new(AppEntitlements::SessionId).uuid().not_null(),
+					)
+					.col(ColumnDef::new(AppEntitlements::OrderId).uuid().null())
+					.col(
+						ColumnDef::new(AppEntitlements::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_entitlements_user")
+							.from(AppEntitlements::Table, AppEntitlements::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_entitlements_session")
+							.from(AppEntitlements::Table, AppEntitlements::SessionId)
+							.to(AppTestSessions::Table, AppTestSessions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_entitlements_order")
+							.from(AppEntitlements::Table, AppEntitlements::OrderId)
+							.to(AppOrders::Table, AppOrders::Id)
+							.on_delete(ForeignKeyAction::SetNull)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_entitlements_unique")
+					.table(AppEntitlements::Table)
+					.col(AppEntitlements::UserId)
+					.col(AppEntitlements::SessionId)
+					.unique()
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppEntitlements::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppOrders::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppPackageSessions::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppPackages::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppPackages {
+	Table,
+	Id,
+	Name,
+	Description,
+	Price,
+	IsActive,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppPackageSessions {
+	Table,
+	Id,
+	PackageId,
+	SessionId,
+}
+
+#[derive(Iden)]
+enum AppOrders {
+	Table,
+	Id,
+	UserId,
+	PackageId,
+	Amount,
+	Status,
+	Provider,
+	ProviderReference,
+	PaymentUrl,
+	PaidAt,
+	ExpiresAt,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppEntitlements {
+	Table,
+	Id,
+	UserId,
+	SessionId,
+	OrderId,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestSessions {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
@@ -0,0 +1,332 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Voucher codes discount a package's price when an order is placed. Each
+/// order that used a code gets a redemption row, written in the same
+/// transaction as the order, which the usage limits are counted from.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppVouchers::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppVouchers::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::Code)
+							.string()
+							.not_null()
+							.unique_key(),
+					)
+					.col(ColumnDef::new(AppVouchers::Description).string().null())
+					.col(
+						ColumnDef::new(AppVouchers::DiscountType)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::DiscountValue)
+							.big_integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::MaxDiscount)
+							.big_integer()
+							.null(),
+					)
+					.col(ColumnDef::new(AppVouchers::MaxUses).integer().null())
+					.col(
+						ColumnDef::new(AppVouchers::MaxUsesPerUser).integer().null(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::StartsAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::EndsAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::IsActive)
+							.boolean()
+							.not_null()
+							.default(true),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::CreatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.col(
+						ColumnDef::new(AppVouchers::UpdatedAt)
+							.timestamp_with_time_zone()
+							.null()
+							.default(Expr::current_timestamp()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		// A voucher without packages applies to every package.
+		manager
+			.create_table(
+				Table::create()
+					.table(AppVoucherPackages::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppVoucherPackages::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherPackages::VoucherId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherPackages::PackageId)
+							.uuid()
+							.not_null(),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_voucher_packages_voucher")
+							.from(
+								AppVoucherPackages::Table,
+								AppVoucherPackages::VoucherId,
+							)
+							.to(AppVouchers::Table, AppVouchers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_voucher_packages_package")
+							.from(
+								AppVoucherPackages::Table,
+								AppVoucherPackages::PackageId,
+							)
+							.to(AppPackages::Table, AppPackages::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_voucher_packages_unique")
+					.table(AppVoucherPackages::Table)
+					.col(AppVoucherPackages::VoucherId)
+					.col(AppVoucherPackages::PackageId)
+					.unique()
+					.to_owned(),
+			)
+			.await?;
+
+		// Referral codes used as discount codes are redeemed without a
+		// voucher row.
+		manager
+			.create_table(
+				Table::create()
+					.table(AppVoucherRedemptions::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::VoucherId)
+							.uuid()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::OrderId)
+							.uuid()
+							.not_null()
+							.unique_key(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::UserId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::Code)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::Discount)
+							.big_integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppVoucherRedemptions::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_voucher_redemptions_voucher")
+							.from(
+								AppVoucherRedemptions::Table,
+								AppVoucherRedemptions::VoucherId,
+							)
+							.to(AppVouchers::Table, AppVouchers::Id)
+							.on_delete(ForeignKeyAction::Restrict)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_voucher_redemptions_order")
+							.from(
+								AppVoucherRedemptions::Table,
+								AppVoucherRedemptions::OrderId,
+							)
+							.to(AppOrders::Table, AppOrders::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_voucher_redemptions_user")
+							.from(
+								AppVoucherRedemptions::Table,
+								AppVoucherRedemptions::UserId,
+							)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_voucher_redemptions_voucher_id")
+					.table(AppVoucherRedemptions::Table)
+					.col(AppVoucherRedemptions::VoucherId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppOrders::Table)
+					.add_column(
+						ColumnDef::new(AppOrders::DiscountAmount)
+							.big_integer()
+							.not_null()
+							.default(0),
+					)
+					.add_column(
+						ColumnDef::new(AppOrders::VoucherCode).string().null(),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppOrders::Table)
+					.drop_column(AppOrders::DiscountAmount)
+					.drop_column(AppOrders::VoucherCode)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppVoucherRedemptions::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppVoucherPackages::Table).to_owned())
+			.await?;
+
+		manager
+			.drop_table(Table::drop().table(AppVouchers::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppVouchers {
+	Table,
+	Id,
+	Code,
+	Description,
+	DiscountType,
+	DiscountValue,
+	MaxDiscount,
+	MaxUses,
+	MaxUsesPerUser,
+	StartsAt,
+	EndsAt,
+	IsActive,
+	CreatedAt,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppVoucherPackages {
+	Table,
+	Id,
+	VoucherId,
+	PackageId,
+}
+
+#[derive(Iden)]
+enum AppVoucherRedemptions {
+	Table,
+	Id,
+	VoucherId,
+	OrderId,
+	UserId,
+	Code,
+	Discount,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppOrders {
+	Table,
+	Id,
+	DiscountAmount,
+	VoucherCode,
+}
+
+#[derive(Iden)]
+enum AppPackages {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// In-app notifications, one row per recipient. `kind` tells clients how to
+/// render it and `data` carries the ids it links to.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppNotifications::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppNotifications::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(ColumnDef::new(AppNotifications::UserId).uuid().not_null())
+					.col(ColumnDef::new(AppNotifications::Kind).string().not_null())
+					.col(ColumnDef::new(AppNotifications::Title).string().not_null())
+					.col(ColumnDef::new(AppNotifications::Body).text().not_null())
+					.col(ColumnDef::new(AppNotifications::Data).json_binary().null())
+					.col(
+						ColumnDef::new(AppNotifications::ReadAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppNotifications::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_notifications_user")
+							.from(AppNotifications::Table, AppNotifications::UserId)
+							.to(AppUsers::Table, AppUsers::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_notifications_user_created_at")
+					.table(AppNotifications::Table)
+					.col(AppNotifications::UserId)
+					.col(AppNotifications::CreatedAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppNotifications::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppNotifications {
+	Table,
+	Id,
+	UserId,
+	Kind,
+	Title,
+	Body,
+	Data,
+	ReadAt,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppUsers {
+	Table,
+	Id,
+}
@@ -0,0 +1,157 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Client reported integrity events (tab switches, leaving fullscreen,
+/// pasting) per attempt. A test may set `max_violations`, after which the
+/// attempt is submitted automatically and `auto_submitted_at` is stamped.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppAttemptIntegrityEvents::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::AttemptId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::Kind)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::Weight)
+							.integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::Detail)
+							.json_binary()
+							.null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::OccurredAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptIntegrityEvents::CreatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_attempt_integrity_events_attempt")
+							.from(
+								AppAttemptIntegrityEvents::Table,
+								AppAttemptIntegrityEvents::AttemptId,
+							)
+							.to(AppTestAttempts::Table, AppTestAttempts::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_attempt_integrity_events_attempt_id")
+					.table(AppAttemptIntegrityEvents::Table)
+					.col(AppAttemptIntegrityEvents::AttemptId)
+					.col(AppAttemptIntegrityEvents::OccurredAt)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTests::Table)
+					.add_column(
+						ColumnDef::new(AppTests::MaxViolations).integer().null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestAttempts::Table)
+					.add_column(
+						ColumnDef::new(AppTestAttempts::AutoSubmittedAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestAttempts::Table)
+					.drop_column(AppTestAttempts::AutoSubmittedAt)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTests::Table)
+					.drop_column(AppTests::MaxViolations)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.drop_table(
+				Table::drop()
+					.table(AppAttemptIntegrityEvents::Table)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppAttemptIntegrityEvents {
+	Table,
+	Id,
+	AttemptId,
+	Kind,
+	Weight,
+	Detail,
+	OccurredAt,
+	CreatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestAttempts {
+	Table,
+	Id,
+	AutoSubmittedAt,
+}
+
+#[derive(Iden)]
+enum AppTests {
+	Table,
+	MaxViolations,
+}
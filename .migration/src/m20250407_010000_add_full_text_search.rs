@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::*;
+use std::env;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const DEFAULT_SEARCH_LANGUAGE: &str = "indonesian";
+
+/// Stored `search_vector` columns with GIN indexes for tests, questions and
+/// users. They are built with the `app_search` text search configuration,
+/// copied from `SEARCH_LANGUAGE` (any Postgres configuration such as
+/// `indonesian`, `english` or `simple`) when this migration runs, and
+/// queries use the same configuration so stemming always agrees. An existing
+/// `app_search` is kept, so a later language change needs it dropped first.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		let language = env::var("SEARCH_LANGUAGE")
+			.ok()
+			.filter(|language| !language.is_empty())
+			.unwrap_or(DEFAULT_SEARCH_LANGUAGE.to_string());
+		if !language.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+			return Err(DbErr::Migration(format!(
+				"Invalid SEARCH_LANGUAGE: {}",
+				language
+			)));
+		}
+
+		let db = manager.get_connection();
+
+		db.execute_unprepared(&format!(
+			r#"
+			DO $$
+			BEGIN
+				IF NOT EXISTS (
+					SELECT 1 FROM pg_ts_config WHERE cfgname = 'app_search'
+				) THEN
+					CREATE TEXT SEARCH CONFIGURATION app_search (COPY = {});
+				END IF;
+			END $$;
+			"#,
+			language
+		))
+		.await?;
+
+		// Emails are indexed whole and split on punctuation, so both
+		// `budi@mail.com` and `budi` find the account.
+		db.execute_unprepared(
+			r#"
+			ALTER TABLE app_tests ADD COLUMN IF NOT EXISTS search_vector tsvector
+				GENERATED ALWAYS AS (
+					setweight(to_tsvector('app_search', coalesce(test_name, '')), 'A')
+				) STORED;
+			CREATE INDEX IF NOT EXISTS idx_app_tests_search_vector
+				ON app_tests USING GIN (search_vector);
+
+			ALTER TABLE app_questions ADD COLUMN IF NOT EXISTS search_vector tsvector
+				GENERATED ALWAYS AS (
+					setweight(to_tsvector('app_search', coalesce(question, '')), 'A')
+					|| setweight(to_tsvector('app_search', coalesce(discussion, '')), 'B')
+				) STORED;
+			CREATE INDEX IF NOT EXISTS idx_app_questions_search_vector
+				ON app_questions USING GIN (search_vector);
+
+			ALTER TABLE app_users ADD COLUMN IF NOT EXISTS search_vector tsvector
+				GENERATED ALWAYS AS (
+					setweight(to_tsvector('app_search', coalesce(fullname, '')), 'A')
+					|| setweight(to_tsvector(
+						'app_search',
+						coalesce(email, '') || ' '
+							|| translate(coalesce(email, ''), '@._-+', '     ')
+					), 'B')
+					|| setweight(to_tsvector('app_search', coalesce(phone_number, '')), 'C')
+				) STORED;
+			CREATE INDEX IF NOT EXISTS idx_app_users_search_vector
+				ON app_users USING GIN (search_vector);
+			"#,
+		)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				ALTER TABLE app_users DROP COLUMN IF EXISTS search_vector;
+				ALTER TABLE app_questions DROP COLUMN IF EXISTS search_vector;
+				ALTER TABLE app_tests DROP COLUMN IF EXISTS search_vector;
+				DROP TEXT SEARCH CONFIGURATION IF EXISTS app_search;
+				"#,
+			)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Every instance schedules the soft-delete purge, so at most one purge job
+/// may be pending or running at a time. Duplicates queued before this
+/// migration are dropped first.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				DELETE FROM app_jobs j
+				WHERE j.kind = 'purge_soft_deleted'
+					AND j.status IN ('pending', 'running')
+					AND EXISTS (
+						SELECT 1 FROM app_jobs o
+						WHERE o.kind = j.kind
+							AND o.status IN ('pending', 'running')
+							AND (o.run_at, o.id) < (j.run_at, j.id)
+					);
+				CREATE UNIQUE INDEX idx_jobs_active_purge_soft_deleted
+					ON app_jobs (kind)
+					WHERE kind = 'purge_soft_deleted'
+						AND status IN ('pending', 'running');
+				"#,
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name("idx_jobs_active_purge_soft_deleted")
+					.table(AppJobs::Table)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppJobs {
+	Table,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Attempts and answers remember the session they were taken in, so results
+/// are released by that session's settings rather than by any session that
+/// schedules the same test. Existing rows are only backfilled when their test
+/// belongs to a single session.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				ALTER TABLE app_test_attempts ADD COLUMN session_id UUID NULL
+					REFERENCES app_test_sessions (id)
+					ON UPDATE CASCADE ON DELETE SET NULL;
+				ALTER TABLE app_user_answers ADD COLUMN session_id UUID NULL
+					REFERENCES app_test_sessions (id)
+					ON UPDATE CASCADE ON DELETE SET NULL;
+				UPDATE app_test_attempts t SET session_id = st.session_id
+				FROM app_sessions_has_tests st
+				WHERE st.test_id = t.test_id
+					AND (
+						SELECT COUNT(*) FROM app_sessions_has_tests o
+						WHERE o.test_id = t.test_id
+					) = 1;
+				UPDATE app_user_answers a SET session_id = st.session_id
+				FROM app_sessions_has_tests st
+				WHERE st.test_id = a.test_id
+					AND (
+						SELECT COUNT(*) FROM app_sessions_has_tests o
+						WHERE o.test_id = a.test_id
+					) = 1;
+				"#,
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppTestAttempts::Table)
+					.drop_column(AppTestAttempts::SessionId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.drop_column(AppUserAnswers::SessionId)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppTestAttempts {
+	Table,
+	SessionId,
+}
+
+#[derive(Iden)]
+enum AppUserAnswers {
+	Table,
+	SessionId,
+}
@@ -0,0 +1,127 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The option currently picked for each question of an open attempt, saved
+/// while the student works. An attempt that is auto-submitted is scored from
+/// these instead of being recorded empty.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(AppAttemptAnswers::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(AppAttemptAnswers::Id)
+							.uuid()
+							.not_null()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptAnswers::AttemptId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptAnswers::QuestionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptAnswers::OptionId)
+							.uuid()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(AppAttemptAnswers::UpdatedAt)
+							.timestamp_with_time_zone()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_attempt_answers_attempt")
+							.from(
+								AppAttemptAnswers::Table,
+								AppAttemptAnswers::AttemptId,
+							)
+							.to(AppTestAttempts::Table, AppTestAttempts::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_attempt_answers_question")
+							.from(
+								AppAttemptAnswers::Table,
+								AppAttemptAnswers::QuestionId,
+							)
+							.to(AppQuestions::Table, AppQuestions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_app_attempt_answers_option")
+							.from(
+								AppAttemptAnswers::Table,
+								AppAttemptAnswers::OptionId,
+							)
+							.to(AppOptions::Table, AppOptions::Id)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_attempt_answers_attempt_question")
+					.table(AppAttemptAnswers::Table)
+					.col(AppAttemptAnswers::AttemptId)
+					.col(AppAttemptAnswers::QuestionId)
+					.unique()
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(AppAttemptAnswers::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppAttemptAnswers {
+	Table,
+	Id,
+	AttemptId,
+	QuestionId,
+	OptionId,
+	UpdatedAt,
+}
+
+#[derive(Iden)]
+enum AppTestAttempts {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppQuestions {
+	Table,
+	Id,
+}
+
+#[derive(Iden)]
+enum AppOptions {
+	Table,
+	Id,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Answers keep the score the background worker computed for them, so result
+/// lists can show and sort by it without regrading every submission.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.add_column(
+						ColumnDef::new(AppUserAnswers::Score).double().null(),
+					)
+					.add_column(
+						ColumnDef::new(AppUserAnswers::ScoredAt)
+							.timestamp_with_time_zone()
+							.null(),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		// Answers submitted before the worker scored them.
+		manager
+			.get_connection()
+			.execute_unprepared(
+				r#"
+				UPDATE app_user_answers a
+				SET score = s.score, scored_at = now()
+				FROM (
+					SELECT
+						a.id,
+						COALESCE(
+							COUNT(qa.id) FILTER (WHERE o.is_correct) * 100.0
+								/ NULLIF(GREATEST(COUNT(DISTINCT qa.question_id), MAX(t.total)), 0),
+							0
+						)::float8 AS score
+					FROM app_user_answers a
+					LEFT JOIN app_user_question_answers qa ON qa.answer_id = a.id
+					LEFT JOIN app_options o ON o.id = qa.option_id
+					LEFT JOIN LATERAL (
+						SELECT CASE
+							WHEN a.attempt_id IS NOT NULL THEN (
+								SELECT COUNT(*) FROM app_test_attempt_questions aq
+								WHERE aq.attempt_id = a.attempt_id
+							)
+							ELSE (
+								SELECT COUNT(*) FROM app_tests_has_questions l
+								JOIN app_questions q ON q.id = l.question_id
+								WHERE l.test_id = a.test_id AND q.archived_at IS NULL
+							)
+						END AS total
+					) t ON TRUE
+					GROUP BY a.id
+				) s
+				WHERE s.id = a.id;
+				"#,
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_app_user_answers_session_id_score")
+					.table(AppUserAnswers::Table)
+					.col(AppUserAnswers::SessionId)
+					.col(AppUserAnswers::Score)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(AppUserAnswers::Table)
+					.drop_column(AppUserAnswers::Score)
+					.drop_column(AppUserAnswers::ScoredAt)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(Iden)]
+enum AppUserAnswers {
+	Table,
+	SessionId,
+	Score,
+	ScoredAt,
+}
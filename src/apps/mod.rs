@@ -1,6 +1,6 @@
 use crate::Config;
 use axum::{
-	http::{header, HeaderValue, Method},
+	http::{header, HeaderName, HeaderValue, Method},
 	response::Redirect,
 	routing::get,
 	Router,
@@ -36,6 +36,12 @@ pub async fn routes() -> Router {
 		.allow_origin(allowed_origins)
 		.allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
 		.allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+		.expose_headers([
+			HeaderName::from_static("ratelimit-limit"),
+			HeaderName::from_static("ratelimit-remaining"),
+			HeaderName::from_static("ratelimit-reset"),
+			header::RETRY_AFTER,
+		])
 		.allow_credentials(true);
 
 	Router::new()
@@ -0,0 +1,34 @@
+use axum::{extract::Query, http::HeaderMap, response::IntoResponse};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	ResponseSuccessListDto,
+};
+
+use super::{query_get_audit_logs, AuditLogsItemDto};
+
+#[utoipa::path(
+    get,
+    path = "/v1/audit-logs",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Audit Logs", body = ResponseSuccessListDto<AuditLogsItemDto>),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Audit Logs"
+)]
+pub async fn get_audit_logs(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListAuditLogs])
+		.await
+	{
+		Ok(_) => query_get_audit_logs(params).await,
+		Err(response) => response,
+	}
+}
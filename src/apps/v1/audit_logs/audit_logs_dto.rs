@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogsItemDto {
+	pub id: String,
+	pub actor_id: Option<String>,
+	pub actor_email: Option<String>,
+	pub action: String,
+	pub entity_type: String,
+	pub entity_id: Option<String>,
+	pub before: Option<serde_json::Value>,
+	pub after: Option<serde_json::Value>,
+	pub changes: Option<serde_json::Value>,
+	pub ip_address: Option<String>,
+	pub created_at: String,
+}
@@ -0,0 +1,117 @@
+use axum::response::Response;
+use hyper::StatusCode;
+use sea_orm::{
+	sea_query::{extension::postgres::PgExpr, Expr},
+	ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+	QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::{
+	common_response, get_db,
+	schemas::{AuditLogsColumn, AuditLogsEntity, AuditLogsModel},
+	success_response_list, MetaRequestDto, MetaResponseDto, ResponseSuccessListDto,
+};
+
+use super::AuditLogsItemDto;
+
+fn audit_log_to_dto(log: AuditLogsModel) -> AuditLogsItemDto {
+	AuditLogsItemDto {
+		id: log.id.to_string(),
+		actor_id: log.actor_id.map(|id| id.to_string()),
+		actor_email: log.actor_email,
+		action: log.action,
+		entity_type: log.entity_type,
+		entity_id: log.entity_id,
+		before: log.before,
+		after: log.after,
+		changes: log.changes,
+		ip_address: log.ip_address,
+		created_at: log.created_at.to_string(),
+	}
+}
+
+pub async fn query_get_audit_logs(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let page = params.page.unwrap_or(1).max(1);
+	let per_page = params.per_page.unwrap_or(10).clamp(1, 100);
+	let search = params.search.unwrap_or_default();
+	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
+	let filter = params.filter.unwrap_or_default();
+	let filter_by = params.filter_by.unwrap_or_default().to_lowercase();
+
+	let mut query = AuditLogsEntity::find();
+
+	if !search.is_empty() {
+		query = query.filter(
+			Expr::col(AuditLogsColumn::ActorEmail).ilike(format!("%{}%", search)),
+		);
+	}
+
+	if !filter.is_empty() {
+		query = match filter_by.as_str() {
+			"action" => query.filter(AuditLogsColumn::Action.eq(filter)),
+			"entity_type" => query.filter(AuditLogsColumn::EntityType.eq(filter)),
+			"entity_id" => query.filter(AuditLogsColumn::EntityId.eq(filter)),
+			"actor_email" => query.filter(AuditLogsColumn::ActorEmail.eq(filter)),
+			"actor_id" => match Uuid::parse_str(&filter) {
+				Ok(actor_id) => query.filter(AuditLogsColumn::ActorId.eq(actor_id)),
+				Err(_) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"Invalid actor ID format",
+					)
+				}
+			},
+			_ => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					"Unsupported filter_by value",
+				)
+			}
+		};
+	}
+
+	query = match order.as_str() {
+		"asc" => query.order_by_asc(AuditLogsColumn::CreatedAt),
+		_ => query.order_by_desc(AuditLogsColumn::CreatedAt),
+	};
+
+	let paginator = query.paginate(&db, per_page);
+
+	let total_items = match paginator.num_items().await {
+		Ok(count) => count,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let logs = match paginator.fetch_page(page - 1).await {
+		Ok(data) => data,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let response = ResponseSuccessListDto {
+		data: logs
+			.into_iter()
+			.map(audit_log_to_dto)
+			.collect::<Vec<AuditLogsItemDto>>(),
+		meta: Some(MetaResponseDto {
+			page: Some(page),
+			per_page: Some(per_page),
+			total: Some(total_items),
+			next_cursor: None,
+		}),
+	};
+
+	success_response_list(response)
+}
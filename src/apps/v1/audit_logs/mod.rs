@@ -0,0 +1,19 @@
+use axum::{routing::get, Router};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod audit_logs_controller;
+pub mod audit_logs_dto;
+pub mod audit_logs_repository;
+
+pub use audit_logs_dto::*;
+pub use audit_logs_repository::*;
+
+pub fn audit_logs_router() -> Router {
+	Router::new()
+		.route("/", get(audit_logs_controller::get_audit_logs))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"audit_logs",
+			120,
+		)))
+}
@@ -2,11 +2,17 @@ use crate::{MessageResponseDto, ResponseSuccessDto};
 
 use super::{
 	mutation_change_password, mutation_forgot_password, mutation_login,
-	mutation_new_password, mutation_refresh, mutation_register, mutation_send_otp,
-	mutation_verify_email, AuthChangePasswordRequestDto, AuthDataDto,
-	AuthForgotRequestDto, AuthLoginRequestDto, AuthNewPasswordRequestDto,
+	mutation_login_two_factor, mutation_new_password, mutation_oidc_authorize,
+	mutation_oidc_callback, mutation_refresh,
+	mutation_register, mutation_send_otp, mutation_two_factor_disable,
+	mutation_two_factor_enable, mutation_two_factor_setup, mutation_verify_email,
+	AuthChangePasswordRequestDto, AuthDataDto, AuthForgotRequestDto,
+	AuthLoginRequestDto, AuthLoginTwoFactorRequestDto, AuthNewPasswordRequestDto,
+	AuthOidcAuthorizeDto, AuthOidcCallbackRequestDto,
 	AuthRefreshTokenRequestDto, AuthRegisterRequestDto, AuthTokenItemDto,
-	AuthVerifyEmailRequestDto,
+	AuthTwoFactorDisableRequestDto,
+	AuthTwoFactorEnableRequestDto, AuthTwoFactorRecoveryCodesDto,
+	AuthTwoFactorSetupDto, AuthVerifyEmailRequestDto,
 };
 
 use axum::{extract::Json, response::IntoResponse};
@@ -29,6 +35,54 @@ pub async fn post_login(
 	mutation_login(Json(payload)).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/auth/login/2fa",
+    request_body = AuthLoginTwoFactorRequestDto,
+    responses(
+        (status = 200, description = "Login successful", body = ResponseSuccessDto<AuthDataDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn post_login_two_factor(
+	Json(payload): Json<AuthLoginTwoFactorRequestDto>,
+) -> impl IntoResponse {
+	mutation_login_two_factor(Json(payload)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/auth/oidc/authorize",
+    responses(
+        (status = 200, description = "Authorization URL created", body = ResponseSuccessDto<AuthOidcAuthorizeDto>),
+        (status = 503, description = "Provider not configured", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn get_oidc_authorize() -> impl IntoResponse {
+	mutation_oidc_authorize().await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/auth/oidc/callback",
+    request_body = AuthOidcCallbackRequestDto,
+    responses(
+        (status = 200, description = "Login successful", body = ResponseSuccessDto<AuthDataDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn post_oidc_callback(
+	Json(payload): Json<AuthOidcCallbackRequestDto>,
+) -> impl IntoResponse {
+	mutation_oidc_callback(Json(payload)).await
+}
+
 #[utoipa::path(
     post,
     path = "/v1/auth/register",
@@ -151,3 +205,62 @@ pub async fn post_change_password(
 ) -> impl IntoResponse {
 	mutation_change_password(headers, Json(payload)).await
 }
+
+#[utoipa::path(
+    post,
+    path = "/v1/users/2fa/setup",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Two-factor setup started", body = ResponseSuccessDto<AuthTwoFactorSetupDto>),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn post_two_factor_setup(headers: HeaderMap) -> impl IntoResponse {
+	mutation_two_factor_setup(headers).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/users/2fa/enable",
+    security(
+        ("Bearer" = [])
+    ),
+    request_body = AuthTwoFactorEnableRequestDto,
+    responses(
+        (status = 200, description = "Two-factor enabled", body = ResponseSuccessDto<AuthTwoFactorRecoveryCodesDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn post_two_factor_enable(
+	headers: HeaderMap,
+	Json(payload): Json<AuthTwoFactorEnableRequestDto>,
+) -> impl IntoResponse {
+	mutation_two_factor_enable(headers, Json(payload)).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/users/2fa/disable",
+    security(
+        ("Bearer" = [])
+    ),
+    request_body = AuthTwoFactorDisableRequestDto,
+    responses(
+        (status = 200, description = "Two-factor disabled", body = MessageResponseDto),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Authentication"
+)]
+
+pub async fn post_two_factor_disable(
+	headers: HeaderMap,
+	Json(payload): Json<AuthTwoFactorDisableRequestDto>,
+) -> impl IntoResponse {
+	mutation_two_factor_disable(headers, Json(payload)).await
+}
@@ -8,6 +8,54 @@ pub struct AuthLoginRequestDto {
 	pub password: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthLoginTwoFactorRequestDto {
+	pub challenge_token: String,
+	pub code: Option<String>,
+	pub recovery_code: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthTwoFactorChallengeDto {
+	pub two_factor_required: bool,
+	pub challenge_token: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthTwoFactorSetupDto {
+	pub secret: String,
+	pub provisioning_uri: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthTwoFactorEnableRequestDto {
+	pub code: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthTwoFactorDisableRequestDto {
+	pub password: String,
+	pub code: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthTwoFactorRecoveryCodesDto {
+	pub recovery_codes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthOidcAuthorizeDto {
+	pub authorization_url: String,
+	pub state: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthOidcCallbackRequestDto {
+	pub code: String,
+	pub state: String,
+	pub student_type: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct Email(String);
 
@@ -21,7 +69,6 @@ pub struct AuthRegisterRequestDto {
 	pub student_type: String,
 	pub phone_number: String,
 	pub password: String,
-	pub referral_code: Option<String>,
 	pub referred_by: Option<String>,
 }
 
@@ -73,5 +120,9 @@ pub struct AuthUsersItemDto {
 	pub avatar: Option<String>,
 	pub phone_number: String,
 	pub is_profile_completed: bool,
+	#[serde(default)]
+	pub two_factor_enabled: bool,
+	#[serde(default)]
+	pub two_factor_setup_required: bool,
 	pub role: Option<RolesItemDto>,
 }
@@ -4,13 +4,14 @@ use axum::{
 	http::{header::AUTHORIZATION, Response, StatusCode},
 	middleware::Next,
 };
-use sea_orm::{ColumnTrait, EntityTrait};
+use sea_orm::ColumnTrait;
 use sea_orm::{QueryFilter, QuerySelect};
 use std::convert::Infallible;
 
 use crate::{
 	common_response, decode_access_token, get_db,
 	schemas::{UsersColumn, UsersEntity},
+	SoftDelete,
 };
 
 pub async fn authorization_middleware(
@@ -47,7 +48,7 @@ pub async fn authorization_middleware(
 		}
 	};
 
-	let token_data = match decode_access_token(&token) {
+	let token_data = match decode_access_token(token) {
 		Ok(data) => data,
 		Err(_) => {
 			return Ok(common_response(
@@ -57,7 +58,7 @@ pub async fn authorization_middleware(
 		}
 	};
 
-	let user = UsersEntity::find()
+	let user = UsersEntity::find_active()
 		.select_only()
 		.column(UsersColumn::Email)
 		.filter(UsersColumn::Email.eq(token_data.claims.email.clone()))
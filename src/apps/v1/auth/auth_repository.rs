@@ -1,35 +1,56 @@
 use super::{
 	auth_dto::{
 		AuthDataDto, AuthForgotRequestDto, AuthLoginRequestDto,
-		AuthNewPasswordRequestDto, AuthRefreshTokenRequestDto,
-		AuthRegisterRequestDto, AuthTokenItemDto, AuthVerifyEmailRequestDto,
+		AuthLoginTwoFactorRequestDto, AuthNewPasswordRequestDto,
+		AuthOidcAuthorizeDto, AuthOidcCallbackRequestDto,
+		AuthRefreshTokenRequestDto, AuthRegisterRequestDto, AuthTokenItemDto,
+		AuthTwoFactorChallengeDto, AuthTwoFactorDisableRequestDto,
+		AuthTwoFactorEnableRequestDto, AuthTwoFactorRecoveryCodesDto,
+		AuthTwoFactorSetupDto, AuthVerifyEmailRequestDto,
 	},
 	AuthChangePasswordRequestDto, AuthUsersItemDto,
 };
 use crate::{
 	common_response, connect_redis, decode_access_token, decode_refresh_token,
-	encode_access_token, encode_refresh_token, get_db, hash_password,
+	encode_access_token, encode_refresh_token, enqueue_job, generate_recovery_codes,
+	generate_totp_secret, get_db, hash_password,
+	notifications::{
+		notify_user, NewNotification, NOTIFICATION_KIND_PASSWORD_CHANGED,
+	},
 	permissions::PermissionsItemDto,
+	referrals::{
+		find_referrer, generate_referral_code, mark_referral_activated,
+		record_referral,
+	},
 	roles::{RolesEnum, RolesItemDto},
 	schemas::{
-		PermissionsEntity, RolesColumn, RolesEntity, RolesPermissionsColumn,
-		RolesPermissionsEntity, UsersActiveModel, UsersColumn, UsersEntity,
-		UsersRelation,
+		PermissionsEntity, RolesColumn, RolesEntity, RolesModel,
+		RolesPermissionsColumn, RolesPermissionsEntity, UserIdentitiesActiveModel,
+		UserIdentitiesColumn, UserIdentitiesEntity, UsersActiveModel, UsersColumn,
+		UsersEntity, UsersModel,
 	},
-	send_email, success_response, verify_password, OtpManager, ResponseSuccessDto,
+	success_response, totp_provisioning_uri, verify_password, verify_totp,
+	EmailTemplate, Job, OidcClaims, OidcError, OidcProvider, OtpManager,
+	ResponseSuccessDto, SoftDelete,
 };
 use axum::{http::StatusCode, response::Response, Json};
 use chrono::Utc;
 use email_address::EmailAddress;
 use hyper::HeaderMap;
+use log::error;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use redis::Commands;
 use sea_orm::{
 	prelude::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
-	JoinType, QueryFilter, QuerySelect, RelationTrait, Set,
+	QueryFilter, QuerySelect, Set,
 };
 use std::env;
 use uuid::Uuid;
 
+const TOTP_ISSUER: &str = "Najm Course";
+const TWO_FACTOR_CHALLENGE_TTL: u64 = 300;
+const TWO_FACTOR_MAX_ATTEMPTS: i64 = 5;
+
 pub async fn mutation_login(
 	Json(credentials): Json<AuthLoginRequestDto>,
 ) -> Response {
@@ -49,58 +70,13 @@ pub async fn mutation_login(
 
 	let mut redis = connect_redis();
 
-	let user_data = match UsersEntity::find()
-		.select_only()
-		.column(UsersColumn::Id)
-		.column(UsersColumn::Email)
-		.column(UsersColumn::Password)
-		.column(UsersColumn::IsActive)
-		.column(UsersColumn::IsProfileCompleted)
-		.column(UsersColumn::Fullname)
-		.column(UsersColumn::Avatar)
-		.column(UsersColumn::PhoneNumber)
-		.column_as(Expr::col((RolesEntity, RolesColumn::Id)), "role_id")
-		.column_as(Expr::col((RolesEntity, RolesColumn::Name)), "role_name")
+	let (user, role) = match UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(credentials.email.clone()))
-		.join(JoinType::LeftJoin, UsersRelation::Role.def())
-		.into_tuple::<(
-			Uuid,
-			String,
-			String,
-			bool,
-			bool,
-			String,
-			Option<String>,
-			String,
-			Option<Uuid>,
-			Option<String>,
-		)>()
+		.find_also_related(RolesEntity)
 		.one(&db)
 		.await
 	{
-		Ok(Some((
-			id,
-			email,
-			hashed_password,
-			is_active,
-			is_profile_completed,
-			fullname,
-			avatar,
-			phone_number,
-			role_id,
-			role_name,
-		))) => (
-			id,
-			email,
-			hashed_password,
-			is_active,
-			is_profile_completed,
-			fullname,
-			avatar,
-			phone_number,
-			role_id,
-			role_name,
-		),
+		Ok(Some(data)) => data,
 		Ok(None) => {
 			return common_response(
 				StatusCode::UNAUTHORIZED,
@@ -115,34 +91,465 @@ pub async fn mutation_login(
 		}
 	};
 
-	let (
-		id,
-		email,
-		hashed_password,
-		is_active,
-		is_profile_completed,
-		fullname,
-		avatar,
-		phone_number,
-		role_id,
-		role_name,
-	) = user_data;
-
-	if !verify_password(&credentials.password, &hashed_password).unwrap_or(false) {
+	if !verify_password(&credentials.password, &user.password).unwrap_or(false) {
 		return common_response(
 			StatusCode::UNAUTHORIZED,
 			"Email or password invalid",
 		);
 	}
 
-	if !is_active {
+	if !user.is_active {
 		return common_response(
 			StatusCode::FORBIDDEN,
 			"Your account is not active, please verify your email",
 		);
 	}
 
-	let access_token = match encode_access_token(&email) {
+	if user.totp_enabled_at.is_some() && user.totp_secret.is_some() {
+		return two_factor_challenge_response(&mut redis, &user.email);
+	}
+
+	login_success_response(&db, &mut redis, user, role).await
+}
+
+fn two_factor_challenge_response(
+	redis: &mut redis::Connection,
+	email: &str,
+) -> Response {
+	let challenge_token = Uuid::new_v4().to_string();
+	let redis_key = format!("two_factor_challenge:{}", challenge_token);
+
+	if let Err(err) = redis.set_ex::<_, String, ()>(
+		&redis_key,
+		email.to_string(),
+		TWO_FACTOR_CHALLENGE_TTL,
+	) {
+		return common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&format!("Redis storage failed: {}", err),
+		);
+	}
+
+	let response = ResponseSuccessDto {
+		data: AuthTwoFactorChallengeDto {
+			two_factor_required: true,
+			challenge_token,
+		},
+	};
+
+	success_response(response)
+}
+
+/// Counts a wrong code against the challenge and revokes it once
+/// `TWO_FACTOR_MAX_ATTEMPTS` is reached, so the six digits cannot be
+/// brute-forced within the challenge lifetime.
+fn reject_two_factor_attempt(
+	redis: &mut redis::Connection,
+	challenge_key: &str,
+	message: &str,
+) -> Response {
+	let attempts_key = format!("{}:attempts", challenge_key);
+	let attempts: i64 = redis
+		.incr(&attempts_key, 1)
+		.unwrap_or(TWO_FACTOR_MAX_ATTEMPTS);
+	let _: () = redis
+		.expire(&attempts_key, TWO_FACTOR_CHALLENGE_TTL as i64)
+		.unwrap_or(());
+
+	if attempts >= TWO_FACTOR_MAX_ATTEMPTS {
+		let _: () = redis
+			.del(&[challenge_key, attempts_key.as_str()])
+			.unwrap_or(());
+		return common_response(
+			StatusCode::UNAUTHORIZED,
+			"Too many invalid codes, please log in again",
+		);
+	}
+
+	common_response(StatusCode::UNAUTHORIZED, message)
+}
+
+pub async fn mutation_login_two_factor(
+	Json(payload): Json<AuthLoginTwoFactorRequestDto>,
+) -> Response {
+	if payload.challenge_token.is_empty() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Challenge token is required",
+		);
+	}
+
+	let db = get_db().await;
+
+	let mut redis = connect_redis();
+
+	let challenge_key = format!("two_factor_challenge:{}", payload.challenge_token);
+
+	let email: String = match redis.get::<_, Option<String>>(&challenge_key) {
+		Ok(Some(email)) => email,
+		Ok(None) => {
+			return common_response(
+				StatusCode::UNAUTHORIZED,
+				"Two-factor challenge is invalid or expired",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let (user, role) = match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email.clone()))
+		.find_also_related(RolesEntity)
+		.one(&db)
+		.await
+	{
+		Ok(Some(data)) => data,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let user = match (&payload.code, &payload.recovery_code) {
+		(Some(code), _) => {
+			if !verify_totp_once(&mut redis, &user, code) {
+				return reject_two_factor_attempt(
+					&mut redis,
+					&challenge_key,
+					"Invalid two-factor code",
+				);
+			}
+			user
+		}
+		(None, Some(recovery_code)) => {
+			match consume_recovery_code(&db, user, recovery_code).await {
+				Ok(Some(user)) => user,
+				Ok(None) => {
+					return reject_two_factor_attempt(
+						&mut redis,
+						&challenge_key,
+						"Invalid recovery code",
+					)
+				}
+				Err(err) => {
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					)
+				}
+			}
+		}
+		(None, None) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Two-factor code or recovery code is required",
+			)
+		}
+	};
+
+	let _: () = redis
+		.del(&[challenge_key.clone(), format!("{}:attempts", challenge_key)])
+		.unwrap_or(());
+
+	login_success_response(&db, &mut redis, user, role).await
+}
+
+pub async fn mutation_oidc_authorize() -> Response {
+	let provider = match OidcProvider::from_config() {
+		Ok(provider) => provider,
+		Err(err) => {
+			return common_response(StatusCode::SERVICE_UNAVAILABLE, &err.to_string())
+		}
+	};
+
+	let discovery = match provider.discover().await {
+		Ok(discovery) => discovery,
+		Err(err) => return common_response(StatusCode::BAD_GATEWAY, &err.to_string()),
+	};
+
+	let mut redis = connect_redis();
+
+	let state = Uuid::new_v4().to_string();
+	let nonce = Uuid::new_v4().to_string();
+	let redis_key = format!("oidc_state:{}", state);
+
+	if let Err(err) = redis.set_ex::<_, String, ()>(&redis_key, nonce.clone(), 600) {
+		return common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&format!("Redis storage failed: {}", err),
+		);
+	}
+
+	let response = ResponseSuccessDto {
+		data: AuthOidcAuthorizeDto {
+			authorization_url: provider.authorization_url(&discovery, &state, &nonce),
+			state,
+		},
+	};
+
+	success_response(response)
+}
+
+pub async fn mutation_oidc_callback(
+	Json(payload): Json<AuthOidcCallbackRequestDto>,
+) -> Response {
+	if payload.code.is_empty() || payload.state.is_empty() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Authorization code and state are required",
+		);
+	}
+
+	let provider = match OidcProvider::from_config() {
+		Ok(provider) => provider,
+		Err(err) => {
+			return common_response(StatusCode::SERVICE_UNAVAILABLE, &err.to_string())
+		}
+	};
+
+	let db = get_db().await;
+
+	let mut redis = connect_redis();
+
+	let state_key = format!("oidc_state:{}", payload.state);
+
+	let nonce = match redis.get::<_, Option<String>>(&state_key) {
+		Ok(Some(nonce)) => nonce,
+		Ok(None) => {
+			return common_response(
+				StatusCode::UNAUTHORIZED,
+				"Login state is invalid or expired",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let _: () = redis.del(&state_key).unwrap_or(());
+
+	let discovery = match provider.discover().await {
+		Ok(discovery) => discovery,
+		Err(err) => return common_response(StatusCode::BAD_GATEWAY, &err.to_string()),
+	};
+
+	let claims = match provider.exchange_code(&discovery, &payload.code, &nonce).await
+	{
+		Ok(claims) => claims,
+		Err(OidcError::Request(err)) => {
+			return common_response(StatusCode::BAD_GATEWAY, &err.to_string())
+		}
+		Err(err) => return common_response(StatusCode::UNAUTHORIZED, &err.to_string()),
+	};
+
+	let email = match &claims.email {
+		Some(email) if claims.email_verified && EmailAddress::is_valid(email) => {
+			email.to_lowercase()
+		}
+		_ => {
+			return common_response(
+				StatusCode::FORBIDDEN,
+				"Identity provider did not return a verified email",
+			)
+		}
+	};
+
+	let user = match find_or_create_oidc_user(
+		&db,
+		&provider.name,
+		&claims,
+		&email,
+		payload.student_type.clone().unwrap_or_default(),
+	)
+	.await
+	{
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	if !user.is_active {
+		return common_response(StatusCode::FORBIDDEN, "Your account is not active");
+	}
+
+	if user.totp_enabled_at.is_some() && user.totp_secret.is_some() {
+		return two_factor_challenge_response(&mut redis, &user.email);
+	}
+
+	let role = match RolesEntity::find_by_id(user.role_id).one(&db).await {
+		Ok(role) => role,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	login_success_response(&db, &mut redis, user, role).await
+}
+
+/// Hash of a password nobody knows, for accounts that sign in through a
+/// provider.
+fn random_password_hash() -> Result<String, Response> {
+	let random_password: String = thread_rng()
+		.sample_iter(&Alphanumeric)
+		.take(32)
+		.map(char::from)
+		.collect();
+
+	hash_password(&random_password).map_err(|_| {
+		common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			"Error occurred while hashing the password",
+		)
+	})
+}
+
+async fn find_or_create_oidc_user(
+	db: &DatabaseConnection,
+	provider: &str,
+	claims: &OidcClaims,
+	email: &str,
+	student_type: String,
+) -> Result<UsersModel, Response> {
+	let internal_error = |err: sea_orm::DbErr| {
+		common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	};
+
+	let linked = UserIdentitiesEntity::find()
+		.filter(UserIdentitiesColumn::Provider.eq(provider))
+		.filter(UserIdentitiesColumn::Subject.eq(claims.sub.clone()))
+		.find_also_related(UsersEntity)
+		.one(db)
+		.await
+		.map_err(internal_error)?;
+
+	if let Some((_, Some(user))) = linked {
+		return reject_deleted_user(user);
+	}
+
+	// Deleted accounts still own their email until purged, so look them up
+	// unscoped and refuse instead of trying to create a duplicate.
+	let existing = UsersEntity::find()
+		.filter(Expr::col(UsersColumn::Email).eq(email))
+		.one(db)
+		.await
+		.map_err(internal_error)?;
+
+	let user = match existing {
+		Some(user) if user.deleted_at.is_some() => return reject_deleted_user(user),
+		Some(user) if user.email_verified.is_none() => {
+			// The provider vouches for the address, which is what the OTP
+			// verification step would otherwise establish. Whoever registered
+			// it unverified may not own it, so their password is replaced.
+			let hashed_password = random_password_hash()?;
+			let mut active_user: UsersActiveModel = user.into();
+			active_user.password = Set(hashed_password);
+			active_user.email_verified = Set(Some(Utc::now()));
+			active_user.is_active = Set(true);
+			active_user.updated_at = Set(Some(Utc::now()));
+			let user = active_user.update(db).await.map_err(internal_error)?;
+			mark_referral_activated(db, user.id)
+				.await
+				.map_err(internal_error)?;
+			user
+		}
+		Some(user) => user,
+		None => {
+			let student_role = RolesEntity::find_active()
+				.filter(RolesColumn::Name.eq(RolesEnum::Student.to_string()))
+				.one(db)
+				.await
+				.map_err(internal_error)?
+				.ok_or_else(|| {
+					common_response(StatusCode::BAD_REQUEST, "Student role not found")
+				})?;
+
+			let hashed_password = random_password_hash()?;
+
+			let referral_code =
+				generate_referral_code(db).await.map_err(internal_error)?;
+
+			let fullname = claims
+				.name
+				.clone()
+				.unwrap_or_else(|| email.split('@').next().unwrap_or(email).to_string());
+
+			UsersActiveModel {
+				id: Set(Uuid::new_v4()),
+				role_id: Set(student_role.id),
+				fullname: Set(fullname),
+				email: Set(email.to_string()),
+				email_verified: Set(Some(Utc::now())),
+				avatar: Set(claims.picture.clone()),
+				phone_number: Set("".to_string()),
+				password: Set(hashed_password),
+				referral_code: Set(Some(referral_code)),
+				referred_by: Set(None),
+				birth_date: Set(None),
+				gender: Set(None),
+				religion: Set(None),
+				identity_number: Set(None),
+				is_deleted: Set(false),
+				is_active: Set(true),
+				is_profile_completed: Set(false),
+				totp_secret: Set(None),
+				totp_enabled_at: Set(None),
+				totp_recovery_codes: Set(None),
+				student_type: Set(student_type),
+				created_at: Set(Some(Utc::now())),
+				updated_at: Set(Some(Utc::now())),
+				deleted_at: Set(None),
+			}
+			.insert(db)
+			.await
+			.map_err(internal_error)?
+		}
+	};
+
+	UserIdentitiesActiveModel {
+		id: Set(Uuid::new_v4()),
+		user_id: Set(user.id),
+		provider: Set(provider.to_string()),
+		subject: Set(claims.sub.clone()),
+		email: Set(email.to_string()),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	}
+	.insert(db)
+	.await
+	.map_err(internal_error)?;
+
+	Ok(user)
+}
+
+fn reject_deleted_user(user: UsersModel) -> Result<UsersModel, Response> {
+	match user.deleted_at {
+		Some(_) => Err(common_response(
+			StatusCode::FORBIDDEN,
+			"This account has been deleted",
+		)),
+		None => Ok(user),
+	}
+}
+
+async fn login_success_response(
+	db: &DatabaseConnection,
+	redis: &mut redis::Connection,
+	user: UsersModel,
+	role: Option<RolesModel>,
+) -> Response {
+	let access_token = match encode_access_token(&user.email) {
 		Ok(token) => token,
 		Err(err) => {
 			return common_response(
@@ -152,7 +559,7 @@ pub async fn mutation_login(
 		}
 	};
 
-	let refresh_token = match encode_refresh_token(&email) {
+	let refresh_token = match encode_refresh_token(&user.email) {
 		Ok(token) => token,
 		Err(err) => {
 			return common_response(
@@ -162,11 +569,11 @@ pub async fn mutation_login(
 		}
 	};
 
-	let permissions = if let Some(role_id) = role_id {
+	let permissions = if let Some(role) = &role {
 		match RolesPermissionsEntity::find()
-			.filter(RolesPermissionsColumn::RoleId.eq(role_id))
+			.filter(RolesPermissionsColumn::RoleId.eq(role.id))
 			.find_also_related(PermissionsEntity)
-			.all(&db)
+			.all(db)
 			.await
 		{
 			Ok(data) => data
@@ -186,16 +593,18 @@ pub async fn mutation_login(
 		vec![]
 	};
 
-	let role_dto = match (role_id, role_name) {
-		(Some(id), Some(name)) => Some(RolesItemDto {
-			id: id.to_string(),
-			name,
-			permissions,
-			created_at: None,
-			updated_at: None,
-		}),
-		_ => None,
-	};
+	let two_factor_enabled = user.totp_enabled_at.is_some();
+	let two_factor_required =
+		role.as_ref().map(|r| r.require_two_factor).unwrap_or(false);
+
+	let role_dto = role.map(|role| RolesItemDto {
+		id: role.id.to_string(),
+		name: role.name,
+		require_two_factor: role.require_two_factor,
+		permissions,
+		created_at: None,
+		updated_at: None,
+	});
 
 	let response = ResponseSuccessDto {
 		data: AuthDataDto {
@@ -204,18 +613,20 @@ pub async fn mutation_login(
 				refresh_token,
 			},
 			user: AuthUsersItemDto {
-				id: id.to_string(),
-				email,
-				fullname,
-				avatar,
-				is_profile_completed,
-				phone_number,
+				id: user.id.to_string(),
+				email: user.email.clone(),
+				fullname: user.fullname,
+				avatar: user.avatar,
+				is_profile_completed: user.is_profile_completed,
+				phone_number: user.phone_number,
+				two_factor_enabled,
+				two_factor_setup_required: two_factor_required && !two_factor_enabled,
 				role: role_dto,
 			},
 		},
 	};
 
-	let redis_key = format!("authenticated_users_data:{}", credentials.email);
+	let redis_key = format!("authenticated_users_data:{}", user.email);
 
 	match redis.set_ex::<_, String, ()>(
 		&redis_key,
@@ -310,7 +721,7 @@ pub async fn mutation_register(new_user: Json<AuthRegisterRequestDto>) -> Respon
 	let otp_manager = OtpManager::new(300);
 	let otp = otp_manager.generate_otp(redis, &new_user.email);
 
-	let student_role = match RolesEntity::find()
+	let student_role = match RolesEntity::find_active()
 		.select_only()
 		.column(RolesColumn::Id)
 		.column(RolesColumn::Name)
@@ -334,8 +745,41 @@ pub async fn mutation_register(new_user: Json<AuthRegisterRequestDto>) -> Respon
 		}
 	};
 
+	let referrer = match &new_user.referred_by {
+		Some(code) if !code.trim().is_empty() => {
+			match find_referrer(&db, code).await {
+				Ok(Some(referrer)) => Some(referrer),
+				Ok(None) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"Referral code not found",
+					)
+				}
+				Err(err) => {
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					)
+				}
+			}
+		}
+		_ => None,
+	};
+
+	let referral_code = match generate_referral_code(&db).await {
+		Ok(code) => code,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let user_id = Uuid::new_v4();
+
 	let active_model = UsersActiveModel {
-		id: Set(Uuid::new_v4()),
+		id: Set(user_id),
 		role_id: Set(student_role.id),
 		fullname: Set(new_user.fullname.clone()),
 		email: Set(new_user.email.clone()),
@@ -343,8 +787,8 @@ pub async fn mutation_register(new_user: Json<AuthRegisterRequestDto>) -> Respon
 		avatar: Set(None),
 		phone_number: Set(new_user.phone_number.clone()),
 		password: Set(hashed_password),
-		referral_code: Set(new_user.referral_code.clone()),
-		referred_by: Set(new_user.referred_by.clone()),
+		referral_code: Set(Some(referral_code)),
+		referred_by: Set(referrer.as_ref().and_then(|r| r.referral_code.clone())),
 		birth_date: Set(None),
 		gender: Set(None),
 		religion: Set(None),
@@ -352,28 +796,41 @@ pub async fn mutation_register(new_user: Json<AuthRegisterRequestDto>) -> Respon
 		is_deleted: Set(false),
 		is_active: Set(false),
 		is_profile_completed: Set(false),
+		totp_secret: Set(None),
+		totp_enabled_at: Set(None),
+		totp_recovery_codes: Set(None),
 		student_type: Set(new_user.student_type.clone()),
 		created_at: Set(Some(Utc::now())),
 		updated_at: Set(Some(Utc::now())),
+		deleted_at: Set(None),
 	};
 
-	let email_content = &format!("Your OTP Code is {}", otp);
+	let email_template = EmailTemplate::Otp {
+		otp,
+		ttl_minutes: 5,
+	};
 
-	if let Err(err) = send_email(&new_user.email, "Verification", email_content) {
-		println!("Email sending error: {:?}", err);
-		return common_response(
-			StatusCode::INTERNAL_SERVER_ERROR,
-			"Failed to send verification email",
-		);
+	if let Err(err) = active_model.insert(&db).await {
+		error!("Failed to create user: {}", err);
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
 	}
 
-	match active_model.insert(&db).await {
-		Ok(_) => common_response(StatusCode::CREATED, "User created successfully"),
-		Err(err) => {
-			println!("Insert error: {:?}", err);
-			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	if let Some(referrer) = &referrer {
+		if let Err(err) = record_referral(&db, referrer, user_id).await {
+			error!("Failed to record referral: {}", err);
 		}
 	}
+
+	let email_job = Job::SendEmail {
+		to: new_user.email.clone(),
+		template: email_template,
+	};
+
+	if let Err(err) = enqueue_job(&db, email_job).await {
+		error!("Failed to enqueue email: {}", err);
+	}
+
+	common_response(StatusCode::CREATED, "User created successfully")
 }
 
 pub async fn mutation_forgot_password(
@@ -389,7 +846,7 @@ pub async fn mutation_forgot_password(
 
 	let email_lower = payload.email.to_lowercase();
 
-	let user_result = UsersEntity::find()
+	let user_result = UsersEntity::find_active()
 		.select_only()
 		.column(UsersColumn::Email)
 		.filter(Expr::col(UsersColumn::Email).eq(email_lower.clone()))
@@ -428,22 +885,27 @@ pub async fn mutation_forgot_password(
 			}
 
 			let fe_url = env::var("FE_URL").unwrap_or_else(|_| "".to_string());
-			let email_content = format!(
-                "You have requested a password reset. Please click the link below to continue: {}/auth/reset-password?token={}",
-                fe_url, reset_token
-            );
-
-			if let Err(err) =
-				send_email(&user_email, "Reset Password Request", &email_content)
-			{
-				println!("Email sending error: {:?}", err);
+			let email_template = EmailTemplate::PasswordReset {
+				reset_url: format!(
+					"{}/auth/reset-password?token={}",
+					fe_url, reset_token
+				),
+			};
+
+			let email_job = Job::SendEmail {
+				to: user_email.clone(),
+				template: email_template,
+			};
+
+			if let Err(err) = enqueue_job(&db, email_job).await {
+				error!("Failed to enqueue email: {}", err);
 				return common_response(
 					StatusCode::INTERNAL_SERVER_ERROR,
 					"Failed to send email",
 				);
 			}
 
-			return common_response(StatusCode::OK, "Password reset token sent");
+			common_response(StatusCode::OK, "Password reset token sent")
 		}
 		Ok(None) => {
 			println!("No user found with email: {}", email_lower);
@@ -465,7 +927,7 @@ pub async fn mutation_send_otp(
 		return common_response(StatusCode::BAD_REQUEST, "Email is required");
 	}
 
-	let user = UsersEntity::find()
+	let user = UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(payload.email.clone()))
 		.one(&db)
 		.await;
@@ -474,8 +936,24 @@ pub async fn mutation_send_otp(
 		let redis = connect_redis();
 		let otp_manager = OtpManager::new(300);
 		let otp = otp_manager.generate_otp(redis, &user.email);
-		let email_message = &format!("Your OTP Code is {}", otp);
-		send_email(&user.email, "Verification", email_message).unwrap();
+		let email_template = EmailTemplate::Otp {
+			otp,
+			ttl_minutes: 5,
+		};
+
+		let email_job = Job::SendEmail {
+			to: user.email.clone(),
+			template: email_template,
+		};
+
+		if let Err(err) = enqueue_job(&db, email_job).await {
+			error!("Failed to enqueue email: {}", err);
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				"Failed to send verification email",
+			);
+		}
+
 		return common_response(StatusCode::OK, "OTP Has Been sent");
 	}
 
@@ -496,12 +974,19 @@ pub async fn mutation_verify_email(
 	let is_valid = otp_manager.validate_otp(redis, &payload.email, payload.otp);
 
 	if is_valid {
-		if let Some(user) = UsersEntity::find()
+		if let Some(user) = UsersEntity::find_active()
 			.filter(UsersColumn::Email.eq(payload.email.clone()))
 			.one(&db)
 			.await
 			.unwrap()
 		{
+			let is_first_verification = user.email_verified.is_none();
+			let user_id = user.id;
+			let welcome_template = EmailTemplate::Welcome {
+				fullname: user.fullname.clone(),
+			};
+			let user_email = user.email.clone();
+
 			let mut active_user: UsersActiveModel = user.into();
 			active_user.is_active = Set(true);
 			active_user.email_verified = Set(Some(Utc::now()));
@@ -513,6 +998,21 @@ pub async fn mutation_verify_email(
 				);
 			}
 
+			if let Err(err) = mark_referral_activated(&db, user_id).await {
+				error!("Failed to record referral: {}", err);
+			}
+
+			if is_first_verification {
+				let email_job = Job::SendEmail {
+					to: user_email,
+					template: welcome_template,
+				};
+
+				if let Err(err) = enqueue_job(&db, email_job).await {
+					error!("Failed to enqueue email: {}", err);
+				}
+			}
+
 			return common_response(StatusCode::OK, "Email successfully verified");
 		}
 	}
@@ -520,6 +1020,22 @@ pub async fn mutation_verify_email(
 	common_response(StatusCode::BAD_REQUEST, "Invalid OTP")
 }
 
+/// Lets the account owner know, so a change they didn't make stands out.
+async fn notify_password_changed(db: &DatabaseConnection, user_id: Uuid) {
+	let notification = NewNotification {
+		kind: NOTIFICATION_KIND_PASSWORD_CHANGED,
+		title: "Password changed".to_string(),
+		body: "Your password was just changed. If this wasn't you, reset it \
+		       right away."
+			.to_string(),
+		data: None,
+	};
+
+	if let Err(err) = notify_user(db, user_id, &notification).await {
+		error!("Failed to notify password change: {}", err);
+	}
+}
+
 pub async fn mutation_new_password(
 	Json(payload): Json<AuthNewPasswordRequestDto>,
 ) -> Response {
@@ -556,13 +1072,14 @@ pub async fn mutation_new_password(
 
 	let hashed_password = hash_password(&payload.password).unwrap();
 
-	if let Some(user) = UsersEntity::find()
+	if let Some(user) = UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(email.clone()))
 		.one(&db)
 		.await
 		.ok()
 		.flatten()
 	{
+		let user_id = user.id;
 		let mut active_user: UsersActiveModel = user.into();
 		active_user.password = Set(hashed_password);
 
@@ -573,6 +1090,8 @@ pub async fn mutation_new_password(
 			);
 		}
 
+		notify_password_changed(&db, user_id).await;
+
 		let _: () = redis.del(&key).unwrap_or(());
 
 		return common_response(StatusCode::OK, "Password updated successfully");
@@ -659,7 +1178,7 @@ pub async fn mutation_change_password(
 		}
 	};
 
-	let token_data = match decode_access_token(&token) {
+	let token_data = match decode_access_token(token) {
 		Ok(data) => data,
 		Err(err) => {
 			return common_response(StatusCode::UNAUTHORIZED, &err.to_string())
@@ -675,7 +1194,7 @@ pub async fn mutation_change_password(
 
 	let email = token_data.claims.email;
 
-	let old_password_query = UsersEntity::find()
+	let old_password_query = UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(&email))
 		.one(&db)
 		.await
@@ -693,13 +1212,14 @@ pub async fn mutation_change_password(
 
 	let hashed_password = hash_password(&payload.password).unwrap();
 
-	if let Some(user) = UsersEntity::find()
+	if let Some(user) = UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(email.clone()))
 		.one(&db)
 		.await
 		.ok()
 		.flatten()
 	{
+		let user_id = user.id;
 		let mut active_user: UsersActiveModel = user.into();
 		active_user.password = Set(hashed_password);
 
@@ -710,8 +1230,322 @@ pub async fn mutation_change_password(
 			);
 		}
 
+		notify_password_changed(&db, user_id).await;
+
 		return common_response(StatusCode::OK, "Password updated successfully");
 	}
 
 	common_response(StatusCode::NOT_FOUND, "User not found")
 }
+
+pub async fn mutation_two_factor_setup(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let (user, role) = match find_user_with_role(&db, &email).await {
+		Ok(data) => data,
+		Err(response) => return response,
+	};
+
+	let role_name = role.as_ref().map(|r| r.name.clone()).unwrap_or_default();
+	let role_requires = role.as_ref().map(|r| r.require_two_factor).unwrap_or(false);
+
+	if role_name != RolesEnum::Admin.to_string()
+		&& role_name != RolesEnum::Staf.to_string()
+		&& !role_requires
+	{
+		return common_response(
+			StatusCode::FORBIDDEN,
+			"Two-factor authentication is not available for your role",
+		);
+	}
+
+	if user.totp_enabled_at.is_some() {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Two-factor authentication is already enabled",
+		);
+	}
+
+	let secret = generate_totp_secret();
+	let provisioning_uri = totp_provisioning_uri(&secret, &user.email, TOTP_ISSUER);
+
+	let mut active_user: UsersActiveModel = user.into();
+	active_user.totp_secret = Set(Some(secret.clone()));
+	active_user.updated_at = Set(Some(Utc::now()));
+
+	if let Err(err) = active_user.update(&db).await {
+		return common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		);
+	}
+
+	let response = ResponseSuccessDto {
+		data: AuthTwoFactorSetupDto {
+			secret,
+			provisioning_uri,
+		},
+	};
+
+	success_response(response)
+}
+
+pub async fn mutation_two_factor_enable(
+	headers: HeaderMap,
+	Json(payload): Json<AuthTwoFactorEnableRequestDto>,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let mut redis = connect_redis();
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let (user, _) = match find_user_with_role(&db, &email).await {
+		Ok(data) => data,
+		Err(response) => return response,
+	};
+
+	if user.totp_enabled_at.is_some() {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Two-factor authentication is already enabled",
+		);
+	}
+
+	if user.totp_secret.is_none() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Two-factor setup has not been started",
+		);
+	}
+
+	if !verify_totp_once(&mut redis, &user, &payload.code) {
+		return common_response(StatusCode::UNAUTHORIZED, "Invalid two-factor code");
+	}
+
+	let recovery_codes = generate_recovery_codes();
+
+	let hashed_codes = match recovery_codes
+		.iter()
+		.map(|code| hash_password(code))
+		.collect::<Result<Vec<String>, _>>()
+	{
+		Ok(hashes) => hashes,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut active_user: UsersActiveModel = user.into();
+	active_user.totp_enabled_at = Set(Some(Utc::now()));
+	active_user.totp_recovery_codes = Set(Some(serde_json::json!(hashed_codes)));
+	active_user.updated_at = Set(Some(Utc::now()));
+
+	if let Err(err) = active_user.update(&db).await {
+		return common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		);
+	}
+
+	refresh_cached_two_factor(&mut redis, &email, true);
+
+	let response = ResponseSuccessDto {
+		data: AuthTwoFactorRecoveryCodesDto { recovery_codes },
+	};
+
+	success_response(response)
+}
+
+pub async fn mutation_two_factor_disable(
+	headers: HeaderMap,
+	Json(payload): Json<AuthTwoFactorDisableRequestDto>,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let mut redis = connect_redis();
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let (user, role) = match find_user_with_role(&db, &email).await {
+		Ok(data) => data,
+		Err(response) => return response,
+	};
+
+	if user.totp_enabled_at.is_none() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Two-factor authentication is not enabled",
+		);
+	}
+
+	if role.map(|r| r.require_two_factor).unwrap_or(false) {
+		return common_response(
+			StatusCode::FORBIDDEN,
+			"Your role requires two-factor authentication",
+		);
+	}
+
+	if !verify_password(&payload.password, &user.password).unwrap_or(false) {
+		return common_response(StatusCode::FORBIDDEN, "Password dont match");
+	}
+
+	if !verify_totp_once(&mut redis, &user, &payload.code) {
+		return common_response(StatusCode::UNAUTHORIZED, "Invalid two-factor code");
+	}
+
+	let mut active_user: UsersActiveModel = user.into();
+	active_user.totp_secret = Set(None);
+	active_user.totp_enabled_at = Set(None);
+	active_user.totp_recovery_codes = Set(None);
+	active_user.updated_at = Set(Some(Utc::now()));
+
+	if let Err(err) = active_user.update(&db).await {
+		return common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		);
+	}
+
+	refresh_cached_two_factor(&mut redis, &email, false);
+
+	common_response(StatusCode::OK, "Two-factor authentication disabled")
+}
+
+pub fn email_from_headers(headers: &HeaderMap) -> Result<String, Response> {
+	let token = headers
+		.get("Authorization")
+		.and_then(|header| header.to_str().ok())
+		.and_then(|header| header.split_whitespace().nth(1))
+		.ok_or_else(|| {
+			common_response(StatusCode::FORBIDDEN, "You are not authorized")
+		})?;
+
+	decode_access_token(token)
+		.map(|data| data.claims.email)
+		.map_err(|err| common_response(StatusCode::UNAUTHORIZED, &err.to_string()))
+}
+
+async fn find_user_with_role(
+	db: &DatabaseConnection,
+	email: &str,
+) -> Result<(UsersModel, Option<RolesModel>), Response> {
+	match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email))
+		.find_also_related(RolesEntity)
+		.one(db)
+		.await
+	{
+		Ok(Some(data)) => Ok(data),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "User not found")),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+fn verify_totp_once(
+	redis: &mut redis::Connection,
+	user: &UsersModel,
+	code: &str,
+) -> bool {
+	let secret = match &user.totp_secret {
+		Some(secret) => secret,
+		None => return false,
+	};
+
+	let step = match verify_totp(secret, code) {
+		Some(step) => step,
+		None => return false,
+	};
+
+	// A code stays valid for the whole skew window, so remember used steps.
+	let used_key = format!("totp_used:{}:{}", user.email, step);
+
+	redis::cmd("SET")
+		.arg(&used_key)
+		.arg(1)
+		.arg("NX")
+		.arg("EX")
+		.arg(90)
+		.query::<Option<String>>(redis)
+		.map(|result| result.is_some())
+		.unwrap_or(false)
+}
+
+async fn consume_recovery_code(
+	db: &DatabaseConnection,
+	user: UsersModel,
+	recovery_code: &str,
+) -> Result<Option<UsersModel>, sea_orm::DbErr> {
+	let recovery_code = recovery_code.trim().to_uppercase();
+
+	let hashes: Vec<String> = user
+		.totp_recovery_codes
+		.clone()
+		.and_then(|codes| serde_json::from_value(codes).ok())
+		.unwrap_or_default();
+
+	let matched = hashes
+		.iter()
+		.position(|hash| verify_password(&recovery_code, hash).unwrap_or(false));
+
+	let index = match matched {
+		Some(index) => index,
+		None => return Ok(None),
+	};
+
+	let remaining: Vec<String> = hashes
+		.into_iter()
+		.enumerate()
+		.filter(|(i, _)| *i != index)
+		.map(|(_, hash)| hash)
+		.collect();
+
+	let mut active_user: UsersActiveModel = user.into();
+	active_user.totp_recovery_codes = Set(Some(serde_json::json!(remaining)));
+	active_user.updated_at = Set(Some(Utc::now()));
+
+	active_user.update(db).await.map(Some)
+}
+
+fn refresh_cached_two_factor(
+	redis: &mut redis::Connection,
+	email: &str,
+	enabled: bool,
+) {
+	let redis_key = format!("authenticated_users_data:{}", email);
+
+	let cached: Option<String> = redis.get(&redis_key).unwrap_or(None);
+
+	if let Some(mut user) =
+		cached.and_then(|data| serde_json::from_str::<AuthUsersItemDto>(&data).ok())
+	{
+		user.two_factor_enabled = enabled;
+		user.two_factor_setup_required = !enabled
+			&& user.role.as_ref().map(|r| r.require_two_factor).unwrap_or(false);
+
+		let _: () = redis
+			.set_ex(
+				&redis_key,
+				serde_json::to_string(&user).unwrap_or_default(),
+				86400,
+			)
+			.unwrap_or(());
+	}
+}
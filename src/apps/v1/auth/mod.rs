@@ -1,4 +1,9 @@
-use axum::{routing::post, Router};
+use axum::{
+	routing::{get, post},
+	Router,
+};
+
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
 
 pub mod auth_controller;
 pub mod auth_dto;
@@ -11,11 +16,73 @@ pub use auth_repository::*;
 
 pub fn auth_router() -> Router {
 	Router::new()
-		.route("/login", post(auth_controller::post_login))
-		.route("/register", post(auth_controller::post_register))
-		.route("/forgot", post(auth_controller::post_forgot))
-		.route("/verify-email", post(auth_controller::post_verify_email))
-		.route("/send-otp", post(auth_controller::post_send_otp))
-		.route("/new-password", post(auth_controller::post_new_password))
-		.route("/refresh", post(auth_controller::post_refresh))
+		.route(
+			"/login",
+			post(auth_controller::post_login).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_login", 10)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/login/2fa",
+			post(auth_controller::post_login_two_factor).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_login_two_factor", 10)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/oidc/authorize",
+			get(auth_controller::get_oidc_authorize).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_oidc_authorize", 20)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/oidc/callback",
+			post(auth_controller::post_oidc_callback).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_oidc_callback", 10)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/register",
+			post(auth_controller::post_register).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_hour("auth_register", 5)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/forgot",
+			post(auth_controller::post_forgot).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_hour("auth_forgot", 5).keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/verify-email",
+			post(auth_controller::post_verify_email).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_verify_email", 10)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/send-otp",
+			post(auth_controller::post_send_otp).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_hour("auth_send_otp", 5)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/new-password",
+			post(auth_controller::post_new_password).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_new_password", 10)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
+		.route(
+			"/refresh",
+			post(auth_controller::post_refresh).layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("auth_refresh", 30)
+					.keyed_by(RateLimitKey::Ip),
+			)),
+		)
 }
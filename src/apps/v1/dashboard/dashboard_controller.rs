@@ -0,0 +1,42 @@
+use axum::{
+	extract::{Query, State},
+	http::HeaderMap,
+	response::IntoResponse,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::MessageResponseDto,
+	AppState, ResponseSuccessDto,
+};
+
+use super::{
+	query_get_dashboard_stats, DashboardStatsDto, DashboardStatsRequestDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/dashboard/stats",
+    params(DashboardStatsRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Dashboard statistics", body = ResponseSuccessDto<DashboardStatsDto>),
+        (status = 400, description = "Invalid date range", body = MessageResponseDto),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Dashboard"
+)]
+pub async fn get_dashboard_stats(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	Query(params): Query<DashboardStatsRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDashboardStats])
+		.await
+	{
+		Ok(_) => query_get_dashboard_stats(state, params).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,56 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct DashboardStatsRequestDto {
+	/// First day of the range (YYYY-MM-DD). Defaults to a 30 day range.
+	pub start_date: Option<String>,
+	/// Last day of the range (YYYY-MM-DD), inclusive. Defaults to today.
+	pub end_date: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardStatsDto {
+	pub start_date: String,
+	pub end_date: String,
+	pub users: DashboardUsersStatsDto,
+	pub active_sessions: u64,
+	pub registrations_per_day: Vec<DashboardDailyCountDto>,
+	pub attempts_per_day: Vec<DashboardDailyCountDto>,
+	pub session_scores: Vec<DashboardSessionScoreDto>,
+	/// Whole bucket, not limited to the date range. Missing when storage is
+	/// unreachable.
+	pub storage: Option<DashboardStorageDto>,
+	pub generated_at: String,
+}
+
+/// `registered` and `verified` count accounts as of `end_date`; `active`
+/// counts users who started or submitted a test within the range.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardUsersStatsDto {
+	pub registered: u64,
+	pub verified: u64,
+	pub active: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct DashboardDailyCountDto {
+	pub date: String,
+	pub count: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct DashboardSessionScoreDto {
+	#[schema(value_type = String)]
+	pub session_id: uuid::Uuid,
+	pub session_name: String,
+	pub attempts: i64,
+	pub average_score: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardStorageDto {
+	pub objects: u64,
+	pub bytes: u64,
+}
@@ -0,0 +1,282 @@
+use axum::response::Response;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use hyper::StatusCode;
+use log::error;
+use redis::Commands;
+use sea_orm::{
+	ColumnTrait, DatabaseConnection, DbBackend, DbErr, FromQueryResult,
+	PaginatorTrait, QueryFilter, Statement,
+};
+
+use crate::{
+	common_response, connect_redis, get_db,
+	schemas::{TestSessionsColumn, TestSessionsEntity, UsersColumn, UsersEntity},
+	success_response,
+	users::ANSWER_SCORES_CTE,
+	AppState, ResponseSuccessDto, SoftDelete,
+};
+
+use super::{
+	DashboardDailyCountDto, DashboardSessionScoreDto, DashboardStatsDto,
+	DashboardStatsRequestDto, DashboardStorageDto, DashboardUsersStatsDto,
+};
+
+const DASHBOARD_DEFAULT_RANGE_DAYS: i64 = 30;
+const DASHBOARD_MAX_RANGE_DAYS: i64 = 366;
+// Short enough that the numbers still feel live, long enough that a dashboard
+// left open on several screens doesn't rerun the aggregates on every poll.
+const DASHBOARD_STATS_CACHE_TTL: u64 = 60;
+// Storage usage lists every object in the bucket and doesn't depend on the
+// date range, so it is cached once and for longer.
+const DASHBOARD_STORAGE_CACHE_KEY: &str = "dashboard_storage_usage";
+const DASHBOARD_STORAGE_CACHE_TTL: u64 = 15 * 60;
+
+// Days are bucketed in UTC and every day of the range is returned, including
+// the ones without rows, so charts don't have to fill gaps.
+const REGISTRATIONS_PER_DAY_SQL: &str = r#"
+SELECT to_char(d, 'YYYY-MM-DD') AS date, COUNT(u.id) AS count
+FROM generate_series($1::date, $2::date, interval '1 day') d
+LEFT JOIN app_users u
+	ON u.deleted_at IS NULL
+	AND (u.created_at AT TIME ZONE 'UTC')::date = d::date
+GROUP BY d
+ORDER BY d
+"#;
+
+const ATTEMPTS_PER_DAY_SQL: &str = r#"
+SELECT to_char(d, 'YYYY-MM-DD') AS date, COUNT(a.id) AS count
+FROM generate_series($1::date, $2::date, interval '1 day') d
+LEFT JOIN app_user_answers a
+	ON (a.created_at AT TIME ZONE 'UTC')::date = d::date
+GROUP BY d
+ORDER BY d
+"#;
+
+const ACTIVE_USERS_SQL: &str = r#"
+SELECT COUNT(DISTINCT activity.user_id) AS count
+FROM (
+	SELECT user_id FROM app_user_answers
+	WHERE created_at >= $1 AND created_at < $2
+	UNION
+	SELECT user_id FROM app_test_attempts
+	WHERE created_at >= $1 AND created_at < $2
+) activity
+JOIN app_users u ON u.id = activity.user_id
+WHERE u.deleted_at IS NULL
+"#;
+
+const SESSION_SCORES_SQL: &str = r#"
+SELECT
+	ts.id AS session_id,
+	ts.session_name,
+	COUNT(*) AS attempts,
+	AVG(s.score)::float8 AS average_score
+FROM scored s
+JOIN app_sessions_has_tests st ON st.test_id = s.test_id
+JOIN app_test_sessions ts ON ts.id = st.session_id
+WHERE ts.deleted_at IS NULL AND s.created_at >= $1 AND s.created_at < $2
+GROUP BY ts.id, ts.session_name
+ORDER BY ts.session_name
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct DashboardCountRow {
+	count: i64,
+}
+
+fn parse_stats_range(
+	params: &DashboardStatsRequestDto,
+) -> Result<(NaiveDate, NaiveDate), Response> {
+	let parse = |value: &str| {
+		NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+			common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid date format, expected YYYY-MM-DD",
+			)
+		})
+	};
+
+	let end_date = match params.end_date.as_deref() {
+		Some(value) => parse(value)?,
+		None => Utc::now().date_naive(),
+	};
+	let start_date = match params.start_date.as_deref() {
+		Some(value) => parse(value)?,
+		None => end_date - Duration::days(DASHBOARD_DEFAULT_RANGE_DAYS - 1),
+	};
+
+	if start_date > end_date {
+		return Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"start_date must not be after end_date",
+		));
+	}
+
+	if (end_date - start_date).num_days() >= DASHBOARD_MAX_RANGE_DAYS {
+		return Err(common_response(
+			StatusCode::BAD_REQUEST,
+			&format!(
+				"Date range must not exceed {} days",
+				DASHBOARD_MAX_RANGE_DAYS
+			),
+		));
+	}
+
+	Ok((start_date, end_date))
+}
+
+fn day_start(date: NaiveDate) -> DateTime<Utc> {
+	date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+async fn collect_dashboard_stats(
+	db: &DatabaseConnection,
+	start_date: NaiveDate,
+	end_date: NaiveDate,
+) -> Result<DashboardStatsDto, DbErr> {
+	let range_start = day_start(start_date);
+	let range_end = day_start(end_date + Duration::days(1));
+	let statement = |sql: &str, values: Vec<sea_orm::Value>| {
+		Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+	};
+
+	let registered = UsersEntity::find_active()
+		.filter(UsersColumn::CreatedAt.lt(range_end))
+		.count(db)
+		.await?;
+
+	let verified = UsersEntity::find_active()
+		.filter(UsersColumn::CreatedAt.lt(range_end))
+		.filter(UsersColumn::EmailVerified.is_not_null())
+		.count(db)
+		.await?;
+
+	let active = DashboardCountRow::find_by_statement(statement(
+		ACTIVE_USERS_SQL,
+		vec![range_start.into(), range_end.into()],
+	))
+	.one(db)
+	.await?
+	.map(|row| row.count as u64)
+	.unwrap_or_default();
+
+	let active_sessions = TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::IsActive.eq(true))
+		.count(db)
+		.await?;
+
+	let registrations_per_day =
+		DashboardDailyCountDto::find_by_statement(statement(
+			REGISTRATIONS_PER_DAY_SQL,
+			vec![start_date.into(), end_date.into()],
+		))
+		.all(db)
+		.await?;
+
+	let attempts_per_day = DashboardDailyCountDto::find_by_statement(statement(
+		ATTEMPTS_PER_DAY_SQL,
+		vec![start_date.into(), end_date.into()],
+	))
+	.all(db)
+	.await?;
+
+	let session_scores = DashboardSessionScoreDto::find_by_statement(statement(
+		&format!("{}{}", ANSWER_SCORES_CTE, SESSION_SCORES_SQL),
+		vec![range_start.into(), range_end.into()],
+	))
+	.all(db)
+	.await?;
+
+	Ok(DashboardStatsDto {
+		start_date: start_date.to_string(),
+		end_date: end_date.to_string(),
+		users: DashboardUsersStatsDto {
+			registered,
+			verified,
+			active,
+		},
+		active_sessions,
+		registrations_per_day,
+		attempts_per_day,
+		session_scores,
+		storage: None,
+		generated_at: Utc::now().to_rfc3339(),
+	})
+}
+
+async fn storage_usage(
+	state: &AppState,
+	redis: &mut redis::Connection,
+) -> Option<DashboardStorageDto> {
+	if let Ok(Some(cached)) =
+		redis.get::<_, Option<String>>(DASHBOARD_STORAGE_CACHE_KEY)
+	{
+		if let Ok(storage) = serde_json::from_str::<DashboardStorageDto>(&cached) {
+			return Some(storage);
+		}
+	}
+
+	// Listing can take a while, so it runs on a copy of the client rather
+	// than holding the lock uploads wait on.
+	let minio = state.minio.lock().await.clone();
+	let storage = match minio.bucket_usage().await {
+		Ok(usage) => DashboardStorageDto {
+			objects: usage.objects,
+			bytes: usage.bytes,
+		},
+		Err(err) => {
+			error!("Failed to read storage usage: {}", err);
+			return None;
+		}
+	};
+
+	if let Ok(payload) = serde_json::to_string(&storage) {
+		let _: Result<(), _> = redis.set_ex(
+			DASHBOARD_STORAGE_CACHE_KEY,
+			payload,
+			DASHBOARD_STORAGE_CACHE_TTL,
+		);
+	}
+
+	Some(storage)
+}
+
+pub async fn query_get_dashboard_stats(
+	state: AppState,
+	params: DashboardStatsRequestDto,
+) -> Response {
+	let (start_date, end_date) = match parse_stats_range(&params) {
+		Ok(range) => range,
+		Err(response) => return response,
+	};
+
+	let mut redis = connect_redis();
+	let redis_key = format!("dashboard_stats:{}:{}", start_date, end_date);
+
+	if let Ok(Some(cached)) = redis.get::<_, Option<String>>(&redis_key) {
+		if let Ok(data) = serde_json::from_str::<DashboardStatsDto>(&cached) {
+			return success_response(ResponseSuccessDto { data });
+		}
+	}
+
+	let db: DatabaseConnection = get_db().await;
+
+	let mut stats = match collect_dashboard_stats(&db, start_date, end_date).await {
+		Ok(stats) => stats,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	stats.storage = storage_usage(&state, &mut redis).await;
+
+	if let Ok(payload) = serde_json::to_string(&stats) {
+		let _: Result<(), _> =
+			redis.set_ex(&redis_key, payload, DASHBOARD_STATS_CACHE_TTL);
+	}
+
+	success_response(ResponseSuccessDto { data: stats })
+}
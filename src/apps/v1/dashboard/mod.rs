@@ -0,0 +1,25 @@
+use axum::{routing::get, Router};
+
+use crate::storage::storage_state::storage_state;
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod dashboard_controller;
+pub mod dashboard_dto;
+pub mod dashboard_repository;
+
+pub use dashboard_dto::*;
+pub use dashboard_repository::*;
+
+pub async fn dashboard_router() -> Router {
+	let state = storage_state().await.unwrap();
+
+	Router::new()
+		.route(
+			"/stats",
+			get(dashboard_controller::get_dashboard_stats).with_state(state),
+		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"dashboard",
+			120,
+		)))
+}
@@ -1,23 +1,46 @@
 use crate::apps::v1;
 
 use crate::apps::v1::{
-	AuthChangePasswordRequestDto, AuthDataDto, AuthForgotRequestDto,
+	AuditLogsItemDto, AuthChangePasswordRequestDto, AuthDataDto,
+	AuthForgotRequestDto,
 	AuthLoginRequestDto, AuthNewPasswordRequestDto, AuthRefreshTokenRequestDto,
-	AuthRegisterRequestDto, AuthTokenItemDto, AuthVerifyEmailRequestDto,
+	AuthLoginTwoFactorRequestDto, AuthOidcAuthorizeDto, AuthOidcCallbackRequestDto,
+	AuthRegisterRequestDto, AuthTokenItemDto,
+	AuthTwoFactorChallengeDto, AuthTwoFactorDisableRequestDto,
+	AuthTwoFactorEnableRequestDto, AuthTwoFactorRecoveryCodesDto,
+	AuthTwoFactorSetupDto, AuthVerifyEmailRequestDto, DashboardDailyCountDto,
+	DashboardSessionScoreDto, DashboardStatsDto, DashboardStorageDto,
+	DashboardUsersStatsDto, EventsAttemptsRequestDto, EventsItemDto, GroupsItemDto, GroupsItemListDto, GroupsMemberDto,
+	GroupsRequestCreateDto, GroupsRequestUpdateDto, GroupsResultItemDto,
+	IntegrityAttemptItemDto, IntegrityEventItemDto, IntegrityEventRequestDto,
+	IntegrityEventsRequestDto, IntegrityIngestDto, IntegrityTimelineDto,
 	OptionsItemDto, OptionsRequestCreateDto, OptionsRequestUpdateDto,
-	PermissionsItemDto, PermissionsRequestDto, QuestionsItemDto,
-	QuestionsRequestUpdateDto, RolesItemDto, RolesItemListDto,
+	JobsItemDto, NotificationsBroadcastDto, NotificationsBroadcastRequestDto,
+	NotificationsItemDto, NotificationsUnreadCountDto, OrdersItemDto, OrdersMockPayRequestDto, OrdersRequestCreateDto,
+	PackagesItemDto, PackagesItemListDto, PackagesRequestCreateDto,
+	PackagesRequestUpdateDto, PackagesSessionDto, PermissionsItemDto, PermissionsRequestDto, QuestionBankItemDto,
+	QuestionBankItemListDto, QuestionsItemDto,
+	QuestionsRequestUpdateDto, ReferralsItemDto, ReferralsReportItemDto,
+	ReferralsStatsDto, RolesItemDto, RolesItemListDto,
 	RolesRequestCreateDto, RolesRequestUpdateDto, SessionsItemDto,
-	SessionsItemListDto, SessionsRequestCreateDto, SessionsRequestUpdateDto,
+	SessionsEnrollmentItemDto, SessionsEnrollmentRequestDto, SessionsItemListDto,
+	SessionsExportItemDto, SessionsExtendRequestDto, SessionsRequestCreateDto,
+	SessionsRequestUpdateDto,
 	StorageRequestDto, StorageResponseDto, TestsItemDto, TestsItemListDto,
 	TestsRequestCreateDto, TestsRequestUpdateDto, UsersActiveInactiveRequestDto,
-	UsersCreateRequestDto, UsersItemDto, UsersItemListDto, UsersUpdateRequestDto,
+	UsersCreateRequestDto, UsersItemDto, UsersItemListDto, UsersProgressCategoryDto,
+	UsersProgressDto, UsersProgressHistoryItemDto, UsersProgressTopicDto,
+	UsersUpdateRequestDto, VouchersItemDto, VouchersItemListDto, VouchersPackageDto,
+	VouchersQuoteDto, VouchersRequestCreateDto, VouchersRequestUpdateDto,
+	VouchersValidateRequestDto,
 };
 
 use crate::{
 	MessageResponseDto, MetaRequestDto, MetaResponseDto, QuestionsRequestCreateDto,
 	ResponseSuccessDto, ResponseSuccessListDto, TestAnswersItemDto,
-	TestAnswersRequestCreateDto,
+	OptionAnalyticsDto, QuestionAnalyticsDto, TestAnalyticsDto,
+	TestAnswersRequestCreateDto, TestAttemptAnswersRequestDto, TestAttemptItemDto,
+	TestBlueprintRuleDto,
 };
 
 use utoipa::{
@@ -29,6 +52,9 @@ use utoipa::{
 #[openapi(
     paths(
         v1::auth::auth_controller::post_login,
+        v1::auth::auth_controller::post_login_two_factor,
+        v1::auth::auth_controller::get_oidc_authorize,
+        v1::auth::auth_controller::post_oidc_callback,
         v1::auth::auth_controller::post_register,
         v1::auth::auth_controller::post_forgot,
         v1::auth::auth_controller::post_verify_email,
@@ -36,21 +62,27 @@ use utoipa::{
         v1::auth::auth_controller::post_new_password,
         v1::auth::auth_controller::post_change_password,
         v1::auth::auth_controller::post_refresh,
+        v1::auth::auth_controller::post_two_factor_setup,
+        v1::auth::auth_controller::post_two_factor_enable,
+        v1::auth::auth_controller::post_two_factor_disable,
 
         v1::users::users_controller::get_users,
         v1::users::users_controller::get_detail_user,
         v1::users::users_controller::get_user_me,
+        v1::users::users_controller::get_user_me_progress,
         v1::users::users_controller::put_update_user_me,
         v1::users::users_controller::post_create_user,
         v1::users::users_controller::put_update_user,
         v1::users::users_controller::put_activate_user,
         v1::users::users_controller::delete_user,
+        v1::users::users_controller::put_restore_user,
 
         v1::roles::roles_controller::get_roles,
         v1::roles::roles_controller::get_detail_role,
         v1::roles::roles_controller::post_create_role,
         v1::roles::roles_controller::put_update_role,
         v1::roles::roles_controller::delete_role,
+        v1::roles::roles_controller::put_restore_role,
 
         v1::permissions::permissions_controller::get_permissions,
         v1::permissions::permissions_controller::get_detail_permission,
@@ -63,17 +95,85 @@ use utoipa::{
         v1::sessions::sessions_controller::post_create_session,
         v1::sessions::sessions_controller::put_update_session,
         v1::sessions::sessions_controller::delete_session,
+        v1::sessions::sessions_controller::put_restore_session,
+        v1::sessions::sessions_controller::post_export_session_results,
+        v1::sessions::sessions_controller::put_publish_session_results,
+        v1::sessions::sessions_controller::put_extend_session,
+        v1::sessions::sessions_controller::get_my_sessions,
+        v1::sessions::sessions_controller::get_session_enrollments,
+        v1::sessions::sessions_controller::put_session_enrollments,
 
         v1::tests::tests_controller::get_tests,
         v1::tests::tests_controller::get_detail_test,
         v1::tests::tests_controller::post_create_test,
         v1::tests::tests_controller::put_update_test,
         v1::tests::tests_controller::delete_test,
+        v1::tests::tests_controller::put_restore_test,
+        v1::tests::tests_controller::post_start_test_attempt,
+        v1::tests::tests_controller::put_save_attempt_answers,
         v1::tests::tests_controller::get_test_answer,
         v1::tests::tests_controller::post_create_test_answer,
         v1::tests::tests_controller::delete_test_answer,
+        v1::tests::tests_controller::get_test_analytics,
+
+        v1::questions::questions_controller::get_questions,
+        v1::questions::questions_controller::get_detail_question,
+        v1::questions::questions_controller::post_create_question,
+        v1::questions::questions_controller::put_update_question,
+        v1::questions::questions_controller::delete_question,
 
         v1::storage::storage_controller::post_upload,
+
+        v1::jobs::jobs_controller::get_jobs,
+        v1::jobs::jobs_controller::get_detail_job,
+        v1::jobs::jobs_controller::post_retry_job,
+        v1::jobs::jobs_controller::delete_job,
+
+        v1::referrals::referrals_controller::get_referrals_me,
+        v1::referrals::referrals_controller::get_referrals_report,
+
+        v1::audit_logs::audit_logs_controller::get_audit_logs,
+
+        v1::dashboard::dashboard_controller::get_dashboard_stats,
+
+        v1::groups::groups_controller::get_groups,
+        v1::groups::groups_controller::get_detail_group,
+        v1::groups::groups_controller::post_create_group,
+        v1::groups::groups_controller::put_update_group,
+        v1::groups::groups_controller::delete_group,
+        v1::groups::groups_controller::get_group_results,
+
+        v1::packages::packages_controller::get_packages,
+        v1::packages::packages_controller::get_available_packages,
+        v1::packages::packages_controller::get_detail_package,
+        v1::packages::packages_controller::post_create_package,
+        v1::packages::packages_controller::put_update_package,
+        v1::packages::packages_controller::delete_package,
+
+        v1::orders::orders_controller::get_orders,
+        v1::orders::orders_controller::get_my_orders,
+        v1::orders::orders_controller::post_create_order,
+        v1::orders::orders_controller::put_cancel_order,
+        v1::orders::orders_controller::post_mock_pay_order,
+        v1::orders::orders_controller::post_payment_webhook,
+
+        v1::vouchers::vouchers_controller::get_vouchers,
+        v1::vouchers::vouchers_controller::post_validate_voucher,
+        v1::vouchers::vouchers_controller::get_detail_voucher,
+        v1::vouchers::vouchers_controller::post_create_voucher,
+        v1::vouchers::vouchers_controller::put_update_voucher,
+        v1::vouchers::vouchers_controller::delete_voucher,
+
+        v1::notifications::notifications_controller::get_notifications,
+        v1::notifications::notifications_controller::get_unread_notifications_count,
+        v1::notifications::notifications_controller::put_read_notification,
+        v1::notifications::notifications_controller::put_read_all_notifications,
+        v1::notifications::notifications_controller::post_broadcast_notification,
+        v1::events::events_controller::get_my_events,
+        v1::events::events_controller::get_attempt_events,
+        v1::integrity::integrity_controller::post_integrity_events,
+        v1::integrity::integrity_controller::get_attempt_integrity,
+        v1::integrity::integrity_controller::get_test_integrity,
     ),
     components(
         schemas(
@@ -85,6 +185,7 @@ use utoipa::{
            ResponseSuccessDto<AuthDataDto>,
 
            ResponseSuccessDto<UsersItemDto>,
+           ResponseSuccessDto<UsersProgressDto>,
            ResponseSuccessListDto<UsersItemListDto>,
 
            ResponseSuccessListDto<RolesItemListDto>,
@@ -95,11 +196,64 @@ use utoipa::{
 
            ResponseSuccessListDto<SessionsItemListDto>,
            ResponseSuccessDto<SessionsItemDto>,
+           ResponseSuccessDto<Vec<SessionsItemDto>>,
+           ResponseSuccessDto<Vec<SessionsEnrollmentItemDto>>,
+
+           ResponseSuccessListDto<GroupsItemListDto>,
+           ResponseSuccessDto<GroupsItemDto>,
+           ResponseSuccessListDto<GroupsResultItemDto>,
+
+           ResponseSuccessListDto<PackagesItemListDto>,
+           ResponseSuccessDto<PackagesItemDto>,
+           ResponseSuccessDto<Vec<PackagesItemDto>>,
+
+           ResponseSuccessListDto<OrdersItemDto>,
+           ResponseSuccessDto<OrdersItemDto>,
+           ResponseSuccessDto<Vec<OrdersItemDto>>,
+
+           ResponseSuccessListDto<VouchersItemListDto>,
+           ResponseSuccessDto<VouchersItemDto>,
+           ResponseSuccessDto<VouchersQuoteDto>,
+
+           ResponseSuccessListDto<NotificationsItemDto>,
+           ResponseSuccessDto<NotificationsUnreadCountDto>,
+           ResponseSuccessDto<NotificationsBroadcastDto>,
+           ResponseSuccessDto<IntegrityIngestDto>,
+           ResponseSuccessDto<IntegrityTimelineDto>,
+           ResponseSuccessListDto<IntegrityAttemptItemDto>,
 
            ResponseSuccessListDto<TestsItemListDto>,
            ResponseSuccessDto<TestsItemDto>,
 
+           ResponseSuccessListDto<QuestionBankItemListDto>,
+           ResponseSuccessDto<QuestionBankItemDto>,
+           QuestionBankItemListDto,
+           QuestionBankItemDto,
+
+           ResponseSuccessListDto<JobsItemDto>,
+           ResponseSuccessDto<JobsItemDto>,
+           JobsItemDto,
+
+           ResponseSuccessDto<ReferralsStatsDto>,
+           ResponseSuccessListDto<ReferralsReportItemDto>,
+           ReferralsStatsDto,
+           ReferralsItemDto,
+           ReferralsReportItemDto,
+
+           ResponseSuccessListDto<AuditLogsItemDto>,
+           AuditLogsItemDto,
+
+           ResponseSuccessDto<DashboardStatsDto>,
+           DashboardStatsDto,
+           DashboardUsersStatsDto,
+           DashboardDailyCountDto,
+           DashboardSessionScoreDto,
+           DashboardStorageDto,
+
+
            ResponseSuccessDto<TestAnswersItemDto>,
+           ResponseSuccessDto<TestAttemptItemDto>,
+           ResponseSuccessDto<TestAnalyticsDto>,
 
            ResponseSuccessDto<StorageResponseDto>,
 
@@ -115,11 +269,23 @@ use utoipa::{
            AuthNewPasswordRequestDto,
            AuthChangePasswordRequestDto,
            AuthRefreshTokenRequestDto,
+           AuthLoginTwoFactorRequestDto,
+           AuthTwoFactorChallengeDto,
+           AuthTwoFactorSetupDto,
+           AuthTwoFactorEnableRequestDto,
+           AuthTwoFactorDisableRequestDto,
+           AuthTwoFactorRecoveryCodesDto,
+           AuthOidcAuthorizeDto,
+           AuthOidcCallbackRequestDto,
 
            UsersCreateRequestDto,
            UsersActiveInactiveRequestDto,
            UsersUpdateRequestDto,
            UsersItemDto,
+           UsersProgressDto,
+           UsersProgressHistoryItemDto,
+           UsersProgressCategoryDto,
+           UsersProgressTopicDto,
 
            RolesItemDto,
            RolesItemListDto,
@@ -133,6 +299,48 @@ use utoipa::{
            SessionsItemListDto,
            SessionsRequestCreateDto,
            SessionsRequestUpdateDto,
+           SessionsEnrollmentRequestDto,
+           SessionsExtendRequestDto,
+           SessionsExportItemDto,
+           SessionsEnrollmentItemDto,
+
+           GroupsItemDto,
+           GroupsItemListDto,
+           GroupsMemberDto,
+           GroupsRequestCreateDto,
+           GroupsRequestUpdateDto,
+           GroupsResultItemDto,
+
+           PackagesItemDto,
+           PackagesItemListDto,
+           PackagesSessionDto,
+           PackagesRequestCreateDto,
+           PackagesRequestUpdateDto,
+
+           OrdersItemDto,
+           OrdersRequestCreateDto,
+           OrdersMockPayRequestDto,
+
+           VouchersItemDto,
+           VouchersItemListDto,
+           VouchersPackageDto,
+           VouchersRequestCreateDto,
+           VouchersRequestUpdateDto,
+           VouchersValidateRequestDto,
+           VouchersQuoteDto,
+
+           NotificationsItemDto,
+           NotificationsUnreadCountDto,
+           NotificationsBroadcastRequestDto,
+           NotificationsBroadcastDto,
+           EventsAttemptsRequestDto,
+           EventsItemDto,
+           IntegrityEventRequestDto,
+           IntegrityEventsRequestDto,
+           IntegrityIngestDto,
+           IntegrityEventItemDto,
+           IntegrityTimelineDto,
+           IntegrityAttemptItemDto,
 
            TestsItemDto,
            TestsItemListDto,
@@ -140,6 +348,12 @@ use utoipa::{
            TestsRequestUpdateDto,
            TestAnswersItemDto,
            TestAnswersRequestCreateDto,
+           TestAttemptAnswersRequestDto,
+           TestAttemptItemDto,
+           TestBlueprintRuleDto,
+           TestAnalyticsDto,
+           QuestionAnalyticsDto,
+           OptionAnalyticsDto,
            QuestionsRequestCreateDto,
            QuestionsRequestUpdateDto,
            OptionsRequestCreateDto,
@@ -167,7 +381,6 @@ use utoipa::{
         (name = "Users", description = "List of Users Endpoints")
     )
 )]
-
 pub struct ApiDoc;
 
 struct SecurityAddon;
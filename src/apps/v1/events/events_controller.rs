@@ -0,0 +1,59 @@
+use axum::{extract::Query, http::HeaderMap, response::IntoResponse};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::MessageResponseDto,
+};
+
+use super::{
+	query_stream_attempt_events, query_stream_my_events, EventsAttemptsRequestDto,
+	EventsItemDto,
+};
+
+/// Server-sent events for the caller: `time_warning`, `session_extended`
+/// and `results_published` for the sessions that admit them. The stream
+/// needs the bearer header, so browsers connect with a fetch based SSE
+/// client rather than `EventSource`.
+#[utoipa::path(
+    get,
+    path = "/v1/events/stream",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Event Stream", body = EventsItemDto, content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Events"
+)]
+pub async fn get_my_events(headers: HeaderMap) -> impl IntoResponse {
+	query_stream_my_events(headers).await
+}
+
+/// Server-sent `attempt_progress` events as students start and submit
+/// attempts.
+#[utoipa::path(
+    get,
+    path = "/v1/events/attempts",
+    params(EventsAttemptsRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Attempt Progress Stream", body = EventsItemDto, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid test ID format", body = MessageResponseDto),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Events"
+)]
+pub async fn get_attempt_events(
+	headers: HeaderMap,
+	Query(params): Query<EventsAttemptsRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::MonitorAttempts])
+		.await
+	{
+		Ok(_) => query_stream_attempt_events(params).await,
+		Err(response) => response,
+	}
+}
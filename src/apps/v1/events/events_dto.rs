@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct EventsAttemptsRequestDto {
+	/// Only stream progress for this test.
+	pub test_id: Option<String>,
+}
+
+/// One server-sent event. The SSE `event` field carries `event` and the
+/// `data` field carries this whole object as JSON.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EventsItemDto {
+	/// `user:{id}`, `session:{id}` or `attempts`.
+	pub topic: String,
+	/// `time_warning`, `session_extended`, `results_published`,
+	/// `attempt_auto_submitted` or `attempt_progress`.
+	pub event: String,
+	pub data: serde_json::Value,
+}
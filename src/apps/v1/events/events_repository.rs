@@ -0,0 +1,116 @@
+use axum::{
+	http::StatusCode,
+	response::{
+		sse::{Event, KeepAlive, Sse},
+		IntoResponse, Response,
+	},
+};
+use futures::stream;
+use hyper::HeaderMap;
+use log::warn;
+use sea_orm::DatabaseConnection;
+use std::{collections::HashSet, convert::Infallible};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::{
+	common_response, get_db, orders::find_caller, session_topic,
+	sessions::find_admitted_session_ids, subscribe_events, user_topic,
+	RealtimeEvent, REALTIME_TOPIC_ATTEMPTS,
+};
+
+use super::{EventsAttemptsRequestDto, EventsItemDto};
+
+fn event_to_sse(event: RealtimeEvent) -> Event {
+	let name = event.event.clone();
+	let dto = EventsItemDto {
+		topic: event.topic,
+		event: event.event,
+		data: event.data,
+	};
+
+	Event::default()
+		.event(name)
+		.data(serde_json::to_string(&dto).unwrap_or_default())
+}
+
+/// Streams the events relayed to this instance that `accept` lets through.
+fn event_stream<F>(accept: F) -> Response
+where
+	F: Fn(&RealtimeEvent) -> bool + Send + 'static,
+{
+	let events = stream::unfold(
+		(subscribe_events(), accept),
+		|(mut receiver, accept)| async move {
+			loop {
+				match receiver.recv().await {
+					Ok(event) if accept(&event) => {
+						return Some((
+							Ok::<Event, Infallible>(event_to_sse(event)),
+							(receiver, accept),
+						))
+					}
+					Ok(_) => continue,
+					Err(RecvError::Lagged(skipped)) => {
+						warn!("Event stream lagged, skipped {} events", skipped);
+						continue;
+					}
+					Err(RecvError::Closed) => return None,
+				}
+			}
+		},
+	);
+
+	Sse::new(events)
+		.keep_alive(KeepAlive::default())
+		.into_response()
+}
+
+pub async fn query_stream_my_events(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	// Sessions are resolved on connect; clients reconnect to pick up
+	// sessions they were admitted to later.
+	let session_ids = match find_admitted_session_ids(&db, user.id).await {
+		Ok(session_ids) => session_ids,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut topics: HashSet<String> =
+		session_ids.into_iter().map(session_topic).collect();
+	topics.insert(user_topic(user.id));
+
+	event_stream(move |event| topics.contains(&event.topic))
+}
+
+pub async fn query_stream_attempt_events(
+	params: EventsAttemptsRequestDto,
+) -> Response {
+	let test_id = match params.test_id.as_deref().map(Uuid::parse_str) {
+		Some(Ok(test_id)) => Some(test_id.to_string()),
+		Some(Err(_)) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
+			)
+		}
+		None => None,
+	};
+
+	event_stream(move |event| {
+		event.topic == REALTIME_TOPIC_ATTEMPTS
+			&& test_id.as_ref().is_none_or(|test_id| {
+				event.data["test_id"].as_str() == Some(test_id.as_str())
+			})
+	})
+}
@@ -0,0 +1,20 @@
+use axum::{routing::get, Router};
+
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
+
+pub mod events_controller;
+pub mod events_dto;
+pub mod events_repository;
+
+pub use events_dto::*;
+pub use events_repository::*;
+
+// Streams are long lived, so this only limits how often a client reconnects.
+pub fn events_router() -> Router {
+	Router::new()
+		.route("/stream", get(events_controller::get_my_events))
+		.route("/attempts", get(events_controller::get_attempt_events))
+		.layer(RateLimitLayer::new(
+			RateLimitPolicy::per_minute("events", 30).keyed_by(RateLimitKey::User),
+		))
+}
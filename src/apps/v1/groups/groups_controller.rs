@@ -0,0 +1,170 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_create_group, mutation_delete_group, mutation_update_group,
+	query_get_group_by_id, query_get_group_results, query_get_groups, GroupsItemDto,
+	GroupsItemListDto, GroupsRequestCreateDto, GroupsRequestUpdateDto,
+	GroupsResultItemDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/groups",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Groups", body = ResponseSuccessListDto<GroupsItemListDto>),
+        (status = 400, description = "Invalid Groups data", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn get_groups(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListGroups])
+		.await
+	{
+		Ok(_) => query_get_groups(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/groups/detail/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Detail Group", body = ResponseSuccessDto<GroupsItemDto>),
+        (status = 404, description = "Group not found", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn get_detail_group(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailGroups])
+		.await
+	{
+		Ok(_) => query_get_group_by_id(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/groups/create",
+    request_body = GroupsRequestCreateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 201, description = "Group Created", body = MessageResponseDto),
+        (status = 400, description = "Invalid Group data", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn post_create_group(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<GroupsRequestCreateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreateGroups]).await
+	{
+		Ok(_) => mutation_create_group(Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/groups/update/{id}",
+    request_body = GroupsRequestUpdateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Group Updated", body = MessageResponseDto),
+        (status = 400, description = "Invalid Group data", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn put_update_group(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<GroupsRequestUpdateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateGroups]).await
+	{
+		Ok(_) => mutation_update_group(id, Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/groups/delete/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Group Deleted", body = MessageResponseDto),
+        (status = 404, description = "Group not found", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn delete_group(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteGroups]).await
+	{
+		Ok(_) => mutation_delete_group(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/groups/{id}/results",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Results of the group's students", body = ResponseSuccessListDto<GroupsResultItemDto>),
+        (status = 403, description = "Not an instructor of this group", body = MessageResponseDto),
+        (status = 404, description = "Group not found", body = MessageResponseDto)
+    ),
+    tag = "Groups"
+)]
+pub async fn get_group_results(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailGroups])
+		.await
+	{
+		Ok(_) => query_get_group_results(headers, id, params).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,70 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupsRequestCreateDto {
+	pub name: String,
+	pub description: Option<String>,
+	pub member_ids: Option<Vec<String>>,
+	/// Users with the Staf role who teach this group.
+	pub instructor_ids: Option<Vec<String>>,
+}
+
+/// `member_ids` and `instructor_ids` replace the current lists when present.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupsRequestUpdateDto {
+	pub name: Option<String>,
+	pub description: Option<String>,
+	pub member_ids: Option<Vec<String>>,
+	pub instructor_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupsItemListDto {
+	pub id: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub member_count: i64,
+	pub instructor_count: i64,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct GroupsMemberDto {
+	#[schema(value_type = String)]
+	pub user_id: uuid::Uuid,
+	pub fullname: String,
+	pub email: String,
+	pub student_type: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupsItemDto {
+	pub id: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub instructors: Vec<GroupsMemberDto>,
+	pub members: Vec<GroupsMemberDto>,
+	pub session_ids: Vec<String>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct GroupsResultItemDto {
+	#[schema(value_type = String)]
+	pub answer_id: uuid::Uuid,
+	#[schema(value_type = String)]
+	pub user_id: uuid::Uuid,
+	pub fullname: String,
+	#[schema(value_type = String)]
+	pub test_id: uuid::Uuid,
+	pub test_name: String,
+	pub correct_answers: i64,
+	pub total_questions: i64,
+	pub score: f64,
+	#[schema(value_type = Option<String>)]
+	pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1,805 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::Utc;
+use hyper::HeaderMap;
+use sea_orm::{
+	prelude::Expr, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
+	ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+	FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QuerySelect,
+	RelationTrait, Set, Statement,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+	app_users_schema, audit_snapshot,
+	auth::email_from_headers,
+	common_response, get_db, record_audit_log,
+	roles::RolesEnum,
+	schemas::{
+		GroupMembersActiveModel, GroupMembersColumn, GroupMembersEntity,
+		GroupsActiveModel, GroupsColumn, GroupsEntity, GroupsModel, RolesColumn,
+		SessionGroupsColumn, SessionGroupsEntity, UsersColumn, UsersEntity,
+	},
+	success_response, success_response_list,
+	tests::find_viewer,
+	users::ANSWER_SCORES_CTE,
+	AuditAction, AuditContext, FieldKind, ListParams, ListSpec, MetaRequestDto,
+	MetaResponseDto, ResponseSuccessDto, ResponseSuccessListDto, SoftDelete,
+};
+
+use super::{
+	GroupsItemDto, GroupsItemListDto, GroupsMemberDto, GroupsRequestCreateDto,
+	GroupsRequestUpdateDto, GroupsResultItemDto,
+};
+
+pub const GROUP_ROLE_STUDENT: &str = "student";
+pub const GROUP_ROLE_INSTRUCTOR: &str = "instructor";
+
+const GROUP_MEMBERS_SQL: &str = r#"
+SELECT u.id AS user_id, u.fullname, u.email, u.student_type
+FROM app_group_members m
+JOIN app_users u ON u.id = m.user_id
+WHERE m.group_id = $1 AND m.role = $2 AND u.deleted_at IS NULL
+ORDER BY u.fullname
+"#;
+
+// A group's results are its students' submissions for tests of the sessions
+// assigned to the group, so a student in several classes only shows up with
+// the tests of the class being looked at.
+const GROUP_RESULTS_FROM_SQL: &str = r#"
+FROM scored s
+JOIN app_group_members m
+	ON m.user_id = s.user_id AND m.group_id = $1 AND m.role = 'student'
+JOIN app_users u ON u.id = s.user_id
+WHERE u.deleted_at IS NULL AND EXISTS (
+	SELECT 1 FROM app_session_groups sg
+	JOIN app_sessions_has_tests st ON st.session_id = sg.session_id
+	WHERE sg.group_id = $1 AND st.test_id = s.test_id
+)
+"#;
+
+const GROUP_RESULTS_SELECT_SQL: &str = r#"
+SELECT s.answer_id, s.user_id, u.fullname, s.test_id, s.test_name,
+	s.correct_answers, s.total_questions, s.score, s.created_at
+"#;
+
+const GROUP_RESULTS_PAGE_SQL: &str = r#"
+ORDER BY s.created_at DESC NULLS LAST, s.answer_id
+LIMIT $2 OFFSET $3
+"#;
+
+// Same rule as the group results, for a single answer: `$1` instructs a group
+// that has the answer's author as a student and a session with its test.
+const INSTRUCTOR_SEES_ANSWER_SQL: &str = r#"
+SELECT EXISTS (
+	SELECT 1 FROM app_user_answers a
+	JOIN app_group_members gm
+		ON gm.user_id = a.user_id AND gm.role = 'student'
+	JOIN app_group_members gi
+		ON gi.group_id = gm.group_id AND gi.role = 'instructor'
+	JOIN app_session_groups sg ON sg.group_id = gm.group_id
+	JOIN app_sessions_has_tests st
+		ON st.session_id = sg.session_id AND st.test_id = a.test_id
+	WHERE gi.user_id = $1 AND a.id = $2
+) AS allowed
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct GroupAccessRow {
+	allowed: bool,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct GroupCountRow {
+	count: i64,
+}
+
+/// Whether `instructor_id` teaches the author of `answer_id` in a group the
+/// answer's test was assigned to.
+pub async fn instructor_can_view_answer<C: ConnectionTrait>(
+	db: &C,
+	instructor_id: Uuid,
+	answer_id: Uuid,
+) -> Result<bool, DbErr> {
+	let row = GroupAccessRow::find_by_statement(Statement::from_sql_and_values(
+		DbBackend::Postgres,
+		INSTRUCTOR_SEES_ANSWER_SQL,
+		vec![instructor_id.into(), answer_id.into()],
+	))
+	.one(db)
+	.await?;
+
+	Ok(row.map(|row| row.allowed).unwrap_or(false))
+}
+
+/// Parses and de-duplicates group ids, failing when any group doesn't exist.
+pub async fn resolve_group_ids(
+	db: &DatabaseConnection,
+	group_ids: &[String],
+) -> Result<Vec<Uuid>, Response> {
+	let mut ids: Vec<Uuid> = Vec::with_capacity(group_ids.len());
+	for group_id in group_ids {
+		match Uuid::parse_str(group_id) {
+			Ok(group_id) if !ids.contains(&group_id) => ids.push(group_id),
+			Ok(_) => {}
+			Err(_) => {
+				return Err(common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid group ID format",
+				))
+			}
+		}
+	}
+
+	match GroupsEntity::find()
+		.filter(GroupsColumn::Id.is_in(ids.clone()))
+		.count(db)
+		.await
+	{
+		Ok(found) if found == ids.len() as u64 => Ok(ids),
+		Ok(_) => Err(common_response(
+			StatusCode::NOT_FOUND,
+			"One or more groups not found",
+		)),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+fn parse_user_ids(user_ids: &[String]) -> Result<Vec<Uuid>, Response> {
+	let mut ids: Vec<Uuid> = Vec::with_capacity(user_ids.len());
+	for user_id in user_ids {
+		match Uuid::parse_str(user_id) {
+			Ok(user_id) if !ids.contains(&user_id) => ids.push(user_id),
+			Ok(_) => {}
+			Err(_) => {
+				return Err(common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid user ID format",
+				))
+			}
+		}
+	}
+	Ok(ids)
+}
+
+async fn group_user_ids(
+	db: &DatabaseConnection,
+	group_id: Uuid,
+	role: &str,
+) -> Result<Vec<Uuid>, DbErr> {
+	GroupMembersEntity::find()
+		.select_only()
+		.column(GroupMembersColumn::UserId)
+		.filter(GroupMembersColumn::GroupId.eq(group_id))
+		.filter(GroupMembersColumn::Role.eq(role))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+}
+
+/// Checks that every member exists and every instructor has the Staf role.
+async fn validate_group_users(
+	db: &DatabaseConnection,
+	member_ids: &[Uuid],
+	instructor_ids: &[Uuid],
+) -> Result<(), Response> {
+	if member_ids.iter().any(|id| instructor_ids.contains(id)) {
+		return Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"A user cannot be both a member and an instructor of a group",
+		));
+	}
+
+	let members = UsersEntity::find_active()
+		.filter(UsersColumn::Id.is_in(member_ids.to_vec()))
+		.count(db)
+		.await;
+
+	match members {
+		Ok(found) if found == member_ids.len() as u64 => {}
+		Ok(_) => {
+			return Err(common_response(
+				StatusCode::NOT_FOUND,
+				"One or more users not found",
+			))
+		}
+		Err(err) => {
+			return Err(common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			))
+		}
+	}
+
+	let instructors = UsersEntity::find_active()
+		.join(
+			JoinType::InnerJoin,
+			<app_users_schema::Entity as EntityTrait>::Relation::Role.def(),
+		)
+		.filter(UsersColumn::Id.is_in(instructor_ids.to_vec()))
+		.filter(RolesColumn::Name.eq(RolesEnum::Staf.to_string()))
+		.count(db)
+		.await;
+
+	match instructors {
+		Ok(found) if found == instructor_ids.len() as u64 => Ok(()),
+		Ok(_) => Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"Instructors must be existing users with the Staf role",
+		)),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+async fn replace_group_users(
+	db: &DatabaseConnection,
+	group_id: Uuid,
+	member_ids: &[Uuid],
+	instructor_ids: &[Uuid],
+) -> Result<(), DbErr> {
+	GroupMembersEntity::delete_many()
+		.filter(GroupMembersColumn::GroupId.eq(group_id))
+		.exec(db)
+		.await?;
+
+	let users = member_ids
+		.iter()
+		.map(|id| (id, GROUP_ROLE_STUDENT))
+		.chain(instructor_ids.iter().map(|id| (id, GROUP_ROLE_INSTRUCTOR)));
+
+	for (user_id, role) in users {
+		GroupMembersActiveModel {
+			id: Set(Uuid::new_v4()),
+			group_id: Set(group_id),
+			user_id: Set(*user_id),
+			role: Set(role.to_string()),
+			created_at: Set(Utc::now()),
+		}
+		.insert(db)
+		.await?;
+	}
+
+	Ok(())
+}
+
+const GROUPS_LIST: ListSpec<GroupsEntity> = ListSpec {
+	filters: &[("created_at", GroupsColumn::CreatedAt, FieldKind::DateTime)],
+	sorts: &[
+		("name", GroupsColumn::Name),
+		("created_at", GroupsColumn::CreatedAt),
+		("updated_at", GroupsColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: GroupsColumn::Id,
+};
+
+pub async fn query_get_groups(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query = GroupsEntity::find();
+
+	if !params.search.is_empty() {
+		query = query.filter(
+			Expr::col(GroupsColumn::Name).ilike(format!("%{}%", params.search)),
+		);
+	}
+
+	let (groups, meta) = match params.fetch(&db, query, &GROUPS_LIST, None).await {
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let group_ids: Vec<Uuid> = groups.iter().map(|group| group.id).collect();
+	let counts = match GroupMembersEntity::find()
+		.select_only()
+		.column(GroupMembersColumn::GroupId)
+		.column(GroupMembersColumn::Role)
+		.column_as(GroupMembersColumn::Id.count(), "count")
+		.filter(GroupMembersColumn::GroupId.is_in(group_ids))
+		.group_by(GroupMembersColumn::GroupId)
+		.group_by(GroupMembersColumn::Role)
+		.into_tuple::<(Uuid, String, i64)>()
+		.all(&db)
+		.await
+	{
+		Ok(counts) => counts,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let count_for = |group_id: Uuid, role: &str| {
+		counts
+			.iter()
+			.find(|(id, r, _)| *id == group_id && r == role)
+			.map(|(_, _, count)| *count)
+			.unwrap_or_default()
+	};
+
+	let data: Vec<GroupsItemListDto> = groups
+		.into_iter()
+		.map(|group| GroupsItemListDto {
+			id: group.id.to_string(),
+			member_count: count_for(group.id, GROUP_ROLE_STUDENT),
+			instructor_count: count_for(group.id, GROUP_ROLE_INSTRUCTOR),
+			name: group.name,
+			description: group.description,
+			created_at: group.created_at.map(|dt| dt.to_string()),
+			updated_at: group.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
+
+	success_response_list(ResponseSuccessListDto {
+		data,
+		meta: Some(meta),
+	})
+}
+
+async fn group_item_dto(
+	db: &DatabaseConnection,
+	group: GroupsModel,
+) -> Result<GroupsItemDto, DbErr> {
+	let members_of = |role: &str| {
+		GroupsMemberDto::find_by_statement(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			GROUP_MEMBERS_SQL,
+			vec![group.id.into(), role.into()],
+		))
+		.all(db)
+	};
+
+	let instructors = members_of(GROUP_ROLE_INSTRUCTOR).await?;
+	let members = members_of(GROUP_ROLE_STUDENT).await?;
+
+	let session_ids = SessionGroupsEntity::find()
+		.select_only()
+		.column(SessionGroupsColumn::SessionId)
+		.filter(SessionGroupsColumn::GroupId.eq(group.id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await?;
+
+	Ok(GroupsItemDto {
+		id: group.id.to_string(),
+		name: group.name,
+		description: group.description,
+		instructors,
+		members,
+		session_ids: session_ids.iter().map(Uuid::to_string).collect(),
+		created_at: group.created_at.map(|dt| dt.to_string()),
+		updated_at: group.updated_at.map(|dt| dt.to_string()),
+	})
+}
+
+pub async fn query_get_group_by_id(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let group = match GroupsEntity::find()
+		.filter(GroupsColumn::Id.eq(Uuid::parse_str(&id).unwrap_or_default()))
+		.one(&db)
+		.await
+	{
+		Ok(Some(group)) => group,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Group not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	match group_item_dto(&db, group).await {
+		Ok(data) => success_response(ResponseSuccessDto { data }),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_create_group(
+	payload: Json<GroupsRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	if payload.name.trim().is_empty() {
+		return common_response(StatusCode::BAD_REQUEST, "Group name is required");
+	}
+
+	let member_ids =
+		match parse_user_ids(payload.member_ids.as_deref().unwrap_or_default()) {
+			Ok(ids) => ids,
+			Err(response) => return response,
+		};
+	let instructor_ids = match parse_user_ids(
+		payload.instructor_ids.as_deref().unwrap_or_default(),
+	) {
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
+	if let Err(response) =
+		validate_group_users(&db, &member_ids, &instructor_ids).await
+	{
+		return response;
+	}
+
+	let new_group = GroupsActiveModel {
+		id: Set(Uuid::new_v4()),
+		name: Set(payload.name.trim().to_string()),
+		description: Set(payload.description.clone()),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	};
+
+	let group = match new_group.insert(&db).await {
+		Ok(group) => group,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Err(err) =
+		replace_group_users(&db, group.id, &member_ids, &instructor_ids).await
+	{
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	let after = group_audit_snapshot(&db, &group).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"group",
+		group.id,
+		None,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::CREATED, "Group created successfully")
+}
+
+pub async fn mutation_update_group(
+	id: String,
+	payload: Json<GroupsRequestUpdateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let group_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid group ID format",
+			)
+		}
+	};
+
+	let group = match GroupsEntity::find_by_id(group_id).one(&db).await {
+		Ok(Some(group)) => group,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Group not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Lists that aren't sent keep their current users, so the overlap check
+	// below sees the group as it will be after the update.
+	let member_ids = match &payload.member_ids {
+		Some(ids) => parse_user_ids(ids),
+		None => group_user_ids(&db, group.id, GROUP_ROLE_STUDENT)
+			.await
+			.map_err(|err| {
+				common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			}),
+	};
+	let member_ids = match member_ids {
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
+	let instructor_ids = match &payload.instructor_ids {
+		Some(ids) => parse_user_ids(ids),
+		None => group_user_ids(&db, group.id, GROUP_ROLE_INSTRUCTOR)
+			.await
+			.map_err(|err| {
+				common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			}),
+	};
+	let instructor_ids = match instructor_ids {
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
+	if let Err(response) =
+		validate_group_users(&db, &member_ids, &instructor_ids).await
+	{
+		return response;
+	}
+
+	let before = group_audit_snapshot(&db, &group).await;
+	let mut active_model: GroupsActiveModel = group.into();
+
+	if let Some(name) = &payload.name {
+		if name.trim().is_empty() {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Group name is required",
+			);
+		}
+		active_model.name = Set(name.trim().to_string());
+	}
+	if let Some(description) = &payload.description {
+		active_model.description = Set(Some(description.clone()));
+	}
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	let updated_group = match active_model.update(&db).await {
+		Ok(group) => group,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if payload.member_ids.is_some() || payload.instructor_ids.is_some() {
+		if let Err(err) =
+			replace_group_users(&db, updated_group.id, &member_ids, &instructor_ids)
+				.await
+		{
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	let after = group_audit_snapshot(&db, &updated_group).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"group",
+		updated_group.id,
+		before,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::OK, "Group updated successfully")
+}
+
+pub async fn mutation_delete_group(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let group_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid group ID format",
+			)
+		}
+	};
+
+	let group = match GroupsEntity::find_by_id(group_id).one(&db).await {
+		Ok(Some(group)) => group,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Group not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Members and session assignments go with the group; sessions themselves
+	// and the students' answers are untouched.
+	let before = group_audit_snapshot(&db, &group).await;
+
+	match GroupsEntity::delete_by_id(group.id).exec(&db).await {
+		Ok(_) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"group",
+				group.id,
+				before,
+				None,
+			)
+			.await;
+			common_response(StatusCode::OK, "Group deleted successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+/// Results of the group's students. Admins see every group; anyone else only
+/// the groups they instruct.
+pub async fn query_get_group_results(
+	headers: HeaderMap,
+	id: String,
+	params: MetaRequestDto,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let group_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid group ID format",
+			)
+		}
+	};
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let (viewer_id, is_admin) = match find_viewer(&db, &email).await {
+		Ok(Some(viewer)) => viewer,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	match GroupsEntity::find_by_id(group_id).one(&db).await {
+		Ok(Some(_)) => {}
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Group not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	if !is_admin {
+		match group_user_ids(&db, group_id, GROUP_ROLE_INSTRUCTOR).await {
+			Ok(instructors) if instructors.contains(&viewer_id) => {}
+			Ok(_) => {
+				return common_response(
+					StatusCode::FORBIDDEN,
+					"You are not an instructor of this group",
+				)
+			}
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
+	}
+
+	let page = params.page.unwrap_or(1).max(1);
+	let per_page = params.per_page.unwrap_or(10).clamp(1, 100);
+
+	let total =
+		match GroupCountRow::find_by_statement(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			format!(
+				"{}SELECT COUNT(*) AS count{}",
+				ANSWER_SCORES_CTE, GROUP_RESULTS_FROM_SQL
+			),
+			vec![group_id.into()],
+		))
+		.one(&db)
+		.await
+		{
+			Ok(row) => row.map(|row| row.count as u64).unwrap_or_default(),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		};
+
+	let data =
+		match GroupsResultItemDto::find_by_statement(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			format!(
+				"{}{}{}{}",
+				ANSWER_SCORES_CTE,
+				GROUP_RESULTS_SELECT_SQL,
+				GROUP_RESULTS_FROM_SQL,
+				GROUP_RESULTS_PAGE_SQL
+			),
+			vec![
+				group_id.into(),
+				(per_page as i64).into(),
+				(((page - 1) * per_page) as i64).into(),
+			],
+		))
+		.all(&db)
+		.await
+		{
+			Ok(rows) => rows,
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		};
+
+	success_response_list(ResponseSuccessListDto {
+		data,
+		meta: Some(MetaResponseDto {
+			page: Some(page),
+			per_page: Some(per_page),
+			total: Some(total),
+			next_cursor: None,
+		}),
+	})
+}
+
+/// Group fields plus its students, instructors and assigned sessions.
+async fn group_audit_snapshot(
+	db: &DatabaseConnection,
+	group: &GroupsModel,
+) -> Option<Value> {
+	let member_ids = group_user_ids(db, group.id, GROUP_ROLE_STUDENT)
+		.await
+		.unwrap_or_default();
+	let instructor_ids = group_user_ids(db, group.id, GROUP_ROLE_INSTRUCTOR)
+		.await
+		.unwrap_or_default();
+	let session_ids = SessionGroupsEntity::find()
+		.select_only()
+		.column(SessionGroupsColumn::SessionId)
+		.filter(SessionGroupsColumn::GroupId.eq(group.id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(group)?;
+	snapshot["member_ids"] = json!(member_ids);
+	snapshot["instructor_ids"] = json!(instructor_ids);
+	snapshot["session_ids"] = json!(session_ids);
+	Some(snapshot)
+}
@@ -0,0 +1,26 @@
+use axum::{
+	routing::{delete, get, post, put},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod groups_controller;
+pub mod groups_dto;
+pub mod groups_repository;
+
+pub use groups_dto::*;
+pub use groups_repository::*;
+
+pub fn groups_router() -> Router {
+	Router::new()
+		.route("/", get(groups_controller::get_groups))
+		.route("/create", post(groups_controller::post_create_group))
+		.route("/detail/{id}", get(groups_controller::get_detail_group))
+		.route("/update/{id}", put(groups_controller::put_update_group))
+		.route("/delete/{id}", delete(groups_controller::delete_group))
+		.route("/{id}/results", get(groups_controller::get_group_results))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"groups", 120,
+		)))
+}
@@ -0,0 +1,96 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_ingest_integrity_events, query_get_attempt_integrity,
+	query_get_test_integrity, IntegrityAttemptItemDto, IntegrityEventsRequestDto,
+	IntegrityIngestDto, IntegrityTimelineDto,
+};
+
+/// Reports integrity events for the caller's own attempt, in batches of up
+/// to 50. Reaching the test's `max_violations` submits the attempt.
+#[utoipa::path(
+    post,
+    path = "/v1/integrity/attempts/{id}/events",
+    request_body = IntegrityEventsRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Integrity Events Recorded", body = ResponseSuccessDto<IntegrityIngestDto>),
+        (status = 400, description = "Invalid events", body = MessageResponseDto),
+        (status = 404, description = "Test attempt not found", body = MessageResponseDto),
+        (status = 409, description = "Test attempt already submitted", body = MessageResponseDto)
+    ),
+    tag = "Integrity"
+)]
+pub async fn post_integrity_events(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<IntegrityEventsRequestDto>,
+) -> impl IntoResponse {
+	mutation_ingest_integrity_events(headers, id, Json(payload), &audit).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/integrity/attempts/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Attempt Integrity Timeline", body = ResponseSuccessDto<IntegrityTimelineDto>),
+        (status = 404, description = "Test attempt not found", body = MessageResponseDto)
+    ),
+    tag = "Integrity"
+)]
+pub async fn get_attempt_integrity(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::MonitorAttempts])
+		.await
+	{
+		Ok(_) => query_get_attempt_integrity(id).await,
+		Err(response) => response,
+	}
+}
+
+/// Attempts at a test with their violation counts and suspicion scores,
+/// most suspicious first.
+#[utoipa::path(
+    get,
+    path = "/v1/integrity/tests/{id}",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Test Integrity Overview", body = ResponseSuccessListDto<IntegrityAttemptItemDto>),
+        (status = 400, description = "Invalid test ID format", body = MessageResponseDto)
+    ),
+    tag = "Integrity"
+)]
+pub async fn get_test_integrity(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::MonitorAttempts])
+		.await
+	{
+		Ok(_) => query_get_test_integrity(id, params).await,
+		Err(response) => response,
+	}
+}
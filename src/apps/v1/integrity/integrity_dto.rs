@@ -0,0 +1,73 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// `kind` is one of `tab_hidden`, `tab_visible`, `fullscreen_exit`,
+/// `fullscreen_enter`, `window_blur`, `window_focus`, `copy`, `paste` or
+/// `context_menu`. `occurred_at` is RFC 3339 and defaults to the time the
+/// event is received.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrityEventRequestDto {
+	pub kind: String,
+	pub occurred_at: Option<String>,
+	pub detail: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrityEventsRequestDto {
+	pub events: Vec<IntegrityEventRequestDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrityIngestDto {
+	pub violations: u64,
+	pub max_violations: Option<i32>,
+	/// Set once the attempt has been submitted for exceeding
+	/// `max_violations`; later answers for it are rejected.
+	pub auto_submitted: bool,
+	pub answer_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrityEventItemDto {
+	pub id: String,
+	pub kind: String,
+	/// What the event adds to the suspicion score; `0` for events that only
+	/// mark a return to normal, such as `tab_visible`.
+	pub weight: i32,
+	pub detail: Option<serde_json::Value>,
+	pub occurred_at: String,
+	pub created_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrityTimelineDto {
+	pub attempt_id: String,
+	pub test_id: String,
+	pub user_id: String,
+	pub answer_id: Option<String>,
+	pub violations: u64,
+	/// Sum of the event weights, capped at 100.
+	pub suspicion_score: i64,
+	pub auto_submitted_at: Option<String>,
+	pub started_at: String,
+	pub events: Vec<IntegrityEventItemDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct IntegrityAttemptItemDto {
+	#[schema(value_type = String)]
+	pub attempt_id: uuid::Uuid,
+	#[schema(value_type = String)]
+	pub user_id: uuid::Uuid,
+	pub fullname: String,
+	pub email: String,
+	#[schema(value_type = Option<String>)]
+	pub answer_id: Option<uuid::Uuid>,
+	pub violations: i64,
+	pub suspicion_score: i64,
+	#[schema(value_type = Option<String>)]
+	pub auto_submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+	#[schema(value_type = String)]
+	pub started_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,564 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::{DateTime, Utc};
+use hyper::HeaderMap;
+use log::error;
+use sea_orm::{
+	sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait,
+	DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
+	PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement,
+	TransactionTrait,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+	audit_snapshot, common_response, enqueue_job, get_db,
+	orders::find_caller,
+	publish_event, record_audit_log,
+	schemas::{
+		AttemptAnswersColumn, AttemptAnswersEntity,
+		AttemptIntegrityEventsActiveModel, AttemptIntegrityEventsColumn,
+		AttemptIntegrityEventsEntity, AttemptIntegrityEventsModel,
+		TestAnswersActiveModel, TestAnswersColumn, TestAnswersEntity,
+		TestAnswersModel, TestAttemptsActiveModel, TestAttemptsColumn,
+		TestAttemptsEntity, TestAttemptsModel, TestQuestionsAnswersActiveModel,
+		TestQuestionsAnswersEntity,
+	},
+	success_response, success_response_list, user_topic, AuditAction, AuditContext,
+	FieldKind, Job, ListParams, ListSpec, MetaRequestDto, RealtimeEvent,
+	ResponseSuccessDto, ResponseSuccessListDto, SoftDelete, TestsColumn,
+	TestsEntity, REALTIME_EVENT_ATTEMPT_AUTO_SUBMITTED,
+	REALTIME_EVENT_ATTEMPT_PROGRESS, REALTIME_TOPIC_ATTEMPTS,
+};
+
+use super::{
+	IntegrityAttemptItemDto, IntegrityEventItemDto, IntegrityEventsRequestDto,
+	IntegrityIngestDto, IntegrityTimelineDto,
+};
+
+const INTEGRITY_MAX_BATCH: usize = 50;
+const SUSPICION_SCORE_MAX: i64 = 100;
+
+// How much each kind adds to the suspicion score. Zero weight kinds mark a
+// return to normal and are kept only for the timeline.
+const INTEGRITY_EVENT_WEIGHTS: &[(&str, i32)] = &[
+	("tab_hidden", 8),
+	("tab_visible", 0),
+	("fullscreen_exit", 6),
+	("fullscreen_enter", 0),
+	("window_blur", 3),
+	("window_focus", 0),
+	("copy", 5),
+	("paste", 10),
+	("context_menu", 2),
+];
+
+// Overview rows for one page of attempts, whose ids are bound as a JSON
+// array.
+const ATTEMPTS_INTEGRITY_SQL: &str = r#"
+SELECT
+	a.id AS attempt_id, a.user_id, u.fullname, u.email, ans.id AS answer_id,
+	COUNT(e.id) FILTER (WHERE e.weight > 0) AS violations,
+	LEAST(COALESCE(SUM(e.weight), 0), 100)::bigint AS suspicion_score,
+	a.auto_submitted_at, a.created_at AS started_at
+FROM app_test_attempts a
+JOIN app_users u ON u.id = a.user_id
+LEFT JOIN app_user_answers ans ON ans.attempt_id = a.id
+LEFT JOIN app_attempt_integrity_events e ON e.attempt_id = a.id
+WHERE a.id IN (SELECT jsonb_array_elements_text($1::jsonb)::uuid)
+GROUP BY a.id, u.id, ans.id
+"#;
+
+// Suspicion score of the attempt row, the overview's default order.
+const ATTEMPT_SUSPICION_SQL: &str = r#"
+LEAST(COALESCE((
+	SELECT SUM(e.weight) FROM app_attempt_integrity_events e
+	WHERE e.attempt_id = "app_test_attempts"."id"
+), 0), 100)
+"#;
+
+const ATTEMPTS_INTEGRITY_LIST: ListSpec<TestAttemptsEntity> = ListSpec {
+	filters: &[
+		("user_id", TestAttemptsColumn::UserId, FieldKind::Uuid),
+		("session_id", TestAttemptsColumn::SessionId, FieldKind::Uuid),
+		(
+			"auto_submitted_at",
+			TestAttemptsColumn::AutoSubmittedAt,
+			FieldKind::DateTime,
+		),
+		(
+			"started_at",
+			TestAttemptsColumn::CreatedAt,
+			FieldKind::DateTime,
+		),
+	],
+	sorts: &[
+		("started_at", TestAttemptsColumn::CreatedAt),
+		("auto_submitted_at", TestAttemptsColumn::AutoSubmittedAt),
+	],
+	default_sort: "started_at",
+	id: TestAttemptsColumn::Id,
+};
+
+fn integrity_event_weight(kind: &str) -> Option<i32> {
+	INTEGRITY_EVENT_WEIGHTS
+		.iter()
+		.find(|(name, _)| *name == kind)
+		.map(|(_, weight)| *weight)
+}
+
+fn suspicion_score(events: &[AttemptIntegrityEventsModel]) -> i64 {
+	events
+		.iter()
+		.map(|event| event.weight as i64)
+		.sum::<i64>()
+		.min(SUSPICION_SCORE_MAX)
+}
+
+fn integrity_event_to_dto(
+	event: AttemptIntegrityEventsModel,
+) -> IntegrityEventItemDto {
+	IntegrityEventItemDto {
+		id: event.id.to_string(),
+		kind: event.kind,
+		weight: event.weight,
+		detail: event.detail,
+		occurred_at: event.occurred_at.to_string(),
+		created_at: event.created_at.to_string(),
+	}
+}
+
+enum IngestOutcome {
+	AlreadySubmitted,
+	Recorded {
+		violations: u64,
+		auto_submitted: Option<TestAnswersModel>,
+	},
+}
+
+/// Stores the events and, once the attempt reaches `max_violations`,
+/// submits it. The attempt row is locked so concurrent batches neither
+/// record events after the submission nor submit it twice.
+async fn record_integrity_events<C: ConnectionTrait>(
+	db: &C,
+	attempt_id: Uuid,
+	rows: Vec<AttemptIntegrityEventsActiveModel>,
+	max_violations: Option<i32>,
+) -> Result<IngestOutcome, DbErr> {
+	let attempt = TestAttemptsEntity::find_by_id(attempt_id)
+		.lock_exclusive()
+		.one(db)
+		.await?
+		.ok_or_else(|| DbErr::RecordNotFound("Test attempt not found".into()))?;
+
+	let submitted = attempt.auto_submitted_at.is_some()
+		|| TestAnswersEntity::find()
+			.filter(TestAnswersColumn::AttemptId.eq(attempt.id))
+			.count(db)
+			.await? > 0;
+	if submitted {
+		return Ok(IngestOutcome::AlreadySubmitted);
+	}
+
+	AttemptIntegrityEventsEntity::insert_many(rows)
+		.exec_without_returning(db)
+		.await?;
+
+	let violations = AttemptIntegrityEventsEntity::find()
+		.filter(AttemptIntegrityEventsColumn::AttemptId.eq(attempt.id))
+		.filter(AttemptIntegrityEventsColumn::Weight.gt(0))
+		.count(db)
+		.await?;
+
+	let limit_reached = max_violations.is_some_and(|max| violations >= max as u64);
+	if !limit_reached {
+		return Ok(IngestOutcome::Recorded {
+			violations,
+			auto_submitted: None,
+		});
+	}
+
+	let answer = TestAnswersActiveModel {
+		id: Set(Uuid::new_v4()),
+		user_id: Set(attempt.user_id),
+		test_id: Set(attempt.test_id),
+		attempt_id: Set(Some(attempt.id)),
+		session_id: Set(attempt.session_id),
+		created_at: Set(Some(Utc::now())),
+		score: Set(None),
+		scored_at: Set(None),
+	}
+	.insert(db)
+	.await?;
+
+	// The submission is scored from the answers saved so far.
+	let saved = AttemptAnswersEntity::find()
+		.filter(AttemptAnswersColumn::AttemptId.eq(attempt.id))
+		.all(db)
+		.await?;
+	if !saved.is_empty() {
+		TestQuestionsAnswersEntity::insert_many(saved.into_iter().map(|saved| {
+			TestQuestionsAnswersActiveModel {
+				id: Set(Uuid::new_v4()),
+				answer_id: Set(answer.id),
+				question_id: Set(saved.question_id),
+				option_id: Set(saved.option_id),
+			}
+		}))
+		.exec_without_returning(db)
+		.await?;
+	}
+
+	let mut active_attempt: TestAttemptsActiveModel = attempt.into();
+	active_attempt.auto_submitted_at = Set(Some(Utc::now()));
+	active_attempt.update(db).await?;
+
+	Ok(IngestOutcome::Recorded {
+		violations,
+		auto_submitted: Some(answer),
+	})
+}
+
+pub async fn mutation_ingest_integrity_events(
+	headers: HeaderMap,
+	id: String,
+	payload: Json<IntegrityEventsRequestDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let attempt_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid attempt ID format",
+			)
+		}
+	};
+
+	if payload.events.is_empty() || payload.events.len() > INTEGRITY_MAX_BATCH {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"events must contain between 1 and 50 items",
+		);
+	}
+
+	let attempt = match TestAttemptsEntity::find_by_id(attempt_id)
+		.filter(TestAttemptsColumn::UserId.eq(user.id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(attempt)) => attempt,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Test attempt not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut rows = Vec::with_capacity(payload.events.len());
+	for event in &payload.events {
+		let weight = match integrity_event_weight(&event.kind) {
+			Some(weight) => weight,
+			None => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					&format!("Unknown integrity event kind: {}", event.kind),
+				)
+			}
+		};
+
+		// A client clock running ahead can't date events in the future.
+		let occurred_at = match event.occurred_at.as_deref() {
+			Some(value) => match DateTime::parse_from_rfc3339(value) {
+				Ok(dt) => dt.with_timezone(&Utc).min(Utc::now()),
+				Err(_) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"occurred_at must be an RFC 3339 timestamp",
+					)
+				}
+			},
+			None => Utc::now(),
+		};
+
+		rows.push(AttemptIntegrityEventsActiveModel {
+			id: Set(Uuid::new_v4()),
+			attempt_id: Set(attempt.id),
+			kind: Set(event.kind.clone()),
+			weight: Set(weight),
+			detail: Set(event.detail.clone()),
+			occurred_at: Set(occurred_at),
+			created_at: Set(Utc::now()),
+		});
+	}
+
+	let max_violations = match TestsEntity::find_active()
+		.filter(TestsColumn::Id.eq(attempt.test_id))
+		.one(&db)
+		.await
+	{
+		Ok(test) => test.and_then(|test| test.max_violations),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let txn = match db.begin().await {
+		Ok(txn) => txn,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let outcome = match record_integrity_events(
+		&txn,
+		attempt.id,
+		rows,
+		max_violations,
+	)
+	.await
+	{
+		Ok(outcome) => outcome,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Err(err) = txn.commit().await {
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	let (violations, answer) = match outcome {
+		IngestOutcome::AlreadySubmitted => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Test attempt already submitted",
+			)
+		}
+		IngestOutcome::Recorded {
+			violations,
+			auto_submitted,
+		} => (violations, auto_submitted),
+	};
+
+	publish_event(RealtimeEvent::new(
+		REALTIME_TOPIC_ATTEMPTS.to_string(),
+		REALTIME_EVENT_ATTEMPT_PROGRESS,
+		json!({
+			"status": if answer.is_some() { "auto_submitted" } else { "flagged" },
+			"attempt_id": attempt.id,
+			"test_id": attempt.test_id,
+			"user_id": attempt.user_id,
+			"violations": violations,
+			"kinds": payload
+				.events
+				.iter()
+				.map(|event| event.kind.as_str())
+				.collect::<Vec<_>>(),
+		}),
+	));
+
+	if let Some(answer) = &answer {
+		let job = Job::ComputeResult {
+			answer_id: answer.id,
+		};
+		if let Err(err) = enqueue_job(&db, job).await {
+			error!("Failed to enqueue result computation: {}", err);
+		}
+
+		record_audit_log(
+			&db,
+			audit,
+			AuditAction::Create,
+			"test_answer",
+			answer.id,
+			None,
+			audit_snapshot(answer),
+		)
+		.await;
+
+		publish_event(RealtimeEvent::new(
+			user_topic(attempt.user_id),
+			REALTIME_EVENT_ATTEMPT_AUTO_SUBMITTED,
+			json!({
+				"attempt_id": attempt.id,
+				"test_id": attempt.test_id,
+				"answer_id": answer.id,
+			}),
+		));
+	}
+
+	success_response(ResponseSuccessDto {
+		data: IntegrityIngestDto {
+			violations,
+			max_violations,
+			auto_submitted: answer.is_some(),
+			answer_id: answer.map(|answer| answer.id.to_string()),
+		},
+	})
+}
+
+async fn find_attempt(
+	db: &DatabaseConnection,
+	id: &str,
+) -> Result<TestAttemptsModel, Response> {
+	let attempt_id = Uuid::parse_str(id).map_err(|_| {
+		common_response(StatusCode::BAD_REQUEST, "Invalid attempt ID format")
+	})?;
+
+	match TestAttemptsEntity::find_by_id(attempt_id).one(db).await {
+		Ok(Some(attempt)) => Ok(attempt),
+		Ok(None) => Err(common_response(
+			StatusCode::NOT_FOUND,
+			"Test attempt not found",
+		)),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+pub async fn query_get_attempt_integrity(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let attempt = match find_attempt(&db, &id).await {
+		Ok(attempt) => attempt,
+		Err(response) => return response,
+	};
+
+	let events = match AttemptIntegrityEventsEntity::find()
+		.filter(AttemptIntegrityEventsColumn::AttemptId.eq(attempt.id))
+		.order_by_asc(AttemptIntegrityEventsColumn::OccurredAt)
+		.all(&db)
+		.await
+	{
+		Ok(events) => events,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let answer_id = match TestAnswersEntity::find()
+		.select_only()
+		.column(TestAnswersColumn::Id)
+		.filter(TestAnswersColumn::AttemptId.eq(attempt.id))
+		.into_tuple::<Uuid>()
+		.one(&db)
+		.await
+	{
+		Ok(answer_id) => answer_id,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	success_response(ResponseSuccessDto {
+		data: IntegrityTimelineDto {
+			attempt_id: attempt.id.to_string(),
+			test_id: attempt.test_id.to_string(),
+			user_id: attempt.user_id.to_string(),
+			answer_id: answer_id.map(|id| id.to_string()),
+			violations: events.iter().filter(|event| event.weight > 0).count()
+				as u64,
+			suspicion_score: suspicion_score(&events),
+			auto_submitted_at: attempt.auto_submitted_at.map(|dt| dt.to_string()),
+			started_at: attempt.created_at.to_string(),
+			events: events.into_iter().map(integrity_event_to_dto).collect(),
+		},
+	})
+}
+
+/// Every attempt at the test, most suspicious first.
+pub async fn query_get_test_integrity(
+	id: String,
+	params: MetaRequestDto,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let test_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
+			)
+		}
+	};
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	// Most suspicious first unless the caller picks a sort.
+	let query =
+		TestAttemptsEntity::find().filter(TestAttemptsColumn::TestId.eq(test_id));
+	let rank = Some(Expr::cust(ATTEMPT_SUSPICION_SQL));
+	let (attempts, meta) = match params
+		.fetch(&db, query, &ATTEMPTS_INTEGRITY_LIST, rank)
+		.await
+	{
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let attempt_ids: Vec<Uuid> = attempts.iter().map(|attempt| attempt.id).collect();
+	let mut rows = match IntegrityAttemptItemDto::find_by_statement(
+		Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			ATTEMPTS_INTEGRITY_SQL,
+			vec![json!(attempt_ids).into()],
+		),
+	)
+	.all(&db)
+	.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Keep the page order from the list query.
+	rows.sort_by_key(|row| {
+		attempt_ids
+			.iter()
+			.position(|id| *id == row.attempt_id)
+			.unwrap_or(usize::MAX)
+	});
+
+	success_response_list(ResponseSuccessListDto {
+		data: rows,
+		meta: Some(meta),
+	})
+}
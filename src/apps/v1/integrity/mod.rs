@@ -0,0 +1,35 @@
+use axum::{
+	routing::{get, post},
+	Router,
+};
+
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
+
+pub mod integrity_controller;
+pub mod integrity_dto;
+pub mod integrity_repository;
+
+pub use integrity_dto::*;
+pub use integrity_repository::*;
+
+pub fn integrity_router() -> Router {
+	Router::new()
+		.route(
+			"/attempts/{id}/events",
+			post(integrity_controller::post_integrity_events).layer(
+				RateLimitLayer::new(
+					RateLimitPolicy::per_minute("integrity_events", 60)
+						.keyed_by(RateLimitKey::User),
+				),
+			),
+		)
+		.route(
+			"/attempts/{id}",
+			get(integrity_controller::get_attempt_integrity),
+		)
+		.route("/tests/{id}", get(integrity_controller::get_test_integrity))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"integrity",
+			120,
+		)))
+}
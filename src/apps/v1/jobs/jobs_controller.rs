@@ -0,0 +1,108 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_delete_job, mutation_retry_job, query_get_job_by_id, query_get_jobs,
+	JobsItemDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/jobs",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Jobs", body = ResponseSuccessListDto<JobsItemDto>),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Jobs"
+)]
+pub async fn get_jobs(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListJobs]).await
+	{
+		Ok(_) => query_get_jobs(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/detail/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Detail Job", body = ResponseSuccessDto<JobsItemDto>),
+        (status = 404, description = "Job not found", body = MessageResponseDto)
+    ),
+    tag = "Jobs"
+)]
+pub async fn get_detail_job(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailJobs])
+		.await
+	{
+		Ok(_) => query_get_job_by_id(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/jobs/retry/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Job queued for retry", body = MessageResponseDto),
+        (status = 409, description = "Job is not failed", body = MessageResponseDto)
+    ),
+    tag = "Jobs"
+)]
+pub async fn post_retry_job(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateJobs]).await {
+		Ok(_) => mutation_retry_job(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/jobs/delete/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Job Deleted", body = MessageResponseDto),
+        (status = 409, description = "Job is running", body = MessageResponseDto)
+    ),
+    tag = "Jobs"
+)]
+pub async fn delete_job(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteJobs]).await {
+		Ok(_) => mutation_delete_job(id).await,
+		Err(response) => response,
+	}
+}
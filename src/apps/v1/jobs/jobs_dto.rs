@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct JobsItemDto {
+	pub id: String,
+	pub kind: String,
+	pub status: String,
+	pub payload: serde_json::Value,
+	pub attempts: i32,
+	pub max_attempts: i32,
+	pub run_at: String,
+	pub last_error: Option<String>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
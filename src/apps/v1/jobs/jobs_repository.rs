@@ -0,0 +1,183 @@
+use axum::response::Response;
+use chrono::Utc;
+use hyper::StatusCode;
+use sea_orm::{
+	ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait,
+	PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use uuid::Uuid;
+
+use crate::{
+	common_response, get_db,
+	schemas::{JobsActiveModel, JobsColumn, JobsEntity, JobsModel},
+	success_response, success_response_list, MetaRequestDto, MetaResponseDto,
+	ResponseSuccessDto, ResponseSuccessListDto, JOB_STATUS_FAILED,
+	JOB_STATUS_PENDING, JOB_STATUS_RUNNING,
+};
+
+use super::JobsItemDto;
+
+fn job_to_dto(job: JobsModel) -> JobsItemDto {
+	JobsItemDto {
+		id: job.id.to_string(),
+		kind: job.kind,
+		status: job.status,
+		payload: job.payload,
+		attempts: job.attempts,
+		max_attempts: job.max_attempts,
+		run_at: job.run_at.to_string(),
+		last_error: job.last_error,
+		created_at: job.created_at.map(|dt| dt.to_string()),
+		updated_at: job.updated_at.map(|dt| dt.to_string()),
+	}
+}
+
+async fn find_job(
+	db: &DatabaseConnection,
+	id: &str,
+) -> Result<JobsModel, Response> {
+	let job_id = Uuid::parse_str(id).map_err(|_| {
+		common_response(StatusCode::BAD_REQUEST, "Invalid job ID format")
+	})?;
+
+	match JobsEntity::find_by_id(job_id).one(db).await {
+		Ok(Some(job)) => Ok(job),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "Job not found")),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+pub async fn query_get_jobs(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let page = params.page.unwrap_or(1).max(1);
+	let per_page = params.per_page.unwrap_or(10).clamp(1, 100);
+	let sort_by = params
+		.sort_by
+		.unwrap_or("created_at".to_string())
+		.to_lowercase();
+	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
+	let filter = params.filter.unwrap_or_default();
+	let filter_by = params.filter_by.unwrap_or_default().to_lowercase();
+
+	let mut query = JobsEntity::find();
+
+	if !filter.is_empty() {
+		query = match filter_by.as_str() {
+			"kind" => query.filter(JobsColumn::Kind.eq(filter)),
+			_ => query.filter(JobsColumn::Status.eq(filter)),
+		};
+	}
+
+	query = match (sort_by.as_str(), order.as_str()) {
+		("run_at", "asc") => query.order_by_asc(JobsColumn::RunAt),
+		("run_at", "desc") => query.order_by_desc(JobsColumn::RunAt),
+		("attempts", "asc") => query.order_by_asc(JobsColumn::Attempts),
+		("attempts", "desc") => query.order_by_desc(JobsColumn::Attempts),
+		("created_at", "asc") => query.order_by_asc(JobsColumn::CreatedAt),
+		("updated_at", "asc") => query.order_by_asc(JobsColumn::UpdatedAt),
+		("updated_at", "desc") => query.order_by_desc(JobsColumn::UpdatedAt),
+		_ => query.order_by_desc(JobsColumn::CreatedAt),
+	};
+
+	let paginator = query.paginate(&db, per_page);
+
+	let total_items = match paginator.num_items().await {
+		Ok(count) => count,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let jobs = match paginator.fetch_page(page - 1).await {
+		Ok(data) => data,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let response = ResponseSuccessListDto {
+		data: jobs.into_iter().map(job_to_dto).collect::<Vec<JobsItemDto>>(),
+		meta: Some(MetaResponseDto {
+			page: Some(page),
+			per_page: Some(per_page),
+			total: Some(total_items),
+			next_cursor: None,
+		}),
+	};
+
+	success_response_list(response)
+}
+
+pub async fn query_get_job_by_id(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	match find_job(&db, &id).await {
+		Ok(job) => success_response(ResponseSuccessDto {
+			data: job_to_dto(job),
+		}),
+		Err(response) => response,
+	}
+}
+
+pub async fn mutation_retry_job(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let job = match find_job(&db, &id).await {
+		Ok(job) => job,
+		Err(response) => return response,
+	};
+
+	if job.status != JOB_STATUS_FAILED {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Only failed jobs can be retried",
+		);
+	}
+
+	let mut active_job: JobsActiveModel = job.into();
+	active_job.status = Set(JOB_STATUS_PENDING.to_string());
+	active_job.attempts = Set(0);
+	active_job.run_at = Set(Utc::now());
+	active_job.last_error = Set(None);
+	active_job.updated_at = Set(Some(Utc::now()));
+
+	match active_job.update(&db).await {
+		Ok(_) => common_response(StatusCode::OK, "Job queued for retry"),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_delete_job(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let job = match find_job(&db, &id).await {
+		Ok(job) => job,
+		Err(response) => return response,
+	};
+
+	if job.status == JOB_STATUS_RUNNING {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Running jobs cannot be deleted",
+		);
+	}
+
+	match job.delete(&db).await {
+		Ok(_) => common_response(StatusCode::OK, "Job deleted successfully"),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
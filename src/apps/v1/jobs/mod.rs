@@ -0,0 +1,22 @@
+use axum::{
+	routing::{delete, get, post},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod jobs_controller;
+pub mod jobs_dto;
+pub mod jobs_repository;
+
+pub use jobs_dto::*;
+pub use jobs_repository::*;
+
+pub fn jobs_router() -> Router {
+	Router::new()
+		.route("/", get(jobs_controller::get_jobs))
+		.route("/detail/{id}", get(jobs_controller::get_detail_job))
+		.route("/retry/{id}", post(jobs_controller::post_retry_job))
+		.route("/delete/{id}", delete(jobs_controller::delete_job))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute("jobs", 120)))
+}
@@ -1,32 +1,70 @@
 use axum::{middleware::from_fn, Router};
 
+pub mod audit_logs;
 pub mod auth;
+pub mod dashboard;
 pub mod docs;
+pub mod events;
+pub mod groups;
+pub mod integrity;
+pub mod jobs;
+pub mod notifications;
+pub mod orders;
+pub mod packages;
 pub mod permissions;
+pub mod questions;
+pub mod referrals;
 pub mod roles;
 pub mod sessions;
 pub mod storage;
 pub mod tests;
 pub mod users;
+pub mod vouchers;
 
+pub use audit_logs::*;
 pub use auth::*;
+pub use dashboard::*;
 pub use docs::*;
+pub use events::*;
+pub use groups::*;
+pub use integrity::*;
+pub use jobs::*;
+pub use notifications::*;
+pub use orders::*;
+pub use packages::*;
 pub use permissions::*;
+pub use questions::*;
+pub use referrals::*;
 pub use roles::*;
 pub use sessions::*;
 pub use storage::*;
 pub use tests::*;
 pub use users::*;
+pub use vouchers::*;
 
 pub async fn routes() -> Router {
-	let public_routes = Router::new().nest("/auth", auth::auth_router());
+	let public_routes = Router::new()
+		.nest("/auth", auth::auth_router())
+		.nest("/payments", orders::payments_router());
 
 	let protected_routes = Router::new()
 		.nest("/users", users::users_router())
 		.nest("/roles", roles::roles_router())
 		.nest("/sessions", sessions::sessions_router())
+		.nest("/groups", groups::groups_router())
+		.nest("/packages", packages::packages_router())
+		.nest("/orders", orders::orders_router())
+		.nest("/vouchers", vouchers::vouchers_router())
+		.nest("/notifications", notifications::notifications_router())
+		.nest("/events", events::events_router())
+		.nest("/integrity", integrity::integrity_router())
 		.nest("/tests", tests::tests_router())
+		.nest("/questions", questions::questions_router())
 		.nest("/permissions", permissions::permissions_router())
+		.nest("/jobs", jobs::jobs_router())
+		.nest("/referrals", referrals::referrals_router())
+		.nest("/audit-logs", audit_logs::audit_logs_router())
+		.nest("/dashboard", dashboard::dashboard_router().await)
 		.nest("/storage", storage::storage_router().await)
 		.layer(from_fn(auth::authorization_middleware));
 
@@ -0,0 +1,38 @@
+use axum::{
+	routing::{get, post, put},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod notifications_controller;
+pub mod notifications_dto;
+pub mod notifications_repository;
+
+pub use notifications_dto::*;
+pub use notifications_repository::*;
+
+pub fn notifications_router() -> Router {
+	Router::new()
+		.route("/", get(notifications_controller::get_notifications))
+		.route(
+			"/unread-count",
+			get(notifications_controller::get_unread_notifications_count),
+		)
+		.route(
+			"/read/{id}",
+			put(notifications_controller::put_read_notification),
+		)
+		.route(
+			"/read-all",
+			put(notifications_controller::put_read_all_notifications),
+		)
+		.route(
+			"/broadcast",
+			post(notifications_controller::post_broadcast_notification),
+		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"notifications",
+			120,
+		)))
+}
@@ -0,0 +1,125 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_broadcast_notification, mutation_read_all_notifications,
+	mutation_read_notification, query_get_notifications,
+	query_get_unread_notifications_count, NotificationsBroadcastDto,
+	NotificationsBroadcastRequestDto, NotificationsItemDto,
+	NotificationsUnreadCountDto,
+};
+
+/// The caller's notifications. `filters` takes `status` (`read` or
+/// `unread`), `kind`, `read_at` and `created_at`; the legacy `filter` is
+/// `read` or `unread`, or a kind when `filter_by=kind`.
+#[utoipa::path(
+    get,
+    path = "/v1/notifications",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Notifications", body = ResponseSuccessListDto<NotificationsItemDto>),
+        (status = 400, description = "Invalid filter", body = MessageResponseDto)
+    ),
+    tag = "Notifications"
+)]
+pub async fn get_notifications(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	query_get_notifications(headers, params).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/notifications/unread-count",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Unread Notifications", body = ResponseSuccessDto<NotificationsUnreadCountDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Notifications"
+)]
+pub async fn get_unread_notifications_count(
+	headers: HeaderMap,
+) -> impl IntoResponse {
+	query_get_unread_notifications_count(headers).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/notifications/read/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Notification Read", body = MessageResponseDto),
+        (status = 404, description = "Notification not found", body = MessageResponseDto)
+    ),
+    tag = "Notifications"
+)]
+pub async fn put_read_notification(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	mutation_read_notification(headers, id).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/notifications/read-all",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "All Notifications Read", body = MessageResponseDto),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Notifications"
+)]
+pub async fn put_read_all_notifications(headers: HeaderMap) -> impl IntoResponse {
+	mutation_read_all_notifications(headers).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/notifications/broadcast",
+    request_body = NotificationsBroadcastRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Notification Broadcast", body = ResponseSuccessDto<NotificationsBroadcastDto>),
+        (status = 400, description = "Invalid Broadcast data", body = MessageResponseDto)
+    ),
+    tag = "Notifications"
+)]
+pub async fn post_broadcast_notification(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<NotificationsBroadcastRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(
+		&headers,
+		vec![PermissionsEnum::BroadcastNotifications],
+	)
+	.await
+	{
+		Ok(_) => mutation_broadcast_notification(Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationsItemDto {
+	pub id: String,
+	/// `session_opened`, `results_published`, `password_changed` or
+	/// `broadcast`.
+	pub kind: String,
+	pub title: String,
+	pub body: String,
+	pub data: Option<serde_json::Value>,
+	pub is_read: bool,
+	pub read_at: Option<String>,
+	pub created_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationsUnreadCountDto {
+	pub unread: u64,
+}
+
+/// Reaches active users holding any of `role_ids` or any of `student_types`.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationsBroadcastRequestDto {
+	pub title: String,
+	pub body: String,
+	pub role_ids: Option<Vec<String>>,
+	pub student_types: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationsBroadcastDto {
+	pub recipients: u64,
+}
@@ -0,0 +1,390 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::Utc;
+use hyper::HeaderMap;
+use sea_orm::{
+	sea_query::{Expr, Func},
+	ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr,
+	EntityTrait, PaginatorTrait, QueryFilter, QuerySelect, Set,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+	common_response, get_db,
+	orders::find_caller,
+	record_audit_log,
+	schemas::{
+		NotificationsActiveModel, NotificationsColumn, NotificationsEntity,
+		NotificationsModel, UsersColumn, UsersEntity,
+	},
+	success_response, success_response_list, AuditAction, AuditContext, FieldKind,
+	FilterOp, ListParams, ListSpec, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto, SoftDelete,
+};
+
+use super::{
+	NotificationsBroadcastDto, NotificationsBroadcastRequestDto,
+	NotificationsItemDto, NotificationsUnreadCountDto,
+};
+
+pub const NOTIFICATION_KIND_SESSION_OPENED: &str = "session_opened";
+pub const NOTIFICATION_KIND_RESULTS_PUBLISHED: &str = "results_published";
+pub const NOTIFICATION_KIND_PASSWORD_CHANGED: &str = "password_changed";
+pub const NOTIFICATION_KIND_BROADCAST: &str = "broadcast";
+pub const NOTIFICATION_KIND_EXPORT_READY: &str = "export_ready";
+
+const NOTIFICATION_INSERT_CHUNK: usize = 1000;
+
+/// A notification to deliver; each recipient gets their own copy.
+#[derive(Clone, Debug)]
+pub struct NewNotification {
+	pub kind: &'static str,
+	pub title: String,
+	pub body: String,
+	pub data: Option<Value>,
+}
+
+/// Stores one copy of `notification` per user and returns how many were
+/// written.
+pub async fn notify_users(
+	db: &DatabaseConnection,
+	user_ids: &[Uuid],
+	notification: &NewNotification,
+) -> Result<u64, DbErr> {
+	for chunk in user_ids.chunks(NOTIFICATION_INSERT_CHUNK) {
+		let rows = chunk.iter().map(|user_id| NotificationsActiveModel {
+			id: Set(Uuid::new_v4()),
+			user_id: Set(*user_id),
+			kind: Set(notification.kind.to_string()),
+			title: Set(notification.title.clone()),
+			body: Set(notification.body.clone()),
+			data: Set(notification.data.clone()),
+			read_at: Set(None),
+			created_at: Set(Utc::now()),
+		});
+
+		NotificationsEntity::insert_many(rows)
+			.exec_without_returning(db)
+			.await?;
+	}
+
+	Ok(user_ids.len() as u64)
+}
+
+pub async fn notify_user(
+	db: &DatabaseConnection,
+	user_id: Uuid,
+	notification: &NewNotification,
+) -> Result<(), DbErr> {
+	notify_users(db, &[user_id], notification).await?;
+	Ok(())
+}
+
+fn notification_to_dto(notification: NotificationsModel) -> NotificationsItemDto {
+	NotificationsItemDto {
+		id: notification.id.to_string(),
+		kind: notification.kind,
+		title: notification.title,
+		body: notification.body,
+		data: notification.data,
+		is_read: notification.read_at.is_some(),
+		read_at: notification.read_at.map(|dt| dt.to_string()),
+		created_at: notification.created_at.to_string(),
+	}
+}
+
+const NOTIFICATIONS_LIST: ListSpec<NotificationsEntity> = ListSpec {
+	filters: &[
+		("kind", NotificationsColumn::Kind, FieldKind::Text),
+		("read_at", NotificationsColumn::ReadAt, FieldKind::DateTime),
+		(
+			"created_at",
+			NotificationsColumn::CreatedAt,
+			FieldKind::DateTime,
+		),
+	],
+	sorts: &[
+		("read_at", NotificationsColumn::ReadAt),
+		("created_at", NotificationsColumn::CreatedAt),
+	],
+	default_sort: "created_at",
+	id: NotificationsColumn::Id,
+};
+
+/// Matches notifications in any of `states`, each `read` or `unread`.
+fn read_state_condition(states: &[String]) -> Result<Condition, Response> {
+	let mut condition = Condition::any();
+	for state in states {
+		condition = condition.add(match state.to_lowercase().as_str() {
+			"read" => NotificationsColumn::ReadAt.is_not_null(),
+			"unread" => NotificationsColumn::ReadAt.is_null(),
+			_ => {
+				return Err(common_response(
+					StatusCode::BAD_REQUEST,
+					"status must be one of read or unread",
+				))
+			}
+		});
+	}
+	Ok(condition)
+}
+
+pub async fn query_get_notifications(
+	headers: HeaderMap,
+	mut params: MetaRequestDto,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	// Older clients send `filter=read|unread` with any other `filter_by`.
+	let mut read_states: Vec<Vec<String>> = Vec::new();
+	let filter_by = params.filter_by.clone().unwrap_or_default();
+	if !filter_by.eq_ignore_ascii_case("kind") {
+		if let Some(filter) =
+			params.filter.take().filter(|filter| !filter.is_empty())
+		{
+			read_states.push(vec![filter]);
+		}
+	}
+
+	let mut params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	for filter in params.take_filters("status") {
+		if !matches!(filter.op, FilterOp::Eq | FilterOp::In) {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"status only supports eq and in",
+			);
+		}
+		read_states.push(filter.values);
+	}
+
+	let mut query =
+		NotificationsEntity::find().filter(NotificationsColumn::UserId.eq(user.id));
+
+	for states in &read_states {
+		query = match read_state_condition(states) {
+			Ok(condition) => query.filter(condition),
+			Err(response) => return response,
+		};
+	}
+
+	match params.fetch(&db, query, &NOTIFICATIONS_LIST, None).await {
+		Ok((notifications, meta)) => success_response_list(ResponseSuccessListDto {
+			data: notifications
+				.into_iter()
+				.map(notification_to_dto)
+				.collect::<Vec<_>>(),
+			meta: Some(meta),
+		}),
+		Err(response) => response,
+	}
+}
+
+pub async fn query_get_unread_notifications_count(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	match NotificationsEntity::find()
+		.filter(NotificationsColumn::UserId.eq(user.id))
+		.filter(NotificationsColumn::ReadAt.is_null())
+		.count(&db)
+		.await
+	{
+		Ok(unread) => success_response(ResponseSuccessDto {
+			data: NotificationsUnreadCountDto { unread },
+		}),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_read_notification(headers: HeaderMap, id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let notification_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid notification ID format",
+			)
+		}
+	};
+
+	let notification = match NotificationsEntity::find_by_id(notification_id)
+		.filter(NotificationsColumn::UserId.eq(user.id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(notification)) => notification,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Notification not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Marking an already read notification keeps its original read_at.
+	if notification.read_at.is_none() {
+		let mut active_model: NotificationsActiveModel = notification.into();
+		active_model.read_at = Set(Some(Utc::now()));
+
+		if let Err(err) = active_model.update(&db).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	common_response(StatusCode::OK, "Notification marked as read")
+}
+
+pub async fn mutation_read_all_notifications(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	match NotificationsEntity::update_many()
+		.col_expr(NotificationsColumn::ReadAt, Expr::value(Utc::now()))
+		.filter(NotificationsColumn::UserId.eq(user.id))
+		.filter(NotificationsColumn::ReadAt.is_null())
+		.exec(&db)
+		.await
+	{
+		Ok(_) => common_response(StatusCode::OK, "All notifications marked as read"),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_broadcast_notification(
+	payload: Json<NotificationsBroadcastRequestDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	if payload.title.trim().is_empty() || payload.body.trim().is_empty() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Title and body are required",
+		);
+	}
+
+	let mut role_ids: Vec<Uuid> = vec![];
+	for role_id in payload.role_ids.iter().flatten() {
+		match Uuid::parse_str(role_id) {
+			Ok(role_id) => role_ids.push(role_id),
+			Err(_) => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid role ID format",
+				)
+			}
+		}
+	}
+
+	let student_types: Vec<String> = payload
+		.student_types
+		.iter()
+		.flatten()
+		.map(|student_type| student_type.trim().to_lowercase())
+		.filter(|student_type| !student_type.is_empty())
+		.collect();
+
+	if role_ids.is_empty() && student_types.is_empty() {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Choose at least one role or student type",
+		);
+	}
+
+	let user_ids = match UsersEntity::find_active()
+		.select_only()
+		.column(UsersColumn::Id)
+		.filter(UsersColumn::IsActive.eq(true))
+		.filter(
+			Condition::any()
+				.add(UsersColumn::RoleId.is_in(role_ids.clone()))
+				.add(
+					Expr::expr(Func::lower(Expr::col(UsersColumn::StudentType)))
+						.is_in(student_types.clone()),
+				),
+		)
+		.into_tuple::<Uuid>()
+		.all(&db)
+		.await
+	{
+		Ok(user_ids) => user_ids,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let notification = NewNotification {
+		kind: NOTIFICATION_KIND_BROADCAST,
+		title: payload.title.trim().to_string(),
+		body: payload.body.trim().to_string(),
+		data: None,
+	};
+
+	let recipients = match notify_users(&db, &user_ids, &notification).await {
+		Ok(recipients) => recipients,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"notification_broadcast",
+		Uuid::new_v4(),
+		None,
+		Some(json!({
+			"title": notification.title,
+			"body": notification.body,
+			"role_ids": role_ids,
+			"student_types": student_types,
+			"recipients": recipients,
+		})),
+	)
+	.await;
+
+	success_response(ResponseSuccessDto {
+		data: NotificationsBroadcastDto { recipients },
+	})
+}
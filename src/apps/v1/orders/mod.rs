@@ -0,0 +1,45 @@
+use axum::{
+	routing::{get, post, put},
+	Router,
+};
+
+use crate::{PaymentGateway, RateLimitKey, RateLimitLayer, RateLimitPolicy};
+
+pub mod orders_controller;
+pub mod orders_dto;
+pub mod orders_repository;
+
+pub use orders_dto::*;
+pub use orders_repository::*;
+
+/// `/mock-pay/{id}` only exists while the mock gateway is explicitly enabled.
+pub fn orders_router() -> Router {
+	let router = Router::new()
+		.route("/", get(orders_controller::get_orders))
+		.route("/me", get(orders_controller::get_my_orders))
+		.route("/create", post(orders_controller::post_create_order))
+		.route("/cancel/{id}", put(orders_controller::put_cancel_order));
+
+	let router = match PaymentGateway::from_config() {
+		Ok(gateway) if gateway.is_mock() => router.route(
+			"/mock-pay/{id}",
+			post(orders_controller::post_mock_pay_order),
+		),
+		_ => router,
+	};
+
+	router.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+		"orders", 60,
+	)))
+}
+
+/// Gateway callbacks. Public: requests are authenticated by their signature.
+pub fn payments_router() -> Router {
+	Router::new().route(
+		"/webhook",
+		post(orders_controller::post_payment_webhook).layer(RateLimitLayer::new(
+			RateLimitPolicy::per_minute("payments_webhook", 300)
+				.keyed_by(RateLimitKey::Ip),
+		)),
+	)
+}
@@ -0,0 +1,142 @@
+use axum::{
+	body::Bytes,
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_cancel_order, mutation_create_order, mutation_mock_pay_order,
+	mutation_payment_webhook, query_get_my_orders, query_get_orders, OrdersItemDto,
+	OrdersMockPayRequestDto, OrdersRequestCreateDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/orders",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Orders", body = ResponseSuccessListDto<OrdersItemDto>),
+        (status = 400, description = "Invalid Orders data", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn get_orders(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListOrders])
+		.await
+	{
+		Ok(_) => query_get_orders(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/orders/me",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Orders placed by the caller", body = ResponseSuccessDto<Vec<OrdersItemDto>>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn get_my_orders(headers: HeaderMap) -> impl IntoResponse {
+	query_get_my_orders(headers).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/orders/create",
+    request_body = OrdersRequestCreateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Order placed, pay at payment_url", body = ResponseSuccessDto<OrdersItemDto>),
+        (status = 409, description = "Package already purchased", body = MessageResponseDto),
+        (status = 502, description = "Payment gateway unavailable", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn post_create_order(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<OrdersRequestCreateDto>,
+) -> impl IntoResponse {
+	mutation_create_order(headers, Json(payload), &audit).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/orders/cancel/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Order Cancelled", body = MessageResponseDto),
+        (status = 409, description = "Order is not pending", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn put_cancel_order(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	mutation_cancel_order(headers, id, &audit).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/orders/mock-pay/{id}",
+    request_body = OrdersMockPayRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Mock payment processed", body = MessageResponseDto),
+        (status = 404, description = "Mock payments are disabled", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn post_mock_pay_order(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<OrdersMockPayRequestDto>,
+) -> impl IntoResponse {
+	mutation_mock_pay_order(headers, id, Json(payload), &audit).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/payments/webhook",
+    request_body(content = String, description = "Raw notification body signed by the gateway", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Notification processed", body = MessageResponseDto),
+        (status = 401, description = "Invalid signature", body = MessageResponseDto)
+    ),
+    tag = "Orders"
+)]
+pub async fn post_payment_webhook(
+	headers: HeaderMap,
+	audit: AuditContext,
+	body: Bytes,
+) -> impl IntoResponse {
+	mutation_payment_webhook(headers, body, &audit).await
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrdersRequestCreateDto {
+	pub package_id: String,
+	/// Voucher code, or another student's referral code, to discount the price.
+	pub voucher_code: Option<String>,
+}
+
+/// Outcome the mock gateway reports: `paid` (default), `failed` or `expired`.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrdersMockPayRequestDto {
+	pub status: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrdersItemDto {
+	pub id: String,
+	pub user_id: String,
+	pub package_id: String,
+	pub package_name: String,
+	/// Amount charged, after the discount.
+	pub amount: i64,
+	pub discount_amount: i64,
+	pub voucher_code: Option<String>,
+	/// `pending`, `paid`, `failed`, `expired` or `cancelled`.
+	pub status: String,
+	pub provider: String,
+	pub payment_url: Option<String>,
+	pub paid_at: Option<String>,
+	pub expires_at: String,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
@@ -0,0 +1,773 @@
+use axum::{http::StatusCode, response::Response, Json};
+use bytes::Bytes;
+use chrono::{Duration, Utc};
+use hyper::{header::HeaderValue, HeaderMap};
+use log::{error, warn};
+use sea_orm::{
+	sea_query::OnConflict, ActiveModelTrait, ColumnTrait, ConnectionTrait,
+	DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+	Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+	audit_snapshot,
+	auth::email_from_headers,
+	common_response, get_db,
+	packages::package_session_ids,
+	record_audit_log,
+	referrals::mark_referral_converted,
+	schemas::{
+		EntitlementsActiveModel, EntitlementsColumn, EntitlementsEntity,
+		OrdersActiveModel, OrdersColumn, OrdersEntity, OrdersModel, PackagesColumn,
+		PackagesEntity, PackagesModel, UsersColumn, UsersEntity, UsersModel,
+	},
+	success_response, success_response_list,
+	vouchers::{
+		normalize_voucher_code, quote_discount_code, record_voucher_redemption,
+	},
+	AuditAction, AuditContext, Config, FieldKind, ListParams, ListSpec,
+	MetaRequestDto, PaymentChargeRequest, PaymentError, PaymentGateway,
+	PaymentNotification, PaymentStatus, ResponseSuccessDto, ResponseSuccessListDto,
+	SoftDelete, MOCK_SIGNATURE_HEADER,
+};
+
+use super::{OrdersItemDto, OrdersMockPayRequestDto, OrdersRequestCreateDto};
+
+pub const ORDER_STATUS_PENDING: &str = "pending";
+pub const ORDER_STATUS_PAID: &str = "paid";
+pub const ORDER_STATUS_FAILED: &str = "failed";
+pub const ORDER_STATUS_EXPIRED: &str = "expired";
+pub const ORDER_STATUS_CANCELLED: &str = "cancelled";
+
+/// Pending orders can move anywhere. Money that reached the gateway wins over
+/// a local cancel or an earlier failure; a paid order never changes again.
+fn order_transition_allowed(from: &str, to: &str) -> bool {
+	from == ORDER_STATUS_PENDING
+		|| (to == ORDER_STATUS_PAID
+			&& matches!(
+				from,
+				ORDER_STATUS_FAILED | ORDER_STATUS_EXPIRED | ORDER_STATUS_CANCELLED
+			))
+}
+
+fn order_status_from_payment(status: PaymentStatus) -> &'static str {
+	match status {
+		PaymentStatus::Pending => ORDER_STATUS_PENDING,
+		PaymentStatus::Paid => ORDER_STATUS_PAID,
+		PaymentStatus::Failed => ORDER_STATUS_FAILED,
+		PaymentStatus::Expired => ORDER_STATUS_EXPIRED,
+	}
+}
+
+fn order_item_dto(
+	order: OrdersModel,
+	package: Option<PackagesModel>,
+) -> OrdersItemDto {
+	OrdersItemDto {
+		id: order.id.to_string(),
+		user_id: order.user_id.to_string(),
+		package_id: order.package_id.to_string(),
+		package_name: package.map(|package| package.name).unwrap_or_default(),
+		amount: order.amount,
+		discount_amount: order.discount_amount,
+		voucher_code: order.voucher_code,
+		status: order.status,
+		provider: order.provider,
+		payment_url: order.payment_url,
+		paid_at: order.paid_at.map(|dt| dt.to_string()),
+		expires_at: order.expires_at.to_string(),
+		created_at: order.created_at.map(|dt| dt.to_string()),
+		updated_at: order.updated_at.map(|dt| dt.to_string()),
+	}
+}
+
+pub async fn find_caller(
+	db: &DatabaseConnection,
+	headers: &HeaderMap,
+) -> Result<UsersModel, Response> {
+	let email = email_from_headers(headers)?;
+
+	match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email))
+		.one(db)
+		.await
+	{
+		Ok(Some(user)) => Ok(user),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "User not found")),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+async fn find_caller_order(
+	db: &DatabaseConnection,
+	user: &UsersModel,
+	id: &str,
+) -> Result<OrdersModel, Response> {
+	let order_id = Uuid::parse_str(id).map_err(|_| {
+		common_response(StatusCode::BAD_REQUEST, "Invalid order ID format")
+	})?;
+
+	match OrdersEntity::find_by_id(order_id)
+		.filter(OrdersColumn::UserId.eq(user.id))
+		.one(db)
+		.await
+	{
+		Ok(Some(order)) => Ok(order),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "Order not found")),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+/// Gives the buyer every session in the package. Sessions they already hold
+/// are left alone, so replayed notifications don't fail.
+async fn grant_order_entitlements<C: ConnectionTrait>(
+	db: &C,
+	order: &OrdersModel,
+) -> Result<(), DbErr> {
+	let session_ids = package_session_ids(db, order.package_id).await?;
+	if session_ids.is_empty() {
+		return Ok(());
+	}
+
+	let entitlements =
+		session_ids
+			.into_iter()
+			.map(|session_id| EntitlementsActiveModel {
+				id: Set(Uuid::new_v4()),
+				user_id: Set(order.user_id),
+				session_id: Set(session_id),
+				order_id: Set(Some(order.id)),
+				created_at: Set(Utc::now()),
+			});
+
+	EntitlementsEntity::insert_many(entitlements)
+		.on_conflict(
+			OnConflict::columns([
+				EntitlementsColumn::UserId,
+				EntitlementsColumn::SessionId,
+			])
+			.do_nothing()
+			.to_owned(),
+		)
+		.exec_without_returning(db)
+		.await?;
+
+	Ok(())
+}
+
+enum OrderTransition {
+	Applied(OrdersModel),
+	/// The locked order had already moved somewhere the transition can't
+	/// start from; it is returned as it stands.
+	Skipped(OrdersModel),
+}
+
+/// Moves the order to `status`, granting the package when it becomes paid.
+/// The order is locked for the whole change so concurrent notifications
+/// apply one after another, and a failed grant rolls the status back so the
+/// gateway's retry can pay the order again.
+async fn transition_order(
+	db: &DatabaseConnection,
+	order_id: Uuid,
+	status: &str,
+	reference: Option<String>,
+	audit: &AuditContext,
+) -> Result<OrderTransition, DbErr> {
+	let txn = db.begin().await?;
+
+	let order = OrdersEntity::find_by_id(order_id)
+		.lock_exclusive()
+		.one(&txn)
+		.await?
+		.ok_or_else(|| DbErr::RecordNotFound("Order not found".to_string()))?;
+
+	if order.status == status || !order_transition_allowed(&order.status, status) {
+		return Ok(OrderTransition::Skipped(order));
+	}
+
+	let before = audit_snapshot(&order);
+	let mut active_model: OrdersActiveModel = order.into();
+	active_model.status = Set(status.to_string());
+	if status == ORDER_STATUS_PAID {
+		active_model.paid_at = Set(Some(Utc::now()));
+	}
+	if let Some(reference) = reference {
+		active_model.provider_reference = Set(Some(reference));
+	}
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	let order = active_model.update(&txn).await?;
+
+	if status == ORDER_STATUS_PAID {
+		grant_order_entitlements(&txn, &order).await?;
+	}
+
+	txn.commit().await?;
+
+	if status == ORDER_STATUS_PAID {
+		if let Err(err) = mark_referral_converted(db, order.user_id).await {
+			error!("Failed to convert referral for order {}: {}", order.id, err);
+		}
+	}
+
+	record_audit_log(
+		db,
+		audit,
+		AuditAction::Update,
+		"order",
+		order.id,
+		before,
+		audit_snapshot(&order),
+	)
+	.await;
+
+	Ok(OrderTransition::Applied(order))
+}
+
+const ORDERS_LIST: ListSpec<OrdersEntity> = ListSpec {
+	filters: &[
+		("status", OrdersColumn::Status, FieldKind::Text),
+		("user_id", OrdersColumn::UserId, FieldKind::Uuid),
+		("package_id", OrdersColumn::PackageId, FieldKind::Uuid),
+		("provider", OrdersColumn::Provider, FieldKind::Text),
+		("voucher_code", OrdersColumn::VoucherCode, FieldKind::Text),
+		("paid_at", OrdersColumn::PaidAt, FieldKind::DateTime),
+		("created_at", OrdersColumn::CreatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("amount", OrdersColumn::Amount),
+		("paid_at", OrdersColumn::PaidAt),
+		("created_at", OrdersColumn::CreatedAt),
+		("updated_at", OrdersColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: OrdersColumn::Id,
+};
+
+pub async fn query_get_orders(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let (orders, meta) = match params
+		.fetch(&db, OrdersEntity::find(), &ORDERS_LIST, None)
+		.await
+	{
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let package_ids: Vec<Uuid> =
+		orders.iter().map(|order| order.package_id).collect();
+	let packages: HashMap<Uuid, PackagesModel> = match PackagesEntity::find()
+		.filter(PackagesColumn::Id.is_in(package_ids))
+		.all(&db)
+		.await
+	{
+		Ok(packages) => packages
+			.into_iter()
+			.map(|package| (package.id, package))
+			.collect(),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let data: Vec<OrdersItemDto> = orders
+		.into_iter()
+		.map(|order| {
+			let package = packages.get(&order.package_id).cloned();
+			order_item_dto(order, package)
+		})
+		.collect();
+
+	success_response_list(ResponseSuccessListDto {
+		data,
+		meta: Some(meta),
+	})
+}
+
+pub async fn query_get_my_orders(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	match OrdersEntity::find()
+		.filter(OrdersColumn::UserId.eq(user.id))
+		.find_also_related(PackagesEntity)
+		.order_by_desc(OrdersColumn::CreatedAt)
+		.all(&db)
+		.await
+	{
+		Ok(orders) => {
+			let data: Vec<OrdersItemDto> = orders
+				.into_iter()
+				.map(|(order, package)| order_item_dto(order, package))
+				.collect();
+			success_response(ResponseSuccessDto { data })
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_create_order(
+	headers: HeaderMap,
+	payload: Json<OrdersRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let package = match PackagesEntity::find_by_id(
+		Uuid::parse_str(&payload.package_id).unwrap_or_default(),
+	)
+	.one(&db)
+	.await
+	{
+		Ok(Some(package)) if package.is_active => package,
+		Ok(_) => return common_response(StatusCode::NOT_FOUND, "Package not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut existing = match OrdersEntity::find()
+		.filter(OrdersColumn::UserId.eq(user.id))
+		.filter(OrdersColumn::PackageId.eq(package.id))
+		.filter(
+			OrdersColumn::Status.is_in([ORDER_STATUS_PAID, ORDER_STATUS_PENDING]),
+		)
+		.order_by_desc(OrdersColumn::CreatedAt)
+		.all(&db)
+		.await
+	{
+		Ok(orders) => orders,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if existing
+		.iter()
+		.any(|order| order.status == ORDER_STATUS_PAID)
+	{
+		return common_response(
+			StatusCode::CONFLICT,
+			"You have already purchased this package",
+		);
+	}
+
+	let voucher_code = payload
+		.voucher_code
+		.as_deref()
+		.map(normalize_voucher_code)
+		.filter(|code| !code.is_empty());
+
+	// Hand back a pending order that can still be paid instead of opening a
+	// second charge for the same package and code.
+	if let Some(position) = existing.iter().position(|order| {
+		order.expires_at > Utc::now()
+			&& order.payment_url.is_some()
+			&& order.voucher_code == voucher_code
+	}) {
+		return success_response(ResponseSuccessDto {
+			data: order_item_dto(existing.swap_remove(position), Some(package)),
+		});
+	}
+
+	let gateway = match PaymentGateway::from_config() {
+		Ok(gateway) => gateway,
+		Err(err) => {
+			return common_response(
+				StatusCode::SERVICE_UNAVAILABLE,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// The code's usage limits are checked and its redemption written in the
+	// same transaction as the order, with the voucher row locked throughout.
+	let txn = match db.begin().await {
+		Ok(txn) => txn,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// The new order supersedes the caller's other pending ones for this
+	// package, which also releases any code they were holding. They are
+	// cancelled before the code is quoted so their hold does not count
+	// against it, and a rejected code rolls the cancellation back.
+	let mut cancelled = Vec::new();
+	for order in existing {
+		let before = audit_snapshot(&order);
+		let mut active_model: OrdersActiveModel = order.into();
+		active_model.status = Set(ORDER_STATUS_CANCELLED.to_string());
+		active_model.updated_at = Set(Some(Utc::now()));
+		match active_model.update(&txn).await {
+			Ok(order) => cancelled.push((before, order)),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
+	}
+
+	let quote = match &voucher_code {
+		Some(code) => match quote_discount_code(&txn, &user, &package, code).await {
+			Ok(quote) => Some(quote),
+			Err(response) => return response,
+		},
+		None => None,
+	};
+	let discount = quote.as_ref().map_or(0, |quote| quote.discount);
+
+	let new_order = OrdersActiveModel {
+		id: Set(Uuid::new_v4()),
+		user_id: Set(user.id),
+		package_id: Set(package.id),
+		amount: Set(package.price - discount),
+		discount_amount: Set(discount),
+		voucher_code: Set(quote.as_ref().map(|quote| quote.code.clone())),
+		status: Set(ORDER_STATUS_PENDING.to_string()),
+		provider: Set(gateway.provider.clone()),
+		provider_reference: Set(None),
+		payment_url: Set(None),
+		paid_at: Set(None),
+		expires_at: Set(
+			Utc::now() + Duration::hours(Config::new().order_expiry_hours)
+		),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	};
+
+	let order = match new_order.insert(&txn).await {
+		Ok(order) => order,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Some(quote) = &quote {
+		if let Err(err) = record_voucher_redemption(&txn, quote, &order).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	if let Err(err) = txn.commit().await {
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	for (before, order) in cancelled {
+		record_audit_log(
+			&db,
+			audit,
+			AuditAction::Update,
+			"order",
+			order.id,
+			before,
+			audit_snapshot(&order),
+		)
+		.await;
+	}
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"order",
+		order.id,
+		None,
+		audit_snapshot(&order),
+	)
+	.await;
+
+	// Free packages, or codes covering the full price, have nothing to charge.
+	if order.amount == 0 {
+		return match transition_order(&db, order.id, ORDER_STATUS_PAID, None, audit)
+			.await
+		{
+			Ok(
+				OrderTransition::Applied(order) | OrderTransition::Skipped(order),
+			) => success_response(ResponseSuccessDto {
+				data: order_item_dto(order, Some(package)),
+			}),
+			Err(err) => {
+				common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			}
+		};
+	}
+
+	let charge = gateway
+		.create_charge(&PaymentChargeRequest {
+			order_id: order.id.to_string(),
+			amount: order.amount,
+			item_name: package.name.clone(),
+			customer_name: user.fullname.clone(),
+			customer_email: user.email.clone(),
+		})
+		.await;
+
+	let charge = match charge {
+		Ok(charge) => charge,
+		Err(err) => {
+			error!("Failed to open payment for order {}: {}", order.id, err);
+			if let Err(err) =
+				transition_order(&db, order.id, ORDER_STATUS_FAILED, None, audit)
+					.await
+			{
+				error!("Failed to mark order as failed: {}", err);
+			}
+			return common_response(StatusCode::BAD_GATEWAY, &err.to_string());
+		}
+	};
+
+	let mut active_model: OrdersActiveModel = order.into();
+	active_model.provider_reference = Set(Some(charge.reference));
+	active_model.payment_url = Set(Some(charge.payment_url));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(order) => success_response(ResponseSuccessDto {
+			data: order_item_dto(order, Some(package)),
+		}),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_cancel_order(
+	headers: HeaderMap,
+	id: String,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let order = match find_caller_order(&db, &user, &id).await {
+		Ok(order) => order,
+		Err(response) => return response,
+	};
+
+	if order.status != ORDER_STATUS_PENDING {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Only pending orders can be cancelled",
+		);
+	}
+
+	match transition_order(&db, order.id, ORDER_STATUS_CANCELLED, None, audit).await
+	{
+		Ok(OrderTransition::Applied(_)) => {
+			common_response(StatusCode::OK, "Order cancelled successfully")
+		}
+		Ok(OrderTransition::Skipped(_)) => common_response(
+			StatusCode::CONFLICT,
+			"Only pending orders can be cancelled",
+		),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+/// Applies a verified gateway notification. Replays and stale updates are
+/// acknowledged without changes so the gateway stops retrying them.
+async fn apply_payment_notification(
+	db: &DatabaseConnection,
+	notification: PaymentNotification,
+	audit: &AuditContext,
+) -> Response {
+	let order_id = match Uuid::parse_str(&notification.order_id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid order ID format",
+			)
+		}
+	};
+
+	let order = match OrdersEntity::find_by_id(order_id).one(db).await {
+		Ok(Some(order)) => order,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Order not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Some(amount) = notification.amount {
+		if amount != order.amount {
+			warn!(
+				"Payment amount {} does not match order {} amount {}",
+				amount, order.id, order.amount
+			);
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Payment amount does not match the order",
+			);
+		}
+	}
+
+	let status = order_status_from_payment(notification.status);
+	match transition_order(db, order.id, status, notification.reference, audit).await
+	{
+		Ok(OrderTransition::Applied(_)) => {
+			common_response(StatusCode::OK, "Payment notification processed")
+		}
+		Ok(OrderTransition::Skipped(order)) => common_response(
+			StatusCode::OK,
+			&format!("Order is already {}", order.status),
+		),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_payment_webhook(
+	headers: HeaderMap,
+	body: Bytes,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let gateway = match PaymentGateway::from_config() {
+		Ok(gateway) => gateway,
+		Err(err) => {
+			return common_response(
+				StatusCode::SERVICE_UNAVAILABLE,
+				&err.to_string(),
+			)
+		}
+	};
+
+	match gateway.verify_notification(&headers, &body) {
+		Ok(notification) => {
+			apply_payment_notification(&db, notification, audit).await
+		}
+		Err(PaymentError::InvalidSignature) => {
+			warn!("Rejected payment webhook with an invalid signature");
+			common_response(
+				StatusCode::UNAUTHORIZED,
+				&PaymentError::InvalidSignature.to_string(),
+			)
+		}
+		Err(err) => common_response(StatusCode::BAD_REQUEST, &err.to_string()),
+	}
+}
+
+/// Plays the gateway's part for the mock provider: signs a notification for
+/// the caller's order and runs it through the same path as a real webhook.
+pub async fn mutation_mock_pay_order(
+	headers: HeaderMap,
+	id: String,
+	payload: Json<OrdersMockPayRequestDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let gateway = match PaymentGateway::from_config() {
+		Ok(gateway) if gateway.is_mock() => gateway,
+		_ => {
+			return common_response(
+				StatusCode::NOT_FOUND,
+				"Mock payments are disabled",
+			)
+		}
+	};
+
+	let status = match payload.status.as_deref().unwrap_or(ORDER_STATUS_PAID) {
+		ORDER_STATUS_PAID => PaymentStatus::Paid,
+		ORDER_STATUS_FAILED => PaymentStatus::Failed,
+		ORDER_STATUS_EXPIRED => PaymentStatus::Expired,
+		_ => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"status must be one of paid, failed or expired",
+			)
+		}
+	};
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let order = match find_caller_order(&db, &user, &id).await {
+		Ok(order) => order,
+		Err(response) => return response,
+	};
+
+	let (body, signature) = match gateway.mock_notification(
+		&order.id.to_string(),
+		order.provider_reference.clone(),
+		status,
+		order.amount,
+	) {
+		Ok(notification) => notification,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut webhook_headers = HeaderMap::new();
+	if let Ok(value) = HeaderValue::from_str(&signature) {
+		webhook_headers.insert(MOCK_SIGNATURE_HEADER, value);
+	}
+
+	mutation_payment_webhook(webhook_headers, Bytes::from(body), audit).await
+}
@@ -0,0 +1,29 @@
+use axum::{
+	routing::{delete, get, post, put},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod packages_controller;
+pub mod packages_dto;
+pub mod packages_repository;
+
+pub use packages_dto::*;
+pub use packages_repository::*;
+
+pub fn packages_router() -> Router {
+	Router::new()
+		.route("/", get(packages_controller::get_packages))
+		.route(
+			"/available",
+			get(packages_controller::get_available_packages),
+		)
+		.route("/create", post(packages_controller::post_create_package))
+		.route("/detail/{id}", get(packages_controller::get_detail_package))
+		.route("/update/{id}", put(packages_controller::put_update_package))
+		.route("/delete/{id}", delete(packages_controller::delete_package))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"packages", 120,
+		)))
+}
@@ -0,0 +1,162 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_create_package, mutation_delete_package, mutation_update_package,
+	query_get_available_packages, query_get_package_by_id, query_get_packages,
+	PackagesItemDto, PackagesItemListDto, PackagesRequestCreateDto,
+	PackagesRequestUpdateDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/packages",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Packages", body = ResponseSuccessListDto<PackagesItemListDto>),
+        (status = 400, description = "Invalid Packages data", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn get_packages(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListPackages])
+		.await
+	{
+		Ok(_) => query_get_packages(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/packages/available",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Packages on sale", body = ResponseSuccessDto<Vec<PackagesItemDto>>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn get_available_packages() -> impl IntoResponse {
+	query_get_available_packages().await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/packages/detail/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Detail Package", body = ResponseSuccessDto<PackagesItemDto>),
+        (status = 404, description = "Package not found", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn get_detail_package(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailPackages])
+		.await
+	{
+		Ok(_) => query_get_package_by_id(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/packages/create",
+    request_body = PackagesRequestCreateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 201, description = "Package Created", body = MessageResponseDto),
+        (status = 400, description = "Invalid Package data", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn post_create_package(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<PackagesRequestCreateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreatePackages])
+		.await
+	{
+		Ok(_) => mutation_create_package(Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/packages/update/{id}",
+    request_body = PackagesRequestUpdateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Package Updated", body = MessageResponseDto),
+        (status = 400, description = "Invalid Package data", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn put_update_package(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<PackagesRequestUpdateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdatePackages])
+		.await
+	{
+		Ok(_) => mutation_update_package(id, Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/packages/delete/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Package Deleted", body = MessageResponseDto),
+        (status = 409, description = "Package has orders", body = MessageResponseDto)
+    ),
+    tag = "Packages"
+)]
+pub async fn delete_package(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeletePackages])
+		.await
+	{
+		Ok(_) => mutation_delete_package(id, &audit).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PackagesRequestCreateDto {
+	pub name: String,
+	pub description: Option<String>,
+	/// Price in rupiah. Free packages are granted without a payment.
+	pub price: i64,
+	pub is_active: Option<bool>,
+	pub session_ids: Vec<String>,
+}
+
+/// `session_ids` replaces the bundled sessions when present.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PackagesRequestUpdateDto {
+	pub name: Option<String>,
+	pub description: Option<String>,
+	pub price: Option<i64>,
+	pub is_active: Option<bool>,
+	pub session_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PackagesSessionDto {
+	pub id: String,
+	pub session_name: String,
+	pub category: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PackagesItemListDto {
+	pub id: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub price: i64,
+	pub is_active: bool,
+	pub session_count: u64,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PackagesItemDto {
+	pub id: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub price: i64,
+	pub is_active: bool,
+	pub sessions: Vec<PackagesSessionDto>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
@@ -0,0 +1,527 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::Utc;
+use sea_orm::{
+	prelude::Expr, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
+	ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+	PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+	audit_snapshot, common_response, get_db, record_audit_log,
+	schemas::{
+		OrdersColumn, OrdersEntity, PackageSessionsActiveModel,
+		PackageSessionsColumn, PackageSessionsEntity, PackagesActiveModel,
+		PackagesColumn, PackagesEntity, PackagesModel, TestSessionsColumn,
+		TestSessionsEntity,
+	},
+	success_response, success_response_list, AuditAction, AuditContext, FieldKind,
+	ListParams, ListSpec, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto, SoftDelete,
+};
+
+use super::{
+	PackagesItemDto, PackagesItemListDto, PackagesRequestCreateDto,
+	PackagesRequestUpdateDto, PackagesSessionDto,
+};
+
+/// Parses and de-duplicates session ids, failing when any session doesn't
+/// exist or was deleted.
+async fn resolve_session_ids(
+	db: &DatabaseConnection,
+	session_ids: &[String],
+) -> Result<Vec<Uuid>, Response> {
+	let mut ids: Vec<Uuid> = Vec::with_capacity(session_ids.len());
+	for session_id in session_ids {
+		match Uuid::parse_str(session_id) {
+			Ok(session_id) if !ids.contains(&session_id) => ids.push(session_id),
+			Ok(_) => {}
+			Err(_) => {
+				return Err(common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid session ID format",
+				))
+			}
+		}
+	}
+
+	match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.is_in(ids.clone()))
+		.count(db)
+		.await
+	{
+		Ok(found) if found == ids.len() as u64 => Ok(ids),
+		Ok(_) => Err(common_response(
+			StatusCode::NOT_FOUND,
+			"One or more sessions not found",
+		)),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+fn validate_price(price: i64) -> Result<(), Response> {
+	match price >= 0 {
+		true => Ok(()),
+		false => Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"Price must not be negative",
+		)),
+	}
+}
+
+async fn replace_package_sessions(
+	db: &DatabaseConnection,
+	package_id: Uuid,
+	session_ids: &[Uuid],
+) -> Result<(), DbErr> {
+	PackageSessionsEntity::delete_many()
+		.filter(PackageSessionsColumn::PackageId.eq(package_id))
+		.exec(db)
+		.await?;
+
+	for session_id in session_ids {
+		PackageSessionsActiveModel {
+			id: Set(Uuid::new_v4()),
+			package_id: Set(package_id),
+			session_id: Set(*session_id),
+		}
+		.insert(db)
+		.await?;
+	}
+
+	Ok(())
+}
+
+/// Ids of the sessions bundled in a package, deleted sessions included.
+pub async fn package_session_ids<C: ConnectionTrait>(
+	db: &C,
+	package_id: Uuid,
+) -> Result<Vec<Uuid>, DbErr> {
+	PackageSessionsEntity::find()
+		.select_only()
+		.column(PackageSessionsColumn::SessionId)
+		.filter(PackageSessionsColumn::PackageId.eq(package_id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+}
+
+/// Builds the detail DTOs for several packages with one query for all of
+/// their sessions.
+async fn package_item_dtos(
+	db: &DatabaseConnection,
+	packages: Vec<PackagesModel>,
+) -> Result<Vec<PackagesItemDto>, DbErr> {
+	let package_ids: Vec<Uuid> = packages.iter().map(|package| package.id).collect();
+
+	let sessions = PackageSessionsEntity::find()
+		.filter(PackageSessionsColumn::PackageId.is_in(package_ids))
+		.find_also_related(TestSessionsEntity)
+		.all(db)
+		.await?;
+
+	Ok(packages
+		.into_iter()
+		.map(|package| PackagesItemDto {
+			id: package.id.to_string(),
+			sessions: sessions
+				.iter()
+				.filter(|(link, _)| link.package_id == package.id)
+				.filter_map(|(_, session)| session.as_ref())
+				.filter(|session| session.deleted_at.is_none())
+				.map(|session| PackagesSessionDto {
+					id: session.id.to_string(),
+					session_name: session.session_name.clone(),
+					category: session.category.clone(),
+				})
+				.collect(),
+			name: package.name,
+			description: package.description,
+			price: package.price,
+			is_active: package.is_active,
+			created_at: package.created_at.map(|dt| dt.to_string()),
+			updated_at: package.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect())
+}
+
+const PACKAGES_LIST: ListSpec<PackagesEntity> = ListSpec {
+	filters: &[
+		("is_active", PackagesColumn::IsActive, FieldKind::Bool),
+		("created_at", PackagesColumn::CreatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("name", PackagesColumn::Name),
+		("price", PackagesColumn::Price),
+		("created_at", PackagesColumn::CreatedAt),
+		("updated_at", PackagesColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: PackagesColumn::Id,
+};
+
+pub async fn query_get_packages(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query = PackagesEntity::find();
+
+	if !params.search.is_empty() {
+		query = query.filter(
+			Expr::col(PackagesColumn::Name).ilike(format!("%{}%", params.search)),
+		);
+	}
+
+	let (packages, meta) = match params.fetch(&db, query, &PACKAGES_LIST, None).await
+	{
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let package_ids: Vec<Uuid> = packages.iter().map(|package| package.id).collect();
+	let links = match PackageSessionsEntity::find()
+		.select_only()
+		.column(PackageSessionsColumn::PackageId)
+		.filter(PackageSessionsColumn::PackageId.is_in(package_ids))
+		.into_tuple::<Uuid>()
+		.all(&db)
+		.await
+	{
+		Ok(links) => links,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let data: Vec<PackagesItemListDto> = packages
+		.into_iter()
+		.map(|package| PackagesItemListDto {
+			id: package.id.to_string(),
+			session_count: links.iter().filter(|id| **id == package.id).count()
+				as u64,
+			name: package.name,
+			description: package.description,
+			price: package.price,
+			is_active: package.is_active,
+			created_at: package.created_at.map(|dt| dt.to_string()),
+			updated_at: package.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
+
+	success_response_list(ResponseSuccessListDto {
+		data,
+		meta: Some(meta),
+	})
+}
+
+/// The catalog students buy from: every active package with its sessions.
+pub async fn query_get_available_packages() -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let packages = match PackagesEntity::find()
+		.filter(PackagesColumn::IsActive.eq(true))
+		.order_by_asc(PackagesColumn::Price)
+		.order_by_asc(PackagesColumn::Name)
+		.all(&db)
+		.await
+	{
+		Ok(packages) => packages,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	match package_item_dtos(&db, packages).await {
+		Ok(data) => success_response(ResponseSuccessDto { data }),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn query_get_package_by_id(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let package =
+		match PackagesEntity::find_by_id(Uuid::parse_str(&id).unwrap_or_default())
+			.one(&db)
+			.await
+		{
+			Ok(Some(package)) => package,
+			Ok(None) => {
+				return common_response(StatusCode::NOT_FOUND, "Package not found")
+			}
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		};
+
+	match package_item_dtos(&db, vec![package]).await {
+		Ok(mut items) => success_response(ResponseSuccessDto {
+			data: items.remove(0),
+		}),
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_create_package(
+	payload: Json<PackagesRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	if payload.name.trim().is_empty() {
+		return common_response(StatusCode::BAD_REQUEST, "Package name is required");
+	}
+
+	if let Err(response) = validate_price(payload.price) {
+		return response;
+	}
+
+	let session_ids = match resolve_session_ids(&db, &payload.session_ids).await {
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
+	let new_package = PackagesActiveModel {
+		id: Set(Uuid::new_v4()),
+		name: Set(payload.name.trim().to_string()),
+		description: Set(payload.description.clone()),
+		price: Set(payload.price),
+		is_active: Set(payload.is_active.unwrap_or(true)),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	};
+
+	let package = match new_package.insert(&db).await {
+		Ok(package) => package,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Err(err) = replace_package_sessions(&db, package.id, &session_ids).await {
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	let after = package_audit_snapshot(&db, &package).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"package",
+		package.id,
+		None,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::CREATED, "Package created successfully")
+}
+
+pub async fn mutation_update_package(
+	id: String,
+	payload: Json<PackagesRequestUpdateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let package_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid package ID format",
+			)
+		}
+	};
+
+	let package = match PackagesEntity::find_by_id(package_id).one(&db).await {
+		Ok(Some(package)) => package,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Package not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let session_ids = match &payload.session_ids {
+		Some(session_ids) => match resolve_session_ids(&db, session_ids).await {
+			Ok(ids) => Some(ids),
+			Err(response) => return response,
+		},
+		None => None,
+	};
+
+	let before = package_audit_snapshot(&db, &package).await;
+	let mut active_model: PackagesActiveModel = package.into();
+
+	if let Some(name) = &payload.name {
+		if name.trim().is_empty() {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Package name is required",
+			);
+		}
+		active_model.name = Set(name.trim().to_string());
+	}
+	if let Some(description) = &payload.description {
+		active_model.description = Set(Some(description.clone()));
+	}
+	// Existing orders keep the amount they were created with.
+	if let Some(price) = payload.price {
+		if let Err(response) = validate_price(price) {
+			return response;
+		}
+		active_model.price = Set(price);
+	}
+	if let Some(is_active) = payload.is_active {
+		active_model.is_active = Set(is_active);
+	}
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	let updated_package = match active_model.update(&db).await {
+		Ok(package) => package,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Some(session_ids) = session_ids {
+		if let Err(err) =
+			replace_package_sessions(&db, updated_package.id, &session_ids).await
+		{
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	let after = package_audit_snapshot(&db, &updated_package).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"package",
+		updated_package.id,
+		before,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::OK, "Package updated successfully")
+}
+
+pub async fn mutation_delete_package(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let package_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid package ID format",
+			)
+		}
+	};
+
+	let package = match PackagesEntity::find_by_id(package_id).one(&db).await {
+		Ok(Some(package)) => package,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Package not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Orders are the purchase history; a package that was ordered can only be
+	// deactivated.
+	match OrdersEntity::find()
+		.filter(OrdersColumn::PackageId.eq(package.id))
+		.count(&db)
+		.await
+	{
+		Ok(0) => {}
+		Ok(_) => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Package has orders; deactivate it instead",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	let before = package_audit_snapshot(&db, &package).await;
+
+	match PackagesEntity::delete_by_id(package.id).exec(&db).await {
+		Ok(_) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"package",
+				package.id,
+				before,
+				None,
+			)
+			.await;
+			common_response(StatusCode::OK, "Package deleted successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+/// Package fields plus its bundled sessions.
+async fn package_audit_snapshot(
+	db: &DatabaseConnection,
+	package: &PackagesModel,
+) -> Option<Value> {
+	let session_ids = package_session_ids(db, package.id)
+		.await
+		.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(package)?;
+	snapshot["session_ids"] = json!(session_ids);
+	Some(snapshot)
+}
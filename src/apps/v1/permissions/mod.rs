@@ -3,6 +3,8 @@ use axum::{
 	Router,
 };
 
+use crate::{RateLimitLayer, RateLimitPolicy};
+
 pub mod permissions_controller;
 pub mod permissions_dto;
 pub mod permissions_enum;
@@ -33,4 +35,8 @@ pub fn permissions_router() -> Router {
 			"/delete/{id}",
 			delete(permissions_controller::delete_permission),
 		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"permissions",
+			120,
+		)))
 }
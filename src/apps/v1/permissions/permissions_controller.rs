@@ -8,7 +8,7 @@ use axum::{
 use crate::{
 	permissions::{permissions_middleware, PermissionsEnum},
 	utils::dto::{MessageResponseDto, MetaRequestDto},
-	ResponseSuccessDto, ResponseSuccessListDto,
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
 };
 
 use super::{
@@ -87,12 +87,13 @@ pub async fn get_detail_permission(
 )]
 pub async fn post_create_permission(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<PermissionsRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreatePermissions])
 		.await
 	{
-		Ok(_) => mutation_create_permission(Json(payload)).await,
+		Ok(_) => mutation_create_permission(Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -111,12 +112,13 @@ pub async fn post_create_permission(
 )]
 pub async fn delete_permission(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeletePermissions])
 		.await
 	{
-		Ok(_) => mutation_delete_permission(id).await,
+		Ok(_) => mutation_delete_permission(id, &audit).await,
 		Err(response) => response,
 	}
 }
@@ -136,13 +138,14 @@ pub async fn delete_permission(
 )]
 pub async fn put_update_permission(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<PermissionsRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdatePermissions])
 		.await
 	{
-		Ok(_) => mutation_update_permission(id, Json(payload)).await,
+		Ok(_) => mutation_update_permission(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -27,6 +27,38 @@ pub enum PermissionsEnum {
 	CreateTests,
 	UpdateTests,
 	DeleteTests,
+	ReadTestAnalytics,
+	ReadListQuestions,
+	ReadDetailQuestions,
+	CreateQuestions,
+	UpdateQuestions,
+	DeleteQuestions,
+	ReadListJobs,
+	ReadDetailJobs,
+	UpdateJobs,
+	DeleteJobs,
+	ReadListReferrals,
+	ReadListAuditLogs,
+	ReadDashboardStats,
+	ReadListGroups,
+	ReadDetailGroups,
+	CreateGroups,
+	UpdateGroups,
+	DeleteGroups,
+	ReadListPackages,
+	ReadDetailPackages,
+	CreatePackages,
+	UpdatePackages,
+	DeletePackages,
+	ReadListOrders,
+	ReadListVouchers,
+	ReadDetailVouchers,
+	CreateVouchers,
+	UpdateVouchers,
+	DeleteVouchers,
+	BroadcastNotifications,
+	MonitorAttempts,
+	ExportSessionResults,
 }
 
 impl fmt::Display for PermissionsEnum {
@@ -57,6 +89,38 @@ impl fmt::Display for PermissionsEnum {
 			PermissionsEnum::CreateTests => "Create Tests",
 			PermissionsEnum::UpdateTests => "Update Tests",
 			PermissionsEnum::DeleteTests => "Delete Tests",
+			PermissionsEnum::ReadTestAnalytics => "Read Test Analytics",
+			PermissionsEnum::ReadListQuestions => "Read List Questions",
+			PermissionsEnum::ReadDetailQuestions => "Read Detail Questions",
+			PermissionsEnum::CreateQuestions => "Create Questions",
+			PermissionsEnum::UpdateQuestions => "Update Questions",
+			PermissionsEnum::DeleteQuestions => "Delete Questions",
+			PermissionsEnum::ReadListJobs => "Read List Jobs",
+			PermissionsEnum::ReadDetailJobs => "Read Detail Jobs",
+			PermissionsEnum::UpdateJobs => "Update Jobs",
+			PermissionsEnum::DeleteJobs => "Delete Jobs",
+			PermissionsEnum::ReadListReferrals => "Read List Referrals",
+			PermissionsEnum::ReadListAuditLogs => "Read List Audit Logs",
+			PermissionsEnum::ReadDashboardStats => "Read Dashboard Stats",
+			PermissionsEnum::ReadListGroups => "Read List Groups",
+			PermissionsEnum::ReadDetailGroups => "Read Detail Groups",
+			PermissionsEnum::CreateGroups => "Create Groups",
+			PermissionsEnum::UpdateGroups => "Update Groups",
+			PermissionsEnum::DeleteGroups => "Delete Groups",
+			PermissionsEnum::ReadListPackages => "Read List Packages",
+			PermissionsEnum::ReadDetailPackages => "Read Detail Packages",
+			PermissionsEnum::CreatePackages => "Create Packages",
+			PermissionsEnum::UpdatePackages => "Update Packages",
+			PermissionsEnum::DeletePackages => "Delete Packages",
+			PermissionsEnum::ReadListOrders => "Read List Orders",
+			PermissionsEnum::ReadListVouchers => "Read List Vouchers",
+			PermissionsEnum::ReadDetailVouchers => "Read Detail Vouchers",
+			PermissionsEnum::CreateVouchers => "Create Vouchers",
+			PermissionsEnum::UpdateVouchers => "Update Vouchers",
+			PermissionsEnum::DeleteVouchers => "Delete Vouchers",
+			PermissionsEnum::BroadcastNotifications => "Broadcast Notifications",
+			PermissionsEnum::MonitorAttempts => "Monitor Attempts",
+			PermissionsEnum::ExportSessionResults => "Export Session Results",
 		};
 		write!(f, "{}", permission_str)
 	}
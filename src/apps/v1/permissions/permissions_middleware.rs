@@ -89,6 +89,13 @@ pub async fn permissions_middleware(
 		}
 	};
 
+	if user.two_factor_setup_required {
+		return Err(common_response(
+			StatusCode::FORBIDDEN,
+			"Two-factor authentication setup is required for your role",
+		));
+	}
+
 	let role = match user.role {
 		Some(role) => role,
 		None => {
@@ -2,22 +2,23 @@ use axum::{response::Response, Json};
 use hyper::StatusCode;
 use sea_orm::{
 	prelude::Expr, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
-	ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait,
-	QueryFilter, QueryOrder, Set,
+	ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, Set,
 };
 use uuid::Uuid;
 
 use crate::{
-	common_response, get_db,
+	audit_snapshot, common_response, get_db, record_audit_log,
 	schemas::{PermissionsActiveModel, PermissionsColumn, PermissionsEntity},
-	success_response, success_response_list, MetaRequestDto, MetaResponseDto,
-	ResponseSuccessDto, ResponseSuccessListDto,
+	success_response, success_response_list, AuditAction, AuditContext, FieldKind,
+	ListParams, ListSpec, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto,
 };
 
 use super::{PermissionsItemDto, PermissionsRequestDto};
 
 pub async fn mutation_create_permission(
 	payload: Json<PermissionsRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -40,7 +41,17 @@ pub async fn mutation_create_permission(
 	};
 
 	match new_permission.insert(&db).await {
-		Ok(_) => {
+		Ok(permission) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Create,
+				"permission",
+				permission.id,
+				None,
+				audit_snapshot(&permission),
+			)
+			.await;
 			common_response(StatusCode::CREATED, "Permission created successfully")
 		}
 		Err(err) => {
@@ -49,57 +60,42 @@ pub async fn mutation_create_permission(
 	}
 }
 
+const PERMISSIONS_LIST: ListSpec<PermissionsEntity> = ListSpec {
+	filters: &[(
+		"created_at",
+		PermissionsColumn::CreatedAt,
+		FieldKind::DateTime,
+	)],
+	sorts: &[
+		("name", PermissionsColumn::Name),
+		("created_at", PermissionsColumn::CreatedAt),
+		("updated_at", PermissionsColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: PermissionsColumn::Id,
+};
+
 pub async fn query_get_permissions(params: MetaRequestDto) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	let page = params.page.unwrap_or(1).max(1);
-	let per_page = params.per_page.unwrap_or(10).max(1).min(100);
-	let search = params.search.unwrap_or_default().to_lowercase();
-	let sort_by = params
-		.sort_by
-		.unwrap_or("created_at".to_string())
-		.to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
 
 	let mut query = PermissionsEntity::find();
 
-	if !search.is_empty() {
+	if !params.search.is_empty() {
 		query = query.filter(
-			Expr::col(PermissionsColumn::Name).ilike(format!("%{}%", search)),
+			Expr::col(PermissionsColumn::Name).ilike(format!("%{}%", params.search)),
 		);
 	}
 
-	query = match (sort_by.as_str(), order.as_str()) {
-		("name", "asc") => query.order_by_asc(PermissionsColumn::Name),
-		("name", "desc") => query.order_by_desc(PermissionsColumn::Name),
-		("created_at", "asc") => query.order_by_asc(PermissionsColumn::CreatedAt),
-		("created_at", "desc") => query.order_by_desc(PermissionsColumn::CreatedAt),
-		("updated_at", "asc") => query.order_by_asc(PermissionsColumn::UpdatedAt),
-		("updated_at", "desc") => query.order_by_desc(PermissionsColumn::UpdatedAt),
-		_ => query.order_by_desc(PermissionsColumn::CreatedAt),
-	};
-
-	let paginator = query.paginate(&db, per_page);
-
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
-		}
-	};
-
-	let permissions = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
-		}
-	};
+	let (permissions, meta) =
+		match params.fetch(&db, query, &PERMISSIONS_LIST, None).await {
+			Ok(page) => page,
+			Err(response) => return response,
+		};
 
 	let data: Vec<PermissionsItemDto> = permissions
 		.into_iter()
@@ -113,11 +109,7 @@ pub async fn query_get_permissions(params: MetaRequestDto) -> Response {
 
 	let response = ResponseSuccessListDto {
 		data,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+		meta: Some(meta),
 	};
 
 	success_response_list(response)
@@ -160,6 +152,7 @@ pub async fn query_get_permission_by_id(id: String) -> Response {
 pub async fn mutation_update_permission(
 	id: String,
 	payload: Json<PermissionsRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -190,6 +183,7 @@ pub async fn mutation_update_permission(
 		}
 	};
 
+	let before = audit_snapshot(&permission);
 	let mut active_model: PermissionsActiveModel = permission.into();
 
 	if !payload.name.is_empty() {
@@ -198,14 +192,29 @@ pub async fn mutation_update_permission(
 	active_model.updated_at = Set(Some(chrono::Utc::now()));
 
 	match active_model.update(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "Permission updated successfully"),
+		Ok(permission) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Update,
+				"permission",
+				permission.id,
+				before,
+				audit_snapshot(&permission),
+			)
+			.await;
+			common_response(StatusCode::OK, "Permission updated successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
 	}
 }
 
-pub async fn mutation_delete_permission(id: String) -> Response {
+pub async fn mutation_delete_permission(
+	id: String,
+	audit: &AuditContext,
+) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	let permission_id = match Uuid::parse_str(&id) {
@@ -235,8 +244,22 @@ pub async fn mutation_delete_permission(id: String) -> Response {
 		}
 	};
 
+	let before = audit_snapshot(&permission);
+
 	match permission.delete(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "Permission deleted successfully"),
+		Ok(_) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"permission",
+				permission_id,
+				before,
+				None,
+			)
+			.await;
+			common_response(StatusCode::OK, "Permission deleted successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
@@ -0,0 +1,35 @@
+use axum::{
+	routing::{delete, get, post, put},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod questions_controller;
+pub mod questions_dto;
+pub mod questions_repository;
+
+pub use questions_dto::*;
+pub use questions_repository::*;
+
+pub fn questions_router() -> Router {
+	Router::new()
+		.route("/", get(questions_controller::get_questions))
+		.route("/create", post(questions_controller::post_create_question))
+		.route(
+			"/detail/{id}",
+			get(questions_controller::get_detail_question),
+		)
+		.route(
+			"/update/{id}",
+			put(questions_controller::put_update_question),
+		)
+		.route(
+			"/delete/{id}",
+			delete(questions_controller::delete_question),
+		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"questions",
+			120,
+		)))
+}
@@ -0,0 +1,149 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	tests::{QuestionsRequestCreateDto, QuestionsRequestUpdateDto},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_create_question, mutation_delete_question, mutation_update_question,
+	query_get_question_by_id, query_get_questions, QuestionBankItemDto,
+	QuestionBankItemListDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/questions",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Bank Questions", body = ResponseSuccessListDto<QuestionBankItemListDto>),
+        (status = 400, description = "Invalid filter", body = MessageResponseDto)
+    ),
+    tag = "Questions"
+)]
+pub async fn get_questions(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListQuestions])
+		.await
+	{
+		Ok(_) => query_get_questions(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/questions/detail/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Detail Bank Question", body = ResponseSuccessDto<QuestionBankItemDto>),
+        (status = 404, description = "Question not found", body = MessageResponseDto)
+    ),
+    tag = "Questions"
+)]
+pub async fn get_detail_question(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(
+		&headers,
+		vec![PermissionsEnum::ReadDetailQuestions],
+	)
+	.await
+	{
+		Ok(_) => query_get_question_by_id(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/questions/create",
+    request_body = QuestionsRequestCreateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 201, description = "Question Created", body = MessageResponseDto),
+        (status = 400, description = "Invalid Question data", body = MessageResponseDto)
+    ),
+    tag = "Questions"
+)]
+pub async fn post_create_question(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<QuestionsRequestCreateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreateQuestions])
+		.await
+	{
+		Ok(_) => mutation_create_question(Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/questions/update/{id}",
+    request_body = QuestionsRequestUpdateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Question Updated", body = ResponseSuccessDto<QuestionBankItemDto>),
+        (status = 404, description = "Question not found", body = MessageResponseDto)
+    ),
+    tag = "Questions"
+)]
+pub async fn put_update_question(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<QuestionsRequestUpdateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateQuestions])
+		.await
+	{
+		Ok(_) => mutation_update_question(id, Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/questions/delete/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Question Deleted", body = MessageResponseDto),
+        (status = 409, description = "Question is still used by a test", body = MessageResponseDto)
+    ),
+    tag = "Questions"
+)]
+pub async fn delete_question(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteQuestions])
+		.await
+	{
+		Ok(_) => mutation_delete_question(id, &audit).await,
+		Err(response) => response,
+	}
+}
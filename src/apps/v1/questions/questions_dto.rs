@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::tests::OptionsItemDto;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionBankItemListDto {
+	pub id: String,
+	pub version: i32,
+	pub question: String,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
+	pub author_id: Option<String>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionBankItemDto {
+	pub id: String,
+	pub version: i32,
+	pub previous_version_id: Option<String>,
+	pub question: String,
+	pub discussion: String,
+	pub image_url: Option<String>,
+	pub discussion_image_url: Option<String>,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
+	pub author_id: Option<String>,
+	pub options: Vec<OptionsItemDto>,
+	pub test_ids: Vec<String>,
+	pub archived_at: Option<String>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
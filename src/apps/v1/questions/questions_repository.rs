@@ -0,0 +1,691 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::Utc;
+use sea_orm::{
+	prelude::*, sea_query::Query, ActiveModelTrait, ColumnTrait, DatabaseConnection,
+	EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Set,
+	TransactionTrait,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+	audit_snapshot, common_response, get_db, record_audit_log,
+	schemas::{
+		OptionsActiveModel, OptionsColumn, OptionsEntity, OptionsModel,
+		QuestionsActiveModel, QuestionsColumn, QuestionsEntity, QuestionsModel,
+		TestQuestionsAnswersColumn, TestQuestionsAnswersEntity, TestsEntity,
+		TestsHasQuestionsActiveModel, TestsHasQuestionsColumn,
+		TestsHasQuestionsEntity,
+	},
+	success_response, success_response_list,
+	tests::{OptionsItemDto, QuestionsRequestCreateDto, QuestionsRequestUpdateDto},
+	AuditAction, AuditContext, FieldKind, FilterOp, FullTextSearch, ListParams,
+	ListSpec, MetaRequestDto, ResponseSuccessDto, ResponseSuccessListDto,
+	SoftDelete,
+};
+
+use super::{QuestionBankItemDto, QuestionBankItemListDto};
+
+const QUESTION_DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
+
+fn internal_error(err: DbErr) -> Response {
+	common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+}
+
+pub fn validate_difficulty(difficulty: &Option<String>) -> Result<(), Response> {
+	match difficulty {
+		Some(value) if !QUESTION_DIFFICULTIES.contains(&value.as_str()) => {
+			Err(common_response(
+				StatusCode::BAD_REQUEST,
+				"Difficulty must be one of easy, medium or hard",
+			))
+		}
+		_ => Ok(()),
+	}
+}
+
+/// Current versions of the questions linked to a test, in test order.
+pub fn find_test_questions(test_id: Uuid) -> Select<QuestionsEntity> {
+	QuestionsEntity::find()
+		.inner_join(TestsHasQuestionsEntity)
+		.filter(TestsHasQuestionsColumn::TestId.eq(test_id))
+		.filter(QuestionsColumn::ArchivedAt.is_null())
+		.order_by_asc(TestsHasQuestionsColumn::Position)
+}
+
+pub async fn find_bank_question(
+	db: &DatabaseConnection,
+	id: &str,
+) -> Result<QuestionsModel, Response> {
+	let question_id = Uuid::parse_str(id).map_err(|_| {
+		common_response(StatusCode::BAD_REQUEST, "Invalid question ID format")
+	})?;
+
+	match QuestionsEntity::find_by_id(question_id)
+		.filter(QuestionsColumn::ArchivedAt.is_null())
+		.one(db)
+		.await
+	{
+		Ok(Some(question)) => Ok(question),
+		Ok(None) => {
+			Err(common_response(StatusCode::NOT_FOUND, "Question not found"))
+		}
+		Err(err) => Err(internal_error(err)),
+	}
+}
+
+async fn insert_option<C: ConnectionTrait>(
+	db: &C,
+	question_id: Uuid,
+	label: &str,
+	is_correct: bool,
+	image_url: Option<String>,
+) -> Result<(), Response> {
+	OptionsActiveModel {
+		id: Set(Uuid::new_v4()),
+		question_id: Set(question_id),
+		label: Set(label.to_string()),
+		is_correct: Set(is_correct),
+		image_url: Set(image_url),
+	}
+	.insert(db)
+	.await
+	.map_err(internal_error)?;
+
+	Ok(())
+}
+
+pub async fn insert_bank_question(
+	db: &DatabaseConnection,
+	payload: &QuestionsRequestCreateDto,
+	author_id: Option<Uuid>,
+) -> Result<QuestionsModel, Response> {
+	validate_difficulty(&payload.difficulty)?;
+
+	let question = QuestionsActiveModel {
+		id: Set(Uuid::new_v4()),
+		question: Set(payload.question.clone()),
+		image_url: Set(payload.image_url.clone()),
+		discussion: Set(payload.discussion.clone()),
+		discussion_image_url: Set(payload.discussion_image_url.clone()),
+		topic: Set(payload.topic.clone()),
+		subtopic: Set(payload.subtopic.clone()),
+		difficulty: Set(payload.difficulty.clone()),
+		author_id: Set(author_id),
+		version: Set(1),
+		previous_version_id: Set(None),
+		archived_at: Set(None),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	}
+	.insert(db)
+	.await
+	.map_err(internal_error)?;
+
+	for option in &payload.options {
+		insert_option(
+			db,
+			question.id,
+			&option.label,
+			option.is_correct,
+			Some(option.image_url.clone()),
+		)
+		.await?;
+	}
+
+	Ok(question)
+}
+
+/// Appends a bank question to the end of a test. Linking a question that is
+/// already part of the test is a no-op.
+pub async fn link_question_to_test(
+	db: &DatabaseConnection,
+	test_id: Uuid,
+	question_id: Uuid,
+) -> Result<(), Response> {
+	let linked = TestsHasQuestionsEntity::find()
+		.filter(TestsHasQuestionsColumn::TestId.eq(test_id))
+		.filter(TestsHasQuestionsColumn::QuestionId.eq(question_id))
+		.count(db)
+		.await
+		.map_err(internal_error)?;
+	if linked > 0 {
+		return Ok(());
+	}
+
+	let last_position = TestsHasQuestionsEntity::find()
+		.select_only()
+		.column_as(TestsHasQuestionsColumn::Position.max(), "position")
+		.filter(TestsHasQuestionsColumn::TestId.eq(test_id))
+		.into_tuple::<Option<i32>>()
+		.one(db)
+		.await
+		.map_err(internal_error)?
+		.flatten();
+
+	TestsHasQuestionsActiveModel {
+		id: Set(Uuid::new_v4()),
+		test_id: Set(test_id),
+		question_id: Set(question_id),
+		position: Set(last_position.map_or(0, |position| position + 1)),
+	}
+	.insert(db)
+	.await
+	.map_err(internal_error)?;
+
+	Ok(())
+}
+
+pub async fn unlink_question_from_test(
+	db: &DatabaseConnection,
+	test_id: Uuid,
+	question_id: Uuid,
+) -> Result<(), Response> {
+	TestsHasQuestionsEntity::delete_many()
+		.filter(TestsHasQuestionsColumn::TestId.eq(test_id))
+		.filter(TestsHasQuestionsColumn::QuestionId.eq(question_id))
+		.exec(db)
+		.await
+		.map_err(internal_error)?;
+
+	Ok(())
+}
+
+/// Applies an edit to a live question and returns its current version.
+/// Questions nobody has answered yet are updated in place, as are edits that
+/// only touch topic, subtopic or difficulty. Otherwise the answered row is
+/// archived and the edit is saved as a new version, so past attempts are
+/// still reviewed and scored against what the student actually saw.
+pub async fn save_question_revision(
+	db: &DatabaseConnection,
+	existing: QuestionsModel,
+	payload: &QuestionsRequestUpdateDto,
+) -> Result<QuestionsModel, Response> {
+	validate_difficulty(&payload.difficulty)?;
+
+	// Dropping the transaction on an early return rolls back a partly
+	// written revision.
+	let txn = db.begin().await.map_err(internal_error)?;
+	let question = write_question_revision(&txn, existing, payload).await?;
+	txn.commit().await.map_err(internal_error)?;
+
+	Ok(question)
+}
+
+async fn write_question_revision<C: ConnectionTrait>(
+	db: &C,
+	existing: QuestionsModel,
+	payload: &QuestionsRequestUpdateDto,
+) -> Result<QuestionsModel, Response> {
+	let options = OptionsEntity::find()
+		.filter(OptionsColumn::QuestionId.eq(existing.id))
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+
+	let mut edits = Vec::new();
+	for option in &payload.options {
+		let current = match &option.id {
+			Some(id_str) => {
+				let option_id = Uuid::parse_str(id_str).map_err(|_| {
+					common_response(
+						StatusCode::BAD_REQUEST,
+						"Invalid option ID format",
+					)
+				})?;
+				match options.iter().find(|o| o.id == option_id) {
+					Some(current) => Some(current.clone()),
+					None => {
+						return Err(common_response(
+							StatusCode::NOT_FOUND,
+							"Option not found",
+						))
+					}
+				}
+			}
+			None => None,
+		};
+		edits.push((current, option));
+	}
+
+	let topic = payload.topic.clone().or(existing.topic.clone());
+	let subtopic = payload.subtopic.clone().or(existing.subtopic.clone());
+	let difficulty = payload.difficulty.clone().or(existing.difficulty.clone());
+
+	let answered = TestQuestionsAnswersEntity::find()
+		.filter(TestQuestionsAnswersColumn::QuestionId.eq(existing.id))
+		.count(db)
+		.await
+		.map_err(internal_error)?
+		> 0;
+
+	let unchanged = existing.question == payload.question
+		&& existing.discussion == payload.discussion
+		&& existing.image_url == payload.image_url
+		&& existing.discussion_image_url == payload.discussion_image_url
+		&& edits.iter().all(|(current, option)| match current {
+			Some(current) => {
+				current.label == option.label
+					&& current.is_correct == option.is_correct
+					&& current.image_url.as_deref()
+						== Some(option.image_url.as_str())
+			}
+			None => false,
+		});
+
+	if !answered || unchanged {
+		let mut question_model: QuestionsActiveModel = existing.into();
+		question_model.question = Set(payload.question.clone());
+		question_model.discussion = Set(payload.discussion.clone());
+		question_model.image_url = Set(payload.image_url.clone());
+		question_model.discussion_image_url =
+			Set(payload.discussion_image_url.clone());
+		question_model.topic = Set(topic);
+		question_model.subtopic = Set(subtopic);
+		question_model.difficulty = Set(difficulty);
+		question_model.updated_at = Set(Some(Utc::now()));
+		let question = question_model.update(db).await.map_err(internal_error)?;
+
+		for (current, option) in edits {
+			match current {
+				Some(current) => {
+					let mut option_model: OptionsActiveModel = current.into();
+					option_model.label = Set(option.label.clone());
+					option_model.is_correct = Set(option.is_correct);
+					option_model.image_url = Set(Some(option.image_url.clone()));
+					option_model.update(db).await.map_err(internal_error)?;
+				}
+				None => {
+					insert_option(
+						db,
+						question.id,
+						&option.label,
+						option.is_correct,
+						Some(option.image_url.clone()),
+					)
+					.await?
+				}
+			}
+		}
+
+		return Ok(question);
+	}
+
+	let revision = QuestionsActiveModel {
+		id: Set(Uuid::new_v4()),
+		question: Set(payload.question.clone()),
+		image_url: Set(payload.image_url.clone()),
+		discussion: Set(payload.discussion.clone()),
+		discussion_image_url: Set(payload.discussion_image_url.clone()),
+		topic: Set(topic),
+		subtopic: Set(subtopic),
+		difficulty: Set(difficulty),
+		author_id: Set(existing.author_id),
+		version: Set(existing.version + 1),
+		previous_version_id: Set(Some(existing.id)),
+		archived_at: Set(None),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	}
+	.insert(db)
+	.await
+	.map_err(internal_error)?;
+
+	// Options left out of the payload carry over unchanged, as they do for
+	// in-place edits.
+	for current in &options {
+		let edit = edits
+			.iter()
+			.find(|(edited, _)| edited.as_ref().map(|o| o.id) == Some(current.id))
+			.map(|(_, option)| *option);
+
+		insert_option(
+			db,
+			revision.id,
+			edit.map_or(&current.label, |o| &o.label),
+			edit.map_or(current.is_correct, |o| o.is_correct),
+			edit.map_or(current.image_url.clone(), |o| Some(o.image_url.clone())),
+		)
+		.await?;
+	}
+
+	for (_, option) in edits.iter().filter(|(current, _)| current.is_none()) {
+		insert_option(
+			db,
+			revision.id,
+			&option.label,
+			option.is_correct,
+			Some(option.image_url.clone()),
+		)
+		.await?;
+	}
+
+	// Every test using the question picks up the new version in the same
+	// position. The old links stay so answers to the archived version still
+	// belong to their test.
+	let links = TestsHasQuestionsEntity::find()
+		.filter(TestsHasQuestionsColumn::QuestionId.eq(existing.id))
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+	for link in links {
+		TestsHasQuestionsActiveModel {
+			id: Set(Uuid::new_v4()),
+			test_id: Set(link.test_id),
+			question_id: Set(revision.id),
+			position: Set(link.position),
+		}
+		.insert(db)
+		.await
+		.map_err(internal_error)?;
+	}
+
+	let mut archived: QuestionsActiveModel = existing.into();
+	archived.archived_at = Set(Some(Utc::now()));
+	archived.update(db).await.map_err(internal_error)?;
+
+	Ok(revision)
+}
+
+/// Question fields plus its options, so a changed correct option is visible
+/// in the audit diff.
+pub async fn question_audit_snapshot(
+	db: &DatabaseConnection,
+	question: &QuestionsModel,
+) -> Option<Value> {
+	let options = OptionsEntity::find()
+		.filter(OptionsColumn::QuestionId.eq(question.id))
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(question)?;
+	snapshot["options"] = json!(options);
+	Some(snapshot)
+}
+
+async fn question_to_dto(
+	db: &DatabaseConnection,
+	question: QuestionsModel,
+) -> Result<QuestionBankItemDto, Response> {
+	let options: Vec<OptionsModel> = OptionsEntity::find()
+		.filter(OptionsColumn::QuestionId.eq(question.id))
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+
+	let test_ids = TestsHasQuestionsEntity::find()
+		.select_only()
+		.column(TestsHasQuestionsColumn::TestId)
+		.inner_join(TestsEntity)
+		.filter(TestsHasQuestionsColumn::QuestionId.eq(question.id))
+		.filter(TestsEntity::not_deleted())
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+
+	Ok(QuestionBankItemDto {
+		id: question.id.to_string(),
+		version: question.version,
+		previous_version_id: question.previous_version_id.map(|id| id.to_string()),
+		question: question.question,
+		discussion: question.discussion,
+		image_url: question.image_url,
+		discussion_image_url: question.discussion_image_url,
+		topic: question.topic,
+		subtopic: question.subtopic,
+		difficulty: question.difficulty,
+		author_id: question.author_id.map(|id| id.to_string()),
+		options: options
+			.into_iter()
+			.map(|option| OptionsItemDto {
+				id: option.id.to_string(),
+				label: option.label,
+				is_correct: Some(option.is_correct),
+				image_url: option.image_url,
+			})
+			.collect(),
+		test_ids: test_ids.into_iter().map(|id| id.to_string()).collect(),
+		archived_at: question.archived_at.map(|dt| dt.to_string()),
+		created_at: question.created_at.map(|dt| dt.to_string()),
+		updated_at: question.updated_at.map(|dt| dt.to_string()),
+	})
+}
+
+const QUESTIONS_LIST: ListSpec<QuestionsEntity> = ListSpec {
+	filters: &[
+		("topic", QuestionsColumn::Topic, FieldKind::Text),
+		("subtopic", QuestionsColumn::Subtopic, FieldKind::Text),
+		("difficulty", QuestionsColumn::Difficulty, FieldKind::Text),
+		("author_id", QuestionsColumn::AuthorId, FieldKind::Uuid),
+		(
+			"created_at",
+			QuestionsColumn::CreatedAt,
+			FieldKind::DateTime,
+		),
+	],
+	sorts: &[
+		("topic", QuestionsColumn::Topic),
+		("difficulty", QuestionsColumn::Difficulty),
+		("created_at", QuestionsColumn::CreatedAt),
+		("updated_at", QuestionsColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: QuestionsColumn::Id,
+};
+
+pub async fn query_get_questions(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let mut params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query =
+		QuestionsEntity::find().filter(QuestionsColumn::ArchivedAt.is_null());
+
+	// Searches the question text and its discussion.
+	if !params.search.is_empty() {
+		query = query.filter(QuestionsEntity::search_matches(&params.search));
+	}
+
+	// Questions are linked to tests through the join table.
+	for filter in params.take_filters("test_id") {
+		if !matches!(filter.op, FilterOp::Eq | FilterOp::In) {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"test_id only supports eq and in",
+			);
+		}
+
+		let test_ids = match filter.parse_values(FieldKind::Uuid) {
+			Ok(test_ids) => test_ids,
+			Err(response) => return response,
+		};
+
+		query = query.filter(
+			QuestionsColumn::Id.in_subquery(
+				Query::select()
+					.column(TestsHasQuestionsColumn::QuestionId)
+					.from(TestsHasQuestionsEntity)
+					.and_where(TestsHasQuestionsColumn::TestId.is_in(test_ids))
+					.to_owned(),
+			),
+		);
+	}
+
+	let rank = (!params.search.is_empty())
+		.then(|| QuestionsEntity::search_rank(&params.search));
+	let (questions, meta) =
+		match params.fetch(&db, query, &QUESTIONS_LIST, rank).await {
+			Ok(page) => page,
+			Err(response) => return response,
+		};
+
+	let response = ResponseSuccessListDto {
+		data: questions
+			.into_iter()
+			.map(|question| QuestionBankItemListDto {
+				id: question.id.to_string(),
+				version: question.version,
+				question: question.question,
+				topic: question.topic,
+				subtopic: question.subtopic,
+				difficulty: question.difficulty,
+				author_id: question.author_id.map(|id| id.to_string()),
+				created_at: question.created_at.map(|dt| dt.to_string()),
+				updated_at: question.updated_at.map(|dt| dt.to_string()),
+			})
+			.collect::<Vec<QuestionBankItemListDto>>(),
+		meta: Some(meta),
+	};
+
+	success_response_list(response)
+}
+
+/// Archived versions stay readable here so a past attempt's question can be
+/// looked up by the id it was answered against.
+pub async fn query_get_question_by_id(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let question_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid question ID format",
+			)
+		}
+	};
+
+	let question = match QuestionsEntity::find_by_id(question_id).one(&db).await {
+		Ok(Some(question)) => question,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Question not found")
+		}
+		Err(err) => return internal_error(err),
+	};
+
+	match question_to_dto(&db, question).await {
+		Ok(dto) => success_response(ResponseSuccessDto { data: dto }),
+		Err(response) => response,
+	}
+}
+
+pub async fn mutation_create_question(
+	Json(payload): Json<QuestionsRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let author_id = audit.actor_id(&db).await;
+	let question = match insert_bank_question(&db, &payload, author_id).await {
+		Ok(question) => question,
+		Err(response) => return response,
+	};
+
+	let after = question_audit_snapshot(&db, &question).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"question",
+		question.id,
+		None,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::CREATED, "Question created successfully")
+}
+
+/// Responds with the question's current version, whose id changes when the
+/// edit had to be saved as a new version.
+pub async fn mutation_update_question(
+	id: String,
+	Json(payload): Json<QuestionsRequestUpdateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let existing = match find_bank_question(&db, &id).await {
+		Ok(question) => question,
+		Err(response) => return response,
+	};
+
+	let before = question_audit_snapshot(&db, &existing).await;
+	let question = match save_question_revision(&db, existing, &payload).await {
+		Ok(question) => question,
+		Err(response) => return response,
+	};
+
+	let after = question_audit_snapshot(&db, &question).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"question",
+		question.id,
+		before,
+		after,
+	)
+	.await;
+
+	match question_to_dto(&db, question).await {
+		Ok(dto) => success_response(ResponseSuccessDto { data: dto }),
+		Err(response) => response,
+	}
+}
+
+/// Retires a question from the bank. Rows are archived rather than deleted
+/// because answers may still reference them.
+pub async fn mutation_delete_question(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let question = match find_bank_question(&db, &id).await {
+		Ok(question) => question,
+		Err(response) => return response,
+	};
+
+	let used_by_tests = match TestsHasQuestionsEntity::find()
+		.inner_join(TestsEntity)
+		.filter(TestsHasQuestionsColumn::QuestionId.eq(question.id))
+		.filter(TestsEntity::not_deleted())
+		.count(&db)
+		.await
+	{
+		Ok(count) => count,
+		Err(err) => return internal_error(err),
+	};
+
+	if used_by_tests > 0 {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Question is still used by a test",
+		);
+	}
+
+	let before = audit_snapshot(&question);
+	let mut active_model: QuestionsActiveModel = question.into();
+	active_model.archived_at = Set(Some(Utc::now()));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(question) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"question",
+				question.id,
+				before,
+				audit_snapshot(&question),
+			)
+			.await;
+			common_response(StatusCode::OK, "Question deleted successfully")
+		}
+		Err(err) => internal_error(err),
+	}
+}
@@ -0,0 +1,20 @@
+use axum::{routing::get, Router};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod referrals_controller;
+pub mod referrals_dto;
+pub mod referrals_repository;
+
+pub use referrals_dto::*;
+pub use referrals_repository::*;
+
+pub fn referrals_router() -> Router {
+	Router::new()
+		.route("/me", get(referrals_controller::get_referrals_me))
+		.route("/report", get(referrals_controller::get_referrals_report))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"referrals",
+			120,
+		)))
+}
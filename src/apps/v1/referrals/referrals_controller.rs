@@ -0,0 +1,53 @@
+use axum::{extract::Query, http::HeaderMap, response::IntoResponse};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	query_get_referrals_me, query_get_referrals_report, ReferralsReportItemDto,
+	ReferralsStatsDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/referrals/me",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Referral statistics", body = ResponseSuccessDto<ReferralsStatsDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Referrals"
+)]
+pub async fn get_referrals_me(headers: HeaderMap) -> impl IntoResponse {
+	query_get_referrals_me(headers).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/referrals/report",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Top referrers", body = ResponseSuccessListDto<ReferralsReportItemDto>),
+        (status = 403, description = "Forbidden", body = MessageResponseDto)
+    ),
+    tag = "Referrals"
+)]
+pub async fn get_referrals_report(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListReferrals])
+		.await
+	{
+		Ok(_) => query_get_referrals_report(params).await,
+		Err(response) => response,
+	}
+}
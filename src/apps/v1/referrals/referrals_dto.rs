@@ -0,0 +1,37 @@
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReferralsItemDto {
+	pub id: String,
+	pub fullname: String,
+	pub status: String,
+	pub reward_points: i32,
+	pub activated_at: Option<String>,
+	pub converted_at: Option<String>,
+	pub created_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReferralsStatsDto {
+	pub referral_code: Option<String>,
+	pub total_referrals: u64,
+	pub activated_referrals: u64,
+	pub converted_referrals: u64,
+	pub total_reward_points: i64,
+	pub recent_referrals: Vec<ReferralsItemDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct ReferralsReportItemDto {
+	#[schema(value_type = String)]
+	pub referrer_id: uuid::Uuid,
+	pub fullname: String,
+	pub email: String,
+	pub referral_code: Option<String>,
+	pub total_referrals: i64,
+	pub activated_referrals: i64,
+	pub converted_referrals: i64,
+	pub total_reward_points: i64,
+}
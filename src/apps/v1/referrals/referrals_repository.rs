@@ -0,0 +1,309 @@
+use axum::response::Response;
+use chrono::Utc;
+use hyper::{HeaderMap, StatusCode};
+use rand::{thread_rng, Rng};
+use sea_orm::{
+	prelude::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr,
+	EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+	RelationTrait, Set,
+};
+use uuid::Uuid;
+
+use crate::{
+	auth::email_from_headers,
+	common_response, get_db,
+	schemas::{
+		ReferralsActiveModel, ReferralsColumn, ReferralsEntity, ReferralsRelation,
+		UsersColumn, UsersEntity, UsersModel,
+	},
+	success_response, success_response_list, Config, MetaRequestDto,
+	MetaResponseDto, ResponseSuccessDto, ResponseSuccessListDto, SoftDelete,
+};
+
+use super::{ReferralsItemDto, ReferralsReportItemDto, ReferralsStatsDto};
+
+pub const REFERRAL_STATUS_REGISTERED: &str = "registered";
+pub const REFERRAL_STATUS_ACTIVATED: &str = "activated";
+pub const REFERRAL_STATUS_CONVERTED: &str = "converted";
+
+// No 0/O or 1/I, so codes survive being read aloud or copied by hand.
+const REFERRAL_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const REFERRAL_CODE_LENGTH: usize = 8;
+
+pub fn normalize_referral_code(code: &str) -> String {
+	code.trim().to_uppercase()
+}
+
+pub async fn generate_referral_code(
+	db: &DatabaseConnection,
+) -> Result<String, DbErr> {
+	loop {
+		let code: String = (0..REFERRAL_CODE_LENGTH)
+			.map(|_| {
+				let index = thread_rng().gen_range(0..REFERRAL_CODE_ALPHABET.len());
+				REFERRAL_CODE_ALPHABET[index] as char
+			})
+			.collect();
+
+		let exists = UsersEntity::find()
+			.filter(UsersColumn::ReferralCode.eq(code.clone()))
+			.count(db)
+			.await?;
+
+		if exists == 0 {
+			return Ok(code);
+		}
+	}
+}
+
+pub async fn find_referrer(
+	db: &DatabaseConnection,
+	code: &str,
+) -> Result<Option<UsersModel>, DbErr> {
+	UsersEntity::find_active()
+		.filter(UsersColumn::ReferralCode.eq(normalize_referral_code(code)))
+		.one(db)
+		.await
+}
+
+pub async fn record_referral(
+	db: &DatabaseConnection,
+	referrer: &UsersModel,
+	referred_id: Uuid,
+) -> Result<(), DbErr> {
+	if referrer.id == referred_id {
+		return Ok(());
+	}
+
+	ReferralsActiveModel {
+		id: Set(Uuid::new_v4()),
+		referrer_id: Set(referrer.id),
+		referred_id: Set(referred_id),
+		referral_code: Set(referrer.referral_code.clone().unwrap_or_default()),
+		status: Set(REFERRAL_STATUS_REGISTERED.to_string()),
+		reward_points: Set(0),
+		activated_at: Set(None),
+		converted_at: Set(None),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	}
+	.insert(db)
+	.await?;
+
+	Ok(())
+}
+
+/// Credits the referrer once the referred student activates their account.
+pub async fn mark_referral_activated(
+	db: &DatabaseConnection,
+	referred_id: Uuid,
+) -> Result<(), DbErr> {
+	let referral = ReferralsEntity::find()
+		.filter(ReferralsColumn::ReferredId.eq(referred_id))
+		.filter(ReferralsColumn::Status.eq(REFERRAL_STATUS_REGISTERED))
+		.one(db)
+		.await?;
+
+	if let Some(referral) = referral {
+		let mut active_referral: ReferralsActiveModel = referral.into();
+		active_referral.status = Set(REFERRAL_STATUS_ACTIVATED.to_string());
+		active_referral.reward_points = Set(Config::new().referral_reward_points);
+		active_referral.activated_at = Set(Some(Utc::now()));
+		active_referral.updated_at = Set(Some(Utc::now()));
+		active_referral.update(db).await?;
+	}
+
+	Ok(())
+}
+
+/// Marks the referred student as a paying customer.
+pub async fn mark_referral_converted(
+	db: &DatabaseConnection,
+	referred_id: Uuid,
+) -> Result<(), DbErr> {
+	let referral = ReferralsEntity::find()
+		.filter(ReferralsColumn::ReferredId.eq(referred_id))
+		.filter(ReferralsColumn::Status.ne(REFERRAL_STATUS_CONVERTED))
+		.one(db)
+		.await?;
+
+	if let Some(referral) = referral {
+		let mut active_referral: ReferralsActiveModel = referral.into();
+		active_referral.status = Set(REFERRAL_STATUS_CONVERTED.to_string());
+		active_referral.converted_at = Set(Some(Utc::now()));
+		active_referral.updated_at = Set(Some(Utc::now()));
+		active_referral.update(db).await?;
+	}
+
+	Ok(())
+}
+
+pub async fn query_get_referrals_me(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let user = match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email))
+		.one(&db)
+		.await
+	{
+		Ok(Some(user)) => user,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let referrals = match ReferralsEntity::find()
+		.filter(ReferralsColumn::ReferrerId.eq(user.id))
+		.find_also_related(UsersEntity)
+		.order_by_desc(ReferralsColumn::CreatedAt)
+		.all(&db)
+		.await
+	{
+		Ok(data) => data,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let count_status = |status: &str| {
+		referrals
+			.iter()
+			.filter(|(referral, _)| referral.status == status)
+			.count() as u64
+	};
+
+	let converted_referrals = count_status(REFERRAL_STATUS_CONVERTED);
+	let activated_referrals =
+		count_status(REFERRAL_STATUS_ACTIVATED) + converted_referrals;
+	let total_reward_points = referrals
+		.iter()
+		.map(|(referral, _)| referral.reward_points as i64)
+		.sum();
+
+	let recent_referrals = referrals
+		.iter()
+		.take(10)
+		.map(|(referral, referred)| ReferralsItemDto {
+			id: referral.id.to_string(),
+			fullname: referred
+				.as_ref()
+				.map(|user| user.fullname.clone())
+				.unwrap_or_default(),
+			status: referral.status.clone(),
+			reward_points: referral.reward_points,
+			activated_at: referral.activated_at.map(|dt| dt.to_string()),
+			converted_at: referral.converted_at.map(|dt| dt.to_string()),
+			created_at: referral.created_at.map(|dt| dt.to_string()),
+		})
+		.collect();
+
+	let response = ResponseSuccessDto {
+		data: ReferralsStatsDto {
+			referral_code: user.referral_code,
+			total_referrals: referrals.len() as u64,
+			activated_referrals,
+			converted_referrals,
+			total_reward_points,
+			recent_referrals,
+		},
+	};
+
+	success_response(response)
+}
+
+pub async fn query_get_referrals_report(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let page = params.page.unwrap_or(1).max(1);
+	let per_page = params.per_page.unwrap_or(10).clamp(1, 100);
+	let sort_by = params
+		.sort_by
+		.unwrap_or("total_referrals".to_string())
+		.to_lowercase();
+
+	let sort_column = match sort_by.as_str() {
+		"activated_referrals" => "activated_referrals",
+		"converted_referrals" => "converted_referrals",
+		"total_reward_points" => "total_reward_points",
+		_ => "total_referrals",
+	};
+
+	let query = ReferralsEntity::find()
+		.select_only()
+		.column_as(ReferralsColumn::ReferrerId, "referrer_id")
+		.column_as(UsersColumn::Fullname, "fullname")
+		.column_as(UsersColumn::Email, "email")
+		.column_as(UsersColumn::ReferralCode, "referral_code")
+		.column_as(Expr::cust("COUNT(*)"), "total_referrals")
+		.column_as(
+			Expr::cust(format!(
+				"COUNT(*) FILTER (WHERE app_referrals.status IN ('{}', '{}'))",
+				REFERRAL_STATUS_ACTIVATED, REFERRAL_STATUS_CONVERTED
+			)),
+			"activated_referrals",
+		)
+		.column_as(
+			Expr::cust(format!(
+				"COUNT(*) FILTER (WHERE app_referrals.status = '{}')",
+				REFERRAL_STATUS_CONVERTED
+			)),
+			"converted_referrals",
+		)
+		.column_as(
+			Expr::cust("COALESCE(SUM(app_referrals.reward_points), 0)::bigint"),
+			"total_reward_points",
+		)
+		.join(JoinType::InnerJoin, ReferralsRelation::Referrer.def())
+		.group_by(ReferralsColumn::ReferrerId)
+		.group_by(UsersColumn::Fullname)
+		.group_by(UsersColumn::Email)
+		.group_by(UsersColumn::ReferralCode)
+		.order_by_desc(Expr::cust(sort_column))
+		.into_model::<ReferralsReportItemDto>();
+
+	let paginator = query.paginate(&db, per_page);
+
+	let total_items = match paginator.num_items().await {
+		Ok(count) => count,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let data = match paginator.fetch_page(page - 1).await {
+		Ok(data) => data,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let response = ResponseSuccessListDto {
+		data,
+		meta: Some(MetaResponseDto {
+			page: Some(page),
+			per_page: Some(per_page),
+			total: Some(total_items),
+			next_cursor: None,
+		}),
+	};
+
+	success_response_list(response)
+}
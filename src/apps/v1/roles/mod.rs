@@ -3,6 +3,8 @@ use axum::{
 	Router,
 };
 
+use crate::{RateLimitLayer, RateLimitPolicy};
+
 pub mod roles_controller;
 pub mod roles_dto;
 pub mod roles_enum;
@@ -19,4 +21,6 @@ pub fn roles_router() -> Router {
 		.route("/detail/{id}", get(roles_controller::get_detail_role))
 		.route("/update/{id}", put(roles_controller::put_update_role))
 		.route("/delete/{id}", delete(roles_controller::delete_role))
+		.route("/restore/{id}", put(roles_controller::put_restore_role))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute("roles", 120)))
 }
@@ -8,11 +8,11 @@ use axum::{
 use crate::{
 	permissions::{permissions_middleware, PermissionsEnum},
 	utils::dto::{MessageResponseDto, MetaRequestDto},
-	ResponseSuccessDto, ResponseSuccessListDto,
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
 };
 
 use super::{
-	mutation_delete_role, mutation_update_role,
+	mutation_delete_role, mutation_restore_role, mutation_update_role,
 	roles_dto::RolesRequestUpdateDto,
 	roles_repository::{
 		mutation_create_role, query_get_role_by_id, query_get_roles,
@@ -84,11 +84,12 @@ pub async fn get_detail_role(
 )]
 pub async fn post_create_role(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<RolesRequestCreateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreateRoles]).await
 	{
-		Ok(_) => mutation_create_role(Json(payload)).await,
+		Ok(_) => mutation_create_role(Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -107,11 +108,36 @@ pub async fn post_create_role(
 )]
 pub async fn delete_role(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteRoles]).await
 	{
-		Ok(_) => mutation_delete_role(id).await,
+		Ok(_) => mutation_delete_role(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/roles/restore/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Role Restored", body = MessageResponseDto),
+        (status = 404, description = "Deleted role not found", body = MessageResponseDto)
+    ),
+    tag = "Roles"
+)]
+pub async fn put_restore_role(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteRoles]).await
+	{
+		Ok(_) => mutation_restore_role(id, &audit).await,
 		Err(response) => response,
 	}
 }
@@ -131,12 +157,13 @@ pub async fn delete_role(
 )]
 pub async fn put_update_role(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<RolesRequestUpdateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateRoles]).await
 	{
-		Ok(_) => mutation_update_role(id, Json(payload)).await,
+		Ok(_) => mutation_update_role(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
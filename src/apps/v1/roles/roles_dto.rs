@@ -6,12 +6,14 @@ use crate::permissions::PermissionsItemDto;
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct RolesRequestUpdateDto {
 	pub name: Option<String>,
+	pub require_two_factor: Option<bool>,
 	pub permissions: Option<Vec<String>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct RolesRequestCreateDto {
 	pub name: String,
+	pub require_two_factor: Option<bool>,
 	pub permissions: Option<Vec<String>>,
 }
 
@@ -19,6 +21,7 @@ pub struct RolesRequestCreateDto {
 pub struct RolesItemListDto {
 	pub id: String,
 	pub name: String,
+	pub require_two_factor: bool,
 	pub created_at: Option<String>,
 	pub updated_at: Option<String>,
 }
@@ -27,6 +30,8 @@ pub struct RolesItemListDto {
 pub struct RolesItemDto {
 	pub id: String,
 	pub name: String,
+	#[serde(default)]
+	pub require_two_factor: bool,
 	pub permissions: Vec<PermissionsItemDto>,
 	pub created_at: Option<String>,
 	pub updated_at: Option<String>,
@@ -1,20 +1,26 @@
 use axum::{http::StatusCode, response::Response, Json};
+use log::error;
+use redis::Commands;
 use sea_orm::{
 	prelude::Expr, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
-	ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait,
-	QueryFilter, QueryOrder, QuerySelect, Set,
+	ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+	QuerySelect, Set,
 };
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::{
-	common_response, get_db,
+	audit_snapshot, common_response, connect_redis, get_db,
 	permissions::PermissionsItemDto,
+	record_audit_log,
 	schemas::{
-		PermissionsEntity, RolesActiveModel, RolesColumn, RolesEntity,
+		PermissionsEntity, RolesActiveModel, RolesColumn, RolesEntity, RolesModel,
 		RolesPermissionsActiveModel, RolesPermissionsColumn, RolesPermissionsEntity,
+		UsersColumn, UsersEntity,
 	},
-	success_response, success_response_list, MetaRequestDto, MetaResponseDto,
-	ResponseSuccessDto, ResponseSuccessListDto,
+	success_response, success_response_list, AuditAction, AuditContext, FieldKind,
+	ListParams, ListSpec, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto, SoftDelete,
 };
 
 use super::{
@@ -22,55 +28,43 @@ use super::{
 	RolesItemListDto, RolesRequestUpdateDto,
 };
 
+const ROLES_LIST: ListSpec<RolesEntity> = ListSpec {
+	filters: &[
+		(
+			"require_two_factor",
+			RolesColumn::RequireTwoFactor,
+			FieldKind::Bool,
+		),
+		("created_at", RolesColumn::CreatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("name", RolesColumn::Name),
+		("created_at", RolesColumn::CreatedAt),
+		("updated_at", RolesColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: RolesColumn::Id,
+};
+
 pub async fn query_get_roles(params: MetaRequestDto) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	let page = params.page.unwrap_or(1).max(1);
-	let per_page = params.per_page.unwrap_or(10).max(1).min(100);
-	let search = params.search.unwrap_or_default().to_lowercase();
-	let sort_by = params
-		.sort_by
-		.unwrap_or("created_at".to_string())
-		.to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
-
-	let mut query = RolesEntity::find();
-
-	if !search.is_empty() {
-		query = query
-			.filter(Expr::col(RolesColumn::Name).ilike(format!("%{}%", search)));
-	}
-
-	query = match (sort_by.as_str(), order.as_str()) {
-		("name", "asc") => query.order_by_asc(RolesColumn::Name),
-		("name", "desc") => query.order_by_desc(RolesColumn::Name),
-		("created_at", "asc") => query.order_by_asc(RolesColumn::CreatedAt),
-		("created_at", "desc") => query.order_by_desc(RolesColumn::CreatedAt),
-		("updated_at", "asc") => query.order_by_asc(RolesColumn::UpdatedAt),
-		("updated_at", "desc") => query.order_by_desc(RolesColumn::UpdatedAt),
-		_ => query.order_by_desc(RolesColumn::CreatedAt),
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
 	};
 
-	let paginator = query.paginate(&db, per_page);
+	let mut query = RolesEntity::find_active();
 
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
-		}
-	};
+	if !params.search.is_empty() {
+		query = query.filter(
+			Expr::col(RolesColumn::Name).ilike(format!("%{}%", params.search)),
+		);
+	}
 
-	let roles = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
-		}
+	let (roles, meta) = match params.fetch(&db, query, &ROLES_LIST, None).await {
+		Ok(page) => page,
+		Err(response) => return response,
 	};
 
 	let data: Vec<RolesItemListDto> = roles
@@ -78,6 +72,7 @@ pub async fn query_get_roles(params: MetaRequestDto) -> Response {
 		.map(|role| RolesItemListDto {
 			id: role.id.to_string(),
 			name: role.name,
+			require_two_factor: role.require_two_factor,
 			created_at: role.created_at.map(|dt| dt.to_string()),
 			updated_at: role.updated_at.map(|dt| dt.to_string()),
 		})
@@ -85,11 +80,7 @@ pub async fn query_get_roles(params: MetaRequestDto) -> Response {
 
 	let response = ResponseSuccessListDto {
 		data,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+		meta: Some(meta),
 	};
 
 	success_response_list(response)
@@ -98,7 +89,7 @@ pub async fn query_get_roles(params: MetaRequestDto) -> Response {
 pub async fn query_get_role_by_id(id: String) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	let role = match RolesEntity::find()
+	let role = match RolesEntity::find_active()
 		.filter(RolesColumn::Id.eq(Uuid::parse_str(&id).unwrap_or_default()))
 		.one(&db)
 		.await
@@ -142,6 +133,7 @@ pub async fn query_get_role_by_id(id: String) -> Response {
 	let role_dto = RolesItemDto {
 		id: role.id.to_string(),
 		name: role.name,
+		require_two_factor: role.require_two_factor,
 		created_at: role.created_at.map(|dt| dt.to_string()),
 		updated_at: role.updated_at.map(|dt| dt.to_string()),
 		permissions: permissions_dto,
@@ -151,7 +143,10 @@ pub async fn query_get_role_by_id(id: String) -> Response {
 	success_response(response)
 }
 
-pub async fn mutation_create_role(payload: Json<RolesRequestCreateDto>) -> Response {
+pub async fn mutation_create_role(
+	payload: Json<RolesRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	if let Ok(Some(_)) = RolesEntity::find()
@@ -168,8 +163,10 @@ pub async fn mutation_create_role(payload: Json<RolesRequestCreateDto>) -> Respo
 	let new_role = RolesActiveModel {
 		id: Set(Uuid::new_v4()),
 		name: Set(payload.name.clone()),
+		require_two_factor: Set(payload.require_two_factor.unwrap_or(false)),
 		created_at: Set(Some(chrono::Utc::now())),
 		updated_at: Set(Some(chrono::Utc::now())),
+		deleted_at: Set(None),
 	};
 
 	let role = match new_role.insert(&db).await {
@@ -200,12 +197,25 @@ pub async fn mutation_create_role(payload: Json<RolesRequestCreateDto>) -> Respo
 		}
 	}
 
+	let after = role_audit_snapshot(&db, &role).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"role",
+		role.id,
+		None,
+		after,
+	)
+	.await;
+
 	common_response(StatusCode::CREATED, "Role created successfully")
 }
 
 pub async fn mutation_update_role(
 	id: String,
 	payload: Json<RolesRequestUpdateDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -219,7 +229,7 @@ pub async fn mutation_update_role(
 		}
 	};
 
-	let role = match RolesEntity::find()
+	let role = match RolesEntity::find_active()
 		.filter(RolesColumn::Id.eq(role_id))
 		.one(&db)
 		.await
@@ -234,11 +244,15 @@ pub async fn mutation_update_role(
 		}
 	};
 
+	let before = role_audit_snapshot(&db, &role).await;
 	let mut active_model: RolesActiveModel = role.into();
 
 	if let Some(name) = &payload.name {
 		active_model.name = Set(name.clone());
 	}
+	if let Some(require_two_factor) = payload.require_two_factor {
+		active_model.require_two_factor = Set(require_two_factor);
+	}
 	active_model.updated_at = Set(Some(chrono::Utc::now()));
 
 	let updated_role = match active_model.update(&db).await {
@@ -316,10 +330,55 @@ pub async fn mutation_update_role(
 		}
 	}
 
+	let after = role_audit_snapshot(&db, &updated_role).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"role",
+		updated_role.id,
+		before,
+		after,
+	)
+	.await;
+
+	evict_role_members_cache(&db, updated_role.id).await;
+
 	common_response(StatusCode::OK, "Role updated successfully")
 }
 
-pub async fn mutation_delete_role(id: String) -> Response {
+/// Drops the cached session data of every member of a role, so a changed
+/// two-factor requirement or permission set applies on their next request
+/// rather than when the cache expires.
+async fn evict_role_members_cache(db: &DatabaseConnection, role_id: Uuid) {
+	let emails: Vec<String> = match UsersEntity::find()
+		.select_only()
+		.column(UsersColumn::Email)
+		.filter(UsersColumn::RoleId.eq(role_id))
+		.into_tuple()
+		.all(db)
+		.await
+	{
+		Ok(emails) => emails,
+		Err(err) => {
+			error!("Failed to load members of role {}: {}", role_id, err);
+			return;
+		}
+	};
+
+	if emails.is_empty() {
+		return;
+	}
+
+	let redis_keys: Vec<String> = emails
+		.iter()
+		.map(|email| format!("authenticated_users_data:{}", email))
+		.collect();
+	let mut redis = connect_redis();
+	let _: Result<(), _> = redis.del(redis_keys);
+}
+
+pub async fn mutation_delete_role(id: String, audit: &AuditContext) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	let role_id = match Uuid::parse_str(&id) {
@@ -332,7 +391,7 @@ pub async fn mutation_delete_role(id: String) -> Response {
 		}
 	};
 
-	let role = match RolesEntity::find()
+	let role = match RolesEntity::find_active()
 		.filter(RolesColumn::Id.eq(role_id))
 		.one(&db)
 		.await
@@ -347,18 +406,45 @@ pub async fn mutation_delete_role(id: String) -> Response {
 		}
 	};
 
-	match role.delete(&db).await {
-		Ok(_) => {
-			if let Err(err) = RolesPermissionsEntity::delete_many()
-				.filter(RolesPermissionsColumn::RoleId.eq(role_id))
-				.exec(&db)
-				.await
-			{
-				return common_response(
-					StatusCode::INTERNAL_SERVER_ERROR,
-					&format!("Failed to delete associated permissions: {}", err),
-				);
-			}
+	// Role permissions are kept so a restored role comes back unchanged.
+	let assigned_users = match UsersEntity::find_active()
+		.filter(UsersColumn::RoleId.eq(role_id))
+		.count(&db)
+		.await
+	{
+		Ok(count) => count,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if assigned_users > 0 {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Role is still assigned to users",
+		);
+	}
+
+	let before = audit_snapshot(&role);
+	let mut active_model: RolesActiveModel = role.into();
+	active_model.deleted_at = Set(Some(chrono::Utc::now()));
+	active_model.updated_at = Set(Some(chrono::Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(role) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"role",
+				role.id,
+				before,
+				audit_snapshot(&role),
+			)
+			.await;
 			common_response(StatusCode::OK, "Role deleted successfully")
 		}
 		Err(err) => {
@@ -366,3 +452,80 @@ pub async fn mutation_delete_role(id: String) -> Response {
 		}
 	}
 }
+
+pub async fn mutation_restore_role(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let role_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid role ID format",
+			)
+		}
+	};
+
+	let role = match RolesEntity::find_deleted()
+		.filter(RolesColumn::Id.eq(role_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(role)) => role,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Deleted role not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let before = audit_snapshot(&role);
+	let mut active_model: RolesActiveModel = role.into();
+	active_model.deleted_at = Set(None);
+	active_model.updated_at = Set(Some(chrono::Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(role) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Restore,
+				"role",
+				role.id,
+				before,
+				audit_snapshot(&role),
+			)
+			.await;
+			common_response(StatusCode::OK, "Role restored successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+/// Role fields plus the names of its permissions, so permission edits show
+/// up in the audit diff.
+async fn role_audit_snapshot(
+	db: &DatabaseConnection,
+	role: &RolesModel,
+) -> Option<Value> {
+	let mut permissions: Vec<String> = RolesPermissionsEntity::find()
+		.filter(RolesPermissionsColumn::RoleId.eq(role.id))
+		.find_also_related(PermissionsEntity)
+		.all(db)
+		.await
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|(_, permission)| permission.map(|p| p.name))
+		.collect();
+	permissions.sort();
+
+	let mut snapshot = audit_snapshot(role)?;
+	snapshot["permissions"] = json!(permissions);
+	Some(snapshot)
+}
@@ -3,6 +3,8 @@ use axum::{
 	Router,
 };
 
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
+
 pub mod sessions_controller;
 pub mod sessions_dto;
 pub mod sessions_repository;
@@ -13,8 +15,35 @@ pub use sessions_repository::*;
 pub fn sessions_router() -> Router {
 	Router::new()
 		.route("/", get(sessions_controller::get_sessions))
+		.route("/me", get(sessions_controller::get_my_sessions))
 		.route("/create", post(sessions_controller::post_create_session))
 		.route("/detail/{id}", get(sessions_controller::get_detail_session))
 		.route("/update/{id}", put(sessions_controller::put_update_session))
 		.route("/delete/{id}", delete(sessions_controller::delete_session))
+		.route(
+			"/restore/{id}",
+			put(sessions_controller::put_restore_session),
+		)
+		.route(
+			"/publish-results/{id}",
+			put(sessions_controller::put_publish_session_results),
+		)
+		.route("/extend/{id}", put(sessions_controller::put_extend_session))
+		.route(
+			"/export/{id}",
+			post(sessions_controller::post_export_session_results).layer(
+				RateLimitLayer::new(
+					RateLimitPolicy::per_minute("sessions_export", 5)
+						.keyed_by(RateLimitKey::User),
+				),
+			),
+		)
+		.route(
+			"/enrollments/{id}",
+			get(sessions_controller::get_session_enrollments)
+				.put(sessions_controller::put_session_enrollments),
+		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"sessions", 120,
+		)))
 }
@@ -8,16 +8,21 @@ use axum::{
 use crate::{
 	permissions::{permissions_middleware, PermissionsEnum},
 	utils::dto::{MessageResponseDto, MetaRequestDto},
-	ResponseSuccessDto, ResponseSuccessListDto,
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
 };
 
 use super::{
-	mutation_delete_session, mutation_update_session,
+	mutation_delete_session, mutation_export_session_results,
+	mutation_extend_session, mutation_publish_session_results,
+	mutation_restore_session, mutation_update_session,
+	mutation_update_session_enrollments, query_get_my_sessions,
+	query_get_session_enrollments,
 	sessions_dto::{SessionsRequestCreateDto, SessionsRequestUpdateDto},
 	sessions_repository::{
 		mutation_create_session, query_get_session_by_id, query_get_sessions,
 	},
-	SessionsItemDto, SessionsItemListDto,
+	SessionsEnrollmentItemDto, SessionsEnrollmentRequestDto, SessionsExportItemDto,
+	SessionsExtendRequestDto, SessionsItemDto, SessionsItemListDto,
 };
 
 #[utoipa::path(
@@ -84,12 +89,13 @@ pub async fn get_detail_session(
 )]
 pub async fn post_create_session(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<SessionsRequestCreateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreateSessions])
 		.await
 	{
-		Ok(_) => mutation_create_session(Json(payload)).await,
+		Ok(_) => mutation_create_session(Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -109,13 +115,14 @@ pub async fn post_create_session(
 )]
 pub async fn put_update_session(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<SessionsRequestUpdateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateSessions])
 		.await
 	{
-		Ok(_) => mutation_update_session(id, Json(payload)).await,
+		Ok(_) => mutation_update_session(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -134,12 +141,193 @@ pub async fn put_update_session(
 )]
 pub async fn delete_session(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteSessions])
 		.await
 	{
-		Ok(_) => mutation_delete_session(id).await,
+		Ok(_) => mutation_delete_session(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/sessions/restore/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Restored", body = MessageResponseDto),
+        (status = 404, description = "Deleted session not found", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn put_restore_session(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteSessions])
+		.await
+	{
+		Ok(_) => mutation_restore_session(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/sessions/export/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Results Export Queued", body = ResponseSuccessDto<SessionsExportItemDto>),
+        (status = 400, description = "Invalid session ID format", body = MessageResponseDto),
+        (status = 404, description = "Session not found", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn post_export_session_results(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(
+		&headers,
+		vec![PermissionsEnum::ExportSessionResults],
+	)
+	.await
+	{
+		Ok(_) => mutation_export_session_results(headers, id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/sessions/publish-results/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Results Published", body = MessageResponseDto),
+        (status = 404, description = "Session not found", body = MessageResponseDto),
+        (status = 409, description = "Session results already published", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn put_publish_session_results(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateSessions])
+		.await
+	{
+		Ok(_) => mutation_publish_session_results(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+/// Gives a live session more time. Connected students receive a
+/// `session_extended` event with the new end dates.
+#[utoipa::path(
+    put,
+    path = "/v1/sessions/extend/{id}",
+    request_body = SessionsExtendRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Extended", body = MessageResponseDto),
+        (status = 400, description = "Invalid minutes", body = MessageResponseDto),
+        (status = 404, description = "Session or schedule not found", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn put_extend_session(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<SessionsExtendRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateSessions])
+		.await
+	{
+		Ok(_) => mutation_extend_session(id, Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/sessions/me",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Sessions the caller may take", body = ResponseSuccessDto<Vec<SessionsItemDto>>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn get_my_sessions(headers: HeaderMap) -> impl IntoResponse {
+	query_get_my_sessions(headers).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/sessions/enrollments/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Roster", body = ResponseSuccessDto<Vec<SessionsEnrollmentItemDto>>),
+        (status = 404, description = "Session not found", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn get_session_enrollments(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailSessions])
+		.await
+	{
+		Ok(_) => query_get_session_enrollments(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/sessions/enrollments/{id}",
+    request_body = SessionsEnrollmentRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Session Roster Updated", body = MessageResponseDto),
+        (status = 400, description = "Invalid user ID format", body = MessageResponseDto),
+        (status = 404, description = "Session or user not found", body = MessageResponseDto)
+    ),
+    tag = "Sessions"
+)]
+pub async fn put_session_enrollments(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<SessionsEnrollmentRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateSessions])
+		.await
+	{
+		Ok(_) => {
+			mutation_update_session_enrollments(id, Json(payload), &audit).await
+		}
 		Err(response) => response,
 	}
 }
@@ -18,6 +18,9 @@ pub struct SessionsRequestUpdateDto {
 	pub is_active: bool,
 	pub category: String,
 	pub description: String,
+	pub results_release: Option<String>,
+	pub enrollment: Option<String>,
+	pub group_ids: Option<Vec<String>>,
 	pub tests: Vec<SessionsTestDto>,
 }
 
@@ -28,6 +31,12 @@ pub struct SessionsRequestCreateDto {
 	pub is_active: bool,
 	pub category: String,
 	pub description: String,
+	/// `immediately` (default), `after_end_date` or `manual`.
+	pub results_release: Option<String>,
+	/// `open` (default), `student_type`, `roster` or `paid`.
+	pub enrollment: Option<String>,
+	/// Groups whose students a `roster` session admits besides its roster.
+	pub group_ids: Option<Vec<String>>,
 	pub tests: Vec<SessionsTestDto>,
 }
 
@@ -40,6 +49,9 @@ pub struct SessionsItemListDto {
 	pub is_active: bool,
 	pub description: String,
 	pub test_count: u64,
+	pub results_release: String,
+	pub results_published_at: Option<String>,
+	pub enrollment: String,
 	pub created_at: Option<String>,
 	pub updated_at: Option<String>,
 }
@@ -52,7 +64,40 @@ pub struct SessionsItemDto {
 	pub is_active: bool,
 	pub category: String,
 	pub description: String,
+	pub results_release: String,
+	pub results_published_at: Option<String>,
+	pub enrollment: String,
+	pub group_ids: Vec<String>,
 	pub tests: Vec<TestsItemListDto>,
 	pub created_at: Option<String>,
 	pub updated_at: Option<String>,
 }
+
+/// Replaces the session's roster with exactly these users.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsEnrollmentRequestDto {
+	pub user_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsEnrollmentItemDto {
+	pub user_id: String,
+	pub fullname: String,
+	pub email: String,
+	pub student_type: String,
+	pub enrolled_at: String,
+}
+
+/// Pushes the end date of the session's scheduled tests back by `minutes`,
+/// or only of `test_id` when given.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsExtendRequestDto {
+	pub minutes: i64,
+	pub test_id: Option<String>,
+}
+
+/// The queued export; the file is announced by notification when ready.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionsExportItemDto {
+	pub job_id: String,
+}
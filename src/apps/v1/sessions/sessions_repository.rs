@@ -1,79 +1,400 @@
 use axum::{http::StatusCode, response::Response, Json};
 use chrono::Utc;
-use futures::future::join_all;
+use hyper::HeaderMap;
+use log::error;
 use sea_orm::{
 	prelude::*, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
-	ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait,
-	QueryFilter, QueryOrder, Set,
+	ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+	PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement,
 };
+use serde_json::{json, Value};
+use std::{collections::HashMap, env};
 use uuid::Uuid;
 
 use crate::{
-	app_sessions_has_tests_schema, common_response, get_db,
+	app_sessions_has_tests_schema, audit_snapshot,
+	auth::email_from_headers,
+	common_response, count_test_questions, enqueue_job, get_db,
+	groups::resolve_group_ids,
+	notifications::{
+		notify_user, notify_users, NewNotification, NOTIFICATION_KIND_EXPORT_READY,
+		NOTIFICATION_KIND_RESULTS_PUBLISHED, NOTIFICATION_KIND_SESSION_OPENED,
+	},
+	orders::find_caller,
+	publish_event, record_audit_log,
+	roles::RolesEnum,
 	schemas::{
 		app_sessions_has_tests_schema as sessions_has_tests,
-		TestSessionsActiveModel, TestSessionsColumn, TestSessionsEntity,
-		TestsEntity,
+		SessionEnrollmentsActiveModel, SessionEnrollmentsColumn,
+		SessionEnrollmentsEntity, SessionGroupsActiveModel, SessionGroupsColumn,
+		SessionGroupsEntity, TestSessionsActiveModel, TestSessionsColumn,
+		TestSessionsEntity, TestSessionsModel, TestsEntity, UsersColumn,
+		UsersEntity,
 	},
-	success_response, success_response_list, MetaRequestDto, MetaResponseDto,
-	QuestionsColumn, QuestionsEntity, ResponseSuccessDto, ResponseSuccessListDto,
-	TestsItemListDto,
+	session_topic, success_response, success_response_list,
+	users::ANSWER_SCORES_CTE,
+	AuditAction, AuditContext, EmailTemplate, FieldKind, Job, ListParams, ListSpec,
+	MetaRequestDto, MinioClient, RealtimeEvent, ResponseSuccessDto,
+	ResponseSuccessListDto, SoftDelete, TestsItemListDto,
+	REALTIME_EVENT_RESULTS_PUBLISHED, REALTIME_EVENT_SESSION_EXTENDED,
 };
 
 use super::sessions_dto::{
-	SessionsItemDto, SessionsItemListDto, SessionsRequestCreateDto,
-	SessionsRequestUpdateDto,
+	SessionsEnrollmentItemDto, SessionsEnrollmentRequestDto, SessionsExportItemDto,
+	SessionsExtendRequestDto, SessionsItemDto, SessionsItemListDto,
+	SessionsRequestCreateDto, SessionsRequestUpdateDto,
 };
 
-pub async fn query_get_sessions(params: MetaRequestDto) -> Response {
-	let db: DatabaseConnection = get_db().await;
+pub const RESULTS_RELEASE_IMMEDIATELY: &str = "immediately";
+pub const RESULTS_RELEASE_AFTER_END_DATE: &str = "after_end_date";
+pub const RESULTS_RELEASE_MANUAL: &str = "manual";
 
-	let page = params.page.unwrap_or(1).max(1);
-	let per_page = params.per_page.unwrap_or(10).max(1).min(100);
-	let search = params.search.unwrap_or_default().to_lowercase();
-	let sort_by = params
-		.sort_by
-		.unwrap_or("created_at".to_string())
-		.to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
+pub const SESSION_ENROLLMENT_OPEN: &str = "open";
+pub const SESSION_ENROLLMENT_STUDENT_TYPE: &str = "student_type";
+pub const SESSION_ENROLLMENT_ROSTER: &str = "roster";
+pub const SESSION_ENROLLMENT_PAID: &str = "paid";
 
-	let mut query = TestSessionsEntity::find();
+const SESSION_EXTEND_MAX_MINUTES: i64 = 1440;
 
-	if !search.is_empty() {
-		query = query.filter(
-			Expr::col(TestSessionsColumn::SessionName)
-				.ilike(format!("%{}%", search)),
-		);
+const SESSION_RESULTS_EXPORT_SQL: &str = r#"
+SELECT u.fullname, u.email, s.test_name, s.correct_answers, s.total_questions,
+	s.score, s.created_at
+FROM scored s
+JOIN app_user_answers a ON a.id = s.answer_id
+JOIN app_users u ON u.id = s.user_id
+WHERE a.session_id = $1
+ORDER BY s.test_name, s.score DESC, u.fullname
+"#;
+
+// Whether session `s` lets user `u` take its tests. Shared by the per-test
+// check, the "my sessions" listing and the event stream so they all agree.
+// A purchased entitlement admits in every mode. Sessions sold in a package,
+// like `paid` ones, admit nobody else whatever their enrollment mode.
+const SESSION_ADMITS_USER_SQL: &str = r#"
+	s.deleted_at IS NULL AND s.is_active AND (
+		EXISTS (
+			SELECT 1 FROM app_entitlements en
+			WHERE en.session_id = s.id AND en.user_id = u.id
+		)
+		OR (
+			NOT EXISTS (
+				SELECT 1 FROM app_package_sessions ps
+				WHERE ps.session_id = s.id
+			)
+			AND (
+				s.enrollment = 'open'
+				OR (
+					s.enrollment = 'student_type'
+					AND LOWER(s.student_type) = LOWER(u.student_type)
+				)
+				OR (
+					s.enrollment = 'roster'
+					AND (
+						EXISTS (
+							SELECT 1 FROM app_session_enrollments e
+							WHERE e.session_id = s.id AND e.user_id = u.id
+						)
+						OR EXISTS (
+							SELECT 1 FROM app_session_groups sg
+							JOIN app_group_members gm ON gm.group_id = sg.group_id
+							WHERE sg.session_id = s.id
+								AND gm.user_id = u.id
+								AND gm.role = 'student'
+						)
+					)
+				)
+			)
+		)
+	)
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct SessionAdmissionRow {
+	admitted: bool,
+}
+
+// The latest submission per student and test in the session; each gets one
+// "results published" email.
+const PUBLISHED_RESULTS_RECIPIENTS_SQL: &str = r#"
+SELECT DISTINCT ON (a.user_id, a.test_id)
+	a.id AS answer_id, a.user_id, u.email, u.fullname, t.test_name
+FROM app_user_answers a
+JOIN app_users u ON u.id = a.user_id
+JOIN app_tests t ON t.id = a.test_id
+WHERE a.session_id = $1 AND u.deleted_at IS NULL
+ORDER BY a.user_id, a.test_id, a.created_at DESC NULLS LAST
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct PublishedResultsRecipient {
+	answer_id: Uuid,
+	user_id: Uuid,
+	email: String,
+	fullname: String,
+	test_name: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct IdRow {
+	id: Uuid,
+}
+
+fn validate_results_release(value: &str) -> Result<(), Response> {
+	match value {
+		RESULTS_RELEASE_IMMEDIATELY
+		| RESULTS_RELEASE_AFTER_END_DATE
+		| RESULTS_RELEASE_MANUAL => Ok(()),
+		_ => Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"results_release must be one of immediately, after_end_date or manual",
+		)),
 	}
+}
 
-	query = match (sort_by.as_str(), order.as_str()) {
-		("session_name", "asc") => {
-			query.order_by_asc(TestSessionsColumn::SessionName)
-		}
-		("session_name", "desc") => {
-			query.order_by_desc(TestSessionsColumn::SessionName)
+fn validate_enrollment(
+	enrollment: &str,
+	student_type: Option<&str>,
+) -> Result<(), Response> {
+	match enrollment {
+		SESSION_ENROLLMENT_OPEN
+		| SESSION_ENROLLMENT_ROSTER
+		| SESSION_ENROLLMENT_PAID => Ok(()),
+		SESSION_ENROLLMENT_STUDENT_TYPE => match student_type {
+			Some(student_type) if !student_type.is_empty() => Ok(()),
+			_ => Err(common_response(
+				StatusCode::BAD_REQUEST,
+				"student_type is required when enrollment is student_type",
+			)),
+		},
+		_ => Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"enrollment must be one of open, student_type, roster or paid",
+		)),
+	}
+}
+
+/// Whether the user may take `test_id`: some active session scheduling the
+/// test has to admit them. Admin bypass is left to the caller.
+pub async fn user_can_take_test<C: ConnectionTrait>(
+	db: &C,
+	user_id: Uuid,
+	test_id: Uuid,
+) -> Result<bool, DbErr> {
+	let sql = format!(
+		r#"
+		SELECT EXISTS (
+			SELECT 1 FROM app_sessions_has_tests st
+			JOIN app_test_sessions s ON s.id = st.session_id
+			JOIN app_users u ON u.id = $1
+			WHERE st.test_id = $2 AND {}
+		) AS admitted
+		"#,
+		SESSION_ADMITS_USER_SQL
+	);
+
+	let row =
+		SessionAdmissionRow::find_by_statement(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			&sql,
+			vec![user_id.into(), test_id.into()],
+		))
+		.one(db)
+		.await?;
+
+	Ok(row.is_some_and(|row| row.admitted))
+}
+
+#[derive(Debug, FromQueryResult)]
+pub struct OpenTestSchedule {
+	pub session_id: Uuid,
+	pub start_date: Option<DateTimeUtc>,
+	pub end_date: Option<DateTimeUtc>,
+}
+
+/// The schedule the user can take `test_id` through right now: an active
+/// session that admits them and whose start/end window for the test contains
+/// the current time. When several are open, the one closing first wins.
+pub async fn find_open_test_schedule(
+	db: &DatabaseConnection,
+	user_id: Uuid,
+	test_id: Uuid,
+) -> Result<Option<OpenTestSchedule>, DbErr> {
+	let sql = format!(
+		r#"
+		SELECT st.session_id, st.start_date, st.end_date
+		FROM app_sessions_has_tests st
+		JOIN app_test_sessions s ON s.id = st.session_id
+		JOIN app_users u ON u.id = $1
+		WHERE st.test_id = $2
+			AND (st.start_date IS NULL OR st.start_date <= now())
+			AND (st.end_date IS NULL OR st.end_date > now())
+			AND {}
+		ORDER BY st.end_date ASC NULLS LAST
+		LIMIT 1
+		"#,
+		SESSION_ADMITS_USER_SQL
+	);
+
+	OpenTestSchedule::find_by_statement(Statement::from_sql_and_values(
+		DbBackend::Postgres,
+		&sql,
+		vec![user_id.into(), test_id.into()],
+	))
+	.one(db)
+	.await
+}
+
+/// Ids of the active sessions that admit the user.
+pub async fn find_admitted_session_ids(
+	db: &DatabaseConnection,
+	user_id: Uuid,
+) -> Result<Vec<Uuid>, DbErr> {
+	let sql = format!(
+		r#"
+		SELECT s.id FROM app_test_sessions s
+		JOIN app_users u ON u.id = $1
+		WHERE {}
+		"#,
+		SESSION_ADMITS_USER_SQL
+	);
+
+	let rows = IdRow::find_by_statement(Statement::from_sql_and_values(
+		DbBackend::Postgres,
+		&sql,
+		vec![user_id.into()],
+	))
+	.all(db)
+	.await?;
+
+	Ok(rows.into_iter().map(|row| row.id).collect())
+}
+
+/// Whether students may see correct answers and discussions for a test taken
+/// in `session`, scheduled there until `end_date`. A manual publish releases
+/// the results whatever the configured mode.
+pub fn session_results_released(
+	session: &TestSessionsModel,
+	end_date: Option<DateTimeUtc>,
+) -> bool {
+	if session.results_published_at.is_some() {
+		return true;
+	}
+
+	match session.results_release.as_str() {
+		RESULTS_RELEASE_IMMEDIATELY => true,
+		RESULTS_RELEASE_AFTER_END_DATE => {
+			end_date.is_some_and(|end_date| end_date <= Utc::now())
 		}
-		("created_at", "asc") => query.order_by_asc(TestSessionsColumn::CreatedAt),
-		("created_at", "desc") => query.order_by_desc(TestSessionsColumn::CreatedAt),
-		("updated_at", "asc") => query.order_by_asc(TestSessionsColumn::UpdatedAt),
-		("updated_at", "desc") => query.order_by_desc(TestSessionsColumn::UpdatedAt),
-		_ => query.order_by_desc(TestSessionsColumn::CreatedAt),
-	};
+		_ => false,
+	}
+}
 
-	let paginator = query.paginate(&db, per_page);
+/// Whether results of `test_id` taken in `session_id` are released, judged by
+/// that session alone. Answers that do not know their session wait until
+/// every session scheduling the test has released them. Tests outside any
+/// session have nothing holding their results back.
+pub async fn test_results_released<C: ConnectionTrait>(
+	db: &C,
+	test_id: Uuid,
+	session_id: Option<Uuid>,
+) -> bool {
+	let mut query = sessions_has_tests::Entity::find()
+		.filter(sessions_has_tests::Column::TestId.eq(test_id));
+	if let Some(session_id) = session_id {
+		query = query.filter(sessions_has_tests::Column::SessionId.eq(session_id));
+	}
 
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
+	let schedules = match query.find_also_related(TestSessionsEntity).all(db).await {
+		Ok(schedules) => schedules,
 		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
+			error!("Failed to load schedules for test {}: {}", test_id, err);
+			return false;
 		}
 	};
 
-	let sessions = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
+	let sessions: Vec<(sessions_has_tests::Model, TestSessionsModel)> = schedules
+		.into_iter()
+		.filter_map(|(schedule, session)| Some((schedule, session?)))
+		.filter(|(_, session)| session.deleted_at.is_none())
+		.collect();
+
+	sessions.iter().all(|(schedule, session)| {
+		session_results_released(session, schedule.end_date)
+	})
+}
+
+const SESSIONS_LIST: ListSpec<TestSessionsEntity> = ListSpec {
+	filters: &[
+		("is_active", TestSessionsColumn::IsActive, FieldKind::Bool),
+		("category", TestSessionsColumn::Category, FieldKind::Text),
+		(
+			"student_type",
+			TestSessionsColumn::StudentType,
+			FieldKind::Text,
+		),
+		(
+			"enrollment",
+			TestSessionsColumn::Enrollment,
+			FieldKind::Text,
+		),
+		(
+			"results_release",
+			TestSessionsColumn::ResultsRelease,
+			FieldKind::Text,
+		),
+		(
+			"created_at",
+			TestSessionsColumn::CreatedAt,
+			FieldKind::DateTime,
+		),
+	],
+	sorts: &[
+		("session_name", TestSessionsColumn::SessionName),
+		("created_at", TestSessionsColumn::CreatedAt),
+		("updated_at", TestSessionsColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: TestSessionsColumn::Id,
+};
+
+pub async fn query_get_sessions(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query = TestSessionsEntity::find_active();
+
+	if !params.search.is_empty() {
+		query = query.filter(
+			Expr::col(TestSessionsColumn::SessionName)
+				.ilike(format!("%{}%", params.search)),
+		);
+	}
+
+	let (sessions, meta) = match params.fetch(&db, query, &SESSIONS_LIST, None).await
+	{
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let session_ids: Vec<Uuid> = sessions.iter().map(|session| session.id).collect();
+	let test_counts: HashMap<Uuid, u64> = match sessions_has_tests::Entity::find()
+		.select_only()
+		.column(sessions_has_tests::Column::SessionId)
+		.column_as(sessions_has_tests::Column::Id.count(), "test_count")
+		.filter(sessions_has_tests::Column::SessionId.is_in(session_ids))
+		.group_by(sessions_has_tests::Column::SessionId)
+		.into_tuple::<(Uuid, i64)>()
+		.all(&db)
+		.await
+	{
+		Ok(counts) => counts
+			.into_iter()
+			.map(|(session_id, count)| (session_id, count.max(0) as u64))
+			.collect(),
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -82,38 +403,29 @@ pub async fn query_get_sessions(params: MetaRequestDto) -> Response {
 		}
 	};
 
-	let data: Vec<SessionsItemListDto> =
-		join_all(sessions.into_iter().map(|session| {
-			let db = db.clone();
-			async move {
-				let test_count = sessions_has_tests::Entity::find()
-					.filter(sessions_has_tests::Column::SessionId.eq(session.id))
-					.count(&db)
-					.await
-					.unwrap_or(0);
-
-				SessionsItemListDto {
-					id: session.id.to_string(),
-					session_name: session.session_name,
-					student_type: session.student_type,
-					description: session.description,
-					is_active: session.is_active,
-					category: session.category,
-					test_count,
-					created_at: session.created_at.map(|dt| dt.to_string()),
-					updated_at: session.updated_at.map(|dt| dt.to_string()),
-				}
-			}
-		}))
-		.await;
+	let data: Vec<SessionsItemListDto> = sessions
+		.into_iter()
+		.map(|session| SessionsItemListDto {
+			id: session.id.to_string(),
+			test_count: test_counts.get(&session.id).copied().unwrap_or(0),
+			session_name: session.session_name,
+			student_type: session.student_type,
+			description: session.description,
+			is_active: session.is_active,
+			category: session.category,
+			results_release: session.results_release,
+			results_published_at: session
+				.results_published_at
+				.map(|dt| dt.to_string()),
+			enrollment: session.enrollment,
+			created_at: session.created_at.map(|dt| dt.to_string()),
+			updated_at: session.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
 
 	let response = ResponseSuccessListDto {
 		data,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+		meta: Some(meta),
 	};
 
 	success_response_list(response)
@@ -122,7 +434,7 @@ pub async fn query_get_sessions(params: MetaRequestDto) -> Response {
 pub async fn query_get_session_by_id(id: String) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	let session = match TestSessionsEntity::find()
+	let session = match TestSessionsEntity::find_active()
 		.filter(TestSessionsColumn::Id.eq(Uuid::parse_str(&id).unwrap_or_default()))
 		.one(&db)
 		.await
@@ -139,72 +451,152 @@ pub async fn query_get_session_by_id(id: String) -> Response {
 		}
 	};
 
-	let sessions_tests = match app_sessions_has_tests_schema::Entity::find()
-		.filter(app_sessions_has_tests_schema::Column::SessionId.eq(session.id))
-		.find_also_related(TestsEntity)
-		.all(&db)
-		.await
-	{
-		Ok(result) => result,
+	match session_item_dto(&db, session).await {
+		Ok(session_dto) => {
+			let response = ResponseSuccessDto { data: session_dto };
+			success_response(response)
+		}
 		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
-	};
+	}
+}
+
+async fn session_item_dto(
+	db: &DatabaseConnection,
+	session: TestSessionsModel,
+) -> Result<SessionsItemDto, DbErr> {
+	let sessions_tests = app_sessions_has_tests_schema::Entity::find()
+		.filter(app_sessions_has_tests_schema::Column::SessionId.eq(session.id))
+		.find_also_related(TestsEntity)
+		.all(db)
+		.await?;
 
 	let tests_entities: Vec<<TestsEntity as EntityTrait>::Model> = sessions_tests
 		.into_iter()
 		.filter_map(|(_join, test_opt)| test_opt)
+		.filter(|test| test.deleted_at.is_none())
 		.collect();
 
-	let tests_dto_futures = tests_entities.into_iter().map(|test| {
-		let db = db.clone();
-		async move {
-			let question_count = QuestionsEntity::find()
-				.filter(QuestionsColumn::TestId.eq(test.id))
-				.count(&db)
-				.await
-				.unwrap_or(0);
-			TestsItemListDto {
-				id: test.id.to_string(),
-				test_name: test.test_name,
-				question_count,
-				created_at: test.created_at.map(|dt| dt.to_string()),
-				updated_at: test.updated_at.map(|dt| dt.to_string()),
-			}
-		}
-	});
-	let tests_dto: Vec<TestsItemListDto> = join_all(tests_dto_futures).await;
+	let test_ids: Vec<Uuid> = tests_entities.iter().map(|test| test.id).collect();
+	let question_counts = count_test_questions(db, &test_ids).await?;
+
+	let tests_dto: Vec<TestsItemListDto> = tests_entities
+		.into_iter()
+		.map(|test| TestsItemListDto {
+			id: test.id.to_string(),
+			question_count: question_counts.get(&test.id).copied().unwrap_or(0),
+			test_name: test.test_name,
+			created_at: test.created_at.map(|dt| dt.to_string()),
+			updated_at: test.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
 
-	let session_dto = SessionsItemDto {
+	let group_ids = session_group_ids(db, session.id).await?;
+
+	Ok(SessionsItemDto {
 		id: session.id.to_string(),
 		session_name: session.session_name,
 		student_type: session.student_type,
 		category: "General".to_string(),
 		description: session.description,
 		is_active: session.is_active,
+		results_release: session.results_release,
+		results_published_at: session.results_published_at.map(|dt| dt.to_string()),
+		enrollment: session.enrollment,
+		group_ids: group_ids.iter().map(Uuid::to_string).collect(),
 		tests: tests_dto,
 		created_at: session.created_at.map(|dt| dt.to_string()),
 		updated_at: session.updated_at.map(|dt| dt.to_string()),
+	})
+}
+
+/// Tells every active student the session admits that it is open. Staff and
+/// admins are left out; failures are logged since the session is saved.
+async fn notify_session_opened(
+	db: &DatabaseConnection,
+	session: &TestSessionsModel,
+) {
+	let sql = format!(
+		r#"
+		SELECT u.id FROM app_users u
+		JOIN app_roles r ON r.id = u.role_id
+		JOIN app_test_sessions s ON s.id = $1
+		WHERE u.deleted_at IS NULL AND u.is_active AND r.name = $2 AND {}
+		"#,
+		SESSION_ADMITS_USER_SQL
+	);
+
+	let user_ids: Vec<Uuid> =
+		match IdRow::find_by_statement(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			&sql,
+			vec![session.id.into(), RolesEnum::Student.to_string().into()],
+		))
+		.all(db)
+		.await
+		{
+			Ok(rows) => rows.into_iter().map(|row| row.id).collect(),
+			Err(err) => {
+				error!("Failed to load session students: {}", err);
+				return;
+			}
+		};
+
+	let notification = NewNotification {
+		kind: NOTIFICATION_KIND_SESSION_OPENED,
+		title: "Session opened".to_string(),
+		body: format!("{} is now open.", session.session_name),
+		data: Some(json!({ "session_id": session.id })),
 	};
 
-	let response = ResponseSuccessDto { data: session_dto };
-	success_response(response)
+	if let Err(err) = notify_users(db, &user_ids, &notification).await {
+		error!("Failed to notify session students: {}", err);
+	}
 }
 
 pub async fn mutation_create_session(
 	payload: Json<SessionsRequestCreateDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
+	let results_release = payload
+		.results_release
+		.clone()
+		.unwrap_or(RESULTS_RELEASE_IMMEDIATELY.to_string());
+	if let Err(response) = validate_results_release(&results_release) {
+		return response;
+	}
+
+	let enrollment = payload
+		.enrollment
+		.clone()
+		.unwrap_or(SESSION_ENROLLMENT_OPEN.to_string());
+	if let Err(response) =
+		validate_enrollment(&enrollment, payload.student_type.as_deref())
+	{
+		return response;
+	}
+
+	let group_ids = match resolve_group_ids(
+		&db,
+		payload.group_ids.as_deref().unwrap_or_default(),
+	)
+	.await
+	{
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
 	let new_session = TestSessionsActiveModel {
 		id: Set(Uuid::new_v4()),
 		session_name: Set(payload.session_name.clone()),
 		description: Set(payload.description.clone()),
 		category: Set(payload.category.clone()),
 		is_active: Set(payload.is_active),
+		results_release: Set(results_release),
+		enrollment: Set(enrollment),
 		created_at: Set(Some(Utc::now())),
 		updated_at: Set(Some(Utc::now())),
 		student_type: Set(payload.student_type.clone()),
@@ -258,6 +650,31 @@ pub async fn mutation_create_session(
 				}
 			}
 
+			if let Err(err) =
+				replace_session_groups(&db, inserted_session.id, &group_ids).await
+			{
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				);
+			}
+
+			let after = session_audit_snapshot(&db, &inserted_session).await;
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Create,
+				"session",
+				inserted_session.id,
+				None,
+				after,
+			)
+			.await;
+
+			if inserted_session.is_active {
+				notify_session_opened(&db, &inserted_session).await;
+			}
+
 			common_response(StatusCode::CREATED, "Session created successfully")
 		}
 		Err(err) => {
@@ -269,6 +686,7 @@ pub async fn mutation_create_session(
 pub async fn mutation_update_session(
 	id: String,
 	payload: Json<SessionsRequestUpdateDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -282,7 +700,7 @@ pub async fn mutation_update_session(
 		}
 	};
 
-	let session = match TestSessionsEntity::find()
+	let session = match TestSessionsEntity::find_active()
 		.filter(TestSessionsColumn::Id.eq(session_id))
 		.one(&db)
 		.await
@@ -299,29 +717,86 @@ pub async fn mutation_update_session(
 		}
 	};
 
+	if let Some(enrollment) = &payload.enrollment {
+		let student_type = match payload.student_type.is_empty() {
+			true => session.student_type.as_deref(),
+			false => Some(payload.student_type.as_str()),
+		};
+		if let Err(response) = validate_enrollment(enrollment, student_type) {
+			return response;
+		}
+	}
+
+	let group_ids = match &payload.group_ids {
+		Some(group_ids) => match resolve_group_ids(&db, group_ids).await {
+			Ok(ids) => Some(ids),
+			Err(response) => return response,
+		},
+		None => None,
+	};
+
+	let before = session_audit_snapshot(&db, &session).await;
+	let was_active = session.is_active;
 	let mut active_model: TestSessionsActiveModel = session.into();
 
-	if payload.session_name != "" {
+	if !payload.session_name.is_empty() {
 		active_model.session_name = Set(payload.session_name.clone());
 	}
 
-	if payload.category != "" {
+	if !payload.category.is_empty() {
 		active_model.category = Set(payload.category.clone());
 	}
 
-	if payload.student_type != "" {
+	if !payload.student_type.is_empty() {
 		active_model.student_type = Set(Some(payload.student_type.clone()));
 	}
 
-	if payload.description != "" {
+	if !payload.description.is_empty() {
 		active_model.description = Set(payload.description.clone());
 	}
 
+	if let Some(results_release) = &payload.results_release {
+		if let Err(response) = validate_results_release(results_release) {
+			return response;
+		}
+		active_model.results_release = Set(results_release.clone());
+	}
+
+	if let Some(enrollment) = &payload.enrollment {
+		active_model.enrollment = Set(enrollment.clone());
+	}
+
 	active_model.is_active = Set(payload.is_active);
 	active_model.updated_at = Set(Some(Utc::now()));
 
 	match active_model.update(&db).await {
-		Ok(_session) => {
+		Ok(session) => {
+			if let Some(group_ids) = group_ids {
+				if let Err(err) =
+					replace_session_groups(&db, session.id, &group_ids).await
+				{
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					);
+				}
+			}
+
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Update,
+				"session",
+				session.id,
+				before,
+				session_audit_snapshot(&db, &session).await,
+			)
+			.await;
+
+			if session.is_active && !was_active {
+				notify_session_opened(&db, &session).await;
+			}
+
 			common_response(StatusCode::OK, "Session updated successfully")
 		}
 		Err(err) => {
@@ -330,7 +805,7 @@ pub async fn mutation_update_session(
 	}
 }
 
-pub async fn mutation_delete_session(id: String) -> Response {
+pub async fn mutation_delete_session(id: String, audit: &AuditContext) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	let session_id = match Uuid::parse_str(&id) {
@@ -343,7 +818,7 @@ pub async fn mutation_delete_session(id: String) -> Response {
 		}
 	};
 
-	let session = match TestSessionsEntity::find()
+	let session = match TestSessionsEntity::find_active()
 		.filter(TestSessionsColumn::Id.eq(session_id))
 		.one(&db)
 		.await
@@ -360,10 +835,797 @@ pub async fn mutation_delete_session(id: String) -> Response {
 		}
 	};
 
-	match session.delete(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "Session deleted successfully"),
+	let before = audit_snapshot(&session);
+	let mut active_model: TestSessionsActiveModel = session.into();
+	active_model.deleted_at = Set(Some(Utc::now()));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(session) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"session",
+				session.id,
+				before,
+				audit_snapshot(&session),
+			)
+			.await;
+			common_response(StatusCode::OK, "Session deleted successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn mutation_restore_session(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	let session = match TestSessionsEntity::find_deleted()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(session)) => session,
+		Ok(None) => {
+			return common_response(
+				StatusCode::NOT_FOUND,
+				"Deleted session not found",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let before = audit_snapshot(&session);
+	let mut active_model: TestSessionsActiveModel = session.into();
+	active_model.deleted_at = Set(None);
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(session) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Restore,
+				"session",
+				session.id,
+				before,
+				audit_snapshot(&session),
+			)
+			.await;
+			common_response(StatusCode::OK, "Session restored successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
 	}
 }
+
+#[derive(FromQueryResult)]
+struct SessionResultRow {
+	fullname: String,
+	email: String,
+	test_name: String,
+	correct_answers: i64,
+	total_questions: i64,
+	score: f64,
+	created_at: Option<DateTimeUtc>,
+}
+
+/// Quotes a CSV field when needed. Values a spreadsheet would run as a
+/// formula are prefixed with `'`.
+fn csv_field(value: &str) -> String {
+	let value = match value.chars().next() {
+		Some('=' | '+' | '-' | '@') => format!("'{}", value),
+		_ => value.to_string(),
+	};
+
+	if value.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value
+	}
+}
+
+/// Writes every submission taken in the session to a CSV file and notifies
+/// `requested_by` with a link to it. Runs on the job queue.
+pub async fn export_session_results(
+	db: &DatabaseConnection,
+	session_id: Uuid,
+	requested_by: Uuid,
+) -> Result<(), String> {
+	let session = TestSessionsEntity::find_by_id(session_id)
+		.one(db)
+		.await
+		.map_err(|err| err.to_string())?
+		.ok_or_else(|| "Session not found".to_string())?;
+
+	let rows = SessionResultRow::find_by_statement(Statement::from_sql_and_values(
+		DbBackend::Postgres,
+		format!("{}{}", ANSWER_SCORES_CTE, SESSION_RESULTS_EXPORT_SQL),
+		vec![session_id.into()],
+	))
+	.all(db)
+	.await
+	.map_err(|err| err.to_string())?;
+
+	let mut csv = String::from(
+		"name,email,test,correct_answers,total_questions,score,submitted_at\n",
+	);
+	for row in &rows {
+		csv.push_str(&format!(
+			"{},{},{},{},{},{:.2},{}\n",
+			csv_field(&row.fullname),
+			csv_field(&row.email),
+			csv_field(&row.test_name),
+			row.correct_answers,
+			row.total_questions,
+			row.score,
+			row.created_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+		));
+	}
+
+	let minio = MinioClient::new().await.map_err(|err| err.to_string())?;
+	let url = minio
+		.upload_export(
+			&format!("session-{}-results.csv", session.id),
+			csv.into_bytes(),
+		)
+		.await
+		.map_err(|err| err.to_string())?;
+
+	let notification = NewNotification {
+		kind: NOTIFICATION_KIND_EXPORT_READY,
+		title: "Results export ready".to_string(),
+		body: format!(
+			"The results of {} are ready to download.",
+			session.session_name
+		),
+		data: Some(json!({
+			"session_id": session.id,
+			"url": url,
+			"rows": rows.len(),
+		})),
+	};
+	notify_user(db, requested_by, &notification)
+		.await
+		.map_err(|err| err.to_string())
+}
+
+pub async fn mutation_export_session_results(
+	headers: HeaderMap,
+	id: String,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.count(&db)
+		.await
+	{
+		Ok(0) => return common_response(StatusCode::NOT_FOUND, "Session not found"),
+		Ok(_) => {}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	let job = Job::ExportSessionResults {
+		session_id,
+		requested_by: user.id,
+	};
+	let job_id = match enqueue_job(&db, job).await {
+		Ok(job_id) => job_id,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Export,
+		"session",
+		session_id,
+		None,
+		Some(json!({ "job_id": job_id })),
+	)
+	.await;
+
+	success_response(ResponseSuccessDto {
+		data: SessionsExportItemDto {
+			job_id: job_id.to_string(),
+		},
+	})
+}
+
+pub async fn mutation_publish_session_results(
+	id: String,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	let session = match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(session)) => session,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Session not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if session.results_published_at.is_some() {
+		return common_response(
+			StatusCode::CONFLICT,
+			"Session results already published",
+		);
+	}
+
+	let before = audit_snapshot(&session);
+	let mut active_model: TestSessionsActiveModel = session.into();
+	active_model.results_published_at = Set(Some(Utc::now()));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	let session = match active_model.update(&db).await {
+		Ok(session) => session,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Publish,
+		"session",
+		session.id,
+		before,
+		audit_snapshot(&session),
+	)
+	.await;
+
+	// Results are already visible at this point; a failed notification is
+	// logged rather than undoing the publish.
+	let recipients = PublishedResultsRecipient::find_by_statement(
+		Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			PUBLISHED_RESULTS_RECIPIENTS_SQL,
+			vec![session.id.into()],
+		),
+	)
+	.all(&db)
+	.await
+	.unwrap_or_else(|err| {
+		error!("Failed to load results recipients: {}", err);
+		vec![]
+	});
+
+	let fe_url = env::var("FE_URL").unwrap_or_default();
+	for recipient in recipients {
+		let test_name = recipient.test_name;
+		let job = Job::SendEmail {
+			to: recipient.email,
+			template: EmailTemplate::ResultsPublished {
+				fullname: recipient.fullname,
+				test_name: test_name.clone(),
+				result_url: format!(
+					"{}/tests/result/{}",
+					fe_url, recipient.answer_id
+				),
+			},
+		};
+
+		if let Err(err) = enqueue_job(&db, job).await {
+			error!("Failed to enqueue results email: {}", err);
+		}
+
+		let notification = NewNotification {
+			kind: NOTIFICATION_KIND_RESULTS_PUBLISHED,
+			title: "Results published".to_string(),
+			body: format!("Results for {} are now available.", test_name),
+			data: Some(json!({
+				"session_id": session.id,
+				"answer_id": recipient.answer_id,
+			})),
+		};
+
+		if let Err(err) = notify_user(&db, recipient.user_id, &notification).await {
+			error!("Failed to notify results recipient: {}", err);
+		}
+	}
+
+	publish_event(RealtimeEvent::new(
+		session_topic(session.id),
+		REALTIME_EVENT_RESULTS_PUBLISHED,
+		json!({ "session_id": session.id }),
+	));
+
+	common_response(StatusCode::OK, "Session results published successfully")
+}
+
+pub async fn mutation_extend_session(
+	id: String,
+	payload: Json<SessionsExtendRequestDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	if !(1..=SESSION_EXTEND_MAX_MINUTES).contains(&payload.minutes) {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"minutes must be between 1 and 1440",
+		);
+	}
+
+	let test_id = match payload.test_id.as_deref().map(Uuid::parse_str) {
+		Some(Ok(test_id)) => Some(test_id),
+		Some(Err(_)) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
+			)
+		}
+		None => None,
+	};
+
+	match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(_)) => {}
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Session not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut query = sessions_has_tests::Entity::find()
+		.filter(sessions_has_tests::Column::SessionId.eq(session_id))
+		.filter(sessions_has_tests::Column::EndDate.is_not_null());
+	if let Some(test_id) = test_id {
+		query = query.filter(sessions_has_tests::Column::TestId.eq(test_id));
+	}
+
+	let schedules = match query.all(&db).await {
+		Ok(schedules) if schedules.is_empty() => {
+			return common_response(
+				StatusCode::NOT_FOUND,
+				"No scheduled test with an end date to extend",
+			)
+		}
+		Ok(schedules) => schedules,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let before = audit_snapshot(&schedules);
+	let mut extended = Vec::with_capacity(schedules.len());
+	for schedule in schedules {
+		let end_date = schedule
+			.end_date
+			.map(|end_date| end_date + chrono::Duration::minutes(payload.minutes));
+		let mut active_model: sessions_has_tests::ActiveModel = schedule.into();
+		active_model.end_date = Set(end_date);
+
+		match active_model.update(&db).await {
+			Ok(schedule) => extended.push(schedule),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
+	}
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"session_schedule",
+		session_id,
+		before,
+		audit_snapshot(&extended),
+	)
+	.await;
+
+	publish_event(RealtimeEvent::new(
+		session_topic(session_id),
+		REALTIME_EVENT_SESSION_EXTENDED,
+		json!({
+			"session_id": session_id,
+			"minutes": payload.minutes,
+			"tests": extended
+				.iter()
+				.map(|schedule| json!({
+					"test_id": schedule.test_id,
+					"end_date": schedule.end_date.map(|dt| dt.to_string()),
+				}))
+				.collect::<Vec<_>>(),
+		}),
+	));
+
+	common_response(StatusCode::OK, "Session extended successfully")
+}
+
+pub async fn query_get_my_sessions(headers: HeaderMap) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let user = match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email))
+		.one(&db)
+		.await
+	{
+		Ok(Some(user)) => user,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let sql = format!(
+		r#"
+		SELECT s.* FROM app_test_sessions s
+		JOIN app_users u ON u.id = $1
+		WHERE {}
+		ORDER BY s.created_at DESC
+		"#,
+		SESSION_ADMITS_USER_SQL
+	);
+
+	let sessions = match TestSessionsEntity::find()
+		.from_raw_sql(Statement::from_sql_and_values(
+			DbBackend::Postgres,
+			&sql,
+			vec![user.id.into()],
+		))
+		.all(&db)
+		.await
+	{
+		Ok(sessions) => sessions,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut data = Vec::with_capacity(sessions.len());
+	for session in sessions {
+		match session_item_dto(&db, session).await {
+			Ok(session_dto) => data.push(session_dto),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
+	}
+
+	success_response(ResponseSuccessDto { data })
+}
+
+pub async fn query_get_session_enrollments(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.count(&db)
+		.await
+	{
+		Ok(0) => return common_response(StatusCode::NOT_FOUND, "Session not found"),
+		Ok(_) => {}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	let enrollments = match SessionEnrollmentsEntity::find()
+		.filter(SessionEnrollmentsColumn::SessionId.eq(session_id))
+		.find_also_related(UsersEntity)
+		.order_by_asc(SessionEnrollmentsColumn::CreatedAt)
+		.all(&db)
+		.await
+	{
+		Ok(enrollments) => enrollments,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let data: Vec<SessionsEnrollmentItemDto> = enrollments
+		.into_iter()
+		.filter_map(|(enrollment, user)| {
+			let user = user.filter(|user| user.deleted_at.is_none())?;
+			Some(SessionsEnrollmentItemDto {
+				user_id: user.id.to_string(),
+				fullname: user.fullname,
+				email: user.email,
+				student_type: user.student_type,
+				enrolled_at: enrollment.created_at.to_string(),
+			})
+		})
+		.collect();
+
+	success_response(ResponseSuccessDto { data })
+}
+
+pub async fn mutation_update_session_enrollments(
+	id: String,
+	payload: Json<SessionsEnrollmentRequestDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session ID format",
+			)
+		}
+	};
+
+	let session = match TestSessionsEntity::find_active()
+		.filter(TestSessionsColumn::Id.eq(session_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(session)) => session,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Session not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut user_ids: Vec<Uuid> = Vec::with_capacity(payload.user_ids.len());
+	for user_id in payload.user_ids.iter() {
+		match Uuid::parse_str(user_id) {
+			Ok(user_id) if !user_ids.contains(&user_id) => user_ids.push(user_id),
+			Ok(_) => {}
+			Err(_) => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid user ID format",
+				)
+			}
+		}
+	}
+
+	match UsersEntity::find_active()
+		.filter(UsersColumn::Id.is_in(user_ids.clone()))
+		.count(&db)
+		.await
+	{
+		Ok(found) if found == user_ids.len() as u64 => {}
+		Ok(_) => {
+			return common_response(
+				StatusCode::NOT_FOUND,
+				"One or more users not found",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	let before = session_audit_snapshot(&db, &session).await;
+
+	if let Err(err) = SessionEnrollmentsEntity::delete_many()
+		.filter(SessionEnrollmentsColumn::SessionId.eq(session.id))
+		.exec(&db)
+		.await
+	{
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	for user_id in user_ids {
+		let enrollment = SessionEnrollmentsActiveModel {
+			id: Set(Uuid::new_v4()),
+			session_id: Set(session.id),
+			user_id: Set(user_id),
+			created_at: Set(Utc::now()),
+		};
+
+		if let Err(err) = enrollment.insert(&db).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"session",
+		session.id,
+		before,
+		session_audit_snapshot(&db, &session).await,
+	)
+	.await;
+
+	common_response(StatusCode::OK, "Session enrollments updated successfully")
+}
+
+async fn session_group_ids(
+	db: &DatabaseConnection,
+	session_id: Uuid,
+) -> Result<Vec<Uuid>, DbErr> {
+	SessionGroupsEntity::find()
+		.select_only()
+		.column(SessionGroupsColumn::GroupId)
+		.filter(SessionGroupsColumn::SessionId.eq(session_id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+}
+
+async fn replace_session_groups(
+	db: &DatabaseConnection,
+	session_id: Uuid,
+	group_ids: &[Uuid],
+) -> Result<(), DbErr> {
+	SessionGroupsEntity::delete_many()
+		.filter(SessionGroupsColumn::SessionId.eq(session_id))
+		.exec(db)
+		.await?;
+
+	for group_id in group_ids {
+		SessionGroupsActiveModel {
+			id: Set(Uuid::new_v4()),
+			session_id: Set(session_id),
+			group_id: Set(*group_id),
+		}
+		.insert(db)
+		.await?;
+	}
+
+	Ok(())
+}
+
+/// Session fields plus its scheduled tests, weights, multipliers, roster and
+/// groups.
+async fn session_audit_snapshot(
+	db: &DatabaseConnection,
+	session: &TestSessionsModel,
+) -> Option<Value> {
+	let tests = sessions_has_tests::Entity::find()
+		.filter(sessions_has_tests::Column::SessionId.eq(session.id))
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let enrolled_user_ids = SessionEnrollmentsEntity::find()
+		.select_only()
+		.column(SessionEnrollmentsColumn::UserId)
+		.filter(SessionEnrollmentsColumn::SessionId.eq(session.id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let group_ids = session_group_ids(db, session.id).await.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(session)?;
+	snapshot["tests"] = json!(tests);
+	snapshot["enrolled_user_ids"] = json!(enrolled_user_ids);
+	snapshot["group_ids"] = json!(group_ids);
+	Some(snapshot)
+}
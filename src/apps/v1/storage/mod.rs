@@ -1,4 +1,5 @@
 use crate::storage_state::storage_state;
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
 use axum::{routing::post, Router};
 
 pub mod storage_controller;
@@ -15,6 +16,11 @@ pub async fn storage_router() -> Router {
 
 	Router::new().route(
 		"/upload",
-		post(storage_controller::post_upload).with_state(state),
+		post(storage_controller::post_upload)
+			.with_state(state)
+			.layer(RateLimitLayer::new(
+				RateLimitPolicy::per_minute("storage_upload", 20)
+					.keyed_by(RateLimitKey::User),
+			)),
 	)
 }
@@ -11,7 +11,7 @@ pub async fn mutation_upload_file(
 	State(state): State<AppState>,
 	mut multipart: Multipart,
 ) -> Response {
-	while let Some(field) = match multipart.next_field().await {
+	if let Some(field) = match multipart.next_field().await {
 		Ok(field) => field,
 		Err(e) => {
 			error!("Failed to parse multipart field: {}", e);
@@ -3,6 +3,8 @@ use axum::{
 	Router,
 };
 
+use crate::{RateLimitKey, RateLimitLayer, RateLimitPolicy};
+
 pub mod tests_controller;
 pub mod tests_dto;
 pub mod tests_repository;
@@ -17,13 +19,39 @@ pub fn tests_router() -> Router {
 		.route("/detail/{id}", get(tests_controller::get_detail_test))
 		.route("/update/{id}", put(tests_controller::put_update_test))
 		.route("/delete/{id}", delete(tests_controller::delete_test))
+		.route("/restore/{id}", put(tests_controller::put_restore_test))
+		.route("/{id}/analytics", get(tests_controller::get_test_analytics))
 		.route("/answer/{id}", get(tests_controller::get_test_answer))
 		.route(
 			"/answer/delete/{id}",
 			delete(tests_controller::delete_test_answer),
 		)
+		.route(
+			"/attempt/{id}",
+			post(tests_controller::post_start_test_attempt).layer(
+				RateLimitLayer::new(
+					RateLimitPolicy::per_minute("tests_attempt_start", 10)
+						.keyed_by(RateLimitKey::User),
+				),
+			),
+		)
+		.route(
+			"/attempt/{id}/answers",
+			put(tests_controller::put_save_attempt_answers).layer(
+				RateLimitLayer::new(
+					RateLimitPolicy::per_minute("tests_attempt_answers", 60)
+						.keyed_by(RateLimitKey::User),
+				),
+			),
+		)
 		.route(
 			"/answer/create",
-			post(tests_controller::post_create_test_answer),
+			post(tests_controller::post_create_test_answer).layer(
+				RateLimitLayer::new(
+					RateLimitPolicy::per_minute("tests_answer_create", 10)
+						.keyed_by(RateLimitKey::User),
+				),
+			),
 		)
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute("tests", 120)))
 }
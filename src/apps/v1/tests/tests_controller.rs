@@ -8,18 +8,21 @@ use axum::{
 use crate::{
 	permissions::{permissions_middleware, PermissionsEnum},
 	utils::dto::{MessageResponseDto, MetaRequestDto},
-	ResponseSuccessDto, ResponseSuccessListDto,
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
 };
 
 use super::{
 	mutation_create_test_answer, mutation_delete_test_answer,
-	query_get_test_answer_by_id,
+	mutation_save_attempt_answers, mutation_start_test_attempt,
+	query_get_test_analytics, query_get_test_answer_by_id,
 	tests_dto::{TestsRequestCreateDto, TestsRequestUpdateDto},
 	tests_repository::{
-		mutation_create_test, mutation_delete_test, mutation_update_test,
-		query_get_test_by_id, query_get_tests,
+		mutation_create_test, mutation_delete_test, mutation_restore_test,
+		mutation_update_test, query_get_test_by_id, query_get_tests,
 	},
-	TestAnswersItemDto, TestAnswersRequestCreateDto, TestsItemDto, TestsItemListDto,
+	TestAnalyticsDto, TestAnswersItemDto, TestAnswersRequestCreateDto,
+	TestAttemptAnswersRequestDto, TestAttemptItemDto, TestsItemDto,
+	TestsItemListDto,
 };
 
 #[utoipa::path(
@@ -86,11 +89,12 @@ pub async fn get_detail_test(
 )]
 pub async fn post_create_test(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<TestsRequestCreateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreateTests]).await
 	{
-		Ok(_) => mutation_create_test(Json(payload)).await,
+		Ok(_) => mutation_create_test(Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -110,12 +114,13 @@ pub async fn post_create_test(
 )]
 pub async fn put_update_test(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<TestsRequestUpdateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateTests]).await
 	{
-		Ok(_) => mutation_update_test(id, Json(payload)).await,
+		Ok(_) => mutation_update_test(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -134,11 +139,88 @@ pub async fn put_update_test(
 )]
 pub async fn delete_test(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteTests]).await
 	{
-		Ok(_) => mutation_delete_test(id).await,
+		Ok(_) => mutation_delete_test(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/tests/restore/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Test Restored", body = MessageResponseDto),
+        (status = 404, description = "Deleted test not found", body = MessageResponseDto)
+    ),
+    tag = "Tests"
+)]
+pub async fn put_restore_test(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteTests]).await
+	{
+		Ok(_) => mutation_restore_test(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/tests/attempt/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Test Attempt Started", body = ResponseSuccessDto<TestAttemptItemDto>),
+        (status = 404, description = "Test not found", body = MessageResponseDto),
+        (status = 409, description = "Not enough questions in the bank for this blueprint", body = MessageResponseDto)
+    ),
+    tag = "Tests"
+)]
+pub async fn post_start_test_attempt(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreateTests]).await
+	{
+		Ok(_) => mutation_start_test_attempt(headers, id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/tests/attempt/{id}/answers",
+    request_body = TestAttemptAnswersRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Test Attempt Answers Saved", body = MessageResponseDto),
+        (status = 400, description = "Invalid Test Answer data", body = MessageResponseDto),
+        (status = 404, description = "Test attempt not found", body = MessageResponseDto),
+        (status = 409, description = "Test attempt already submitted", body = MessageResponseDto)
+    ),
+    tag = "Tests"
+)]
+pub async fn put_save_attempt_answers(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+	Json(payload): Json<TestAttemptAnswersRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreateTests]).await
+	{
+		Ok(_) => mutation_save_attempt_answers(headers, id, Json(payload)).await,
 		Err(response) => response,
 	}
 }
@@ -162,7 +244,7 @@ pub async fn get_test_answer(
 	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailTests])
 		.await
 	{
-		Ok(_) => query_get_test_answer_by_id(id).await,
+		Ok(_) => query_get_test_answer_by_id(headers, id).await,
 		Err(response) => response,
 	}
 }
@@ -182,11 +264,12 @@ pub async fn get_test_answer(
 )]
 pub async fn post_create_test_answer(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<TestAnswersRequestCreateDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreateTests]).await
 	{
-		Ok(_) => mutation_create_test_answer(headers, Json(payload)).await,
+		Ok(_) => mutation_create_test_answer(headers, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -205,11 +288,36 @@ pub async fn post_create_test_answer(
 )]
 pub async fn delete_test_answer(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteTests]).await
 	{
-		Ok(_) => mutation_delete_test_answer(id).await,
+		Ok(_) => mutation_delete_test_answer(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/tests/{id}/analytics",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Test Question Analytics", body = ResponseSuccessDto<TestAnalyticsDto>),
+        (status = 404, description = "Test not found", body = MessageResponseDto)
+    ),
+    tag = "Tests"
+)]
+pub async fn get_test_analytics(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadTestAnalytics])
+		.await
+	{
+		Ok(_) => query_get_test_analytics(id).await,
 		Err(response) => response,
 	}
 }
@@ -6,6 +6,10 @@ pub struct TestAnswersItemDto {
 	pub id: String,
 	pub user_id: String,
 	pub test_id: String,
+	pub attempt_id: Option<String>,
+	/// Percentage of questions answered correctly, filled in by the worker
+	/// shortly after submission.
+	pub score: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -26,22 +30,37 @@ pub struct QuestionsAnswersItemDto {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TestAnswersRequestCreateDto {
 	pub test_id: String,
+	pub attempt_id: Option<String>,
+	pub questions: Vec<QuestionsAnswersRequestCreateDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestAttemptAnswersRequestDto {
 	pub questions: Vec<QuestionsAnswersRequestCreateDto>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct QuestionsItemDto {
 	pub id: String,
+	pub version: i32,
 	pub question: String,
-	pub discussion: String,
+	/// Withheld from students until the session releases the results.
+	pub discussion: Option<String>,
+	pub discussion_image_url: Option<String>,
 	pub image_url: Option<String>,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
 	pub options: Vec<OptionsItemDto>,
 }
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct QuestionsAnswersDataItemDto {
 	pub id: String,
+	pub version: i32,
 	pub question: String,
-	pub discussion: String,
+	/// Withheld from students until the session releases the results.
+	pub discussion: Option<String>,
+	pub discussion_image_url: Option<String>,
 	pub options: Vec<OptionsAnswerItemDto>,
 }
 
@@ -68,6 +87,9 @@ pub struct QuestionsRequestCreateDto {
 	pub discussion: String,
 	pub discussion_image_url: Option<String>,
 	pub image_url: Option<String>,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
 	pub options: Vec<OptionsRequestCreateDto>,
 }
 
@@ -78,6 +100,9 @@ pub struct QuestionsRequestUpdateDto {
 	pub discussion: String,
 	pub discussion_image_url: Option<String>,
 	pub image_url: Option<String>,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
 	pub options: Vec<OptionsRequestUpdateDto>,
 }
 
@@ -99,13 +124,32 @@ pub struct OptionsRequestUpdateDto {
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestsRequestUpdateDto {
 	pub test_name: Option<String>,
+	/// Integrity violations after which an attempt is submitted
+	/// automatically; `0` turns auto-submit off.
+	pub max_violations: Option<i32>,
 	pub questions: Vec<QuestionsRequestUpdateDto>,
+	pub question_ids: Option<Vec<String>>,
+	pub remove_question_ids: Option<Vec<String>>,
+	pub blueprint: Option<Vec<TestBlueprintRuleDto>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestsRequestCreateDto {
 	pub test_name: String,
+	/// Integrity violations after which an attempt is submitted
+	/// automatically. Off when empty or `0`.
+	pub max_violations: Option<i32>,
 	pub questions: Vec<QuestionsRequestCreateDto>,
+	pub question_ids: Option<Vec<String>>,
+	pub blueprint: Option<Vec<TestBlueprintRuleDto>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestBlueprintRuleDto {
+	pub topic: String,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
+	pub question_count: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -121,9 +165,80 @@ pub struct TestsItemListDto {
 pub struct TestsItemDto {
 	pub id: String,
 	pub test_name: String,
+	pub max_violations: Option<i32>,
 	pub questions: Vec<QuestionsItemDto>,
+	pub blueprint: Vec<TestBlueprintRuleDto>,
 	pub start_date: Option<String>,
 	pub end_date: Option<String>,
 	pub created_at: Option<String>,
 	pub updated_at: Option<String>,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestAttemptItemDto {
+	pub id: String,
+	pub test_id: String,
+	pub test_name: String,
+	pub max_violations: Option<i32>,
+	pub questions: Vec<QuestionsItemDto>,
+	pub start_date: Option<String>,
+	pub end_date: Option<String>,
+	pub created_at: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TestAnalyticsDto {
+	pub test_id: String,
+	pub test_name: String,
+	pub total_attempts: u64,
+	pub group_size: u64,
+	pub questions: Vec<QuestionAnalyticsDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuestionAnalyticsDto {
+	pub id: String,
+	pub version: i32,
+	pub question: String,
+	pub topic: Option<String>,
+	pub difficulty: Option<String>,
+	pub archived: bool,
+	pub responses: u64,
+	pub correct_responses: u64,
+	pub p_value: Option<f64>,
+	pub discrimination_index: Option<f64>,
+	pub needs_review: bool,
+	pub options: Vec<OptionAnalyticsDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct OptionAnalyticsDto {
+	pub id: String,
+	pub label: String,
+	pub is_correct: bool,
+	pub picked: u64,
+	pub pick_rate: Option<f64>,
+}
+
+impl From<&QuestionsRequestUpdateDto> for QuestionsRequestCreateDto {
+	fn from(question: &QuestionsRequestUpdateDto) -> Self {
+		Self {
+			question: question.question.clone(),
+			discussion: question.discussion.clone(),
+			discussion_image_url: question.discussion_image_url.clone(),
+			image_url: question.image_url.clone(),
+			topic: question.topic.clone(),
+			subtopic: question.subtopic.clone(),
+			difficulty: question.difficulty.clone(),
+			options: question
+				.options
+				.iter()
+				.map(|option| OptionsRequestCreateDto {
+					label: option.label.clone(),
+					is_correct: option.is_correct,
+					image_url: option.image_url.clone(),
+				})
+				.collect(),
+		}
+	}
+}
@@ -1,105 +1,167 @@
 use axum::{http::StatusCode, response::Response, Json};
 use chrono::Utc;
-use futures::future::join_all;
 use hyper::HeaderMap;
+use log::error;
 use sea_orm::{
-	prelude::*, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
-	ColumnTrait, DatabaseConnection, EntityTrait, JoinType, ModelTrait,
-	PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+	prelude::*,
+	sea_query::{OnConflict, Query},
+	ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, EntityTrait,
+	JoinType, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+	QuerySelect, Select, Set, Statement, TransactionTrait,
 };
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::{
 	app_user_answers_schema, app_user_questions_answers_schema, app_users_schema,
-	common_response, decode_access_token, get_db,
+	audit_snapshot,
+	auth::email_from_headers,
+	common_response, decode_access_token, enqueue_job, get_db,
+	groups::instructor_can_view_answer,
+	publish_event,
+	questions::{
+		find_bank_question, find_test_questions, insert_bank_question,
+		link_question_to_test, save_question_revision, unlink_question_from_test,
+		validate_difficulty,
+	},
+	record_audit_log,
 	schemas::{
-		OptionsActiveModel, OptionsColumn, OptionsEntity, QuestionsActiveModel,
-		QuestionsColumn, QuestionsEntity, TestsActiveModel, TestsColumn,
-		TestsEntity,
+		AttemptAnswersActiveModel, AttemptAnswersColumn, AttemptAnswersEntity,
+		OptionsColumn, OptionsEntity, OptionsModel, QuestionsColumn,
+		QuestionsEntity, QuestionsModel, QuestionsRelation,
+		TestAttemptQuestionsActiveModel, TestAttemptQuestionsColumn,
+		TestAttemptQuestionsEntity, TestAttemptQuestionsRelation,
+		TestAttemptsActiveModel, TestAttemptsColumn, TestAttemptsEntity,
+		TestAttemptsModel, TestBlueprintsActiveModel, TestBlueprintsColumn,
+		TestBlueprintsEntity, TestBlueprintsModel, TestQuestionsAnswersRelation,
+		TestsActiveModel, TestsColumn, TestsEntity, TestsHasQuestionsColumn,
+		TestsHasQuestionsEntity, TestsModel,
 	},
-	success_response, success_response_list, MetaRequestDto, MetaResponseDto,
-	ResponseSuccessDto, ResponseSuccessListDto, RolesColumn, RolesEnum,
-	SessionsHasTestsColumn, SessionsHasTestsEntity, TestAnswersColumn,
-	TestAnswersEntity, TestQuestionsAnswersColumn, TestQuestionsAnswersEntity,
-	UsersColumn, UsersEntity,
+	sessions::{find_open_test_schedule, test_results_released, user_can_take_test},
+	success_response, success_response_list,
+	users::ANSWER_SCORES_CTE,
+	AuditAction, AuditContext, FieldKind, FilterOp, FullTextSearch, Job, ListParams,
+	ListSpec, MetaRequestDto, RealtimeEvent, ResponseSuccessDto,
+	ResponseSuccessListDto, RolesColumn, RolesEnum, SessionsHasTestsColumn,
+	SessionsHasTestsEntity, SoftDelete, TestAnswersColumn, TestAnswersEntity,
+	TestQuestionsAnswersColumn, TestQuestionsAnswersEntity, UsersColumn,
+	UsersEntity, REALTIME_EVENT_ATTEMPT_PROGRESS, REALTIME_TOPIC_ATTEMPTS,
 };
 
 use super::{
 	tests_dto::{
-		OptionsItemDto, QuestionsItemDto, TestsItemDto, TestsItemListDto,
-		TestsRequestCreateDto, TestsRequestUpdateDto,
+		OptionsItemDto, QuestionsItemDto, QuestionsRequestCreateDto,
+		TestAttemptAnswersRequestDto, TestAttemptItemDto, TestBlueprintRuleDto,
+		TestsItemDto, TestsItemListDto, TestsRequestCreateDto,
+		TestsRequestUpdateDto,
 	},
-	OptionsAnswerItemDto, QuestionsAnswersDataItemDto, QuestionsAnswersItemDto,
+	OptionAnalyticsDto, OptionsAnswerItemDto, QuestionAnalyticsDto,
+	QuestionsAnswersDataItemDto, QuestionsAnswersItemDto, TestAnalyticsDto,
 	TestAnswersItemDto, TestAnswersRequestCreateDto,
 };
 
+const SCORE_TEST_ANSWER_SQL: &str = r#"
+UPDATE app_user_answers a
+SET score = s.score, scored_at = now()
+FROM scored s
+WHERE s.answer_id = a.id AND a.id = $1
+"#;
+
+// Classic item analysis compares the top and bottom 27% of scorers.
+const ANALYTICS_GROUP_RATIO: f64 = 0.27;
+const ANALYTICS_MIN_P_VALUE: f64 = 0.2;
+const ANALYTICS_MAX_P_VALUE: f64 = 0.9;
+const ANALYTICS_MIN_DISCRIMINATION: f64 = 0.2;
+
+const TESTS_LIST: ListSpec<TestsEntity> = ListSpec {
+	filters: &[
+		("created_at", TestsColumn::CreatedAt, FieldKind::DateTime),
+		("updated_at", TestsColumn::UpdatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("test_name", TestsColumn::TestName),
+		("created_at", TestsColumn::CreatedAt),
+		("updated_at", TestsColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: TestsColumn::Id,
+};
+
+const TEST_ANSWERS_LIST: ListSpec<TestAnswersEntity> = ListSpec {
+	filters: &[
+		("user_id", TestAnswersColumn::UserId, FieldKind::Uuid),
+		("attempt_id", TestAnswersColumn::AttemptId, FieldKind::Uuid),
+		(
+			"created_at",
+			TestAnswersColumn::CreatedAt,
+			FieldKind::DateTime,
+		),
+	],
+	sorts: &[
+		("id", TestAnswersColumn::Id),
+		("user_id", TestAnswersColumn::UserId),
+		("test_id", TestAnswersColumn::TestId),
+		("created_at", TestAnswersColumn::CreatedAt),
+		("score", TestAnswersColumn::Score),
+	],
+	default_sort: "id",
+	id: TestAnswersColumn::Id,
+};
+
 pub async fn query_get_tests(params: MetaRequestDto) -> Response {
 	let db: DatabaseConnection = get_db().await;
+	get_tests(&db, params).await
+}
 
-	let page = params.page.unwrap_or(1).max(1);
-	let per_page = params.per_page.unwrap_or(10).max(1).min(100);
-	let search = params.search.unwrap_or_default().to_lowercase();
-	let sort_by = params
-		.sort_by
-		.unwrap_or("created_at".to_string())
-		.to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
-	let filter = params.filter.unwrap_or_default();
-	let filter_by = params.filter_by.unwrap_or_default().to_lowercase();
-
-	let mut query = TestsEntity::find();
-
-	if !search.is_empty() {
-		query = query
-			.filter(Expr::col(TestsColumn::TestName).ilike(format!("%{}%", search)));
-	}
-
-	if filter_by == "session_id" && !filter.is_empty() {
-		if let Ok(session_uuid) = Uuid::parse_str(&filter) {
-			query = query
-				.join(
-					JoinType::InnerJoin,
-					<TestsEntity as Related<SessionsHasTestsEntity>>::to(),
-				)
-				.filter(
-					Expr::col((
-						SessionsHasTestsEntity,
-						SessionsHasTestsColumn::SessionId,
-					))
-					.eq(session_uuid),
-				);
-		} else {
+async fn get_tests<C: ConnectionTrait>(db: &C, params: MetaRequestDto) -> Response {
+	let mut params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query = TestsEntity::find_active();
+
+	if !params.search.is_empty() {
+		query = query.filter(TestsEntity::search_matches(&params.search));
+	}
+
+	// Session membership lives in the join table, so it is matched through
+	// a subquery rather than a column on the test.
+	for filter in params.take_filters("session_id") {
+		if !matches!(filter.op, FilterOp::Eq | FilterOp::In) {
 			return common_response(
 				StatusCode::BAD_REQUEST,
-				"Invalid session_id format",
+				"session_id only supports eq and in",
 			);
 		}
-	}
 
-	query = match (sort_by.as_str(), order.as_str()) {
-		("test_name", "asc") => query.order_by_asc(TestsColumn::TestName),
-		("test_name", "desc") => query.order_by_desc(TestsColumn::TestName),
-		("created_at", "asc") => query.order_by_asc(TestsColumn::CreatedAt),
-		("created_at", "desc") => query.order_by_desc(TestsColumn::CreatedAt),
-		("updated_at", "asc") => query.order_by_asc(TestsColumn::UpdatedAt),
-		("updated_at", "desc") => query.order_by_desc(TestsColumn::UpdatedAt),
-		_ => query.order_by_desc(TestsColumn::CreatedAt),
-	};
+		let session_ids = match filter.parse_values(FieldKind::Uuid) {
+			Ok(session_ids) => session_ids,
+			Err(response) => return response,
+		};
 
-	let paginator = query.paginate(&db, per_page);
+		query = query.filter(
+			TestsColumn::Id.in_subquery(
+				Query::select()
+					.column(SessionsHasTestsColumn::TestId)
+					.from(SessionsHasTestsEntity)
+					.and_where(SessionsHasTestsColumn::SessionId.is_in(session_ids))
+					.to_owned(),
+			),
+		);
+	}
 
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			)
-		}
+	let rank = (!params.search.is_empty())
+		.then(|| TestsEntity::search_rank(&params.search));
+	let (tests, meta) = match params.fetch(db, query, &TESTS_LIST, rank).await {
+		Ok(page) => page,
+		Err(response) => return response,
 	};
 
-	let tests = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
+	let test_ids: Vec<Uuid> = tests.iter().map(|test| test.id).collect();
+	let question_counts = match count_test_questions(db, &test_ids).await {
+		Ok(counts) => counts,
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -108,41 +170,26 @@ pub async fn query_get_tests(params: MetaRequestDto) -> Response {
 		}
 	};
 
-	let data: Vec<TestsItemListDto> = join_all(tests.into_iter().map(|test| {
-		let db = db.clone();
-		async move {
-			let question_count = QuestionsEntity::find()
-				.filter(QuestionsColumn::TestId.eq(test.id))
-				.count(&db)
-				.await
-				.unwrap_or(0);
-
-			TestsItemListDto {
-				id: test.id.to_string(),
-				test_name: test.test_name,
-				question_count,
-				created_at: test.created_at.map(|dt| dt.to_string()),
-				updated_at: test.updated_at.map(|dt| dt.to_string()),
-			}
-		}
-	}))
-	.await;
+	let data: Vec<TestsItemListDto> = tests
+		.into_iter()
+		.map(|test| TestsItemListDto {
+			id: test.id.to_string(),
+			question_count: question_counts.get(&test.id).copied().unwrap_or(0),
+			test_name: test.test_name,
+			created_at: test.created_at.map(|dt| dt.to_string()),
+			updated_at: test.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
 
 	let response = ResponseSuccessListDto {
 		data,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+		meta: Some(meta),
 	};
 
 	success_response_list(response)
 }
 
 pub async fn query_get_test_by_id(headers: HeaderMap, id: String) -> Response {
-	let db: DatabaseConnection = get_db().await;
-
 	let auth_header = match headers.get("Authorization") {
 		Some(header) => header.to_str(),
 		None => {
@@ -166,61 +213,46 @@ pub async fn query_get_test_by_id(headers: HeaderMap, id: String) -> Response {
 		}
 	};
 
-	let token_data = match decode_access_token(&token) {
+	let token_data = match decode_access_token(token) {
 		Ok(data) => data,
 		Err(err) => {
 			return common_response(StatusCode::UNAUTHORIZED, &err.to_string())
 		}
 	};
 
-	let email = token_data.claims.email.clone();
+	let db: DatabaseConnection = get_db().await;
+	get_test_by_id(&db, &token_data.claims.email, &id).await
+}
 
-	let role_name = UsersEntity::find()
-		.select_only()
-		.column_as(RolesColumn::Name, "role_name")
-		.join(
-			JoinType::InnerJoin,
-			<app_users_schema::Entity as sea_orm::EntityTrait>::Relation::Role.def(),
-		)
-		.filter(UsersColumn::Email.eq(email))
-		.into_tuple::<String>()
-		.one(&db)
-		.await
-		.unwrap_or_default();
+async fn get_test_by_id<C: ConnectionTrait>(
+	db: &C,
+	email: &str,
+	id: &str,
+) -> Response {
+	let viewer = find_viewer(db, email).await.unwrap_or_default();
 
-	let start_date = match SessionsHasTestsEntity::find()
+	let (start_date, end_date) = match SessionsHasTestsEntity::find()
 		.select_only()
 		.column(SessionsHasTestsColumn::StartDate)
-		.filter(
-			SessionsHasTestsColumn::TestId
-				.eq(Uuid::parse_str(&id).unwrap_or_default()),
-		)
-		.into_tuple::<Option<chrono::DateTime<chrono::Utc>>>()
-		.one(&db)
-		.await
-	{
-		Ok(Some(date)) => date,
-		_ => None,
-	};
-
-	let end_date = match SessionsHasTestsEntity::find()
-		.select_only()
 		.column(SessionsHasTestsColumn::EndDate)
 		.filter(
 			SessionsHasTestsColumn::TestId
-				.eq(Uuid::parse_str(&id).unwrap_or_default()),
+				.eq(Uuid::parse_str(id).unwrap_or_default()),
 		)
-		.into_tuple::<Option<chrono::DateTime<chrono::Utc>>>()
-		.one(&db)
+		.into_tuple::<(
+			Option<chrono::DateTime<chrono::Utc>>,
+			Option<chrono::DateTime<chrono::Utc>>,
+		)>()
+		.one(db)
 		.await
 	{
-		Ok(Some(date)) => date,
-		_ => None,
+		Ok(Some(dates)) => dates,
+		_ => (None, None),
 	};
 
-	let test = match TestsEntity::find()
-		.filter(TestsColumn::Id.eq(Uuid::parse_str(&id).unwrap_or_default()))
-		.one(&db)
+	let test = match TestsEntity::find_active()
+		.filter(TestsColumn::Id.eq(Uuid::parse_str(id).unwrap_or_default()))
+		.one(db)
 		.await
 	{
 		Ok(Some(test)) => test,
@@ -233,63 +265,70 @@ pub async fn query_get_test_by_id(headers: HeaderMap, id: String) -> Response {
 		}
 	};
 
-	let questions = QuestionsEntity::find()
-		.filter(QuestionsColumn::TestId.eq(test.id))
-		.all(&db)
-		.await
-		.unwrap_or_default();
+	if let Err(response) = ensure_test_access(db, viewer, test.id).await {
+		return response;
+	}
 
-	let questions_dto: Vec<QuestionsItemDto> =
-		join_all(questions.into_iter().map(|q| {
-			let db = db.clone();
-			{
-				let value = role_name.clone();
-				async move {
-					let options = OptionsEntity::find()
-						.filter(OptionsColumn::QuestionId.eq(q.id))
-						.all(&db)
-						.await
-						.unwrap_or_default();
-					let options_dto: Vec<OptionsItemDto> =
-						options
-							.into_iter()
-							.map(|opt| OptionsItemDto {
-								id: opt.id.to_string(),
-								label: opt.label,
-								image_url: opt.image_url,
-								is_correct: if <std::option::Option<
-									std::string::String,
-								> as Clone>::clone(&value)
-								.unwrap_or_default()
-								.to_lowercase() == RolesEnum::Admin
-									.to_string()
-									.to_lowercase()
-								{
-									Some(opt.is_correct)
-								} else {
-									None
-								},
-							})
-							.collect();
-
-					QuestionsItemDto {
-						id: q.id.to_string(),
-						question: q.question,
-						discussion: q.discussion,
-						options: options_dto,
-						image_url: q.image_url,
-					}
-				}
-			}
-		}))
-		.await;
+	// This is the sheet students take the test from, so answers and
+	// discussions stay hidden from them even after submitting; once released
+	// they are shown with the student's own submission.
+	let reveal_results = matches!(viewer, Some((_, true)));
+
+	let questions = match find_test_questions(test.id).all(db).await {
+		Ok(questions) => questions,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let options = match questions.load_many(OptionsEntity, db).await {
+		Ok(options) => options,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let questions_dto: Vec<QuestionsItemDto> = questions
+		.into_iter()
+		.zip(options)
+		.map(|(q, options)| QuestionsItemDto {
+			id: q.id.to_string(),
+			version: q.version,
+			question: q.question,
+			discussion: reveal_results.then_some(q.discussion),
+			discussion_image_url: q.discussion_image_url.filter(|_| reveal_results),
+			options: options
+				.into_iter()
+				.map(|opt| OptionsItemDto {
+					id: opt.id.to_string(),
+					label: opt.label,
+					image_url: opt.image_url,
+					is_correct: reveal_results.then_some(opt.is_correct),
+				})
+				.collect(),
+			image_url: q.image_url,
+			topic: q.topic,
+			subtopic: q.subtopic,
+			difficulty: q.difficulty,
+		})
+		.collect();
+
+	let blueprint = find_blueprint_rules(db, test.id).await.unwrap_or_default();
 
 	let test_dto = TestsItemDto {
 		id: test.id.to_string(),
 		test_name: test.test_name,
+		max_violations: test.max_violations,
 		start_date: start_date.map(|dt| dt.to_string()),
 		end_date: end_date.map(|dt| dt.to_string()),
 		questions: questions_dto,
+		blueprint: blueprint.into_iter().map(blueprint_rule_dto).collect(),
 		created_at: test.created_at.map(|dt| dt.to_string()),
 		updated_at: test.updated_at.map(|dt| dt.to_string()),
 	};
@@ -298,12 +337,25 @@ pub async fn query_get_test_by_id(headers: HeaderMap, id: String) -> Response {
 	success_response(response)
 }
 
-pub async fn mutation_create_test(payload: Json<TestsRequestCreateDto>) -> Response {
+pub async fn mutation_create_test(
+	payload: Json<TestsRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
+	if let Err(response) = validate_blueprint(&payload.blueprint) {
+		return response;
+	}
+
+	let max_violations = match validate_max_violations(payload.max_violations) {
+		Ok(max_violations) => max_violations,
+		Err(response) => return response,
+	};
+
 	let new_test = TestsActiveModel {
 		id: Set(Uuid::new_v4()),
 		test_name: Set(payload.test_name.clone()),
+		max_violations: Set(max_violations),
 		created_at: Set(Some(Utc::now())),
 		updated_at: Set(Some(Utc::now())),
 		..Default::default()
@@ -319,53 +371,52 @@ pub async fn mutation_create_test(payload: Json<TestsRequestCreateDto>) -> Respo
 		}
 	};
 
+	let author_id = audit.actor_id(&db).await;
 	for question in &payload.questions {
-		let new_question = QuestionsActiveModel {
-			id: Set(Uuid::new_v4()),
-			test_id: Set(test.id),
-			question: Set(question.question.clone()),
-			image_url: Set(question.image_url.clone()),
-			discussion_image_url: Set(question.discussion_image_url.clone()),
-			discussion: Set(question.discussion.clone()),
-			..Default::default()
+		let result = match insert_bank_question(&db, question, author_id).await {
+			Ok(question) => link_question_to_test(&db, test.id, question.id).await,
+			Err(response) => Err(response),
 		};
+		if let Err(response) = result {
+			return response;
+		}
+	}
 
-		let inserted_question = match new_question.insert(&db).await {
-			Ok(q) => q,
-			Err(err) => {
-				return common_response(
-					StatusCode::INTERNAL_SERVER_ERROR,
-					&err.to_string(),
-				)
-			}
+	for question_id in payload.question_ids.iter().flatten() {
+		let result = match find_bank_question(&db, question_id).await {
+			Ok(question) => link_question_to_test(&db, test.id, question.id).await,
+			Err(response) => Err(response),
 		};
+		if let Err(response) = result {
+			return response;
+		}
+	}
 
-		for option in &question.options {
-			let new_option = OptionsActiveModel {
-				id: Set(Uuid::new_v4()),
-				question_id: Set(inserted_question.id),
-				is_correct: Set(option.is_correct),
-				image_url: Set(Some(option.image_url.clone())),
-
-				label: Set(option.label.clone()),
-				..Default::default()
-			};
-
-			if let Err(err) = new_option.insert(&db).await {
-				return common_response(
-					StatusCode::INTERNAL_SERVER_ERROR,
-					&err.to_string(),
-				);
-			}
+	if let Some(rules) = &payload.blueprint {
+		if let Err(response) = replace_blueprint(&db, test.id, rules).await {
+			return response;
 		}
 	}
 
+	let after = test_audit_snapshot(&db, &test).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"test",
+		test.id,
+		None,
+		after,
+	)
+	.await;
+
 	common_response(StatusCode::CREATED, "Test created successfully")
 }
 
 pub async fn mutation_update_test(
 	id: String,
 	payload: Json<TestsRequestUpdateDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -379,7 +430,7 @@ pub async fn mutation_update_test(
 		}
 	};
 
-	let test = match TestsEntity::find()
+	let test = match TestsEntity::find_active()
 		.filter(TestsColumn::Id.eq(test_id))
 		.one(&db)
 		.await
@@ -394,98 +445,57 @@ pub async fn mutation_update_test(
 		}
 	};
 
+	if let Err(response) = validate_blueprint(&payload.blueprint) {
+		return response;
+	}
+
+	let before = test_audit_snapshot(&db, &test).await;
 	let mut active_model: TestsActiveModel = test.into();
 
 	if let Some(test_name) = &payload.test_name {
 		active_model.test_name = Set(test_name.clone());
 	}
+	if payload.max_violations.is_some() {
+		match validate_max_violations(payload.max_violations) {
+			Ok(max_violations) => active_model.max_violations = Set(max_violations),
+			Err(response) => return response,
+		}
+	}
 	active_model.updated_at = Set(Some(Utc::now()));
 
-	if let Err(err) = active_model.update(&db).await {
-		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
-	}
+	let test = match active_model.update(&db).await {
+		Ok(test) => test,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
 
 	for question in &payload.questions {
-		let question_id = match &question.id {
-			Some(id_str) => match Uuid::parse_str(id_str) {
-				Ok(uuid) => uuid,
-				Err(_) => {
-					return common_response(
-						StatusCode::BAD_REQUEST,
-						"Invalid question ID format",
-					)
-				}
-			},
-			None => Uuid::new_v4(),
-		};
-
-		let mut question_model = if question.id.is_some() {
-			match QuestionsEntity::find_by_id(question_id).one(&db).await {
-				Ok(Some(q)) => q.into(),
-				Ok(None) => {
-					return common_response(
-						StatusCode::NOT_FOUND,
-						"Question not found",
-					)
-				}
-				Err(err) => {
-					return common_response(
-						StatusCode::INTERNAL_SERVER_ERROR,
-						&err.to_string(),
-					)
-				}
-			}
-		} else {
-			QuestionsActiveModel {
-				id: Set(question_id),
-				test_id: Set(test_id),
-				..Default::default()
-			}
-		};
-
-		question_model.question = Set(question.question.clone());
-		question_model.discussion = Set(question.discussion.clone());
-		question_model.image_url = Set(question.image_url.clone());
-		question_model.discussion_image_url =
-			Set(question.discussion_image_url.clone());
-
-		let saved_question = if question.id.is_some() {
-			question_model.update(&db).await
-		} else {
-			question_model.insert(&db).await
-		};
-
-		let saved_question = match saved_question {
-			Ok(q) => q,
-			Err(err) => {
-				return common_response(
-					StatusCode::INTERNAL_SERVER_ERROR,
-					&err.to_string(),
-				)
-			}
-		};
-
-		for option in &question.options {
-			let option_id = match &option.id {
-				Some(opt_id) => match Uuid::parse_str(opt_id) {
+		let result = match &question.id {
+			Some(id_str) => {
+				let question_id = match Uuid::parse_str(id_str) {
 					Ok(uuid) => uuid,
 					Err(_) => {
 						return common_response(
 							StatusCode::BAD_REQUEST,
-							"Invalid option ID format",
+							"Invalid question ID format",
 						)
 					}
-				},
-				None => Uuid::new_v4(),
-			};
-
-			let mut option_model = if option.id.is_some() {
-				match OptionsEntity::find_by_id(option_id).one(&db).await {
-					Ok(Some(o)) => o.into(),
+				};
+
+				let existing = match find_test_questions(test_id)
+					.filter(QuestionsColumn::Id.eq(question_id))
+					.one(&db)
+					.await
+				{
+					Ok(Some(q)) => q,
 					Ok(None) => {
 						return common_response(
 							StatusCode::NOT_FOUND,
-							"Option not found",
+							"Question not found",
 						)
 					}
 					Err(err) => {
@@ -494,38 +504,81 @@ pub async fn mutation_update_test(
 							&err.to_string(),
 						)
 					}
+				};
+
+				save_question_revision(&db, existing, question)
+					.await
+					.map(|_| ())
+			}
+			None if question.options.iter().any(|o| o.id.is_some()) => {
+				Err(common_response(StatusCode::NOT_FOUND, "Option not found"))
+			}
+			None => {
+				let author_id = audit.actor_id(&db).await;
+				let question = QuestionsRequestCreateDto::from(question);
+				match insert_bank_question(&db, &question, author_id).await {
+					Ok(question) => {
+						link_question_to_test(&db, test_id, question.id).await
+					}
+					Err(response) => Err(response),
 				}
-			} else {
-				OptionsActiveModel {
-					id: Set(option_id),
-					question_id: Set(saved_question.id),
-					..Default::default()
-				}
-			};
+			}
+		};
 
-			option_model.label = Set(option.label.clone());
-			option_model.is_correct = Set(option.is_correct);
-			option_model.image_url = Set(Some(option.image_url.clone()));
+		if let Err(response) = result {
+			return response;
+		}
+	}
 
-			let result = if option.id.is_some() {
-				option_model.update(&db).await
-			} else {
-				option_model.insert(&db).await
-			};
+	for question_id in payload.question_ids.iter().flatten() {
+		let result = match find_bank_question(&db, question_id).await {
+			Ok(question) => link_question_to_test(&db, test_id, question.id).await,
+			Err(response) => Err(response),
+		};
+		if let Err(response) = result {
+			return response;
+		}
+	}
 
-			if let Err(err) = result {
+	for question_id in payload.remove_question_ids.iter().flatten() {
+		let question_id = match Uuid::parse_str(question_id) {
+			Ok(uuid) => uuid,
+			Err(_) => {
 				return common_response(
-					StatusCode::INTERNAL_SERVER_ERROR,
-					&err.to_string(),
-				);
+					StatusCode::BAD_REQUEST,
+					"Invalid question ID format",
+				)
 			}
+		};
+		if let Err(response) =
+			unlink_question_from_test(&db, test_id, question_id).await
+		{
+			return response;
 		}
 	}
 
+	if let Some(rules) = &payload.blueprint {
+		if let Err(response) = replace_blueprint(&db, test_id, rules).await {
+			return response;
+		}
+	}
+
+	let after = test_audit_snapshot(&db, &test).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"test",
+		test.id,
+		before,
+		after,
+	)
+	.await;
+
 	common_response(StatusCode::OK, "Test updated successfully")
 }
 
-pub async fn mutation_delete_test(id: String) -> Response {
+pub async fn mutation_delete_test(id: String, audit: &AuditContext) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	let test_id = match Uuid::parse_str(&id) {
@@ -538,7 +591,7 @@ pub async fn mutation_delete_test(id: String) -> Response {
 		}
 	};
 
-	let test = match TestsEntity::find()
+	let test = match TestsEntity::find_active()
 		.filter(TestsColumn::Id.eq(test_id))
 		.one(&db)
 		.await
@@ -553,65 +606,53 @@ pub async fn mutation_delete_test(id: String) -> Response {
 		}
 	};
 
-	match test.delete(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "Test deleted successfully"),
+	let before = audit_snapshot(&test);
+	let mut active_model: TestsActiveModel = test.into();
+	active_model.deleted_at = Set(Some(Utc::now()));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	match active_model.update(&db).await {
+		Ok(test) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"test",
+				test.id,
+				before,
+				audit_snapshot(&test),
+			)
+			.await;
+			common_response(StatusCode::OK, "Test deleted successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
 	}
 }
 
-pub async fn query_get_test_answer_by_id(id: String) -> Response {
-	let db = get_db().await;
+pub async fn mutation_restore_test(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
 
-	// 1. Fetch the parent test answer record.
-	let test_answer = match TestAnswersEntity::find()
-		.filter(TestAnswersColumn::Id.eq(Uuid::parse_str(&id).unwrap_or_default()))
-		.one(&db)
-		.await
-	{
-		Ok(Some(answer)) => answer,
-		Ok(None) => {
-			return common_response(StatusCode::NOT_FOUND, "Test answer not found")
-		}
-		Err(err) => {
+	let test_id = match Uuid::parse_str(&id) {
+		Ok(id) => id,
+		Err(_) => {
 			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
 			)
 		}
 	};
 
-	// 2. Retrieve the test name.
-	let test_name = match TestsEntity::find_by_id(test_answer.test_id).one(&db).await
-	{
-		Ok(Some(test)) => test.test_name,
-		_ => "Unknown".to_string(),
-	};
-
-	let (test_start_date, test_end_date) = match SessionsHasTestsEntity::find()
-		.select_only()
-		.column(SessionsHasTestsColumn::StartDate)
-		.column(SessionsHasTestsColumn::EndDate)
-		.filter(SessionsHasTestsColumn::TestId.eq(test_answer.test_id))
-		.into_tuple::<(
-			Option<chrono::DateTime<chrono::Utc>>,
-			Option<chrono::DateTime<chrono::Utc>>,
-		)>()
+	let test = match TestsEntity::find_deleted()
+		.filter(TestsColumn::Id.eq(test_id))
 		.one(&db)
 		.await
 	{
-		Ok(Some((start, end))) => (start, end),
-		_ => (None, None),
-	};
-
-	// 3. Get all related question answers for this test answer.
-	let question_answers = match TestQuestionsAnswersEntity::find()
-		.filter(TestQuestionsAnswersColumn::AnswerId.eq(test_answer.id))
-		.all(&db)
-		.await
-	{
-		Ok(list) => list,
+		Ok(Some(test)) => test,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Deleted test not found")
+		}
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -620,118 +661,61 @@ pub async fn query_get_test_answer_by_id(id: String) -> Response {
 		}
 	};
 
-	// 4. Create a unique set of question IDs from the join records.
-	use std::collections::HashSet;
-	let question_ids: HashSet<Uuid> =
-		question_answers.iter().map(|qa| qa.question_id).collect();
-
-	let mut questions: Vec<QuestionsAnswersDataItemDto> = Vec::new();
-
-	// 5. For each unique question, fetch the question details and its options.
-	for qid in question_ids {
-		// Fetch the question.
-		let question = match QuestionsEntity::find_by_id(qid).one(&db).await {
-			Ok(Some(q)) => q,
-			_ => continue, // Skip if question not found.
-		};
+	let before = audit_snapshot(&test);
+	let mut active_model: TestsActiveModel = test.into();
+	active_model.deleted_at = Set(None);
+	active_model.updated_at = Set(Some(Utc::now()));
 
-		// Fetch all options available for this question.
-		let options_for_question = match OptionsEntity::find()
-			.filter(OptionsColumn::QuestionId.eq(qid))
-			.all(&db)
-			.await
-		{
-			Ok(opts) => opts,
-			Err(_) => vec![],
-		};
-
-		// 6. Build the options DTO.
-		let options_dto: Vec<OptionsAnswerItemDto> = options_for_question
-			.into_iter()
-			.map(|option| {
-				// Use .iter() on the join records to check if this option was selected.
-				let is_selected = question_answers
-					.iter()
-					.any(|qa| qa.question_id == qid && qa.option_id == option.id);
-				OptionsAnswerItemDto {
-					id: option.id.to_string(),
-					label: option.label,
-					image_url: option.image_url,
-					is_correct: Some(option.is_correct),
-					is_selected: Some(is_selected),
-				}
-			})
-			.collect();
-
-		// 7. Build the question DTO.
-		let question_dto = QuestionsAnswersDataItemDto {
-			id: qid.to_string(),
-			question: question.question, // Adjust if your field name differs.
-			discussion: question.discussion, // Adjust if your field name differs.
-			options: options_dto,
-		};
-
-		questions.push(question_dto);
+	match active_model.update(&db).await {
+		Ok(test) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Restore,
+				"test",
+				test.id,
+				before,
+				audit_snapshot(&test),
+			)
+			.await;
+			common_response(StatusCode::OK, "Test restored successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
 	}
-
-	// 8. Build the final DTO.
-	let dto = QuestionsAnswersItemDto {
-		id: test_answer.id.to_string(),
-		test_name,
-		questions,
-		start_date: test_start_date.map(|dt| dt.to_string()),
-		end_date: test_end_date.map(|dt| dt.to_string()),
-	};
-
-	let response = ResponseSuccessDto { data: dto };
-	success_response(response)
 }
 
-pub async fn mutation_create_test_answer(
+pub async fn mutation_start_test_attempt(
 	headers: HeaderMap,
-	payload: Json<TestAnswersRequestCreateDto>,
+	id: String,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	// Extract and validate the Authorization header
-	let auth_header = match headers.get("Authorization") {
-		Some(header) => header.to_str(),
-		None => {
-			return common_response(StatusCode::FORBIDDEN, "You are not authorized")
-		}
-	};
-	let auth_header = match auth_header {
-		Ok(header) => header,
-		Err(err) => {
-			return common_response(StatusCode::BAD_REQUEST, &err.to_string())
-		}
-	};
-	let mut header_parts = auth_header.split_whitespace();
-	let token = match header_parts.nth(1) {
-		Some(token) => token,
-		None => {
-			return common_response(StatusCode::BAD_REQUEST, "Invalid token format")
-		}
+	let viewer = match find_token_viewer(&db, &headers).await {
+		Ok(viewer) => viewer,
+		Err(response) => return response,
 	};
-	let token_data = match decode_access_token(&token) {
-		Ok(data) => data,
-		Err(err) => {
-			return common_response(StatusCode::UNAUTHORIZED, &err.to_string())
+	let user_id = viewer.0;
+
+	let test_id = match Uuid::parse_str(&id) {
+		Ok(uuid) => uuid,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
+			)
 		}
 	};
 
-	let user_email = token_data.claims.email.clone();
-
-	let user_id = match UsersEntity::find()
-		.select_only()
-		.column(UsersColumn::Id)
-		.filter(UsersColumn::Email.eq(user_email))
-		.into_tuple::<Uuid>()
+	let test = match TestsEntity::find_active()
+		.filter(TestsColumn::Id.eq(test_id))
 		.one(&db)
 		.await
 	{
-		Ok(Some(user)) => user,
-		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Ok(Some(test)) => test,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "Test not found"),
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -740,15 +724,63 @@ pub async fn mutation_create_test_answer(
 		}
 	};
 
-	// Insert a new answer into the app_user_answers table
-	let new_answer = app_user_answers_schema::ActiveModel {
-		id: Set(Uuid::new_v4()),
-		user_id: Set(user_id),
-		test_id: Set(Uuid::parse_str(&payload.test_id).unwrap_or_default()),
+	if let Err(response) = ensure_test_access(&db, Some(viewer), test.id).await {
+		return response;
+	}
+
+	// Students start inside the test's window in a session that admits them,
+	// which the attempt then belongs to; admins may open a test at any time
+	// to preview it.
+	let (start_date, end_date, session_id) = if viewer.1 {
+		match SessionsHasTestsEntity::find()
+			.select_only()
+			.column(SessionsHasTestsColumn::StartDate)
+			.column(SessionsHasTestsColumn::EndDate)
+			.filter(SessionsHasTestsColumn::TestId.eq(test.id))
+			.into_tuple::<(
+				Option<chrono::DateTime<chrono::Utc>>,
+				Option<chrono::DateTime<chrono::Utc>>,
+			)>()
+			.one(&db)
+			.await
+		{
+			Ok(Some((start, end))) => (start, end, None),
+			_ => (None, None, None),
+		}
+	} else {
+		match find_open_test_schedule(&db, user_id, test.id).await {
+			Ok(Some(schedule)) => (
+				schedule.start_date,
+				schedule.end_date,
+				Some(schedule.session_id),
+			),
+			Ok(None) => {
+				return common_response(
+					StatusCode::FORBIDDEN,
+					"This test is not open for attempts right now",
+				)
+			}
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
 	};
 
-	let answer = match new_answer.insert(&db).await {
-		Ok(answer) => answer,
+	let (attempt, questions, resumed) =
+		match open_test_attempt(&db, test.id, user_id, session_id).await {
+			Ok(opened) => opened,
+			Err(response) => return response,
+		};
+
+	let options = match OptionsEntity::find()
+		.filter(OptionsColumn::QuestionId.is_in(questions.iter().map(|q| q.id)))
+		.all(&db)
+		.await
+	{
+		Ok(options) => options,
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -757,48 +789,83 @@ pub async fn mutation_create_test_answer(
 		}
 	};
 
-	// Iterate over each question-answer pair and insert a record into app_user_question_answers
-	for qa in &payload.questions {
-		let new_question_answer = app_user_questions_answers_schema::ActiveModel {
-			id: Set(Uuid::new_v4()),
-			answer_id: Set(answer.id),
-			question_id: Set(Uuid::parse_str(&qa.question_id).unwrap_or_default()),
-			option_id: Set(Uuid::parse_str(&qa.option_id).unwrap_or_default()),
-		};
-		if let Err(err) = new_question_answer.insert(&db).await {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			);
-		}
+	// A resumed attempt was already recorded and announced when it started.
+	if !resumed {
+		record_audit_log(
+			&db,
+			audit,
+			AuditAction::Create,
+			"test_attempt",
+			attempt.id,
+			None,
+			audit_snapshot(&attempt),
+		)
+		.await;
+
+		publish_event(RealtimeEvent::new(
+			REALTIME_TOPIC_ATTEMPTS.to_string(),
+			REALTIME_EVENT_ATTEMPT_PROGRESS,
+			json!({
+				"status": "started",
+				"attempt_id": attempt.id,
+				"test_id": test.id,
+				"user_id": user_id,
+				"questions": questions.len(),
+			}),
+		));
 	}
 
-	// Build and return the response DTO
-	let dto = TestAnswersItemDto {
-		id: answer.id.to_string(),
-		user_id: answer.user_id.to_string(),
-		test_id: answer.test_id.to_string(),
+	let dto = TestAttemptItemDto {
+		id: attempt.id.to_string(),
+		test_id: test.id.to_string(),
+		test_name: test.test_name,
+		max_violations: test.max_violations,
+		questions: questions
+			.into_iter()
+			.map(|question| attempt_question_dto(question, &options))
+			.collect(),
+		start_date: start_date.map(|dt| dt.to_string()),
+		end_date: end_date.map(|dt| dt.to_string()),
+		created_at: attempt.created_at.to_string(),
 	};
 
 	let response = ResponseSuccessDto { data: dto };
 	success_response(response)
 }
 
-pub async fn mutation_delete_test_answer(id: String) -> Response {
+pub async fn query_get_test_answer_by_id(
+	headers: HeaderMap,
+	id: String,
+) -> Response {
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
 	let db: DatabaseConnection = get_db().await;
-	let answer_id = match Uuid::parse_str(&id) {
-		Ok(uuid) => uuid,
-		Err(_) => {
+	get_test_answer_by_id(&db, &email, &id).await
+}
+
+async fn get_test_answer_by_id<C: ConnectionTrait>(
+	db: &C,
+	email: &str,
+	id: &str,
+) -> Response {
+	let (viewer_id, is_admin) = match find_viewer(db, email).await {
+		Ok(Some(viewer)) => viewer,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
 			return common_response(
-				StatusCode::BAD_REQUEST,
-				"Invalid test answer ID",
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
 			)
 		}
 	};
 
-	let answer = match TestAnswersEntity::find()
-		.filter(TestAnswersColumn::Id.eq(answer_id))
-		.one(&db)
+	// 1. Fetch the parent test answer record.
+	let test_answer = match TestAnswersEntity::find()
+		.filter(TestAnswersColumn::Id.eq(Uuid::parse_str(id).unwrap_or_default()))
+		.one(db)
 		.await
 	{
 		Ok(Some(answer)) => answer,
@@ -813,59 +880,56 @@ pub async fn mutation_delete_test_answer(id: String) -> Response {
 		}
 	};
 
-	match answer.delete(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "Test answer deleted successfully"),
-		Err(err) => {
-			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	// Besides admins and the author, only instructors of the author's group
+	// may open an answer.
+	if !is_admin && test_answer.user_id != viewer_id {
+		match instructor_can_view_answer(db, viewer_id, test_answer.id).await {
+			Ok(true) => {}
+			Ok(false) => {
+				return common_response(
+					StatusCode::FORBIDDEN,
+					"You may only view results of your own groups",
+				)
+			}
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
 		}
 	}
-}
-
-pub async fn query_test_answer_list(
-	session_id: String,
-	params: MetaRequestDto,
-) -> Response {
-	let db: DatabaseConnection = get_db().await;
 
-	let session_uuid = match Uuid::parse_str(&session_id) {
-		Ok(uuid) => uuid,
-		Err(_) => {
-			return common_response(
-				StatusCode::BAD_REQUEST,
-				"Invalid session_id format",
-			)
-		}
+	// 2. Retrieve the test name.
+	let test_name = match TestsEntity::find_by_id(test_answer.test_id).one(db).await
+	{
+		Ok(Some(test)) => test.test_name,
+		_ => "Unknown".to_string(),
 	};
 
-	let page = params.page.unwrap_or(1).max(1);
-	let per_page = params.per_page.unwrap_or(10).max(1).min(100);
-	let search = params.search.unwrap_or_default().to_lowercase();
-	let sort_by = params.sort_by.unwrap_or("id".to_string()).to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
-
-	let mut query =
-		TestAnswersEntity::find().filter(TestAnswersColumn::TestId.eq(session_uuid));
-
-	if !search.is_empty() {
-		query = query.filter(
-			Expr::cust("CAST(user_id AS TEXT)").ilike(format!("%{}%", search)),
-		);
-	}
-
-	query = match (sort_by.as_str(), order.as_str()) {
-		("id", "asc") => query.order_by_asc(TestAnswersColumn::Id),
-		("id", "desc") => query.order_by_desc(TestAnswersColumn::Id),
-		("user_id", "asc") => query.order_by_asc(TestAnswersColumn::UserId),
-		("user_id", "desc") => query.order_by_desc(TestAnswersColumn::UserId),
-		("test_id", "asc") => query.order_by_asc(TestAnswersColumn::TestId),
-		("test_id", "desc") => query.order_by_desc(TestAnswersColumn::TestId),
-		_ => query.order_by_desc(TestAnswersColumn::Id),
+	let (test_start_date, test_end_date) = match SessionsHasTestsEntity::find()
+		.select_only()
+		.column(SessionsHasTestsColumn::StartDate)
+		.column(SessionsHasTestsColumn::EndDate)
+		.filter(SessionsHasTestsColumn::TestId.eq(test_answer.test_id))
+		.into_tuple::<(
+			Option<chrono::DateTime<chrono::Utc>>,
+			Option<chrono::DateTime<chrono::Utc>>,
+		)>()
+		.one(db)
+		.await
+	{
+		Ok(Some((start, end))) => (start, end),
+		_ => (None, None),
 	};
 
-	let paginator = query.paginate(&db, per_page);
-
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
+	// 3. Get all related question answers for this test answer.
+	let question_answers = match TestQuestionsAnswersEntity::find()
+		.filter(TestQuestionsAnswersColumn::AnswerId.eq(test_answer.id))
+		.all(db)
+		.await
+	{
+		Ok(list) => list,
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -874,8 +938,60 @@ pub async fn query_test_answer_list(
 		}
 	};
 
-	let answers = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
+	// 4. Attempts list every question that was drawn, answered or not. Older
+	// answers only know the questions that were answered.
+	let question_ids: Vec<Uuid> = match test_answer.attempt_id {
+		Some(attempt_id) => match TestAttemptQuestionsEntity::find()
+			.select_only()
+			.column(TestAttemptQuestionsColumn::QuestionId)
+			.filter(TestAttemptQuestionsColumn::AttemptId.eq(attempt_id))
+			.order_by_asc(TestAttemptQuestionsColumn::Position)
+			.into_tuple::<Uuid>()
+			.all(db)
+			.await
+		{
+			Ok(ids) => ids,
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		},
+		None => question_answers
+			.iter()
+			.map(|qa| qa.question_id)
+			.collect::<HashSet<Uuid>>()
+			.into_iter()
+			.collect(),
+	};
+
+	let reveal_results = is_admin
+		|| test_results_released(db, test_answer.test_id, test_answer.session_id)
+			.await;
+
+	// 5. Fetch the questions and their options in one query each.
+	let mut question_models: HashMap<Uuid, QuestionsModel> =
+		match QuestionsEntity::find()
+			.filter(QuestionsColumn::Id.is_in(question_ids.clone()))
+			.all(db)
+			.await
+		{
+			Ok(list) => list.into_iter().map(|q| (q.id, q)).collect(),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		};
+	// Skip questions that no longer exist, keeping the drawn order.
+	let ordered: Vec<QuestionsModel> = question_ids
+		.iter()
+		.filter_map(|qid| question_models.remove(qid))
+		.collect();
+	let options = match ordered.load_many(OptionsEntity, db).await {
+		Ok(options) => options,
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -884,23 +1000,1514 @@ pub async fn query_test_answer_list(
 		}
 	};
 
-	let answers_dto: Vec<_> = answers
+	let selected: HashSet<(Uuid, Uuid)> = question_answers
+		.iter()
+		.map(|qa| (qa.question_id, qa.option_id))
+		.collect();
+
+	// 6. Build the question DTOs with their options.
+	let questions: Vec<QuestionsAnswersDataItemDto> = ordered
 		.into_iter()
-		.map(|answer| TestAnswersItemDto {
-			id: answer.id.to_string(),
-			user_id: answer.user_id.to_string(),
-			test_id: answer.test_id.to_string(),
+		.zip(options)
+		.map(|(question, options)| QuestionsAnswersDataItemDto {
+			id: question.id.to_string(),
+			version: question.version,
+			question: question.question,
+			discussion: reveal_results.then_some(question.discussion),
+			discussion_image_url: question
+				.discussion_image_url
+				.filter(|_| reveal_results),
+			options: options
+				.into_iter()
+				.map(|option| OptionsAnswerItemDto {
+					is_selected: Some(selected.contains(&(question.id, option.id))),
+					id: option.id.to_string(),
+					label: option.label,
+					image_url: option.image_url,
+					is_correct: reveal_results.then_some(option.is_correct),
+				})
+				.collect(),
 		})
 		.collect();
 
-	let response = ResponseSuccessListDto {
-		data: answers_dto,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+	// 7. Build the final DTO.
+	let dto = QuestionsAnswersItemDto {
+		id: test_answer.id.to_string(),
+		test_name,
+		questions,
+		start_date: test_start_date.map(|dt| dt.to_string()),
+		end_date: test_end_date.map(|dt| dt.to_string()),
 	};
 
-	success_response_list(response)
+	let response = ResponseSuccessDto { data: dto };
+	success_response(response)
+}
+
+pub async fn mutation_create_test_answer(
+	headers: HeaderMap,
+	payload: Json<TestAnswersRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let viewer = match find_token_viewer(&db, &headers).await {
+		Ok(viewer) => viewer,
+		Err(response) => return response,
+	};
+	let user_id = viewer.0;
+
+	let test_id = Uuid::parse_str(&payload.test_id).unwrap_or_default();
+
+	if let Err(response) = ensure_test_access(&db, Some(viewer), test_id).await {
+		return response;
+	}
+
+	let attempt = match &payload.attempt_id {
+		Some(attempt_id) => {
+			let attempt_id = match Uuid::parse_str(attempt_id) {
+				Ok(uuid) => uuid,
+				Err(_) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"Invalid attempt ID format",
+					)
+				}
+			};
+
+			let attempt = match TestAttemptsEntity::find_by_id(attempt_id)
+				.filter(TestAttemptsColumn::TestId.eq(test_id))
+				.filter(TestAttemptsColumn::UserId.eq(user_id))
+				.one(&db)
+				.await
+			{
+				Ok(Some(attempt)) => attempt,
+				Ok(None) => {
+					return common_response(
+						StatusCode::NOT_FOUND,
+						"Test attempt not found",
+					)
+				}
+				Err(err) => {
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					)
+				}
+			};
+
+			Some(attempt)
+		}
+		None => {
+			// Blueprint tests have no fixed question set, so only an attempt
+			// knows which questions the student received. Proctored tests are
+			// closed by auto-submitting the attempt, which an answer without
+			// one would get around.
+			match attempt_required(&db, test_id).await {
+				Ok(false) => None,
+				Ok(true) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"attempt_id is required for this test",
+					)
+				}
+				Err(err) => {
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					)
+				}
+			}
+		}
+	};
+
+	let mut selections: Vec<(Uuid, Uuid)> = Vec::new();
+	for qa in &payload.questions {
+		match (
+			Uuid::parse_str(&qa.question_id),
+			Uuid::parse_str(&qa.option_id),
+		) {
+			(Ok(question_id), Ok(option_id)) => {
+				selections.push((question_id, option_id))
+			}
+			_ => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid question or option ID format",
+				)
+			}
+		}
+	}
+
+	// Answers pin the question version the student was shown. That version may
+	// have been archived by an edit since, so it only has to belong to the test
+	// or, for attempts, to the questions drawn for that attempt.
+	let options_query = OptionsEntity::find()
+		.select_only()
+		.column(OptionsColumn::QuestionId)
+		.column(OptionsColumn::Id)
+		.inner_join(QuestionsEntity)
+		.filter(OptionsColumn::Id.is_in(selections.iter().map(|(_, id)| *id)));
+
+	let options_query = match &attempt {
+		Some(attempt) => options_query
+			.join(
+				JoinType::InnerJoin,
+				TestAttemptQuestionsRelation::Question.def().rev(),
+			)
+			.filter(TestAttemptQuestionsColumn::AttemptId.eq(attempt.id)),
+		None => options_query
+			.join(
+				JoinType::InnerJoin,
+				QuestionsRelation::TestsHasQuestions.def(),
+			)
+			.filter(TestsHasQuestionsColumn::TestId.eq(test_id)),
+	};
+
+	let valid_selections: HashSet<(Uuid, Uuid)> =
+		match options_query.into_tuple::<(Uuid, Uuid)>().all(&db).await {
+			Ok(rows) => rows.into_iter().collect(),
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		};
+
+	if selections
+		.iter()
+		.any(|pair| !valid_selections.contains(pair))
+	{
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Answer does not match a question of this test",
+		);
+	}
+
+	// The answer belongs to the attempt's session or, for tests taken without
+	// one, to the session the test is open in for the student.
+	let session_id = match &attempt {
+		Some(attempt) => attempt.session_id,
+		None if viewer.1 => None,
+		None => find_open_test_schedule(&db, user_id, test_id)
+			.await
+			.ok()
+			.flatten()
+			.map(|schedule| schedule.session_id),
+	};
+
+	let txn = match db.begin().await {
+		Ok(txn) => txn,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// The attempt stays locked until the answer is stored, so an auto-submit
+	// racing this request can't record a second submission.
+	if let Some(attempt) = &attempt {
+		match lock_open_attempt(&txn, attempt.id).await {
+			Ok(true) => {}
+			Ok(false) => {
+				return common_response(
+					StatusCode::CONFLICT,
+					"Test attempt already submitted",
+				)
+			}
+			Err(err) => {
+				return common_response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					&err.to_string(),
+				)
+			}
+		}
+	}
+
+	// Insert a new answer into the app_user_answers table
+	let new_answer = app_user_answers_schema::ActiveModel {
+		id: Set(Uuid::new_v4()),
+		user_id: Set(user_id),
+		test_id: Set(test_id),
+		attempt_id: Set(attempt.map(|attempt| attempt.id)),
+		session_id: Set(session_id),
+		created_at: Set(Some(Utc::now())),
+		score: Set(None),
+		scored_at: Set(None),
+	};
+
+	let answer = match new_answer.insert(&txn).await {
+		Ok(answer) => answer,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let answered = selections.len();
+
+	// Iterate over each question-answer pair and insert a record into app_user_question_answers
+	for (question_id, option_id) in selections {
+		let new_question_answer = app_user_questions_answers_schema::ActiveModel {
+			id: Set(Uuid::new_v4()),
+			answer_id: Set(answer.id),
+			question_id: Set(question_id),
+			option_id: Set(option_id),
+		};
+		if let Err(err) = new_question_answer.insert(&txn).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	if let Err(err) = txn.commit().await {
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	let job = Job::ComputeResult {
+		answer_id: answer.id,
+	};
+	if let Err(err) = enqueue_job(&db, job).await {
+		error!("Failed to enqueue result computation: {}", err);
+	}
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"test_answer",
+		answer.id,
+		None,
+		audit_snapshot(&answer),
+	)
+	.await;
+
+	publish_event(RealtimeEvent::new(
+		REALTIME_TOPIC_ATTEMPTS.to_string(),
+		REALTIME_EVENT_ATTEMPT_PROGRESS,
+		json!({
+			"status": "submitted",
+			"attempt_id": answer.attempt_id,
+			"answer_id": answer.id,
+			"test_id": answer.test_id,
+			"user_id": answer.user_id,
+			"answered": answered,
+		}),
+	));
+
+	// Build and return the response DTO
+	let dto = TestAnswersItemDto {
+		id: answer.id.to_string(),
+		user_id: answer.user_id.to_string(),
+		test_id: answer.test_id.to_string(),
+		attempt_id: answer.attempt_id.map(|id| id.to_string()),
+		score: answer.score,
+	};
+
+	let response = ResponseSuccessDto { data: dto };
+	success_response(response)
+}
+
+/// Saves the options picked so far in an open attempt, replacing the earlier
+/// pick for each question. An attempt auto-submitted by integrity monitoring is
+/// scored from these.
+pub async fn mutation_save_attempt_answers(
+	headers: HeaderMap,
+	id: String,
+	payload: Json<TestAttemptAnswersRequestDto>,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user_id = match find_token_viewer(&db, &headers).await {
+		Ok(viewer) => viewer.0,
+		Err(response) => return response,
+	};
+
+	let attempt_id = match Uuid::parse_str(&id) {
+		Ok(uuid) => uuid,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid attempt ID format",
+			)
+		}
+	};
+
+	// The latest pick for a question wins.
+	let mut selections: HashMap<Uuid, Uuid> = HashMap::new();
+	for qa in &payload.questions {
+		match (
+			Uuid::parse_str(&qa.question_id),
+			Uuid::parse_str(&qa.option_id),
+		) {
+			(Ok(question_id), Ok(option_id)) => {
+				selections.insert(question_id, option_id);
+			}
+			_ => {
+				return common_response(
+					StatusCode::BAD_REQUEST,
+					"Invalid question or option ID format",
+				)
+			}
+		}
+	}
+
+	match TestAttemptsEntity::find_by_id(attempt_id)
+		.filter(TestAttemptsColumn::UserId.eq(user_id))
+		.count(&db)
+		.await
+	{
+		Ok(0) => {
+			return common_response(StatusCode::NOT_FOUND, "Test attempt not found")
+		}
+		Ok(_) => {}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	let valid_selections: HashSet<(Uuid, Uuid)> = match OptionsEntity::find()
+		.select_only()
+		.column(OptionsColumn::QuestionId)
+		.column(OptionsColumn::Id)
+		.inner_join(QuestionsEntity)
+		.join(
+			JoinType::InnerJoin,
+			TestAttemptQuestionsRelation::Question.def().rev(),
+		)
+		.filter(TestAttemptQuestionsColumn::AttemptId.eq(attempt_id))
+		.filter(OptionsColumn::Id.is_in(selections.values().copied()))
+		.into_tuple::<(Uuid, Uuid)>()
+		.all(&db)
+		.await
+	{
+		Ok(rows) => rows.into_iter().collect(),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if selections.iter().any(|(question_id, option_id)| {
+		!valid_selections.contains(&(*question_id, *option_id))
+	}) {
+		return common_response(
+			StatusCode::BAD_REQUEST,
+			"Answer does not match a question of this attempt",
+		);
+	}
+
+	let txn = match db.begin().await {
+		Ok(txn) => txn,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	match lock_open_attempt(&txn, attempt_id).await {
+		Ok(true) => {}
+		Ok(false) => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Test attempt already submitted",
+			)
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	}
+
+	if !selections.is_empty() {
+		let rows = selections.into_iter().map(|(question_id, option_id)| {
+			AttemptAnswersActiveModel {
+				id: Set(Uuid::new_v4()),
+				attempt_id: Set(attempt_id),
+				question_id: Set(question_id),
+				option_id: Set(option_id),
+				updated_at: Set(Utc::now()),
+			}
+		});
+
+		if let Err(err) = AttemptAnswersEntity::insert_many(rows)
+			.on_conflict(
+				OnConflict::columns([
+					AttemptAnswersColumn::AttemptId,
+					AttemptAnswersColumn::QuestionId,
+				])
+				.update_columns([
+					AttemptAnswersColumn::OptionId,
+					AttemptAnswersColumn::UpdatedAt,
+				])
+				.to_owned(),
+			)
+			.exec_without_returning(&txn)
+			.await
+		{
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
+		}
+	}
+
+	if let Err(err) = txn.commit().await {
+		return common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string());
+	}
+
+	common_response(StatusCode::OK, "Answers saved successfully")
+}
+
+/// Stores the score of a submitted answer, graded the same way as the
+/// progress and dashboard reports.
+pub async fn score_test_answer(
+	db: &DatabaseConnection,
+	answer_id: Uuid,
+) -> Result<(), DbErr> {
+	db.execute(Statement::from_sql_and_values(
+		DbBackend::Postgres,
+		format!("{}{}", ANSWER_SCORES_CTE, SCORE_TEST_ANSWER_SQL),
+		vec![answer_id.into()],
+	))
+	.await?;
+
+	Ok(())
+}
+
+pub async fn mutation_delete_test_answer(
+	id: String,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+	let answer_id = match Uuid::parse_str(&id) {
+		Ok(uuid) => uuid,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test answer ID",
+			)
+		}
+	};
+
+	let answer = match TestAnswersEntity::find()
+		.filter(TestAnswersColumn::Id.eq(answer_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(answer)) => answer,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Test answer not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let before = audit_snapshot(&answer);
+
+	match answer.delete(&db).await {
+		Ok(_) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"test_answer",
+				answer_id,
+				before,
+				None,
+			)
+			.await;
+			common_response(StatusCode::OK, "Test answer deleted successfully")
+		}
+		Err(err) => {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		}
+	}
+}
+
+pub async fn query_test_answer_list(
+	session_id: String,
+	params: MetaRequestDto,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let session_uuid = match Uuid::parse_str(&session_id) {
+		Ok(uuid) => uuid,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid session_id format",
+			)
+		}
+	};
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query =
+		TestAnswersEntity::find().filter(TestAnswersColumn::TestId.eq(session_uuid));
+
+	// Matches the student's name, email or phone number.
+	if !params.search.is_empty() {
+		query = query.filter(
+			TestAnswersColumn::UserId.in_subquery(
+				Query::select()
+					.column(UsersColumn::Id)
+					.from(UsersEntity)
+					.and_where(UsersEntity::search_matches(&params.search))
+					.to_owned(),
+			),
+		);
+	}
+
+	let (answers, meta) =
+		match params.fetch(&db, query, &TEST_ANSWERS_LIST, None).await {
+			Ok(page) => page,
+			Err(response) => return response,
+		};
+
+	let answers_dto: Vec<_> = answers
+		.into_iter()
+		.map(|answer| TestAnswersItemDto {
+			id: answer.id.to_string(),
+			user_id: answer.user_id.to_string(),
+			test_id: answer.test_id.to_string(),
+			attempt_id: answer.attempt_id.map(|id| id.to_string()),
+			score: answer.score,
+		})
+		.collect();
+
+	let response = ResponseSuccessListDto {
+		data: answers_dto,
+		meta: Some(meta),
+	};
+
+	success_response_list(response)
+}
+
+pub async fn query_get_test_analytics(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let test_id = match Uuid::parse_str(&id) {
+		Ok(uuid) => uuid,
+		Err(_) => {
+			return common_response(
+				StatusCode::BAD_REQUEST,
+				"Invalid test ID format",
+			)
+		}
+	};
+
+	let test = match TestsEntity::find_active()
+		.filter(TestsColumn::Id.eq(test_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(test)) => test,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "Test not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let answer_ids = match TestAnswersEntity::find()
+		.select_only()
+		.column(TestAnswersColumn::Id)
+		.filter(TestAnswersColumn::TestId.eq(test.id))
+		.into_tuple::<Uuid>()
+		.all(&db)
+		.await
+	{
+		Ok(ids) => ids,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// One row per picked option across every submitted answer of the test.
+	let selections = match TestQuestionsAnswersEntity::find()
+		.select_only()
+		.column(TestQuestionsAnswersColumn::AnswerId)
+		.column(TestQuestionsAnswersColumn::QuestionId)
+		.column(TestQuestionsAnswersColumn::OptionId)
+		.column(OptionsColumn::IsCorrect)
+		.join(
+			JoinType::InnerJoin,
+			TestQuestionsAnswersRelation::ParentAnswer.def(),
+		)
+		.join(
+			JoinType::InnerJoin,
+			TestQuestionsAnswersRelation::Option.def(),
+		)
+		.filter(TestAnswersColumn::TestId.eq(test.id))
+		.into_tuple::<(Uuid, Uuid, Uuid, bool)>()
+		.all(&db)
+		.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let mut scores: HashMap<Uuid, u64> =
+		answer_ids.iter().map(|id| (*id, 0)).collect();
+	for (answer_id, _, _, is_correct) in &selections {
+		if *is_correct {
+			*scores.entry(*answer_id).or_default() += 1;
+		}
+	}
+
+	let mut ranked: Vec<(Uuid, u64)> = scores.into_iter().collect();
+	ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+	let group_size = if ranked.len() < 2 {
+		0
+	} else {
+		(ranked.len() as f64 * ANALYTICS_GROUP_RATIO).ceil() as usize
+	};
+	let upper: HashSet<Uuid> =
+		ranked.iter().take(group_size).map(|(id, _)| *id).collect();
+	let lower: HashSet<Uuid> = ranked
+		.iter()
+		.rev()
+		.take(group_size)
+		.map(|(id, _)| *id)
+		.collect();
+
+	// Current questions first, then archived versions that were still answered.
+	let mut question_ids: Vec<Uuid> = match find_test_questions(test.id)
+		.select_only()
+		.column(QuestionsColumn::Id)
+		.into_tuple::<Uuid>()
+		.all(&db)
+		.await
+	{
+		Ok(ids) => ids,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+	for (_, question_id, _, _) in &selections {
+		if !question_ids.contains(question_id) {
+			question_ids.push(*question_id);
+		}
+	}
+
+	let questions: HashMap<Uuid, QuestionsModel> = match QuestionsEntity::find()
+		.filter(QuestionsColumn::Id.is_in(question_ids.clone()))
+		.all(&db)
+		.await
+	{
+		Ok(questions) => questions.into_iter().map(|q| (q.id, q)).collect(),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let options = match OptionsEntity::find()
+		.filter(OptionsColumn::QuestionId.is_in(question_ids.clone()))
+		.all(&db)
+		.await
+	{
+		Ok(options) => options,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let ratio =
+		|part: usize, whole: usize| (whole > 0).then(|| part as f64 / whole as f64);
+
+	let questions_dto: Vec<QuestionAnalyticsDto> = question_ids
+		.into_iter()
+		.filter_map(|question_id| {
+			let question = questions.get(&question_id)?;
+			let picks: Vec<&(Uuid, Uuid, Uuid, bool)> = selections
+				.iter()
+				.filter(|(_, id, _, _)| *id == question_id)
+				.collect();
+
+			let responders: HashSet<Uuid> =
+				picks.iter().map(|(answer_id, ..)| *answer_id).collect();
+			let correct: HashSet<Uuid> = picks
+				.iter()
+				.filter(|(.., is_correct)| *is_correct)
+				.map(|(answer_id, ..)| *answer_id)
+				.collect();
+
+			let p_value = ratio(correct.len(), responders.len());
+			let discrimination_index = (group_size > 0).then(|| {
+				let upper_correct = correct.intersection(&upper).count();
+				let lower_correct = correct.intersection(&lower).count();
+				(upper_correct as f64 - lower_correct as f64) / group_size as f64
+			});
+
+			let needs_review = p_value.is_some_and(|p| {
+				!(ANALYTICS_MIN_P_VALUE..=ANALYTICS_MAX_P_VALUE).contains(&p)
+			}) || discrimination_index
+				.is_some_and(|d| d < ANALYTICS_MIN_DISCRIMINATION);
+
+			let options_dto = options
+				.iter()
+				.filter(|option| option.question_id == question_id)
+				.map(|option| {
+					let picked = picks
+						.iter()
+						.filter(|(_, _, option_id, _)| *option_id == option.id)
+						.count();
+					OptionAnalyticsDto {
+						id: option.id.to_string(),
+						label: option.label.clone(),
+						is_correct: option.is_correct,
+						picked: picked as u64,
+						pick_rate: ratio(picked, responders.len()),
+					}
+				})
+				.collect();
+
+			Some(QuestionAnalyticsDto {
+				id: question.id.to_string(),
+				version: question.version,
+				question: question.question.clone(),
+				topic: question.topic.clone(),
+				difficulty: question.difficulty.clone(),
+				archived: question.archived_at.is_some(),
+				responses: responders.len() as u64,
+				correct_responses: correct.len() as u64,
+				p_value,
+				discrimination_index,
+				needs_review,
+				options: options_dto,
+			})
+		})
+		.collect();
+
+	let dto = TestAnalyticsDto {
+		test_id: test.id.to_string(),
+		test_name: test.test_name,
+		total_attempts: ranked.len() as u64,
+		group_size: group_size as u64,
+		questions: questions_dto,
+	};
+
+	let response = ResponseSuccessDto { data: dto };
+	success_response(response)
+}
+
+/// Test fields plus every question with its options, so a changed correct
+/// option is visible in the audit diff.
+async fn test_audit_snapshot(
+	db: &DatabaseConnection,
+	test: &TestsModel,
+) -> Option<Value> {
+	let questions = find_test_questions(test.id)
+		.find_with_related(OptionsEntity)
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let questions: Vec<Value> = questions
+		.into_iter()
+		.filter_map(|(question, options)| {
+			let mut snapshot = audit_snapshot(&question)?;
+			snapshot["options"] = json!(options);
+			Some(snapshot)
+		})
+		.collect();
+
+	let blueprint = find_blueprint_rules(db, test.id).await.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(test)?;
+	snapshot["questions"] = json!(questions);
+	snapshot["blueprint"] = json!(blueprint);
+	Some(snapshot)
+}
+
+/// Questions each test serves, fixed plus drawn by its blueprint, counted
+/// for all of `test_ids` in two grouped queries.
+pub async fn count_test_questions<C: ConnectionTrait>(
+	db: &C,
+	test_ids: &[Uuid],
+) -> Result<HashMap<Uuid, u64>, DbErr> {
+	let mut counts: HashMap<Uuid, u64> = HashMap::new();
+	if test_ids.is_empty() {
+		return Ok(counts);
+	}
+
+	let fixed = QuestionsEntity::find()
+		.select_only()
+		.column(TestsHasQuestionsColumn::TestId)
+		.column_as(QuestionsColumn::Id.count(), "question_count")
+		.inner_join(TestsHasQuestionsEntity)
+		.filter(TestsHasQuestionsColumn::TestId.is_in(test_ids.to_vec()))
+		.filter(QuestionsColumn::ArchivedAt.is_null())
+		.group_by(TestsHasQuestionsColumn::TestId)
+		.into_tuple::<(Uuid, i64)>()
+		.all(db)
+		.await?;
+
+	let drawn = TestBlueprintsEntity::find()
+		.select_only()
+		.column(TestBlueprintsColumn::TestId)
+		.column_as(TestBlueprintsColumn::QuestionCount.sum(), "question_count")
+		.filter(TestBlueprintsColumn::TestId.is_in(test_ids.to_vec()))
+		.group_by(TestBlueprintsColumn::TestId)
+		.into_tuple::<(Uuid, Option<i64>)>()
+		.all(db)
+		.await?;
+
+	for (test_id, count) in fixed.into_iter().chain(
+		drawn
+			.into_iter()
+			.map(|(id, count)| (id, count.unwrap_or(0))),
+	) {
+		*counts.entry(test_id).or_default() += count.max(0) as u64;
+	}
+
+	Ok(counts)
+}
+
+async fn find_blueprint_rules<C: ConnectionTrait>(
+	db: &C,
+	test_id: Uuid,
+) -> Result<Vec<TestBlueprintsModel>, DbErr> {
+	TestBlueprintsEntity::find()
+		.filter(TestBlueprintsColumn::TestId.eq(test_id))
+		.order_by_asc(TestBlueprintsColumn::Position)
+		.all(db)
+		.await
+}
+
+fn blueprint_rule_dto(rule: TestBlueprintsModel) -> TestBlueprintRuleDto {
+	TestBlueprintRuleDto {
+		topic: rule.topic,
+		subtopic: rule.subtopic,
+		difficulty: rule.difficulty,
+		question_count: rule.question_count.max(0) as u32,
+	}
+}
+
+/// `0` means no limit, which is stored as `NULL`.
+fn validate_max_violations(value: Option<i32>) -> Result<Option<i32>, Response> {
+	match value {
+		Some(value) if value < 0 => Err(common_response(
+			StatusCode::BAD_REQUEST,
+			"max_violations must not be negative",
+		)),
+		Some(0) | None => Ok(None),
+		Some(value) => Ok(Some(value)),
+	}
+}
+
+fn validate_blueprint(
+	rules: &Option<Vec<TestBlueprintRuleDto>>,
+) -> Result<(), Response> {
+	for rule in rules.iter().flatten() {
+		if rule.topic.trim().is_empty() || rule.question_count == 0 {
+			return Err(common_response(
+				StatusCode::BAD_REQUEST,
+				"Blueprint rules need a topic and a question count",
+			));
+		}
+		validate_difficulty(&rule.difficulty)?;
+	}
+	Ok(())
+}
+
+/// Blueprint rules are always saved as a whole; an empty list turns the test
+/// back into a fixed one.
+async fn replace_blueprint(
+	db: &DatabaseConnection,
+	test_id: Uuid,
+	rules: &[TestBlueprintRuleDto],
+) -> Result<(), Response> {
+	let internal_error = |err: DbErr| {
+		common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	};
+
+	TestBlueprintsEntity::delete_many()
+		.filter(TestBlueprintsColumn::TestId.eq(test_id))
+		.exec(db)
+		.await
+		.map_err(internal_error)?;
+
+	for (position, rule) in rules.iter().enumerate() {
+		TestBlueprintsActiveModel {
+			id: Set(Uuid::new_v4()),
+			test_id: Set(test_id),
+			topic: Set(rule.topic.trim().to_string()),
+			subtopic: Set(rule.subtopic.clone()),
+			difficulty: Set(rule.difficulty.clone()),
+			question_count: Set(rule.question_count as i32),
+			position: Set(position as i32),
+		}
+		.insert(db)
+		.await
+		.map_err(internal_error)?;
+	}
+
+	Ok(())
+}
+
+/// The test's fixed questions followed by a random draw from the bank for
+/// each blueprint rule. A question is never drawn twice in one attempt.
+async fn draw_attempt_questions<C: ConnectionTrait>(
+	db: &C,
+	test_id: Uuid,
+) -> Result<Vec<QuestionsModel>, Response> {
+	let internal_error = |err: DbErr| {
+		common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	};
+
+	let mut questions = find_test_questions(test_id)
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+
+	for rule in find_blueprint_rules(db, test_id)
+		.await
+		.map_err(internal_error)?
+	{
+		let mut query = QuestionsEntity::find()
+			.filter(QuestionsColumn::ArchivedAt.is_null())
+			.filter(QuestionsColumn::Topic.eq(rule.topic))
+			.filter(QuestionsColumn::Id.is_not_in(questions.iter().map(|q| q.id)));
+
+		if let Some(subtopic) = rule.subtopic {
+			query = query.filter(QuestionsColumn::Subtopic.eq(subtopic));
+		}
+		if let Some(difficulty) = rule.difficulty {
+			query = query.filter(QuestionsColumn::Difficulty.eq(difficulty));
+		}
+
+		let drawn = query
+			.order_by(Expr::cust("RANDOM()"), Order::Asc)
+			.limit(rule.question_count.max(0) as u64)
+			.all(db)
+			.await
+			.map_err(internal_error)?;
+
+		if drawn.len() < rule.question_count.max(0) as usize {
+			return Err(common_response(
+				StatusCode::CONFLICT,
+				"Not enough questions in the bank for this blueprint",
+			));
+		}
+
+		questions.extend(drawn);
+	}
+
+	Ok(questions)
+}
+
+/// Attempts of the user on the test that are neither submitted nor
+/// auto-submitted, newest first.
+fn find_open_attempts(test_id: Uuid, user_id: Uuid) -> Select<TestAttemptsEntity> {
+	TestAttemptsEntity::find()
+		.filter(TestAttemptsColumn::TestId.eq(test_id))
+		.filter(TestAttemptsColumn::UserId.eq(user_id))
+		.filter(TestAttemptsColumn::AutoSubmittedAt.is_null())
+		.filter(
+			TestAttemptsColumn::Id.not_in_subquery(
+				Query::select()
+					.column(TestAnswersColumn::AttemptId)
+					.from(TestAnswersEntity)
+					.and_where(TestAnswersColumn::AttemptId.is_not_null())
+					.to_owned(),
+			),
+		)
+		.order_by_desc(TestAttemptsColumn::CreatedAt)
+}
+
+/// The user's open attempt on the test, or a new one with a fresh draw. The
+/// user row is locked while deciding, so starting twice at once, or reloading
+/// the page, hands back the same attempt instead of re-rolling the questions.
+/// The flag is true when an existing attempt was resumed.
+async fn open_test_attempt(
+	db: &DatabaseConnection,
+	test_id: Uuid,
+	user_id: Uuid,
+	session_id: Option<Uuid>,
+) -> Result<(TestAttemptsModel, Vec<QuestionsModel>, bool), Response> {
+	let internal_error = |err: DbErr| {
+		common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+	};
+
+	let txn = db.begin().await.map_err(internal_error)?;
+
+	UsersEntity::find_by_id(user_id)
+		.lock_exclusive()
+		.one(&txn)
+		.await
+		.map_err(internal_error)?;
+
+	let open_attempt = find_open_attempts(test_id, user_id)
+		.one(&txn)
+		.await
+		.map_err(internal_error)?;
+
+	if let Some(attempt) = open_attempt {
+		let questions = TestAttemptQuestionsEntity::find()
+			.filter(TestAttemptQuestionsColumn::AttemptId.eq(attempt.id))
+			.order_by_asc(TestAttemptQuestionsColumn::Position)
+			.find_also_related(QuestionsEntity)
+			.all(&txn)
+			.await
+			.map_err(internal_error)?
+			.into_iter()
+			.filter_map(|(_, question)| question)
+			.collect();
+		txn.commit().await.map_err(internal_error)?;
+
+		return Ok((attempt, questions, true));
+	}
+
+	let questions = draw_attempt_questions(&txn, test_id).await?;
+
+	let attempt = TestAttemptsActiveModel {
+		id: Set(Uuid::new_v4()),
+		test_id: Set(test_id),
+		user_id: Set(user_id),
+		session_id: Set(session_id),
+		created_at: Set(Utc::now()),
+		auto_submitted_at: Set(None),
+	}
+	.insert(&txn)
+	.await
+	.map_err(internal_error)?;
+
+	if !questions.is_empty() {
+		TestAttemptQuestionsEntity::insert_many(questions.iter().enumerate().map(
+			|(position, question)| TestAttemptQuestionsActiveModel {
+				id: Set(Uuid::new_v4()),
+				attempt_id: Set(attempt.id),
+				question_id: Set(question.id),
+				position: Set(position as i32),
+			},
+		))
+		.exec(&txn)
+		.await
+		.map_err(internal_error)?;
+	}
+
+	txn.commit().await.map_err(internal_error)?;
+
+	Ok((attempt, questions, false))
+}
+
+fn attempt_question_dto(
+	question: QuestionsModel,
+	options: &[OptionsModel],
+) -> QuestionsItemDto {
+	QuestionsItemDto {
+		id: question.id.to_string(),
+		version: question.version,
+		question: question.question,
+		discussion: None,
+		discussion_image_url: None,
+		image_url: question.image_url,
+		topic: question.topic,
+		subtopic: question.subtopic,
+		difficulty: question.difficulty,
+		options: options
+			.iter()
+			.filter(|option| option.question_id == question.id)
+			.map(|option| OptionsItemDto {
+				id: option.id.to_string(),
+				label: option.label.clone(),
+				is_correct: None,
+				image_url: option.image_url.clone(),
+			})
+			.collect(),
+	}
+}
+
+/// The caller's user id and whether they are an admin, who may open any test
+/// and always sees correct answers and discussions.
+pub async fn find_viewer<C: ConnectionTrait>(
+	db: &C,
+	email: &str,
+) -> Result<Option<(Uuid, bool)>, DbErr> {
+	let viewer = UsersEntity::find_active()
+		.select_only()
+		.column(UsersColumn::Id)
+		.column_as(RolesColumn::Name, "role_name")
+		.join(
+			JoinType::InnerJoin,
+			<app_users_schema::Entity as sea_orm::EntityTrait>::Relation::Role.def(),
+		)
+		.filter(UsersColumn::Email.eq(email))
+		.into_tuple::<(Uuid, String)>()
+		.one(db)
+		.await?;
+
+	Ok(viewer.map(|(user_id, role_name)| {
+		(
+			user_id,
+			role_name.eq_ignore_ascii_case(&RolesEnum::Admin.to_string()),
+		)
+	}))
+}
+
+/// Admins may open any test; everyone else needs an active session that
+/// admits them.
+async fn ensure_test_access<C: ConnectionTrait>(
+	db: &C,
+	viewer: Option<(Uuid, bool)>,
+	test_id: Uuid,
+) -> Result<(), Response> {
+	let user_id = match viewer {
+		Some((_, true)) => return Ok(()),
+		Some((user_id, false)) => user_id,
+		None => {
+			return Err(common_response(StatusCode::NOT_FOUND, "User not found"))
+		}
+	};
+
+	match user_can_take_test(db, user_id, test_id).await {
+		Ok(true) => Ok(()),
+		Ok(false) => Err(common_response(
+			StatusCode::FORBIDDEN,
+			"You are not enrolled in a session for this test",
+		)),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+/// Whether answers to the test must name the attempt they belong to.
+async fn attempt_required(
+	db: &DatabaseConnection,
+	test_id: Uuid,
+) -> Result<bool, DbErr> {
+	let proctored = TestsEntity::find_active()
+		.filter(TestsColumn::Id.eq(test_id))
+		.filter(TestsColumn::MaxViolations.is_not_null())
+		.count(db)
+		.await?
+		> 0;
+	if proctored {
+		return Ok(true);
+	}
+
+	Ok(TestBlueprintsEntity::find()
+		.filter(TestBlueprintsColumn::TestId.eq(test_id))
+		.count(db)
+		.await?
+		> 0)
+}
+
+/// Locks the attempt row and tells whether it still takes answers, i.e. it
+/// was neither submitted nor auto-submitted.
+async fn lock_open_attempt<C: ConnectionTrait>(
+	db: &C,
+	attempt_id: Uuid,
+) -> Result<bool, DbErr> {
+	let attempt = match TestAttemptsEntity::find_by_id(attempt_id)
+		.lock_exclusive()
+		.one(db)
+		.await?
+	{
+		Some(attempt) => attempt,
+		None => return Ok(false),
+	};
+
+	if attempt.auto_submitted_at.is_some() {
+		return Ok(false);
+	}
+
+	Ok(TestAnswersEntity::find()
+		.filter(TestAnswersColumn::AttemptId.eq(attempt.id))
+		.count(db)
+		.await?
+		== 0)
+}
+
+async fn find_token_viewer(
+	db: &DatabaseConnection,
+	headers: &HeaderMap,
+) -> Result<(Uuid, bool), Response> {
+	let token = headers
+		.get("Authorization")
+		.ok_or_else(|| {
+			common_response(StatusCode::FORBIDDEN, "You are not authorized")
+		})?
+		.to_str()
+		.map_err(|err| common_response(StatusCode::BAD_REQUEST, &err.to_string()))?
+		.split_whitespace()
+		.nth(1)
+		.ok_or_else(|| {
+			common_response(StatusCode::BAD_REQUEST, "Invalid token format")
+		})?;
+
+	let token_data = decode_access_token(token).map_err(|err| {
+		common_response(StatusCode::UNAUTHORIZED, &err.to_string())
+	})?;
+
+	match find_viewer(db, &token_data.claims.email).await {
+		Ok(Some(viewer)) => Ok(viewer),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "User not found")),
+		Err(err) => Err(common_response(
+			StatusCode::INTERNAL_SERVER_ERROR,
+			&err.to_string(),
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use axum::http::StatusCode;
+	use chrono::Utc;
+	use sea_orm::{DatabaseBackend, MockDatabase, Value};
+	use uuid::Uuid;
+
+	use super::{
+		count_test_questions, get_test_answer_by_id, get_test_by_id, get_tests,
+	};
+	use crate::{
+		schemas::{
+			OptionsModel, QuestionsModel, TestAnswersModel, TestBlueprintsModel,
+			TestQuestionsAnswersModel, TestsModel,
+		},
+		MetaRequestDto,
+	};
+
+	const VIEWER_EMAIL: &str = "admin@example.com";
+
+	type Row = BTreeMap<&'static str, Value>;
+
+	// Mock rows are read by position, which follows the key order.
+	fn count_row(test_id: Uuid, count: Option<i64>) -> Row {
+		BTreeMap::from([("0_test_id", test_id.into()), ("1_count", count.into())])
+	}
+
+	fn admin_row() -> Row {
+		BTreeMap::from([
+			("0_id", Uuid::new_v4().into()),
+			("1_role_name", "Admin".into()),
+		])
+	}
+
+	fn test_model(id: Uuid) -> TestsModel {
+		TestsModel {
+			id,
+			test_name: "Tryout".to_string(),
+			max_violations: None,
+			created_at: Some(Utc::now()),
+			updated_at: Some(Utc::now()),
+			deleted_at: None,
+		}
+	}
+
+	fn question_model(id: Uuid) -> QuestionsModel {
+		QuestionsModel {
+			id,
+			question: "Question".to_string(),
+			image_url: None,
+			discussion: "Discussion".to_string(),
+			discussion_image_url: None,
+			topic: None,
+			subtopic: None,
+			difficulty: None,
+			author_id: None,
+			version: 1,
+			previous_version_id: None,
+			archived_at: None,
+			created_at: Some(Utc::now()),
+			updated_at: Some(Utc::now()),
+		}
+	}
+
+	fn option_models(question_ids: &[Uuid]) -> Vec<OptionsModel> {
+		question_ids
+			.iter()
+			.flat_map(|question_id| {
+				(0..4).map(move |position| OptionsModel {
+					id: Uuid::new_v4(),
+					question_id: *question_id,
+					label: format!("Option {}", position),
+					image_url: None,
+					is_correct: position == 0,
+				})
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn count_test_questions_runs_two_queries_for_any_page_size() {
+		let test_ids: Vec<Uuid> = (0..25).map(|_| Uuid::new_v4()).collect();
+
+		let db = MockDatabase::new(DatabaseBackend::Postgres)
+			.append_query_results([vec![
+				count_row(test_ids[0], Some(3)),
+				count_row(test_ids[1], Some(1)),
+			]])
+			.append_query_results([vec![
+				count_row(test_ids[1], Some(4)),
+				count_row(test_ids[2], None),
+			]])
+			.into_connection();
+
+		let counts = count_test_questions(&db, &test_ids).await.unwrap();
+
+		assert_eq!(counts.get(&test_ids[0]), Some(&3));
+		assert_eq!(counts.get(&test_ids[1]), Some(&5));
+		assert_eq!(counts.get(&test_ids[2]), Some(&0));
+		assert_eq!(counts.get(&test_ids[3]), None);
+		assert_eq!(db.into_transaction_log().len(), 2);
+	}
+
+	#[tokio::test]
+	async fn get_tests_runs_four_queries_for_a_full_page() {
+		let test_ids: Vec<Uuid> = (0..50).map(|_| Uuid::new_v4()).collect();
+		let params = MetaRequestDto {
+			page: Some(1),
+			per_page: Some(50),
+			search: None,
+			sort_by: None,
+			order: None,
+			filter: None,
+			filter_by: None,
+			filters: None,
+			cursor: None,
+		};
+
+		let db = MockDatabase::new(DatabaseBackend::Postgres)
+			.append_query_results([vec![BTreeMap::from([(
+				"num_items",
+				Value::BigInt(Some(120)),
+			)])]])
+			.append_query_results([test_ids
+				.iter()
+				.map(|id| test_model(*id))
+				.collect::<Vec<_>>()])
+			.append_query_results([vec![count_row(test_ids[0], Some(10))]])
+			.append_query_results([vec![count_row(test_ids[1], Some(5))]])
+			.into_connection();
+
+		let response = get_tests(&db, params).await;
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(db.into_transaction_log().len(), 4);
+	}
+
+	#[tokio::test]
+	async fn get_test_by_id_runs_six_queries_for_many_questions() {
+		let test_id = Uuid::new_v4();
+		let question_ids: Vec<Uuid> = (0..100).map(|_| Uuid::new_v4()).collect();
+
+		let db = MockDatabase::new(DatabaseBackend::Postgres)
+			.append_query_results([vec![admin_row()]])
+			.append_query_results([Vec::<Row>::new()])
+			.append_query_results([vec![test_model(test_id)]])
+			.append_query_results([question_ids
+				.iter()
+				.map(|id| question_model(*id))
+				.collect::<Vec<_>>()])
+			.append_query_results([option_models(&question_ids)])
+			.append_query_results([Vec::<TestBlueprintsModel>::new()])
+			.into_connection();
+
+		let response = get_test_by_id(&db, VIEWER_EMAIL, &test_id.to_string()).await;
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(db.into_transaction_log().len(), 6);
+	}
+
+	#[tokio::test]
+	async fn get_test_answer_by_id_runs_eight_queries_for_many_questions() {
+		let test_id = Uuid::new_v4();
+		let answer_id = Uuid::new_v4();
+		let question_ids: Vec<Uuid> = (0..100).map(|_| Uuid::new_v4()).collect();
+		let options = option_models(&question_ids);
+
+		let answer = TestAnswersModel {
+			id: answer_id,
+			user_id: Uuid::new_v4(),
+			test_id,
+			attempt_id: Some(Uuid::new_v4()),
+			session_id: None,
+			created_at: Some(Utc::now()),
+			score: None,
+			scored_at: None,
+		};
+		let question_answers: Vec<TestQuestionsAnswersModel> = options
+			.iter()
+			.step_by(4)
+			.map(|option| TestQuestionsAnswersModel {
+				id: Uuid::new_v4(),
+				answer_id,
+				question_id: option.question_id,
+				option_id: option.id,
+			})
+			.collect();
+		let drawn: Vec<Row> = question_ids
+			.iter()
+			.map(|id| BTreeMap::from([("0_question_id", (*id).into())]))
+			.collect();
+
+		let db = MockDatabase::new(DatabaseBackend::Postgres)
+			.append_query_results([vec![admin_row()]])
+			.append_query_results([vec![answer]])
+			.append_query_results([vec![test_model(test_id)]])
+			.append_query_results([Vec::<Row>::new()])
+			.append_query_results([question_answers])
+			.append_query_results([drawn])
+			.append_query_results([question_ids
+				.iter()
+				.map(|id| question_model(*id))
+				.collect::<Vec<_>>()])
+			.append_query_results([options])
+			.into_connection();
+
+		let response =
+			get_test_answer_by_id(&db, VIEWER_EMAIL, &answer_id.to_string()).await;
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(db.into_transaction_log().len(), 8);
+	}
 }
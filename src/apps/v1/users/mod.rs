@@ -11,19 +11,26 @@ pub use users_dto::*;
 pub use users_repository::*;
 
 use super::auth_controller;
+use crate::{RateLimitLayer, RateLimitPolicy};
 
 pub fn users_router() -> Router {
 	Router::new()
 		.route("/", get(users_controller::get_users))
 		.route("/me", get(users_controller::get_user_me))
+		.route("/me/progress", get(users_controller::get_user_me_progress))
 		.route("/create", post(users_controller::post_create_user))
 		.route(
 			"/change-password",
 			post(auth_controller::post_change_password),
 		)
+		.route("/2fa/setup", post(auth_controller::post_two_factor_setup))
+		.route("/2fa/enable", post(auth_controller::post_two_factor_enable))
+		.route("/2fa/disable", post(auth_controller::post_two_factor_disable))
 		.route("/detail/{id}", get(users_controller::get_detail_user))
 		.route("/update/{id}", put(users_controller::put_update_user))
 		.route("/update/me", put(users_controller::put_update_user_me))
 		.route("/activate/{id}", put(users_controller::put_activate_user))
 		.route("/delete/{id}", delete(users_controller::delete_user))
+		.route("/restore/{id}", put(users_controller::put_restore_user))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute("users", 120)))
 }
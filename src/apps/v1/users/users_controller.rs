@@ -7,17 +7,19 @@ use hyper::HeaderMap;
 
 use crate::{
 	permissions::{permissions_middleware, PermissionsEnum},
-	MessageResponseDto, MetaRequestDto, ResponseSuccessDto, ResponseSuccessListDto,
+	AuditContext, MessageResponseDto, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto,
 };
 
 use super::{
-	mutation_delete_user, mutation_set_active_inactive_user, mutation_update_user,
-	mutation_update_user_me, query_get_user_me,
+	mutation_delete_user, mutation_restore_user, mutation_set_active_inactive_user,
+	mutation_update_user, mutation_update_user_me, query_get_user_me,
+	query_get_user_progress,
 	users_dto::{UsersCreateRequestDto, UsersUpdateRequestDto},
 	users_repository::{
 		mutation_create_users, query_get_user_by_id, query_get_users,
 	},
-	UsersActiveInactiveRequestDto, UsersItemDto, UsersItemListDto,
+	UsersActiveInactiveRequestDto, UsersItemDto, UsersItemListDto, UsersProgressDto,
 };
 
 #[utoipa::path(
@@ -85,6 +87,22 @@ pub async fn get_user_me(headers: HeaderMap) -> impl IntoResponse {
 	query_get_user_me(headers).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/me/progress",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Progress of the current user", body = ResponseSuccessDto<UsersProgressDto>),
+        (status = 401, description = "Unauthorized", body = MessageResponseDto)
+    ),
+    tag = "Users"
+)]
+pub async fn get_user_me_progress(headers: HeaderMap) -> impl IntoResponse {
+	query_get_user_progress(headers).await
+}
+
 #[utoipa::path(
     post,
     path = "/v1/users/create",
@@ -100,11 +118,12 @@ pub async fn get_user_me(headers: HeaderMap) -> impl IntoResponse {
 )]
 pub async fn post_create_user(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<UsersCreateRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::CreateUsers]).await
 	{
-		Ok(_) => mutation_create_users(Json(payload)).await,
+		Ok(_) => mutation_create_users(Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -123,11 +142,36 @@ pub async fn post_create_user(
 )]
 pub async fn delete_user(
 	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteUsers]).await
+	{
+		Ok(_) => mutation_delete_user(id, &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/users/restore/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "User Restored", body = MessageResponseDto),
+        (status = 404, description = "Deleted user not found", body = MessageResponseDto)
+    ),
+    tag = "Users"
+)]
+pub async fn put_restore_user(
+	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteUsers]).await
 	{
-		Ok(_) => mutation_delete_user(id).await,
+		Ok(_) => mutation_restore_user(id, &audit).await,
 		Err(response) => response,
 	}
 }
@@ -147,12 +191,13 @@ pub async fn delete_user(
 )]
 pub async fn put_update_user(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<UsersUpdateRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateUsers]).await
 	{
-		Ok(_) => mutation_update_user(id, Json(payload)).await,
+		Ok(_) => mutation_update_user(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -172,10 +217,11 @@ pub async fn put_update_user(
 )]
 pub async fn put_update_user_me(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Json(payload): Json<UsersUpdateRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![]).await {
-		Ok(_) => mutation_update_user_me(headers, Json(payload)).await,
+		Ok(_) => mutation_update_user_me(headers, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
@@ -195,12 +241,13 @@ pub async fn put_update_user_me(
 )]
 pub async fn put_activate_user(
 	headers: HeaderMap,
+	audit: AuditContext,
 	Path(id): Path<String>,
 	Json(payload): Json<UsersActiveInactiveRequestDto>,
 ) -> impl IntoResponse {
 	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateUsers]).await
 	{
-		Ok(_) => mutation_set_active_inactive_user(id, Json(payload)).await,
+		Ok(_) => mutation_set_active_inactive_user(id, Json(payload), &audit).await,
 		Err(response) => response,
 	}
 }
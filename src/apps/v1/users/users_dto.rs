@@ -1,4 +1,5 @@
 use crate::roles::RolesItemDto;
+use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -11,7 +12,6 @@ pub struct UsersCreateRequestDto {
 	pub student_type: String,
 	pub phone_number: String,
 	pub password: String,
-	pub referral_code: Option<String>,
 	pub referred_by: Option<String>,
 }
 
@@ -75,3 +75,49 @@ pub struct UsersCheckLoginDto {
 	pub password: String,
 	pub is_active: bool,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct UsersProgressDto {
+	pub student_type: String,
+	pub total_attempts: u64,
+	pub average_score: Option<f64>,
+	pub percentile: Option<f64>,
+	pub peer_count: i64,
+	pub history: Vec<UsersProgressHistoryItemDto>,
+	pub categories: Vec<UsersProgressCategoryDto>,
+	pub strongest_topics: Vec<UsersProgressTopicDto>,
+	pub weakest_topics: Vec<UsersProgressTopicDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct UsersProgressHistoryItemDto {
+	#[schema(value_type = String)]
+	pub answer_id: uuid::Uuid,
+	#[schema(value_type = String)]
+	pub test_id: uuid::Uuid,
+	pub test_name: String,
+	pub category: String,
+	pub correct_answers: i64,
+	pub total_questions: i64,
+	pub score: f64,
+	#[schema(value_type = Option<String>)]
+	pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct UsersProgressCategoryDto {
+	pub category: String,
+	pub attempts: i64,
+	pub average_score: f64,
+	pub best_score: f64,
+	pub first_score: f64,
+	pub latest_score: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct UsersProgressTopicDto {
+	pub topic: String,
+	pub answered: i64,
+	pub correct: i64,
+	pub accuracy: f64,
+}
@@ -3,30 +3,160 @@ use axum::response::Response;
 use axum::Json;
 use chrono::{NaiveDate, Utc};
 use hyper::HeaderMap;
-use prelude::Expr;
-use sea_orm::sea_query::extension::postgres::PgExpr;
+use redis::Commands;
 use sea_orm::{
-	entity::*, ActiveModelTrait, DatabaseConnection, JoinType, PaginatorTrait,
-	QueryFilter, QueryOrder, QuerySelect, Set,
+	entity::*, ActiveModelTrait, DatabaseConnection, DbBackend, FromQueryResult,
+	LoaderTrait, QueryFilter, Set, Statement,
 };
 use uuid::Uuid;
 
 use super::users_dto::{UsersCreateRequestDto, UsersItemDto, UsersItemListDto};
-use super::{UsersActiveInactiveRequestDto, UsersUpdateRequestDto};
+use super::{
+	UsersActiveInactiveRequestDto, UsersProgressCategoryDto, UsersProgressDto,
+	UsersProgressHistoryItemDto, UsersProgressTopicDto, UsersUpdateRequestDto,
+};
+use crate::auth::email_from_headers;
 use crate::permissions::PermissionsItemDto;
+use crate::referrals::{
+	find_referrer, generate_referral_code, mark_referral_activated, record_referral,
+};
 use crate::roles::RolesItemDto;
 use crate::schemas::{
 	PermissionsEntity, RolesEntity, RolesPermissionsColumn, RolesPermissionsEntity,
-	UsersActiveModel, UsersColumn, UsersEntity, UsersRelation,
+	UsersActiveModel, UsersColumn, UsersEntity,
 };
 use crate::{
-	common_response, decode_access_token, get_db, hash_password, success_response,
-	success_response_list, MetaRequestDto, MetaResponseDto, ResponseSuccessDto,
-	ResponseSuccessListDto,
+	audit_snapshot, common_response, connect_redis, decode_access_token, get_db,
+	hash_password, record_audit_log, success_response, success_response_list,
+	AuditAction, AuditContext, FieldKind, FullTextSearch, ListParams, ListSpec,
+	MetaRequestDto, ResponseSuccessDto, ResponseSuccessListDto, SoftDelete,
 };
 
+const PROGRESS_TOPIC_LIMIT: usize = 3;
+
+// Scores every submitted answer in one pass. Attempts are scored against the
+// questions they drew, older answers against the test's current questions.
+pub const ANSWER_SCORES_CTE: &str = r#"
+WITH answer_scores AS (
+	SELECT
+		a.id AS answer_id,
+		a.user_id,
+		a.test_id,
+		a.created_at,
+		COUNT(qa.id) FILTER (WHERE o.is_correct) AS correct_answers,
+		GREATEST(COUNT(DISTINCT qa.question_id), MAX(t.total)) AS total_questions
+	FROM app_user_answers a
+	LEFT JOIN app_user_question_answers qa ON qa.answer_id = a.id
+	LEFT JOIN app_options o ON o.id = qa.option_id
+	LEFT JOIN LATERAL (
+		SELECT CASE
+			WHEN a.attempt_id IS NOT NULL THEN (
+				SELECT COUNT(*) FROM app_test_attempt_questions aq
+				WHERE aq.attempt_id = a.attempt_id
+			)
+			ELSE (
+				SELECT COUNT(*) FROM app_tests_has_questions l
+				JOIN app_questions q ON q.id = l.question_id
+				WHERE l.test_id = a.test_id AND q.archived_at IS NULL
+			)
+		END AS total
+	) t ON TRUE
+	GROUP BY a.id, a.user_id, a.test_id, a.created_at
+),
+scored AS (
+	SELECT
+		s.answer_id,
+		s.user_id,
+		s.test_id,
+		t.test_name,
+		COALESCE(c.category, 'General') AS category,
+		s.correct_answers,
+		s.total_questions,
+		COALESCE(
+			(s.correct_answers * 100.0 / NULLIF(s.total_questions, 0))::float8,
+			0
+		) AS score,
+		s.created_at
+	FROM answer_scores s
+	JOIN app_tests t ON t.id = s.test_id
+	LEFT JOIN LATERAL (
+		SELECT ts.category FROM app_sessions_has_tests st
+		JOIN app_test_sessions ts ON ts.id = st.session_id
+		WHERE st.test_id = s.test_id
+		ORDER BY st.start_date DESC NULLS LAST
+		LIMIT 1
+	) c ON TRUE
+)
+"#;
+
+const PROGRESS_HISTORY_SQL: &str = r#"
+SELECT answer_id, test_id, test_name, category, correct_answers,
+	total_questions, score, created_at
+FROM scored
+WHERE user_id = $1
+ORDER BY created_at ASC NULLS FIRST, answer_id
+"#;
+
+const PROGRESS_CATEGORIES_SQL: &str = r#"
+SELECT
+	category,
+	COUNT(*) AS attempts,
+	AVG(score) AS average_score,
+	MAX(score) AS best_score,
+	(ARRAY_AGG(score ORDER BY created_at ASC NULLS FIRST))[1] AS first_score,
+	(ARRAY_AGG(score ORDER BY created_at DESC NULLS LAST))[1] AS latest_score
+FROM scored
+WHERE user_id = $1
+GROUP BY category
+ORDER BY category
+"#;
+
+const PROGRESS_PERCENTILE_SQL: &str = r#"
+, peer_averages AS (
+	SELECT s.user_id, AVG(s.score) AS average_score
+	FROM scored s
+	JOIN app_users u ON u.id = s.user_id
+	WHERE u.deleted_at IS NULL AND u.student_type = $2
+	GROUP BY s.user_id
+),
+own AS (
+	SELECT average_score FROM peer_averages WHERE user_id = $1
+)
+SELECT
+	(SELECT average_score FROM own) AS average_score,
+	COUNT(*) AS peer_count,
+	(
+		COUNT(*) FILTER (WHERE p.average_score < (SELECT average_score FROM own))
+		* 100.0 / NULLIF(COUNT(*), 0)
+	)::float8 AS percentile
+FROM peer_averages p
+"#;
+
+const PROGRESS_TOPICS_SQL: &str = r#"
+SELECT
+	COALESCE(q.topic, 'Uncategorized') AS topic,
+	COUNT(*) AS answered,
+	COUNT(*) FILTER (WHERE o.is_correct) AS correct,
+	(COUNT(*) FILTER (WHERE o.is_correct) * 100.0 / COUNT(*))::float8 AS accuracy
+FROM app_user_question_answers qa
+JOIN app_user_answers a ON a.id = qa.answer_id
+JOIN app_questions q ON q.id = qa.question_id
+JOIN app_options o ON o.id = qa.option_id
+WHERE a.user_id = $1
+GROUP BY 1
+ORDER BY accuracy DESC, answered DESC, topic
+"#;
+
+#[derive(FromQueryResult)]
+struct ProgressPercentileRow {
+	average_score: Option<f64>,
+	peer_count: i64,
+	percentile: Option<f64>,
+}
+
 pub async fn mutation_create_users(
 	new_user: Json<UsersCreateRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
@@ -48,10 +178,43 @@ pub async fn mutation_create_users(
 		);
 	}
 
+	let referrer = match &new_user.referred_by {
+		Some(code) if !code.trim().is_empty() => {
+			match find_referrer(&db, code).await {
+				Ok(Some(referrer)) => Some(referrer),
+				Ok(None) => {
+					return common_response(
+						StatusCode::BAD_REQUEST,
+						"Referral code not found",
+					)
+				}
+				Err(err) => {
+					return common_response(
+						StatusCode::INTERNAL_SERVER_ERROR,
+						&err.to_string(),
+					)
+				}
+			}
+		}
+		_ => None,
+	};
+
+	let referral_code = match generate_referral_code(&db).await {
+		Ok(code) => code,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
 	let hashed_password = hash_password(&new_user.password).unwrap();
 
+	let user_id = Uuid::new_v4();
+
 	let active_model = UsersActiveModel {
-		id: Set(Uuid::new_v4()),
+		id: Set(user_id),
 		role_id: Set(Uuid::parse_str(&new_user.role_id).unwrap()),
 		fullname: Set(new_user.fullname.clone()),
 		email: Set(new_user.email.clone()),
@@ -59,8 +222,8 @@ pub async fn mutation_create_users(
 		avatar: Set(None),
 		phone_number: Set(new_user.phone_number.clone()),
 		password: Set(hashed_password),
-		referral_code: Set(new_user.referral_code.clone()),
-		referred_by: Set(new_user.referred_by.clone()),
+		referral_code: Set(Some(referral_code)),
+		referred_by: Set(referrer.as_ref().and_then(|r| r.referral_code.clone())),
 		birth_date: Set(None),
 		gender: Set(None),
 		religion: Set(None),
@@ -68,17 +231,46 @@ pub async fn mutation_create_users(
 		is_deleted: Set(false),
 		is_active: Set(false),
 		is_profile_completed: Set(false),
+		totp_secret: Set(None),
+		totp_enabled_at: Set(None),
+		totp_recovery_codes: Set(None),
 		student_type: Set(new_user.student_type.clone()),
 		created_at: Set(Some(Utc::now())),
 		updated_at: Set(Some(Utc::now())),
+		deleted_at: Set(None),
 	};
 
-	match active_model.insert(&db).await {
-		Ok(_) => common_response(StatusCode::CREATED, "User created successfully"),
+	let user = match active_model.insert(&db).await {
+		Ok(user) => user,
 		Err(err) => {
-			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	if let Some(referrer) = &referrer {
+		if let Err(err) = record_referral(&db, referrer, user_id).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
 		}
 	}
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"user",
+		user.id,
+		None,
+		audit_snapshot(&user),
+	)
+	.await;
+
+	common_response(StatusCode::CREATED, "User created successfully")
 }
 
 pub async fn query_get_user_me(headers: HeaderMap) -> Response {
@@ -107,7 +299,7 @@ pub async fn query_get_user_me(headers: HeaderMap) -> Response {
 		}
 	};
 
-	let token_data = match decode_access_token(&token) {
+	let token_data = match decode_access_token(token) {
 		Ok(data) => data,
 		Err(err) => {
 			return common_response(StatusCode::UNAUTHORIZED, &err.to_string())
@@ -116,7 +308,7 @@ pub async fn query_get_user_me(headers: HeaderMap) -> Response {
 
 	let email = token_data.claims.email;
 
-	match UsersEntity::find()
+	match UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(email))
 		.find_also_related(RolesEntity)
 		.one(&db)
@@ -146,6 +338,7 @@ pub async fn query_get_user_me(headers: HeaderMap) -> Response {
 			let role_dto = RolesItemDto {
 				id: role.id.to_string(),
 				name: role.name,
+				require_two_factor: role.require_two_factor,
 				permissions: role_permissions,
 				created_at: role.created_at.map(|dt| dt.to_string()),
 				updated_at: role.updated_at.map(|dt| dt.to_string()),
@@ -207,7 +400,7 @@ pub async fn query_get_user_me(headers: HeaderMap) -> Response {
 pub async fn query_get_user_by_id(id_payload: String) -> Response {
 	let db = get_db().await;
 
-	match UsersEntity::find()
+	match UsersEntity::find_active()
 		.filter(UsersColumn::Id.eq(Uuid::parse_str(&id_payload).unwrap()))
 		.find_also_related(RolesEntity)
 		.one(&db)
@@ -237,6 +430,7 @@ pub async fn query_get_user_by_id(id_payload: String) -> Response {
 			let role_dto = RolesItemDto {
 				id: role.id.to_string(),
 				name: role.name,
+				require_two_factor: role.require_two_factor,
 				permissions: role_permissions,
 				created_at: role.created_at.map(|dt| dt.to_string()),
 				updated_at: role.updated_at.map(|dt| dt.to_string()),
@@ -295,71 +489,47 @@ pub async fn query_get_user_by_id(id_payload: String) -> Response {
 	}
 }
 
+const USERS_LIST: ListSpec<UsersEntity> = ListSpec {
+	filters: &[
+		("role_id", UsersColumn::RoleId, FieldKind::Uuid),
+		("student_type", UsersColumn::StudentType, FieldKind::Text),
+		("is_active", UsersColumn::IsActive, FieldKind::Bool),
+		("created_at", UsersColumn::CreatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("fullname", UsersColumn::Fullname),
+		("email", UsersColumn::Email),
+		("created_at", UsersColumn::CreatedAt),
+		("updated_at", UsersColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: UsersColumn::Id,
+};
+
 pub async fn query_get_users(params: MetaRequestDto) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
-	let page = params.page.unwrap_or(1);
-	let per_page = params.per_page.unwrap_or(10);
-	let search = params.search.unwrap_or_default();
-	let sort_by = params
-		.sort_by
-		.unwrap_or("created_at".to_string())
-		.to_lowercase();
-	let order = params.order.unwrap_or("desc".to_string()).to_lowercase();
-	let filter = params.filter.unwrap_or_default();
-	let filter_by = params.filter_by.unwrap_or_default().to_lowercase();
-
-	let mut query = UsersEntity::find().filter(UsersColumn::IsDeleted.eq(false));
-
-	if !search.is_empty() {
-		query = query
-			.filter(Expr::col(UsersColumn::Fullname).ilike(format!("%{}%", search)));
-	}
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
 
-	if filter_by == "role_id" && !filter.is_empty() {
-		if let Ok(role_id) = Uuid::parse_str(&filter) {
-			query = query.filter(UsersColumn::RoleId.eq(role_id));
-		} else {
-			return common_response(
-				StatusCode::BAD_REQUEST,
-				"Invalid role_id format",
-			);
-		}
-	}
+	let mut query = UsersEntity::find_active();
 
-	if filter_by == "student_type" && !filter.is_empty() {
-		query = query.filter(UsersColumn::StudentType.eq(filter));
+	// Searches name, email and phone number.
+	if !params.search.is_empty() {
+		query = query.filter(UsersEntity::search_matches(&params.search));
 	}
 
-	query = match (sort_by.as_str(), order.as_str()) {
-		("fullname", "asc") => query.order_by_asc(UsersColumn::Fullname),
-		("fullname", "desc") => query.order_by_desc(UsersColumn::Fullname),
-		("email", "asc") => query.order_by_asc(UsersColumn::Email),
-		("email", "desc") => query.order_by_desc(UsersColumn::Email),
-		("created_at", "asc") => query.order_by_asc(UsersColumn::CreatedAt),
-		("created_at", "desc") => query.order_by_desc(UsersColumn::CreatedAt),
-		("updated_at", "asc") => query.order_by_asc(UsersColumn::UpdatedAt),
-		("updated_at", "desc") => query.order_by_desc(UsersColumn::UpdatedAt),
-		_ => query.order_by_asc(UsersColumn::CreatedAt),
-	};
-
-	let paginator = query
-		.join(JoinType::LeftJoin, UsersRelation::Role.def())
-		.select_also(RolesEntity)
-		.paginate(&db, per_page);
-
-	let total_items = match paginator.num_items().await {
-		Ok(count) => count,
-		Err(err) => {
-			return common_response(
-				StatusCode::INTERNAL_SERVER_ERROR,
-				&err.to_string(),
-			);
-		}
+	let rank = (!params.search.is_empty())
+		.then(|| UsersEntity::search_rank(&params.search));
+	let (users, meta) = match params.fetch(&db, query, &USERS_LIST, rank).await {
+		Ok(page) => page,
+		Err(response) => return response,
 	};
 
-	let results = match paginator.fetch_page(page - 1).await {
-		Ok(data) => data,
+	let roles = match users.load_one(RolesEntity, &db).await {
+		Ok(roles) => roles,
 		Err(err) => {
 			return common_response(
 				StatusCode::INTERNAL_SERVER_ERROR,
@@ -368,8 +538,9 @@ pub async fn query_get_users(params: MetaRequestDto) -> Response {
 		}
 	};
 
-	let data: Vec<UsersItemListDto> = results
+	let data: Vec<UsersItemListDto> = users
 		.into_iter()
+		.zip(roles)
 		.map(|(user, role)| UsersItemListDto {
 			id: user.id.to_string(),
 			fullname: user.fullname,
@@ -387,17 +558,16 @@ pub async fn query_get_users(params: MetaRequestDto) -> Response {
 
 	let users_response = ResponseSuccessListDto {
 		data,
-		meta: Some(MetaResponseDto {
-			page: Some(page),
-			per_page: Some(per_page),
-			total: Some(total_items),
-		}),
+		meta: Some(meta),
 	};
 
 	success_response_list(users_response)
 }
 
-pub async fn mutation_delete_user(user_id: String) -> Response {
+pub async fn mutation_delete_user(
+	user_id: String,
+	audit: &AuditContext,
+) -> Response {
 	let db: DatabaseConnection = get_db().await;
 
 	let user_id = match Uuid::parse_str(&user_id) {
@@ -407,9 +577,8 @@ pub async fn mutation_delete_user(user_id: String) -> Response {
 		}
 	};
 
-	let user = match UsersEntity::find()
+	let user = match UsersEntity::find_active()
 		.filter(UsersColumn::Id.eq(user_id))
-		.filter(UsersColumn::IsDeleted.eq(false))
 		.one(&db)
 		.await
 	{
@@ -423,12 +592,93 @@ pub async fn mutation_delete_user(user_id: String) -> Response {
 		}
 	};
 
+	let email = user.email.clone();
+	let before = audit_snapshot(&user);
 	let mut active_model: UsersActiveModel = user.into();
 	active_model.is_deleted = Set(true);
+	active_model.deleted_at = Set(Some(Utc::now()));
+	active_model.updated_at = Set(Some(Utc::now()));
+
+	let user = match active_model.update(&db).await {
+		Ok(user) => user,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Delete,
+		"user",
+		user.id,
+		before,
+		audit_snapshot(&user),
+	)
+	.await;
+
+	// Dropping the cached session makes every authorized route reject the
+	// user immediately instead of when the access token expires.
+	let mut redis = connect_redis();
+	let redis_key = format!("authenticated_users_data:{}", email);
+	let _: Result<(), _> = redis.del(&redis_key);
+
+	common_response(StatusCode::OK, "User deleted successfully")
+}
+
+pub async fn mutation_restore_user(
+	user_id: String,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user_id = match Uuid::parse_str(&user_id) {
+		Ok(id) => id,
+		Err(err) => {
+			return common_response(StatusCode::BAD_REQUEST, &err.to_string())
+		}
+	};
+
+	let user = match UsersEntity::find_deleted()
+		.filter(UsersColumn::Id.eq(user_id))
+		.one(&db)
+		.await
+	{
+		Ok(Some(user)) => user,
+		Ok(None) => {
+			return common_response(StatusCode::NOT_FOUND, "Deleted user not found")
+		}
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let before = audit_snapshot(&user);
+	let mut active_model: UsersActiveModel = user.into();
+	active_model.is_deleted = Set(false);
+	active_model.deleted_at = Set(None);
 	active_model.updated_at = Set(Some(Utc::now()));
 
 	match active_model.update(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "User deleted successfully"),
+		Ok(user) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Restore,
+				"user",
+				user.id,
+				before,
+				audit_snapshot(&user),
+			)
+			.await;
+			common_response(StatusCode::OK, "User restored successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
@@ -438,6 +688,7 @@ pub async fn mutation_delete_user(user_id: String) -> Response {
 pub async fn mutation_update_user(
 	id: String,
 	Json(update_data): Json<UsersUpdateRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db = get_db().await;
 
@@ -448,9 +699,8 @@ pub async fn mutation_update_user(
 		}
 	};
 
-	let user = match UsersEntity::find()
+	let user = match UsersEntity::find_active()
 		.filter(UsersColumn::Id.eq(user_id))
-		.filter(UsersColumn::IsDeleted.eq(false))
 		.one(&db)
 		.await
 	{
@@ -464,6 +714,7 @@ pub async fn mutation_update_user(
 		}
 	};
 
+	let before = audit_snapshot(&user);
 	let mut active_model: UsersActiveModel = user.into();
 
 	if let Some(email) = &update_data.email {
@@ -528,7 +779,19 @@ pub async fn mutation_update_user(
 	active_model.updated_at = Set(Some(Utc::now()));
 
 	match active_model.update(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "User updated successfully"),
+		Ok(user) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Update,
+				"user",
+				user.id,
+				before,
+				audit_snapshot(&user),
+			)
+			.await;
+			common_response(StatusCode::OK, "User updated successfully")
+		}
 		Err(err) => {
 			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
 		}
@@ -538,6 +801,7 @@ pub async fn mutation_update_user(
 pub async fn mutation_set_active_inactive_user(
 	id: String,
 	Json(update_data): Json<UsersActiveInactiveRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db = get_db().await;
 
@@ -548,9 +812,8 @@ pub async fn mutation_set_active_inactive_user(
 		}
 	};
 
-	let user = match UsersEntity::find()
+	let user = match UsersEntity::find_active()
 		.filter(UsersColumn::Id.eq(user_id))
-		.filter(UsersColumn::IsDeleted.eq(false))
 		.one(&db)
 		.await
 	{
@@ -564,21 +827,53 @@ pub async fn mutation_set_active_inactive_user(
 		}
 	};
 
+	let before = audit_snapshot(&user);
 	let mut active_model: UsersActiveModel = user.into();
 	active_model.is_active = Set(update_data.is_active);
 	active_model.updated_at = Set(Some(Utc::now()));
 
-	match active_model.update(&db).await {
-		Ok(_) => common_response(StatusCode::OK, "User updated successfully"),
+	let user = match active_model.update(&db).await {
+		Ok(user) => user,
 		Err(err) => {
-			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let action = if update_data.is_active {
+		AuditAction::Activate
+	} else {
+		AuditAction::Deactivate
+	};
+	record_audit_log(
+		&db,
+		audit,
+		action,
+		"user",
+		user.id,
+		before,
+		audit_snapshot(&user),
+	)
+	.await;
+
+	if update_data.is_active {
+		if let Err(err) = mark_referral_activated(&db, user_id).await {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			);
 		}
 	}
+
+	common_response(StatusCode::OK, "User updated successfully")
 }
 
 pub async fn mutation_update_user_me(
 	headers: HeaderMap,
 	Json(update_data): Json<UsersUpdateRequestDto>,
+	audit: &AuditContext,
 ) -> Response {
 	let db = get_db().await;
 
@@ -605,7 +900,7 @@ pub async fn mutation_update_user_me(
 		}
 	};
 
-	let token_data = match decode_access_token(&token) {
+	let token_data = match decode_access_token(token) {
 		Ok(data) => data,
 		Err(err) => {
 			return common_response(StatusCode::UNAUTHORIZED, &err.to_string())
@@ -614,9 +909,8 @@ pub async fn mutation_update_user_me(
 
 	let email = token_data.claims.email;
 
-	let user = match UsersEntity::find()
+	let user = match UsersEntity::find_active()
 		.filter(UsersColumn::Email.eq(&email))
-		.filter(UsersColumn::IsDeleted.eq(false))
 		.one(&db)
 		.await
 	{
@@ -630,6 +924,7 @@ pub async fn mutation_update_user_me(
 		}
 	};
 
+	let before = audit_snapshot(&user);
 	let mut active_model: UsersActiveModel = user.into();
 
 	if let Some(email) = &update_data.email {
@@ -695,8 +990,19 @@ pub async fn mutation_update_user_me(
 	active_model.updated_at = Set(Some(Utc::now()));
 
 	match active_model.update(&db).await {
-		Ok(_) => {
-			match UsersEntity::find()
+		Ok(updated) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Update,
+				"user",
+				updated.id,
+				before,
+				audit_snapshot(&updated),
+			)
+			.await;
+
+			match UsersEntity::find_active()
 				.filter(UsersColumn::Email.eq(&email))
 				.find_also_related(RolesEntity)
 				.one(&db)
@@ -726,6 +1032,7 @@ pub async fn mutation_update_user_me(
 					let role_dto = RolesItemDto {
 						id: role.id.to_string(),
 						name: role.name,
+						require_two_factor: role.require_two_factor,
 						permissions: role_permissions,
 						created_at: role.created_at.map(|dt| dt.to_string()),
 						updated_at: role.updated_at.map(|dt| dt.to_string()),
@@ -791,3 +1098,130 @@ pub async fn mutation_update_user_me(
 		}
 	}
 }
+
+pub async fn query_get_user_progress(headers: HeaderMap) -> Response {
+	let db = get_db().await;
+
+	let email = match email_from_headers(&headers) {
+		Ok(email) => email,
+		Err(response) => return response,
+	};
+
+	let user = match UsersEntity::find_active()
+		.filter(UsersColumn::Email.eq(email))
+		.one(&db)
+		.await
+	{
+		Ok(Some(user)) => user,
+		Ok(None) => return common_response(StatusCode::NOT_FOUND, "User not found"),
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let statement = |sql: &str, values: Vec<sea_orm::Value>| {
+		Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+	};
+
+	let history = match UsersProgressHistoryItemDto::find_by_statement(statement(
+		&format!("{}{}", ANSWER_SCORES_CTE, PROGRESS_HISTORY_SQL),
+		vec![user.id.into()],
+	))
+	.all(&db)
+	.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let categories = match UsersProgressCategoryDto::find_by_statement(statement(
+		&format!("{}{}", ANSWER_SCORES_CTE, PROGRESS_CATEGORIES_SQL),
+		vec![user.id.into()],
+	))
+	.all(&db)
+	.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let percentile = match ProgressPercentileRow::find_by_statement(statement(
+		&format!("{}{}", ANSWER_SCORES_CTE, PROGRESS_PERCENTILE_SQL),
+		vec![user.id.into(), user.student_type.clone().into()],
+	))
+	.one(&db)
+	.await
+	{
+		Ok(row) => row,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	let topics = match UsersProgressTopicDto::find_by_statement(statement(
+		PROGRESS_TOPICS_SQL,
+		vec![user.id.into()],
+	))
+	.all(&db)
+	.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			return common_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				&err.to_string(),
+			)
+		}
+	};
+
+	// Topics are ordered best first; a topic is never both strong and weak.
+	let strongest_count = topics.len().min(PROGRESS_TOPIC_LIMIT);
+	let strongest_topics = topics[..strongest_count].to_vec();
+	let weakest_topics = topics[strongest_count..]
+		.iter()
+		.rev()
+		.take(PROGRESS_TOPIC_LIMIT)
+		.cloned()
+		.collect();
+
+	let (average_score, peer_count, percentile) = match percentile {
+		Some(row) => (
+			row.average_score,
+			row.peer_count,
+			row.average_score.and(row.percentile),
+		),
+		None => (None, 0, None),
+	};
+
+	let response = ResponseSuccessDto {
+		data: UsersProgressDto {
+			student_type: user.student_type,
+			total_attempts: history.len() as u64,
+			average_score,
+			percentile,
+			peer_count,
+			history,
+			categories,
+			strongest_topics,
+			weakest_topics,
+		},
+	};
+
+	success_response(response)
+}
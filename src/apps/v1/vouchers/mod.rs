@@ -0,0 +1,29 @@
+use axum::{
+	routing::{delete, get, post, put},
+	Router,
+};
+
+use crate::{RateLimitLayer, RateLimitPolicy};
+
+pub mod vouchers_controller;
+pub mod vouchers_dto;
+pub mod vouchers_repository;
+
+pub use vouchers_dto::*;
+pub use vouchers_repository::*;
+
+pub fn vouchers_router() -> Router {
+	Router::new()
+		.route("/", get(vouchers_controller::get_vouchers))
+		.route(
+			"/validate",
+			post(vouchers_controller::post_validate_voucher),
+		)
+		.route("/create", post(vouchers_controller::post_create_voucher))
+		.route("/detail/{id}", get(vouchers_controller::get_detail_voucher))
+		.route("/update/{id}", put(vouchers_controller::put_update_voucher))
+		.route("/delete/{id}", delete(vouchers_controller::delete_voucher))
+		.layer(RateLimitLayer::new(RateLimitPolicy::per_minute(
+			"vouchers", 60,
+		)))
+}
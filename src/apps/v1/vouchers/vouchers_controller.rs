@@ -0,0 +1,169 @@
+use axum::{
+	extract::{Path, Query},
+	http::HeaderMap,
+	response::IntoResponse,
+	Json,
+};
+
+use crate::{
+	permissions::{permissions_middleware, PermissionsEnum},
+	utils::dto::{MessageResponseDto, MetaRequestDto},
+	AuditContext, ResponseSuccessDto, ResponseSuccessListDto,
+};
+
+use super::{
+	mutation_create_voucher, mutation_delete_voucher, mutation_update_voucher,
+	query_get_voucher_by_id, query_get_vouchers, query_validate_voucher,
+	VouchersItemDto, VouchersItemListDto, VouchersQuoteDto,
+	VouchersRequestCreateDto, VouchersRequestUpdateDto, VouchersValidateRequestDto,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/vouchers",
+    params(MetaRequestDto),
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "List Vouchers", body = ResponseSuccessListDto<VouchersItemListDto>),
+        (status = 400, description = "Invalid Vouchers data", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn get_vouchers(
+	headers: HeaderMap,
+	Query(params): Query<MetaRequestDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadListVouchers])
+		.await
+	{
+		Ok(_) => query_get_vouchers(params).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/vouchers/validate",
+    request_body = VouchersValidateRequestDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Price with the code applied", body = ResponseSuccessDto<VouchersQuoteDto>),
+        (status = 400, description = "Code does not apply", body = MessageResponseDto),
+        (status = 404, description = "Voucher not found", body = MessageResponseDto),
+        (status = 409, description = "Usage limit reached", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn post_validate_voucher(
+	headers: HeaderMap,
+	Json(payload): Json<VouchersValidateRequestDto>,
+) -> impl IntoResponse {
+	query_validate_voucher(headers, Json(payload)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/vouchers/detail/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Detail Voucher", body = ResponseSuccessDto<VouchersItemDto>),
+        (status = 404, description = "Voucher not found", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn get_detail_voucher(
+	headers: HeaderMap,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::ReadDetailVouchers])
+		.await
+	{
+		Ok(_) => query_get_voucher_by_id(id).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/vouchers/create",
+    request_body = VouchersRequestCreateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 201, description = "Voucher Created", body = MessageResponseDto),
+        (status = 400, description = "Invalid Voucher data", body = MessageResponseDto),
+        (status = 409, description = "Voucher code already exists", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn post_create_voucher(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Json(payload): Json<VouchersRequestCreateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::CreateVouchers])
+		.await
+	{
+		Ok(_) => mutation_create_voucher(Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/vouchers/update/{id}",
+    request_body = VouchersRequestUpdateDto,
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Voucher Updated", body = MessageResponseDto),
+        (status = 400, description = "Invalid Voucher data", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn put_update_voucher(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+	Json(payload): Json<VouchersRequestUpdateDto>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::UpdateVouchers])
+		.await
+	{
+		Ok(_) => mutation_update_voucher(id, Json(payload), &audit).await,
+		Err(response) => response,
+	}
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/vouchers/delete/{id}",
+    security(
+        ("Bearer" = [])
+    ),
+    responses(
+        (status = 200, description = "Voucher Deleted", body = MessageResponseDto),
+        (status = 409, description = "Voucher has been redeemed", body = MessageResponseDto)
+    ),
+    tag = "Vouchers"
+)]
+pub async fn delete_voucher(
+	headers: HeaderMap,
+	audit: AuditContext,
+	Path(id): Path<String>,
+) -> impl IntoResponse {
+	match permissions_middleware(&headers, vec![PermissionsEnum::DeleteVouchers])
+		.await
+	{
+		Ok(_) => mutation_delete_voucher(id, &audit).await,
+		Err(response) => response,
+	}
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// `discount_type` is `percentage` (`discount_value` 1-100, optionally capped by
+/// `max_discount`) or `fixed` (`discount_value` in rupiah). Dates use
+/// `YYYY-MM-DDTHH:MM`. An empty `package_ids` applies to every package.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersRequestCreateDto {
+	pub code: String,
+	pub description: Option<String>,
+	pub discount_type: String,
+	pub discount_value: i64,
+	pub max_discount: Option<i64>,
+	pub max_uses: Option<i32>,
+	pub max_uses_per_user: Option<i32>,
+	pub starts_at: Option<String>,
+	pub ends_at: Option<String>,
+	pub is_active: Option<bool>,
+	pub package_ids: Vec<String>,
+}
+
+/// `package_ids` replaces the allowed packages when present.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersRequestUpdateDto {
+	pub description: Option<String>,
+	pub discount_type: Option<String>,
+	pub discount_value: Option<i64>,
+	pub max_discount: Option<i64>,
+	pub max_uses: Option<i32>,
+	pub max_uses_per_user: Option<i32>,
+	pub starts_at: Option<String>,
+	pub ends_at: Option<String>,
+	pub is_active: Option<bool>,
+	pub package_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersPackageDto {
+	pub id: String,
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersItemListDto {
+	pub id: String,
+	pub code: String,
+	pub description: Option<String>,
+	pub discount_type: String,
+	pub discount_value: i64,
+	pub max_discount: Option<i64>,
+	pub max_uses: Option<i32>,
+	pub max_uses_per_user: Option<i32>,
+	/// Paid orders plus pending orders that can still be paid.
+	pub used_count: u64,
+	pub starts_at: Option<String>,
+	pub ends_at: Option<String>,
+	pub is_active: bool,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersItemDto {
+	pub id: String,
+	pub code: String,
+	pub description: Option<String>,
+	pub discount_type: String,
+	pub discount_value: i64,
+	pub max_discount: Option<i64>,
+	pub max_uses: Option<i32>,
+	pub max_uses_per_user: Option<i32>,
+	pub used_count: u64,
+	pub starts_at: Option<String>,
+	pub ends_at: Option<String>,
+	pub is_active: bool,
+	pub packages: Vec<VouchersPackageDto>,
+	pub created_at: Option<String>,
+	pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersValidateRequestDto {
+	pub code: String,
+	pub package_id: String,
+}
+
+/// What the caller would pay for the package with the code applied.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct VouchersQuoteDto {
+	pub code: String,
+	/// `voucher` or `referral`.
+	pub source: String,
+	pub package_id: String,
+	pub price: i64,
+	pub discount_amount: i64,
+	pub final_amount: i64,
+}
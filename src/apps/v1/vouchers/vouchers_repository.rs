@@ -0,0 +1,844 @@
+use axum::{http::StatusCode, response::Response, Json};
+use chrono::{DateTime, Utc};
+use hyper::HeaderMap;
+use sea_orm::{
+	prelude::Expr, sea_query::extension::postgres::PgExpr, ActiveModelTrait,
+	ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+	JoinType, PaginatorTrait, QueryFilter, QuerySelect, RelationTrait, Set,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+	audit_snapshot, common_response, get_db,
+	orders::{find_caller, ORDER_STATUS_PAID, ORDER_STATUS_PENDING},
+	record_audit_log,
+	schemas::{
+		OrdersColumn, OrdersModel, PackagesColumn, PackagesEntity, PackagesModel,
+		UsersColumn, UsersEntity, UsersModel, VoucherPackagesActiveModel,
+		VoucherPackagesColumn, VoucherPackagesEntity, VoucherRedemptionsActiveModel,
+		VoucherRedemptionsColumn, VoucherRedemptionsEntity,
+		VoucherRedemptionsRelation, VouchersActiveModel, VouchersColumn,
+		VouchersEntity, VouchersModel,
+	},
+	success_response, success_response_list, AuditAction, AuditContext, Config,
+	FieldKind, ListParams, ListSpec, MetaRequestDto, ResponseSuccessDto,
+	ResponseSuccessListDto, SoftDelete,
+};
+
+use super::{
+	VouchersItemDto, VouchersItemListDto, VouchersPackageDto, VouchersQuoteDto,
+	VouchersRequestCreateDto, VouchersRequestUpdateDto, VouchersValidateRequestDto,
+};
+
+pub const VOUCHER_DISCOUNT_PERCENTAGE: &str = "percentage";
+pub const VOUCHER_DISCOUNT_FIXED: &str = "fixed";
+
+pub const VOUCHER_SOURCE_VOUCHER: &str = "voucher";
+pub const VOUCHER_SOURCE_REFERRAL: &str = "referral";
+
+const VOUCHER_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// A discount code resolved against one package for one buyer.
+#[derive(Clone, Debug)]
+pub struct VoucherQuote {
+	pub code: String,
+	pub voucher_id: Option<Uuid>,
+	pub source: &'static str,
+	pub discount: i64,
+}
+
+pub fn normalize_voucher_code(code: &str) -> String {
+	code.trim().to_uppercase()
+}
+
+/// Orders whose redemption still counts toward a code's limits: paid ones and
+/// pending ones that can still be paid.
+fn live_order_condition() -> Condition {
+	Condition::any()
+		.add(OrdersColumn::Status.eq(ORDER_STATUS_PAID))
+		.add(
+			Condition::all()
+				.add(OrdersColumn::Status.eq(ORDER_STATUS_PENDING))
+				.add(OrdersColumn::ExpiresAt.gt(Utc::now())),
+		)
+}
+
+async fn count_live_redemptions<C: ConnectionTrait>(
+	db: &C,
+	filter: Condition,
+) -> Result<u64, DbErr> {
+	VoucherRedemptionsEntity::find()
+		.join(JoinType::InnerJoin, VoucherRedemptionsRelation::Order.def())
+		.filter(filter)
+		.filter(live_order_condition())
+		.count(db)
+		.await
+}
+
+async fn voucher_usage_counts(
+	db: &DatabaseConnection,
+	voucher_ids: Vec<Uuid>,
+) -> Result<Vec<(Uuid, i64)>, DbErr> {
+	VoucherRedemptionsEntity::find()
+		.select_only()
+		.column(VoucherRedemptionsColumn::VoucherId)
+		.column_as(VoucherRedemptionsColumn::Id.count(), "uses")
+		.join(JoinType::InnerJoin, VoucherRedemptionsRelation::Order.def())
+		.filter(VoucherRedemptionsColumn::VoucherId.is_in(voucher_ids))
+		.filter(live_order_condition())
+		.group_by(VoucherRedemptionsColumn::VoucherId)
+		.into_tuple::<(Uuid, i64)>()
+		.all(db)
+		.await
+}
+
+fn voucher_discount(voucher: &VouchersModel, price: i64) -> i64 {
+	let discount = match voucher.discount_type.as_str() {
+		VOUCHER_DISCOUNT_PERCENTAGE => {
+			let discount = price * voucher.discount_value / 100;
+			voucher
+				.max_discount
+				.map_or(discount, |max_discount| discount.min(max_discount))
+		}
+		_ => voucher.discount_value,
+	};
+
+	discount.clamp(0, price)
+}
+
+fn bad_request(message: &str) -> Response {
+	common_response(StatusCode::BAD_REQUEST, message)
+}
+
+fn internal_error(err: DbErr) -> Response {
+	common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+}
+
+async fn quote_voucher<C: ConnectionTrait>(
+	db: &C,
+	user: &UsersModel,
+	package: &PackagesModel,
+	voucher: VouchersModel,
+) -> Result<VoucherQuote, Response> {
+	let now = Utc::now();
+
+	if !voucher.is_active {
+		return Err(bad_request("Voucher is not active"));
+	}
+	if voucher.starts_at.is_some_and(|starts_at| starts_at > now) {
+		return Err(bad_request("Voucher is not valid yet"));
+	}
+	if voucher.ends_at.is_some_and(|ends_at| ends_at <= now) {
+		return Err(bad_request("Voucher has expired"));
+	}
+
+	let package_ids = VoucherPackagesEntity::find()
+		.select_only()
+		.column(VoucherPackagesColumn::PackageId)
+		.filter(VoucherPackagesColumn::VoucherId.eq(voucher.id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+		.map_err(internal_error)?;
+
+	if !package_ids.is_empty() && !package_ids.contains(&package.id) {
+		return Err(bad_request("Voucher does not apply to this package"));
+	}
+
+	if let Some(max_uses) = voucher.max_uses {
+		let uses = count_live_redemptions(
+			db,
+			Condition::all().add(VoucherRedemptionsColumn::VoucherId.eq(voucher.id)),
+		)
+		.await
+		.map_err(internal_error)?;
+
+		if uses >= max_uses as u64 {
+			return Err(common_response(
+				StatusCode::CONFLICT,
+				"Voucher usage limit reached",
+			));
+		}
+	}
+
+	if let Some(max_uses_per_user) = voucher.max_uses_per_user {
+		let uses = count_live_redemptions(
+			db,
+			Condition::all()
+				.add(VoucherRedemptionsColumn::VoucherId.eq(voucher.id))
+				.add(VoucherRedemptionsColumn::UserId.eq(user.id)),
+		)
+		.await
+		.map_err(internal_error)?;
+
+		if uses >= max_uses_per_user as u64 {
+			return Err(common_response(
+				StatusCode::CONFLICT,
+				"You have already used this voucher",
+			));
+		}
+	}
+
+	Ok(VoucherQuote {
+		discount: voucher_discount(&voucher, package.price),
+		code: voucher.code,
+		voucher_id: Some(voucher.id),
+		source: VOUCHER_SOURCE_VOUCHER,
+	})
+}
+
+/// Another student's referral code takes `REFERRAL_DISCOUNT_PERCENT` off,
+/// once per buyer. Disabled while the percentage is zero. The buyer's row is
+/// locked, so inside the order transaction two concurrent orders can't both
+/// take the discount.
+async fn quote_referral_code<C: ConnectionTrait>(
+	db: &C,
+	user: &UsersModel,
+	package: &PackagesModel,
+	code: &str,
+) -> Result<VoucherQuote, Response> {
+	let percent = Config::new().referral_discount_percent.min(100);
+	if percent <= 0 {
+		return Err(common_response(StatusCode::NOT_FOUND, "Voucher not found"));
+	}
+
+	let referrer = UsersEntity::find_active()
+		.filter(UsersColumn::ReferralCode.eq(code))
+		.one(db)
+		.await
+		.map_err(internal_error)?
+		.ok_or_else(|| {
+			common_response(StatusCode::NOT_FOUND, "Voucher not found")
+		})?;
+
+	if referrer.id == user.id {
+		return Err(bad_request("You cannot use your own referral code"));
+	}
+
+	UsersEntity::find_by_id(user.id)
+		.lock_exclusive()
+		.one(db)
+		.await
+		.map_err(internal_error)?;
+
+	let uses = count_live_redemptions(
+		db,
+		Condition::all()
+			.add(VoucherRedemptionsColumn::VoucherId.is_null())
+			.add(VoucherRedemptionsColumn::UserId.eq(user.id)),
+	)
+	.await
+	.map_err(internal_error)?;
+
+	if uses > 0 {
+		return Err(common_response(
+			StatusCode::CONFLICT,
+			"You have already used a referral discount",
+		));
+	}
+
+	Ok(VoucherQuote {
+		code: code.to_string(),
+		voucher_id: None,
+		source: VOUCHER_SOURCE_REFERRAL,
+		discount: (package.price * percent / 100).clamp(0, package.price),
+	})
+}
+
+/// Resolves a voucher or referral code for `user` buying `package`. The
+/// voucher row is locked, so inside the order transaction two buyers can't
+/// both take its last use.
+pub async fn quote_discount_code<C: ConnectionTrait>(
+	db: &C,
+	user: &UsersModel,
+	package: &PackagesModel,
+	code: &str,
+) -> Result<VoucherQuote, Response> {
+	let code = normalize_voucher_code(code);
+	if code.is_empty() {
+		return Err(bad_request("Voucher code is required"));
+	}
+
+	let voucher = VouchersEntity::find()
+		.filter(VouchersColumn::Code.eq(code.clone()))
+		.lock_exclusive()
+		.one(db)
+		.await
+		.map_err(internal_error)?;
+
+	match voucher {
+		Some(voucher) => quote_voucher(db, user, package, voucher).await,
+		None => quote_referral_code(db, user, package, &code).await,
+	}
+}
+
+pub async fn record_voucher_redemption<C: ConnectionTrait>(
+	db: &C,
+	quote: &VoucherQuote,
+	order: &OrdersModel,
+) -> Result<(), DbErr> {
+	VoucherRedemptionsActiveModel {
+		id: Set(Uuid::new_v4()),
+		voucher_id: Set(quote.voucher_id),
+		order_id: Set(order.id),
+		user_id: Set(order.user_id),
+		code: Set(quote.code.clone()),
+		discount: Set(quote.discount),
+		created_at: Set(Utc::now()),
+	}
+	.insert(db)
+	.await?;
+
+	Ok(())
+}
+
+fn parse_voucher_date(value: &str) -> Result<DateTime<Utc>, Response> {
+	chrono::NaiveDateTime::parse_from_str(value, VOUCHER_DATE_FORMAT)
+		.map(|dt| dt.and_utc())
+		.map_err(|_| bad_request("Invalid date format, expected YYYY-MM-DDTHH:MM"))
+}
+
+fn parse_optional_voucher_date(
+	value: &Option<String>,
+) -> Result<Option<DateTime<Utc>>, Response> {
+	value.as_deref().map(parse_voucher_date).transpose()
+}
+
+fn validate_voucher_terms(voucher: &VouchersModel) -> Result<(), Response> {
+	match voucher.discount_type.as_str() {
+		VOUCHER_DISCOUNT_PERCENTAGE
+			if !(1..=100).contains(&voucher.discount_value) =>
+		{
+			return Err(bad_request("Percentage discount must be between 1 and 100"))
+		}
+		VOUCHER_DISCOUNT_FIXED if voucher.discount_value <= 0 => {
+			return Err(bad_request("Fixed discount must be greater than zero"))
+		}
+		VOUCHER_DISCOUNT_PERCENTAGE | VOUCHER_DISCOUNT_FIXED => {}
+		_ => {
+			return Err(bad_request(
+				"discount_type must be one of percentage or fixed",
+			))
+		}
+	}
+
+	if voucher
+		.max_discount
+		.is_some_and(|max_discount| max_discount <= 0)
+	{
+		return Err(bad_request("max_discount must be greater than zero"));
+	}
+
+	if voucher.max_uses.is_some_and(|max_uses| max_uses < 1)
+		|| voucher
+			.max_uses_per_user
+			.is_some_and(|max_uses| max_uses < 1)
+	{
+		return Err(bad_request("Usage limits must be at least 1"));
+	}
+
+	if let (Some(starts_at), Some(ends_at)) = (voucher.starts_at, voucher.ends_at) {
+		if starts_at >= ends_at {
+			return Err(bad_request("starts_at must be before ends_at"));
+		}
+	}
+
+	Ok(())
+}
+
+/// Parses and de-duplicates package ids, failing when any package doesn't
+/// exist.
+async fn resolve_package_ids(
+	db: &DatabaseConnection,
+	package_ids: &[String],
+) -> Result<Vec<Uuid>, Response> {
+	let mut ids: Vec<Uuid> = Vec::with_capacity(package_ids.len());
+	for package_id in package_ids {
+		match Uuid::parse_str(package_id) {
+			Ok(package_id) if !ids.contains(&package_id) => ids.push(package_id),
+			Ok(_) => {}
+			Err(_) => return Err(bad_request("Invalid package ID format")),
+		}
+	}
+
+	match PackagesEntity::find()
+		.filter(PackagesColumn::Id.is_in(ids.clone()))
+		.count(db)
+		.await
+	{
+		Ok(found) if found == ids.len() as u64 => Ok(ids),
+		Ok(_) => Err(common_response(
+			StatusCode::NOT_FOUND,
+			"One or more packages not found",
+		)),
+		Err(err) => Err(internal_error(err)),
+	}
+}
+
+async fn replace_voucher_packages(
+	db: &DatabaseConnection,
+	voucher_id: Uuid,
+	package_ids: &[Uuid],
+) -> Result<(), DbErr> {
+	VoucherPackagesEntity::delete_many()
+		.filter(VoucherPackagesColumn::VoucherId.eq(voucher_id))
+		.exec(db)
+		.await?;
+
+	for package_id in package_ids {
+		VoucherPackagesActiveModel {
+			id: Set(Uuid::new_v4()),
+			voucher_id: Set(voucher_id),
+			package_id: Set(*package_id),
+		}
+		.insert(db)
+		.await?;
+	}
+
+	Ok(())
+}
+
+async fn find_voucher(
+	db: &DatabaseConnection,
+	id: &str,
+) -> Result<VouchersModel, Response> {
+	let voucher_id =
+		Uuid::parse_str(id).map_err(|_| bad_request("Invalid voucher ID format"))?;
+
+	match VouchersEntity::find_by_id(voucher_id).one(db).await {
+		Ok(Some(voucher)) => Ok(voucher),
+		Ok(None) => Err(common_response(StatusCode::NOT_FOUND, "Voucher not found")),
+		Err(err) => Err(internal_error(err)),
+	}
+}
+
+async fn voucher_item_dto(
+	db: &DatabaseConnection,
+	voucher: VouchersModel,
+) -> Result<VouchersItemDto, DbErr> {
+	let packages = VoucherPackagesEntity::find()
+		.filter(VoucherPackagesColumn::VoucherId.eq(voucher.id))
+		.find_also_related(PackagesEntity)
+		.all(db)
+		.await?;
+
+	let used_count = voucher_usage_counts(db, vec![voucher.id])
+		.await?
+		.first()
+		.map_or(0, |(_, uses)| *uses as u64);
+
+	Ok(VouchersItemDto {
+		id: voucher.id.to_string(),
+		code: voucher.code,
+		description: voucher.description,
+		discount_type: voucher.discount_type,
+		discount_value: voucher.discount_value,
+		max_discount: voucher.max_discount,
+		max_uses: voucher.max_uses,
+		max_uses_per_user: voucher.max_uses_per_user,
+		used_count,
+		starts_at: voucher.starts_at.map(|dt| dt.to_string()),
+		ends_at: voucher.ends_at.map(|dt| dt.to_string()),
+		is_active: voucher.is_active,
+		packages: packages
+			.into_iter()
+			.filter_map(|(_, package)| package)
+			.map(|package| VouchersPackageDto {
+				id: package.id.to_string(),
+				name: package.name,
+			})
+			.collect(),
+		created_at: voucher.created_at.map(|dt| dt.to_string()),
+		updated_at: voucher.updated_at.map(|dt| dt.to_string()),
+	})
+}
+
+const VOUCHERS_LIST: ListSpec<VouchersEntity> = ListSpec {
+	filters: &[
+		(
+			"discount_type",
+			VouchersColumn::DiscountType,
+			FieldKind::Text,
+		),
+		("is_active", VouchersColumn::IsActive, FieldKind::Bool),
+		("starts_at", VouchersColumn::StartsAt, FieldKind::DateTime),
+		("ends_at", VouchersColumn::EndsAt, FieldKind::DateTime),
+		("created_at", VouchersColumn::CreatedAt, FieldKind::DateTime),
+	],
+	sorts: &[
+		("code", VouchersColumn::Code),
+		("ends_at", VouchersColumn::EndsAt),
+		("created_at", VouchersColumn::CreatedAt),
+		("updated_at", VouchersColumn::UpdatedAt),
+	],
+	default_sort: "created_at",
+	id: VouchersColumn::Id,
+};
+
+pub async fn query_get_vouchers(params: MetaRequestDto) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let params = match ListParams::parse(params) {
+		Ok(params) => params,
+		Err(response) => return response,
+	};
+
+	let mut query = VouchersEntity::find();
+
+	if !params.search.is_empty() {
+		query = query.filter(
+			Expr::col(VouchersColumn::Code).ilike(format!("%{}%", params.search)),
+		);
+	}
+
+	let (vouchers, meta) = match params.fetch(&db, query, &VOUCHERS_LIST, None).await
+	{
+		Ok(page) => page,
+		Err(response) => return response,
+	};
+
+	let voucher_ids: Vec<Uuid> = vouchers.iter().map(|voucher| voucher.id).collect();
+	let usage = match voucher_usage_counts(&db, voucher_ids).await {
+		Ok(usage) => usage,
+		Err(err) => return internal_error(err),
+	};
+
+	let data: Vec<VouchersItemListDto> = vouchers
+		.into_iter()
+		.map(|voucher| VouchersItemListDto {
+			id: voucher.id.to_string(),
+			used_count: usage
+				.iter()
+				.find(|(voucher_id, _)| *voucher_id == voucher.id)
+				.map_or(0, |(_, uses)| *uses as u64),
+			code: voucher.code,
+			description: voucher.description,
+			discount_type: voucher.discount_type,
+			discount_value: voucher.discount_value,
+			max_discount: voucher.max_discount,
+			max_uses: voucher.max_uses,
+			max_uses_per_user: voucher.max_uses_per_user,
+			starts_at: voucher.starts_at.map(|dt| dt.to_string()),
+			ends_at: voucher.ends_at.map(|dt| dt.to_string()),
+			is_active: voucher.is_active,
+			created_at: voucher.created_at.map(|dt| dt.to_string()),
+			updated_at: voucher.updated_at.map(|dt| dt.to_string()),
+		})
+		.collect();
+
+	success_response_list(ResponseSuccessListDto {
+		data,
+		meta: Some(meta),
+	})
+}
+
+pub async fn query_get_voucher_by_id(id: String) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let voucher = match find_voucher(&db, &id).await {
+		Ok(voucher) => voucher,
+		Err(response) => return response,
+	};
+
+	match voucher_item_dto(&db, voucher).await {
+		Ok(data) => success_response(ResponseSuccessDto { data }),
+		Err(err) => internal_error(err),
+	}
+}
+
+/// Prices a package with a code for the caller without redeeming it.
+pub async fn query_validate_voucher(
+	headers: HeaderMap,
+	payload: Json<VouchersValidateRequestDto>,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let user = match find_caller(&db, &headers).await {
+		Ok(user) => user,
+		Err(response) => return response,
+	};
+
+	let package = match PackagesEntity::find_by_id(
+		Uuid::parse_str(&payload.package_id).unwrap_or_default(),
+	)
+	.one(&db)
+	.await
+	{
+		Ok(Some(package)) if package.is_active => package,
+		Ok(_) => return common_response(StatusCode::NOT_FOUND, "Package not found"),
+		Err(err) => return internal_error(err),
+	};
+
+	match quote_discount_code(&db, &user, &package, &payload.code).await {
+		Ok(quote) => success_response(ResponseSuccessDto {
+			data: VouchersQuoteDto {
+				code: quote.code,
+				source: quote.source.to_string(),
+				package_id: package.id.to_string(),
+				price: package.price,
+				discount_amount: quote.discount,
+				final_amount: package.price - quote.discount,
+			},
+		}),
+		Err(response) => response,
+	}
+}
+
+pub async fn mutation_create_voucher(
+	payload: Json<VouchersRequestCreateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let code = normalize_voucher_code(&payload.code);
+	if code.is_empty() || code.contains(char::is_whitespace) {
+		return bad_request("Voucher code must be a single word");
+	}
+
+	// Codes share one namespace with referral codes, which are looked up
+	// after vouchers.
+	match VouchersEntity::find()
+		.filter(VouchersColumn::Code.eq(code.clone()))
+		.count(&db)
+		.await
+	{
+		Ok(0) => {}
+		Ok(_) => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Voucher code already exists",
+			)
+		}
+		Err(err) => return internal_error(err),
+	}
+
+	match UsersEntity::find()
+		.filter(UsersColumn::ReferralCode.eq(code.clone()))
+		.count(&db)
+		.await
+	{
+		Ok(0) => {}
+		Ok(_) => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Voucher code is already used as a referral code",
+			)
+		}
+		Err(err) => return internal_error(err),
+	}
+
+	let (starts_at, ends_at) = match (
+		parse_optional_voucher_date(&payload.starts_at),
+		parse_optional_voucher_date(&payload.ends_at),
+	) {
+		(Ok(starts_at), Ok(ends_at)) => (starts_at, ends_at),
+		(Err(response), _) | (_, Err(response)) => return response,
+	};
+
+	let voucher = VouchersModel {
+		id: Uuid::new_v4(),
+		code,
+		description: payload.description.clone(),
+		discount_type: payload.discount_type.trim().to_lowercase(),
+		discount_value: payload.discount_value,
+		max_discount: payload.max_discount,
+		max_uses: payload.max_uses,
+		max_uses_per_user: payload.max_uses_per_user,
+		starts_at,
+		ends_at,
+		is_active: payload.is_active.unwrap_or(true),
+		created_at: Some(Utc::now()),
+		updated_at: Some(Utc::now()),
+	};
+
+	if let Err(response) = validate_voucher_terms(&voucher) {
+		return response;
+	}
+
+	let package_ids = match resolve_package_ids(&db, &payload.package_ids).await {
+		Ok(ids) => ids,
+		Err(response) => return response,
+	};
+
+	let active_model: VouchersActiveModel = voucher.into();
+	let voucher = match active_model.reset_all().insert(&db).await {
+		Ok(voucher) => voucher,
+		Err(err) => return internal_error(err),
+	};
+
+	if let Err(err) = replace_voucher_packages(&db, voucher.id, &package_ids).await {
+		return internal_error(err);
+	}
+
+	let after = voucher_audit_snapshot(&db, &voucher).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Create,
+		"voucher",
+		voucher.id,
+		None,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::CREATED, "Voucher created successfully")
+}
+
+pub async fn mutation_update_voucher(
+	id: String,
+	payload: Json<VouchersRequestUpdateDto>,
+	audit: &AuditContext,
+) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let voucher = match find_voucher(&db, &id).await {
+		Ok(voucher) => voucher,
+		Err(response) => return response,
+	};
+
+	let (starts_at, ends_at) = match (
+		parse_optional_voucher_date(&payload.starts_at),
+		parse_optional_voucher_date(&payload.ends_at),
+	) {
+		(Ok(starts_at), Ok(ends_at)) => (starts_at, ends_at),
+		(Err(response), _) | (_, Err(response)) => return response,
+	};
+
+	let package_ids = match &payload.package_ids {
+		Some(package_ids) => match resolve_package_ids(&db, package_ids).await {
+			Ok(ids) => Some(ids),
+			Err(response) => return response,
+		},
+		None => None,
+	};
+
+	let before = voucher_audit_snapshot(&db, &voucher).await;
+
+	// The code is fixed once created: redemptions and orders refer to it.
+	let updated = VouchersModel {
+		description: payload.description.clone().or(voucher.description.clone()),
+		discount_type: payload
+			.discount_type
+			.as_deref()
+			.map(|discount_type| discount_type.trim().to_lowercase())
+			.unwrap_or(voucher.discount_type.clone()),
+		discount_value: payload.discount_value.unwrap_or(voucher.discount_value),
+		max_discount: payload.max_discount.or(voucher.max_discount),
+		max_uses: payload.max_uses.or(voucher.max_uses),
+		max_uses_per_user: payload.max_uses_per_user.or(voucher.max_uses_per_user),
+		starts_at: starts_at.or(voucher.starts_at),
+		ends_at: ends_at.or(voucher.ends_at),
+		is_active: payload.is_active.unwrap_or(voucher.is_active),
+		updated_at: Some(Utc::now()),
+		..voucher.clone()
+	};
+
+	if let Err(response) = validate_voucher_terms(&updated) {
+		return response;
+	}
+
+	let mut active_model: VouchersActiveModel = voucher.into();
+	active_model.description = Set(updated.description);
+	active_model.discount_type = Set(updated.discount_type);
+	active_model.discount_value = Set(updated.discount_value);
+	active_model.max_discount = Set(updated.max_discount);
+	active_model.max_uses = Set(updated.max_uses);
+	active_model.max_uses_per_user = Set(updated.max_uses_per_user);
+	active_model.starts_at = Set(updated.starts_at);
+	active_model.ends_at = Set(updated.ends_at);
+	active_model.is_active = Set(updated.is_active);
+	active_model.updated_at = Set(updated.updated_at);
+
+	let updated_voucher = match active_model.update(&db).await {
+		Ok(voucher) => voucher,
+		Err(err) => return internal_error(err),
+	};
+
+	if let Some(package_ids) = package_ids {
+		if let Err(err) =
+			replace_voucher_packages(&db, updated_voucher.id, &package_ids).await
+		{
+			return internal_error(err);
+		}
+	}
+
+	let after = voucher_audit_snapshot(&db, &updated_voucher).await;
+	record_audit_log(
+		&db,
+		audit,
+		AuditAction::Update,
+		"voucher",
+		updated_voucher.id,
+		before,
+		after,
+	)
+	.await;
+
+	common_response(StatusCode::OK, "Voucher updated successfully")
+}
+
+pub async fn mutation_delete_voucher(id: String, audit: &AuditContext) -> Response {
+	let db: DatabaseConnection = get_db().await;
+
+	let voucher = match find_voucher(&db, &id).await {
+		Ok(voucher) => voucher,
+		Err(response) => return response,
+	};
+
+	// Redemptions are part of the order history; a redeemed voucher can only
+	// be deactivated.
+	match VoucherRedemptionsEntity::find()
+		.filter(VoucherRedemptionsColumn::VoucherId.eq(voucher.id))
+		.count(&db)
+		.await
+	{
+		Ok(0) => {}
+		Ok(_) => {
+			return common_response(
+				StatusCode::CONFLICT,
+				"Voucher has been redeemed; deactivate it instead",
+			)
+		}
+		Err(err) => return internal_error(err),
+	}
+
+	let before = voucher_audit_snapshot(&db, &voucher).await;
+
+	match VouchersEntity::delete_by_id(voucher.id).exec(&db).await {
+		Ok(_) => {
+			record_audit_log(
+				&db,
+				audit,
+				AuditAction::Delete,
+				"voucher",
+				voucher.id,
+				before,
+				None,
+			)
+			.await;
+			common_response(StatusCode::OK, "Voucher deleted successfully")
+		}
+		Err(err) => internal_error(err),
+	}
+}
+
+/// Voucher fields plus the packages it is restricted to.
+async fn voucher_audit_snapshot(
+	db: &DatabaseConnection,
+	voucher: &VouchersModel,
+) -> Option<Value> {
+	let package_ids: Vec<Uuid> = VoucherPackagesEntity::find()
+		.select_only()
+		.column(VoucherPackagesColumn::PackageId)
+		.filter(VoucherPackagesColumn::VoucherId.eq(voucher.id))
+		.into_tuple::<Uuid>()
+		.all(db)
+		.await
+		.unwrap_or_default();
+
+	let mut snapshot = audit_snapshot(voucher)?;
+	snapshot["package_ids"] = json!(package_ids);
+	Some(snapshot)
+}
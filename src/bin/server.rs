@@ -1,6 +1,8 @@
-use najm_course_api::{axum_init, routes};
+use najm_course_api::{axum_init, routes, spawn_job_worker, spawn_realtime_worker};
 
 #[tokio::main]
 async fn main() {
+	spawn_job_worker();
+	spawn_realtime_worker();
 	axum_init(routes).await;
 }
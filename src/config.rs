@@ -9,6 +9,12 @@ pub struct Config {
 	pub smtp_email: String,
 	pub smtp_password: String,
 	pub smtp_name: String,
+	pub smtp_host: String,
+	pub smtp_port: String,
+	pub smtp_tls: String,
+	pub mail_transport: String,
+	pub mail_file_dir: String,
+	pub mail_locale: String,
 	pub redis_hostname: String,
 	pub fe_url: String,
 	pub rust_env: String,
@@ -16,6 +22,27 @@ pub struct Config {
 	pub minio_bucket_name: String,
 	pub minio_access_key: String,
 	pub minio_secret_key: String,
+	pub rate_limit_backend: String,
+	pub trusted_proxies: String,
+	pub referral_reward_points: i32,
+	pub oidc_provider: String,
+	pub oidc_issuer_url: String,
+	pub oidc_client_id: String,
+	pub oidc_client_secret: String,
+	pub oidc_redirect_url: String,
+	pub soft_delete_retention_days: i64,
+	pub payment_provider: String,
+	pub payment_api_url: String,
+	pub payment_server_key: String,
+	pub payment_webhook_secret: String,
+	pub order_expiry_hours: i64,
+	pub referral_discount_percent: i64,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl Config {
@@ -35,6 +62,14 @@ impl Config {
 			smtp_password: env::var("SMTP_PASSWORD")
 				.unwrap_or("default_smtp_password".to_string()),
 			smtp_name: env::var("SMTP_NAME").unwrap_or("MyApp SMTP".to_string()),
+			smtp_host: env::var("SMTP_HOST")
+				.unwrap_or("smtp.gmail.com".to_string()),
+			smtp_port: env::var("SMTP_PORT").unwrap_or("587".to_string()),
+			smtp_tls: env::var("SMTP_TLS").unwrap_or("starttls".to_string()),
+			mail_transport: env::var("MAIL_TRANSPORT").unwrap_or("smtp".to_string()),
+			mail_file_dir: env::var("MAIL_FILE_DIR")
+				.unwrap_or("./storage/mails".to_string()),
+			mail_locale: env::var("MAIL_LOCALE").unwrap_or("id".to_string()),
 			redis_hostname: env::var("REDIS_HOSTNAME")
 				.unwrap_or("localhost".to_string()),
 			fe_url: env::var("FE_URL").unwrap_or("http://localhost".to_string()),
@@ -47,12 +82,45 @@ impl Config {
 				.unwrap_or("minio_access".to_string()),
 			minio_secret_key: env::var("MINIO_SECRET_KEY")
 				.unwrap_or("minio_secret".to_string()),
+			rate_limit_backend: env::var("RATE_LIMIT_BACKEND")
+				.unwrap_or("redis".to_string()),
+			trusted_proxies: env::var("TRUSTED_PROXIES").unwrap_or("".to_string()),
+			referral_reward_points: env::var("REFERRAL_REWARD_POINTS")
+				.ok()
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(10),
+			oidc_provider: env::var("OIDC_PROVIDER").unwrap_or("google".to_string()),
+			oidc_issuer_url: env::var("OIDC_ISSUER_URL")
+				.unwrap_or("https://accounts.google.com".to_string()),
+			oidc_client_id: env::var("OIDC_CLIENT_ID").unwrap_or("".to_string()),
+			oidc_client_secret: env::var("OIDC_CLIENT_SECRET")
+				.unwrap_or("".to_string()),
+			oidc_redirect_url: env::var("OIDC_REDIRECT_URL")
+				.unwrap_or("".to_string()),
+			soft_delete_retention_days: env::var("SOFT_DELETE_RETENTION_DAYS")
+				.ok()
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(30),
+			payment_provider: env::var("PAYMENT_PROVIDER").unwrap_or("".to_string()),
+			payment_api_url: env::var("PAYMENT_API_URL").unwrap_or("".to_string()),
+			payment_server_key: env::var("PAYMENT_SERVER_KEY")
+				.unwrap_or("".to_string()),
+			payment_webhook_secret: env::var("PAYMENT_WEBHOOK_SECRET")
+				.unwrap_or("".to_string()),
+			order_expiry_hours: env::var("ORDER_EXPIRY_HOURS")
+				.ok()
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(24),
+			referral_discount_percent: env::var("REFERRAL_DISCOUNT_PERCENT")
+				.ok()
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(0),
 		}
 	}
 
 	pub fn format(&self) -> String {
 		format!(
-            "Port: {}\nAccess Token Secret: {}\nRefresh Token Secret: {}\nDatabase URL: {}\nDatabase Schema: {}\nSMTP Email: {}\nSMTP Password: {}\nSMTP Name: {}\nRedis Hostname: {}\nFE URL: {}\nRust Env: {}\nMinio Endpoint: {}\nMinio Bucket Name: {}\nMinio Access Key: {}\nMinio Secret Key: {}",
+            "Port: {}\nAccess Token Secret: {}\nRefresh Token Secret: {}\nDatabase URL: {}\nDatabase Schema: {}\nSMTP Email: {}\nSMTP Password: {}\nSMTP Name: {}\nSMTP Host: {}\nSMTP Port: {}\nSMTP TLS: {}\nMail Transport: {}\nMail File Dir: {}\nMail Locale: {}\nRedis Hostname: {}\nFE URL: {}\nRust Env: {}\nMinio Endpoint: {}\nMinio Bucket Name: {}\nMinio Access Key: {}\nMinio Secret Key: {}\nRate Limit Backend: {}\nTrusted Proxies: {}\nReferral Reward Points: {}\nOIDC Provider: {}\nOIDC Issuer URL: {}\nOIDC Client ID: {}\nOIDC Client Secret: {}\nOIDC Redirect URL: {}\nSoft Delete Retention Days: {}\nPayment Provider: {}\nPayment API URL: {}\nPayment Server Key: {}\nPayment Webhook Secret: {}\nOrder Expiry Hours: {}\nReferral Discount Percent: {}",
             self.port,
             self.access_token_secret,
             self.refresh_token_secret,
@@ -61,6 +129,12 @@ impl Config {
             self.smtp_email,
             self.smtp_password,
             self.smtp_name,
+            self.smtp_host,
+            self.smtp_port,
+            self.smtp_tls,
+            self.mail_transport,
+            self.mail_file_dir,
+            self.mail_locale,
             self.redis_hostname,
             self.fe_url,
             self.rust_env,
@@ -68,6 +142,21 @@ impl Config {
             self.minio_bucket_name,
             self.minio_access_key,
             self.minio_secret_key,
+            self.rate_limit_backend,
+            self.trusted_proxies,
+            self.referral_reward_points,
+            self.oidc_provider,
+            self.oidc_issuer_url,
+            self.oidc_client_id,
+            self.oidc_client_secret,
+            self.oidc_redirect_url,
+            self.soft_delete_retention_days,
+            self.payment_provider,
+            self.payment_api_url,
+            self.payment_server_key,
+            self.payment_webhook_secret,
+            self.order_expiry_hours,
+            self.referral_discount_percent,
         )
 	}
 }
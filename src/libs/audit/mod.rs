@@ -0,0 +1,160 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use chrono::Utc;
+use log::error;
+use sea_orm::{
+	ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+	QuerySelect, Set,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{convert::Infallible, fmt};
+use uuid::Uuid;
+
+use crate::{
+	client_ip, decode_access_token,
+	schemas::{AuditLogsActiveModel, UsersColumn, UsersEntity},
+};
+
+const REDACTED_FIELDS: [&str; 3] =
+	["password", "totp_secret", "totp_recovery_codes"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+	Create,
+	Update,
+	Delete,
+	Restore,
+	Activate,
+	Deactivate,
+	Publish,
+	Export,
+}
+
+impl fmt::Display for AuditAction {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let action = match self {
+			AuditAction::Create => "create",
+			AuditAction::Update => "update",
+			AuditAction::Delete => "delete",
+			AuditAction::Restore => "restore",
+			AuditAction::Activate => "activate",
+			AuditAction::Deactivate => "deactivate",
+			AuditAction::Publish => "publish",
+			AuditAction::Export => "export",
+		};
+		write!(f, "{}", action)
+	}
+}
+
+/// Who is making the request and from where. Taken as an extractor by
+/// mutating handlers and passed down to `record_audit_log`.
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+	pub actor_email: Option<String>,
+	pub ip_address: String,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuditContext {
+	type Rejection = Infallible;
+
+	async fn from_request_parts(
+		parts: &mut Parts,
+		_state: &S,
+	) -> Result<Self, Self::Rejection> {
+		let actor_email = parts
+			.headers
+			.get("Authorization")
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.split_whitespace().nth(1))
+			.and_then(|token| decode_access_token(token).ok())
+			.map(|data| data.claims.email);
+
+		Ok(Self {
+			actor_email,
+			ip_address: client_ip(&parts.headers, &parts.extensions),
+		})
+	}
+}
+
+impl AuditContext {
+	/// Id of the authenticated user making the request, if any.
+	pub async fn actor_id(&self, db: &DatabaseConnection) -> Option<Uuid> {
+		let email = self.actor_email.as_ref()?;
+		UsersEntity::find()
+			.select_only()
+			.column(UsersColumn::Id)
+			.filter(UsersColumn::Email.eq(email))
+			.into_tuple::<Uuid>()
+			.one(db)
+			.await
+			.ok()
+			.flatten()
+	}
+}
+
+/// Serializes a value for the log with credentials stripped out.
+pub fn audit_snapshot<T: Serialize>(value: &T) -> Option<Value> {
+	let mut snapshot = serde_json::to_value(value).ok()?;
+	if let Value::Object(fields) = &mut snapshot {
+		for field in REDACTED_FIELDS {
+			if fields.contains_key(field) {
+				fields.insert(field.to_string(), json!("[redacted]"));
+			}
+		}
+	}
+	Some(snapshot)
+}
+
+/// Top-level fields whose value differs between the two snapshots.
+fn audit_changes(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+	let empty = Map::new();
+	let before = before.and_then(Value::as_object).unwrap_or(&empty);
+	let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+	let mut changes = Map::new();
+	for key in before.keys().chain(after.keys()) {
+		if changes.contains_key(key) || key == "updated_at" {
+			continue;
+		}
+		let old = before.get(key).unwrap_or(&Value::Null);
+		let new = after.get(key).unwrap_or(&Value::Null);
+		if old != new {
+			changes.insert(key.clone(), json!({ "before": old, "after": new }));
+		}
+	}
+
+	(!changes.is_empty()).then_some(Value::Object(changes))
+}
+
+/// Stores one audit entry. Failures are logged rather than returned, since
+/// the audited change has already been committed by the time this runs.
+pub async fn record_audit_log(
+	db: &DatabaseConnection,
+	context: &AuditContext,
+	action: AuditAction,
+	entity_type: &str,
+	entity_id: impl ToString,
+	before: Option<Value>,
+	after: Option<Value>,
+) {
+	let entry = AuditLogsActiveModel {
+		id: Set(Uuid::new_v4()),
+		actor_id: Set(context.actor_id(db).await),
+		actor_email: Set(context.actor_email.clone()),
+		action: Set(action.to_string()),
+		entity_type: Set(entity_type.to_string()),
+		entity_id: Set(Some(entity_id.to_string())),
+		changes: Set(audit_changes(before.as_ref(), after.as_ref())),
+		before: Set(before),
+		after: Set(after),
+		ip_address: Set(Some(context.ip_address.clone())),
+		created_at: Set(Utc::now()),
+	};
+
+	if let Err(err) = entry.insert(db).await {
+		error!(
+			"Failed to record audit log for {} {}: {}",
+			action, entity_type, err
+		);
+	}
+}
@@ -1,4 +1,4 @@
-use crate::Config;
+use crate::{mail_transport, Config};
 use axum::{serve, Router};
 use std::{future::Future, net::SocketAddr};
 use tokio::net::TcpListener;
@@ -11,6 +11,7 @@ where
 {
 	let config = Config::new();
 	let port: u16 = config.port.parse().expect("Invalid port number");
+	mail_transport().expect("Invalid mail configuration");
 	let addr = SocketAddr::from(([0, 0, 0, 0], port));
 	let listener = TcpListener::bind(&addr).await.unwrap();
 	println!("Listening on http://{}", addr);
@@ -18,7 +19,12 @@ where
 	// Apply the TraceLayer middleware to log HTTP requests and responses
 	let router = router_fn().await.layer(TraceLayer::new_for_http());
 
-	match serve(listener, router).await {
+	match serve(
+		listener,
+		router.into_make_service_with_connect_info::<SocketAddr>(),
+	)
+	.await
+	{
 		Ok(_) => println!("Server stopped gracefully."),
 		Err(err) => println!("Server encountered an error: {}", err),
 	}
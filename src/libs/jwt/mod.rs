@@ -40,7 +40,7 @@ pub fn decode_access_token(
 	let config = Config::new();
 	let secret: String = config.access_token_secret;
 	let result: Result<TokenData<Claims>, StatusCode> = decode(
-		&jwt_token,
+		jwt_token,
 		&DecodingKey::from_secret(secret.as_ref()),
 		&Validation::default(),
 	)
@@ -74,7 +74,7 @@ pub fn decode_refresh_token(
 	let config = Config::new();
 	let secret: String = config.refresh_token_secret;
 	let result: Result<TokenData<Claims>, StatusCode> = decode(
-		&jwt_token,
+		jwt_token,
 		&DecodingKey::from_secret(secret.as_ref()),
 		&Validation::default(),
 	)
@@ -1,43 +1,180 @@
-use crate::Config;
-use lettre::message::Mailbox;
+use futures::future::BoxFuture;
+use lettre::message::{Mailbox, MultiPart};
 use lettre::transport::smtp::authentication::Credentials;
-use lettre::{Message, SmtpTransport, Transport};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use log::{error, info};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use crate::Config;
+
+pub mod templates;
+
+pub use templates::*;
+
+type TokioSmtpTransport = AsyncSmtpTransport<Tokio1Executor>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MailError {
+	#[error("Invalid email address: {0}")]
+	Address(#[from] lettre::address::AddressError),
+	#[error("Failed to build email: {0}")]
+	Build(#[from] lettre::error::Error),
+	#[error("SMTP error: {0}")]
+	Smtp(#[from] lettre::transport::smtp::Error),
+	#[error("Failed to write email: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("Invalid mail configuration: {0}")]
+	Config(String),
+}
+
+pub trait MailTransport: Send + Sync {
+	fn send(&self, message: Message) -> BoxFuture<'_, Result<(), MailError>>;
+}
+
+pub struct SmtpMailTransport {
+	mailer: TokioSmtpTransport,
+}
+
+impl SmtpMailTransport {
+	pub fn from_config(config: &Config) -> Result<Self, MailError> {
+		let builder = match config.smtp_tls.as_str() {
+			"tls" => TokioSmtpTransport::relay(&config.smtp_host)?,
+			"none" => TokioSmtpTransport::builder_dangerous(&config.smtp_host),
+			"" | "starttls" => {
+				TokioSmtpTransport::starttls_relay(&config.smtp_host)?
+			}
+			other => {
+				return Err(MailError::Config(format!(
+					"SMTP_TLS must be tls, starttls or none, got {}",
+					other
+				)))
+			}
+		};
+
+		let port = match config.smtp_port.as_str() {
+			"" => 587,
+			port => port.parse().map_err(|_| {
+				MailError::Config(format!("Invalid SMTP_PORT {}", port))
+			})?,
+		};
+
+		let credentials = Credentials::new(
+			config.smtp_email.clone(),
+			config.smtp_password.replace("-", " "),
+		);
+
+		let mailer = builder.port(port).credentials(credentials).build();
+
+		Ok(Self { mailer })
+	}
+}
+
+impl MailTransport for SmtpMailTransport {
+	fn send(&self, message: Message) -> BoxFuture<'_, Result<(), MailError>> {
+		Box::pin(async move {
+			self.mailer.send(message).await?;
+			Ok(())
+		})
+	}
+}
+
+/// Writes each message as an `.eml` file, for development and tests.
+pub struct FileMailTransport {
+	dir: PathBuf,
+}
 
-pub fn send_email(
+impl FileMailTransport {
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self { dir: dir.into() }
+	}
+}
+
+impl MailTransport for FileMailTransport {
+	fn send(&self, message: Message) -> BoxFuture<'_, Result<(), MailError>> {
+		Box::pin(async move {
+			tokio::fs::create_dir_all(&self.dir).await?;
+			let path = self.dir.join(format!("{}.eml", uuid::Uuid::new_v4()));
+			tokio::fs::write(&path, message.formatted()).await?;
+			info!("Email written to {}", path.display());
+			Ok(())
+		})
+	}
+}
+
+pub struct StdoutMailTransport;
+
+impl MailTransport for StdoutMailTransport {
+	fn send(&self, message: Message) -> BoxFuture<'_, Result<(), MailError>> {
+		Box::pin(async move {
+			println!("{}", String::from_utf8_lossy(&message.formatted()));
+			Ok(())
+		})
+	}
+}
+
+static MAIL_TRANSPORT: OnceLock<Arc<dyn MailTransport>> = OnceLock::new();
+
+/// Builds the transport selected by `MAIL_TRANSPORT`. SMTP is the default;
+/// the file and stdout transports are only used when asked for by name, so
+/// a broken SMTP setup is reported instead of mail quietly going nowhere.
+pub fn build_mail_transport(
+	config: &Config,
+) -> Result<Arc<dyn MailTransport>, MailError> {
+	match config.mail_transport.as_str() {
+		"file" => Ok(Arc::new(FileMailTransport::new(
+			config.mail_file_dir.clone(),
+		))),
+		"stdout" => Ok(Arc::new(StdoutMailTransport)),
+		"" | "smtp" => Ok(Arc::new(SmtpMailTransport::from_config(config)?)),
+		other => Err(MailError::Config(format!(
+			"MAIL_TRANSPORT must be smtp, file or stdout, got {}",
+			other
+		))),
+	}
+}
+
+/// The configured transport. Startup calls this once so an invalid
+/// configuration stops the server; until it is fixed every send fails.
+pub fn mail_transport() -> Result<Arc<dyn MailTransport>, MailError> {
+	if let Some(transport) = MAIL_TRANSPORT.get() {
+		return Ok(transport.clone());
+	}
+
+	let transport = build_mail_transport(&Config::new())?;
+	Ok(MAIL_TRANSPORT.get_or_init(|| transport).clone())
+}
+
+pub async fn send_email(to: &str, template: EmailTemplate) -> Result<(), MailError> {
+	let locale = EmailLocale::from_code(&Config::new().mail_locale);
+	send_email_localized(to, template, locale).await
+}
+
+pub async fn send_email_localized(
 	to: &str,
-	subject: &str,
-	body: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+	template: EmailTemplate,
+	locale: EmailLocale,
+) -> Result<(), MailError> {
 	let config = Config::new();
-	let sender_email = config.smtp_email;
-	let sender_name = config.smtp_name;
-	let sender_password = config.smtp_password;
-	let recipient_email = to;
+	let rendered = template.render(locale);
 
 	let email = Message::builder()
 		.from(Mailbox::new(
-			Some(sender_name.replace("-", " ")),
-			sender_email.parse()?,
+			Some(config.smtp_name.replace("-", " ")),
+			config.smtp_email.parse()?,
 		))
-		.to(recipient_email.parse()?)
-		.subject(subject)
-		.body(body.to_string())?;
-
-	let smtp_credentials =
-		Credentials::new(sender_email, sender_password.replace("-", " "));
-
-	let mailer = SmtpTransport::relay("smtp.gmail.com")?
-		.credentials(smtp_credentials)
-		.build();
+		.to(to.parse()?)
+		.subject(rendered.subject)
+		.multipart(MultiPart::alternative_plain_html(rendered.text, rendered.html))?;
 
-	match mailer.send(&email) {
+	match mail_transport()?.send(email).await {
 		Ok(_) => {
-			println!("Email sent successfully to {}", to);
+			info!("Email sent successfully to {}", to);
 			Ok(())
 		}
-		Err(e) => {
-			println!("Failed to send email: {}", e);
-			Err(Box::new(e))
+		Err(err) => {
+			error!("Failed to send email: {}", err);
+			Err(err)
 		}
 	}
 }
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailLocale {
+	Id,
+	En,
+}
+
+impl EmailLocale {
+	pub fn from_code(code: &str) -> Self {
+		match code.to_lowercase().as_str() {
+			"en" => EmailLocale::En,
+			_ => EmailLocale::Id,
+		}
+	}
+
+	fn code(&self) -> &'static str {
+		match self {
+			EmailLocale::Id => "id",
+			EmailLocale::En => "en",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "template", rename_all = "snake_case")]
+pub enum EmailTemplate {
+	Otp {
+		otp: u32,
+		ttl_minutes: u64,
+	},
+	PasswordReset {
+		reset_url: String,
+	},
+	Welcome {
+		fullname: String,
+	},
+	ResultsPublished {
+		fullname: String,
+		test_name: String,
+		result_url: String,
+	},
+}
+
+pub struct RenderedEmail {
+	pub subject: String,
+	pub html: String,
+	pub text: String,
+}
+
+struct EmailCopy {
+	subject: String,
+	greeting: String,
+	paragraphs: Vec<String>,
+	action: Option<(String, String)>,
+	footer: String,
+}
+
+impl EmailTemplate {
+	pub fn render(&self, locale: EmailLocale) -> RenderedEmail {
+		let copy = self.copy(locale);
+		RenderedEmail {
+			subject: copy.subject.clone(),
+			html: render_html(&copy, locale),
+			text: render_text(&copy),
+		}
+	}
+
+	fn copy(&self, locale: EmailLocale) -> EmailCopy {
+		let footer = match locale {
+			EmailLocale::Id => "Email ini dikirim otomatis, mohon tidak membalas.",
+			EmailLocale::En => "This is an automated message, please do not reply.",
+		}
+		.to_string();
+
+		match (self, locale) {
+			(EmailTemplate::Otp { otp, ttl_minutes }, EmailLocale::Id) => EmailCopy {
+				subject: "Kode Verifikasi".to_string(),
+				greeting: "Halo,".to_string(),
+				paragraphs: vec![
+					format!("Kode OTP Anda adalah {}.", otp),
+					format!("Kode ini berlaku selama {} menit.", ttl_minutes),
+				],
+				action: None,
+				footer,
+			},
+			(EmailTemplate::Otp { otp, ttl_minutes }, EmailLocale::En) => EmailCopy {
+				subject: "Verification Code".to_string(),
+				greeting: "Hello,".to_string(),
+				paragraphs: vec![
+					format!("Your OTP code is {}.", otp),
+					format!("This code expires in {} minutes.", ttl_minutes),
+				],
+				action: None,
+				footer,
+			},
+			(EmailTemplate::PasswordReset { reset_url }, EmailLocale::Id) => {
+				EmailCopy {
+					subject: "Permintaan Atur Ulang Kata Sandi".to_string(),
+					greeting: "Halo,".to_string(),
+					paragraphs: vec![
+						"Kami menerima permintaan untuk mengatur ulang kata sandi Anda."
+							.to_string(),
+						"Abaikan email ini jika Anda tidak memintanya.".to_string(),
+					],
+					action: Some(("Atur Ulang Kata Sandi".to_string(), reset_url.clone())),
+					footer,
+				}
+			}
+			(EmailTemplate::PasswordReset { reset_url }, EmailLocale::En) => {
+				EmailCopy {
+					subject: "Reset Password Request".to_string(),
+					greeting: "Hello,".to_string(),
+					paragraphs: vec![
+						"You have requested a password reset.".to_string(),
+						"If you did not request this, you can ignore this email."
+							.to_string(),
+					],
+					action: Some(("Reset Password".to_string(), reset_url.clone())),
+					footer,
+				}
+			}
+			(EmailTemplate::Welcome { fullname }, EmailLocale::Id) => EmailCopy {
+				subject: "Selamat Datang di Najm Course".to_string(),
+				greeting: format!("Halo {},", fullname),
+				paragraphs: vec![
+					"Akun Anda sudah aktif. Selamat belajar dan semoga sukses!"
+						.to_string(),
+				],
+				action: None,
+				footer,
+			},
+			(EmailTemplate::Welcome { fullname }, EmailLocale::En) => EmailCopy {
+				subject: "Welcome to Najm Course".to_string(),
+				greeting: format!("Hello {},", fullname),
+				paragraphs: vec![
+					"Your account is now active. Happy studying and good luck!"
+						.to_string(),
+				],
+				action: None,
+				footer,
+			},
+			(
+				EmailTemplate::ResultsPublished {
+					fullname,
+					test_name,
+					result_url,
+				},
+				EmailLocale::Id,
+			) => EmailCopy {
+				subject: format!("Hasil {} Sudah Tersedia", test_name),
+				greeting: format!("Halo {},", fullname),
+				paragraphs: vec![format!(
+					"Hasil ujian {} Anda sudah dapat dilihat.",
+					test_name
+				)],
+				action: Some(("Lihat Hasil".to_string(), result_url.clone())),
+				footer,
+			},
+			(
+				EmailTemplate::ResultsPublished {
+					fullname,
+					test_name,
+					result_url,
+				},
+				EmailLocale::En,
+			) => EmailCopy {
+				subject: format!("Your {} Results Are Available", test_name),
+				greeting: format!("Hello {},", fullname),
+				paragraphs: vec![format!(
+					"Your results for {} have been published.",
+					test_name
+				)],
+				action: Some(("View Results".to_string(), result_url.clone())),
+				footer,
+			},
+		}
+	}
+}
+
+fn render_html(copy: &EmailCopy, locale: EmailLocale) -> String {
+	let paragraphs: String = copy
+		.paragraphs
+		.iter()
+		.map(|p| format!("<p style=\"margin:0 0 16px\">{}</p>", escape_html(p)))
+		.collect();
+
+	let action = copy
+		.action
+		.as_ref()
+		.map(|(label, url)| {
+			format!(
+				"<p style=\"margin:24px 0\"><a href=\"{}\" style=\"background:#1d4ed8;color:#ffffff;padding:12px 20px;border-radius:6px;text-decoration:none\">{}</a></p>",
+				escape_html(url),
+				escape_html(label)
+			)
+		})
+		.unwrap_or_default();
+
+	format!(
+		"<!DOCTYPE html><html lang=\"{}\"><head><meta charset=\"utf-8\"><title>{}</title></head>\
+<body style=\"font-family:Arial,sans-serif;background:#f4f4f5;padding:24px;color:#18181b\">\
+<div style=\"max-width:560px;margin:0 auto;background:#ffffff;padding:32px;border-radius:8px\">\
+<p style=\"margin:0 0 16px\">{}</p>{}{}\
+<p style=\"margin:32px 0 0;font-size:12px;color:#71717a\">{}</p></div></body></html>",
+		locale.code(),
+		escape_html(&copy.subject),
+		escape_html(&copy.greeting),
+		paragraphs,
+		action,
+		escape_html(&copy.footer),
+	)
+}
+
+fn render_text(copy: &EmailCopy) -> String {
+	let mut lines = vec![copy.greeting.clone(), String::new()];
+	lines.extend(copy.paragraphs.iter().cloned());
+
+	if let Some((label, url)) = &copy.action {
+		lines.push(String::new());
+		lines.push(format!("{}: {}", label, url));
+	}
+
+	lines.push(String::new());
+	lines.push(copy.footer.clone());
+	lines.join("\n")
+}
+
+fn escape_html(input: &str) -> String {
+	input
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&#39;")
+}
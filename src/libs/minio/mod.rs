@@ -1,7 +1,7 @@
 use bytes::Bytes;
 use log::{error, info};
 use mime_guess::from_path;
-use minio_rsc::client::Minio;
+use minio_rsc::client::{ListObjectsArgs, Minio, PresignedArgs};
 use minio_rsc::error::Error;
 use minio_rsc::provider::StaticProvider;
 use std::sync::Arc;
@@ -16,6 +16,12 @@ pub struct MinioClient {
 	bucket_name: String,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BucketUsage {
+	pub objects: u64,
+	pub bytes: u64,
+}
+
 #[derive(Clone)]
 pub struct AppState {
 	pub minio: Arc<Mutex<MinioClient>>,
@@ -111,4 +117,57 @@ impl MinioClient {
 		);
 		Ok(file_url)
 	}
+
+	/// Stores a generated export under `exports/`. Exports hold personal data,
+	/// so the returned link is presigned and expires after a week instead of
+	/// pointing at the bucket directly.
+	pub async fn upload_export(
+		&self,
+		filename: &str,
+		data: Vec<u8>,
+	) -> Result<String, Error> {
+		let object_name = format!("exports/{}-{}", Uuid::new_v4(), filename);
+
+		info!(
+			"Uploading export: {} ({} KB)",
+			object_name,
+			data.len() / 1024
+		);
+
+		self.client
+			.put_object(&self.bucket_name, &object_name, Bytes::from(data))
+			.await?;
+
+		self.client
+			.presigned_get_object(PresignedArgs::new(
+				self.bucket_name.as_str(),
+				object_name.as_str(),
+			))
+			.await
+	}
+
+	/// Totals every object in the bucket. Listings are capped at 1000 keys, so
+	/// this pages through them with the continuation token.
+	pub async fn bucket_usage(&self) -> Result<BucketUsage, Error> {
+		let mut usage = BucketUsage::default();
+		let mut args = ListObjectsArgs::default();
+
+		loop {
+			let result = self
+				.client
+				.list_objects(self.bucket_name.as_str(), args)
+				.await?;
+
+			usage.objects += result.contents.len() as u64;
+			usage.bytes += result.contents.iter().map(|obj| obj.size).sum::<u64>();
+
+			if !result.is_truncated || result.next_continuation_token.is_empty() {
+				break;
+			}
+			args = ListObjectsArgs::default()
+				.continuation_token(result.next_continuation_token);
+		}
+
+		Ok(usage)
+	}
 }
@@ -1,17 +1,31 @@
 pub mod argon;
+pub mod audit;
 pub mod axum;
 pub mod jwt;
 pub mod lettre;
 pub mod minio;
+pub mod oidc;
 pub mod otp;
+pub mod payments;
+pub mod queue;
+pub mod ratelimit;
+pub mod realtime;
 pub mod redis;
 pub mod seaorm;
+pub mod totp;
 
 pub use argon::*;
+pub use audit::*;
 pub use axum::*;
 pub use jwt::*;
 pub use lettre::*;
 pub use minio::*;
+pub use oidc::*;
 pub use otp::*;
+pub use payments::*;
+pub use queue::*;
+pub use ratelimit::*;
+pub use realtime::*;
 pub use redis::*;
 pub use seaorm::*;
+pub use totp::*;
@@ -0,0 +1,182 @@
+use jsonwebtoken::{
+	decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::Config;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcError {
+	#[error("OIDC provider is not configured")]
+	NotConfigured,
+	#[error("OIDC provider request failed: {0}")]
+	Request(#[from] reqwest::Error),
+	#[error("OIDC provider rejected the authorization code: {0}")]
+	TokenExchange(String),
+	#[error("Invalid ID token: {0}")]
+	InvalidIdToken(String),
+	#[error("OIDC nonce mismatch")]
+	NonceMismatch,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcDiscovery {
+	pub issuer: String,
+	pub authorization_endpoint: String,
+	pub token_endpoint: String,
+	pub jwks_uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OidcTokenResponse {
+	id_token: Option<String>,
+	error: Option<String>,
+	error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcClaims {
+	pub sub: String,
+	pub email: Option<String>,
+	#[serde(default)]
+	pub email_verified: bool,
+	pub name: Option<String>,
+	pub picture: Option<String>,
+	pub nonce: Option<String>,
+}
+
+/// Provider-agnostic OIDC client. Endpoints come from the issuer's discovery
+/// document, so any compliant IdP (Google, a local mock) works the same way.
+#[derive(Debug, Clone)]
+pub struct OidcProvider {
+	pub name: String,
+	pub issuer_url: String,
+	pub client_id: String,
+	pub client_secret: String,
+	pub redirect_url: String,
+}
+
+impl OidcProvider {
+	pub fn from_config() -> Result<Self, OidcError> {
+		let config = Config::new();
+
+		if config.oidc_client_id.is_empty() || config.oidc_redirect_url.is_empty() {
+			return Err(OidcError::NotConfigured);
+		}
+
+		Ok(Self {
+			name: config.oidc_provider,
+			issuer_url: config.oidc_issuer_url.trim_end_matches('/').to_string(),
+			client_id: config.oidc_client_id,
+			client_secret: config.oidc_client_secret,
+			redirect_url: config.oidc_redirect_url,
+		})
+	}
+
+	pub async fn discover(&self) -> Result<OidcDiscovery, OidcError> {
+		let url = format!("{}/.well-known/openid-configuration", self.issuer_url);
+		let discovery = reqwest::get(url)
+			.await?
+			.error_for_status()?
+			.json::<OidcDiscovery>()
+			.await?;
+		Ok(discovery)
+	}
+
+	pub fn authorization_url(
+		&self,
+		discovery: &OidcDiscovery,
+		state: &str,
+		nonce: &str,
+	) -> String {
+		format!(
+			"{}?response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&nonce={}",
+			discovery.authorization_endpoint,
+			urlencoding::encode(&self.client_id),
+			urlencoding::encode(&self.redirect_url),
+			urlencoding::encode("openid email profile"),
+			urlencoding::encode(state),
+			urlencoding::encode(nonce),
+		)
+	}
+
+	pub async fn exchange_code(
+		&self,
+		discovery: &OidcDiscovery,
+		code: &str,
+		nonce: &str,
+	) -> Result<OidcClaims, OidcError> {
+		let response = reqwest::Client::new()
+			.post(&discovery.token_endpoint)
+			.form(&[
+				("grant_type", "authorization_code"),
+				("code", code),
+				("redirect_uri", self.redirect_url.as_str()),
+				("client_id", self.client_id.as_str()),
+				("client_secret", self.client_secret.as_str()),
+			])
+			.send()
+			.await?
+			.json::<OidcTokenResponse>()
+			.await?;
+
+		if let Some(error) = response.error {
+			return Err(OidcError::TokenExchange(
+				response.error_description.unwrap_or(error),
+			));
+		}
+
+		let id_token = response.id_token.ok_or_else(|| {
+			OidcError::TokenExchange("Missing id_token in response".to_string())
+		})?;
+
+		let claims = self.verify_id_token(discovery, &id_token).await?;
+
+		if claims.nonce.as_deref() != Some(nonce) {
+			return Err(OidcError::NonceMismatch);
+		}
+
+		Ok(claims)
+	}
+
+	async fn verify_id_token(
+		&self,
+		discovery: &OidcDiscovery,
+		id_token: &str,
+	) -> Result<OidcClaims, OidcError> {
+		let header = decode_header(id_token)
+			.map_err(|err| OidcError::InvalidIdToken(err.to_string()))?;
+
+		let jwks = reqwest::get(&discovery.jwks_uri)
+			.await?
+			.error_for_status()?
+			.json::<JwkSet>()
+			.await?;
+
+		let jwk = match &header.kid {
+			Some(kid) => jwks.find(kid),
+			None => jwks.keys.first(),
+		}
+		.ok_or_else(|| OidcError::InvalidIdToken("Unknown signing key".to_string()))?;
+
+		let key = DecodingKey::from_jwk(jwk)
+			.map_err(|err| OidcError::InvalidIdToken(err.to_string()))?;
+
+		if !matches!(
+			header.alg,
+			Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 | Algorithm::ES256
+		) {
+			return Err(OidcError::InvalidIdToken(
+				"Unsupported signing algorithm".to_string(),
+			));
+		}
+
+		let mut validation = Validation::new(header.alg);
+		validation.set_audience(&[&self.client_id]);
+		validation.set_issuer(&[&discovery.issuer]);
+
+		decode::<OidcClaims>(id_token, &key, &validation)
+			.map(|data| data.claims)
+			.map_err(|err| OidcError::InvalidIdToken(err.to_string()))
+	}
+}
@@ -0,0 +1,413 @@
+use hmac::{Hmac, Mac};
+use hyper::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::Config;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const PAYMENT_PROVIDER_MOCK: &str = "mock";
+pub const PAYMENT_PROVIDER_MIDTRANS: &str = "midtrans";
+pub const PAYMENT_PROVIDER_XENDIT: &str = "xendit";
+
+pub const MOCK_SIGNATURE_HEADER: &str = "x-mock-signature";
+const XENDIT_CALLBACK_TOKEN_HEADER: &str = "x-callback-token";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PaymentError {
+	#[error("Payment gateway is not configured")]
+	NotConfigured,
+	#[error("Payment gateway request failed: {0}")]
+	Request(#[from] reqwest::Error),
+	#[error("Payment gateway rejected the charge: {0}")]
+	Charge(String),
+	#[error("Invalid payment notification signature")]
+	InvalidSignature,
+	#[error("Invalid payment notification: {0}")]
+	InvalidNotification(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+	Pending,
+	Paid,
+	Failed,
+	Expired,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentChargeRequest {
+	pub order_id: String,
+	pub amount: i64,
+	pub item_name: String,
+	pub customer_name: String,
+	pub customer_email: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentCharge {
+	pub reference: String,
+	pub payment_url: String,
+}
+
+/// A verified status update for one order, whatever gateway sent it.
+#[derive(Debug, Clone)]
+pub struct PaymentNotification {
+	pub order_id: String,
+	pub reference: Option<String>,
+	pub status: PaymentStatus,
+	pub amount: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MockNotification {
+	order_id: String,
+	reference: Option<String>,
+	status: PaymentStatus,
+	amount: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MidtransNotification {
+	order_id: String,
+	status_code: String,
+	gross_amount: String,
+	signature_key: String,
+	transaction_status: String,
+	fraud_status: Option<String>,
+	transaction_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MidtransChargeResponse {
+	token: Option<String>,
+	redirect_url: Option<String>,
+	error_messages: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct XenditNotification {
+	id: Option<String>,
+	external_id: String,
+	status: String,
+	amount: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct XenditChargeResponse {
+	id: Option<String>,
+	invoice_url: Option<String>,
+	message: Option<String>,
+}
+
+/// Payment gateway client. Midtrans and Xendit are called over HTTP; the mock
+/// provider settles nothing remotely and signs its own notifications, so the
+/// whole order flow runs offline. No provider is configured by default: the
+/// mock has to be chosen with `PAYMENT_PROVIDER=mock` and its own
+/// `PAYMENT_WEBHOOK_SECRET`, and is refused in production.
+#[derive(Debug, Clone)]
+pub struct PaymentGateway {
+	pub provider: String,
+	pub api_url: String,
+	pub server_key: String,
+	pub webhook_secret: String,
+	pub fe_url: String,
+}
+
+impl PaymentGateway {
+	pub fn from_config() -> Result<Self, PaymentError> {
+		let config = Config::new();
+		let provider = config.payment_provider.to_lowercase();
+
+		let default_api_url = match provider.as_str() {
+			PAYMENT_PROVIDER_MOCK if config.rust_env != "production" => "",
+			PAYMENT_PROVIDER_MIDTRANS => "https://app.sandbox.midtrans.com",
+			PAYMENT_PROVIDER_XENDIT => "https://api.xendit.co",
+			_ => return Err(PaymentError::NotConfigured),
+		};
+
+		let gateway = Self {
+			api_url: match config.payment_api_url.is_empty() {
+				true => default_api_url.to_string(),
+				false => config.payment_api_url.trim_end_matches('/').to_string(),
+			},
+			provider,
+			server_key: config.payment_server_key,
+			webhook_secret: config.payment_webhook_secret,
+			fe_url: config.fe_url,
+		};
+
+		let configured = match gateway.provider.as_str() {
+			PAYMENT_PROVIDER_MIDTRANS => !gateway.server_key.is_empty(),
+			PAYMENT_PROVIDER_XENDIT => {
+				!gateway.server_key.is_empty() && !gateway.webhook_secret.is_empty()
+			}
+			_ => !gateway.webhook_secret.is_empty(),
+		};
+
+		match configured {
+			true => Ok(gateway),
+			false => Err(PaymentError::NotConfigured),
+		}
+	}
+
+	pub fn is_mock(&self) -> bool {
+		self.provider == PAYMENT_PROVIDER_MOCK
+	}
+
+	/// Opens a payment for the order and returns where the student pays.
+	pub async fn create_charge(
+		&self,
+		request: &PaymentChargeRequest,
+	) -> Result<PaymentCharge, PaymentError> {
+		match self.provider.as_str() {
+			PAYMENT_PROVIDER_MIDTRANS => self.create_midtrans_charge(request).await,
+			PAYMENT_PROVIDER_XENDIT => self.create_xendit_charge(request).await,
+			_ => Ok(PaymentCharge {
+				reference: format!("mock-{}", request.order_id),
+				payment_url: format!(
+					"{}/orders/{}/pay",
+					self.fe_url, request.order_id
+				),
+			}),
+		}
+	}
+
+	async fn create_midtrans_charge(
+		&self,
+		request: &PaymentChargeRequest,
+	) -> Result<PaymentCharge, PaymentError> {
+		let response = reqwest::Client::new()
+			.post(format!("{}/snap/v1/transactions", self.api_url))
+			.basic_auth(&self.server_key, Some(""))
+			.json(&json!({
+				"transaction_details": {
+					"order_id": request.order_id,
+					"gross_amount": request.amount,
+				},
+				"item_details": [{
+					"id": request.order_id,
+					"price": request.amount,
+					"quantity": 1,
+					"name": request.item_name,
+				}],
+				"customer_details": {
+					"first_name": request.customer_name,
+					"email": request.customer_email,
+				},
+			}))
+			.send()
+			.await?
+			.json::<MidtransChargeResponse>()
+			.await?;
+
+		match (response.token, response.redirect_url) {
+			(Some(reference), Some(payment_url)) => Ok(PaymentCharge {
+				reference,
+				payment_url,
+			}),
+			_ => Err(PaymentError::Charge(
+				response.error_messages.unwrap_or_default().join(", "),
+			)),
+		}
+	}
+
+	async fn create_xendit_charge(
+		&self,
+		request: &PaymentChargeRequest,
+	) -> Result<PaymentCharge, PaymentError> {
+		let response = reqwest::Client::new()
+			.post(format!("{}/v2/invoices", self.api_url))
+			.basic_auth(&self.server_key, Some(""))
+			.json(&json!({
+				"external_id": request.order_id,
+				"amount": request.amount,
+				"payer_email": request.customer_email,
+				"description": request.item_name,
+				"success_redirect_url": format!("{}/orders", self.fe_url),
+			}))
+			.send()
+			.await?
+			.json::<XenditChargeResponse>()
+			.await?;
+
+		match (response.id, response.invoice_url) {
+			(Some(reference), Some(payment_url)) => Ok(PaymentCharge {
+				reference,
+				payment_url,
+			}),
+			_ => Err(PaymentError::Charge(response.message.unwrap_or_default())),
+		}
+	}
+
+	/// Checks the gateway's signature on a webhook and reads the order status
+	/// out of it. Nothing in the body is trusted before the signature matches.
+	pub fn verify_notification(
+		&self,
+		headers: &HeaderMap,
+		body: &[u8],
+	) -> Result<PaymentNotification, PaymentError> {
+		match self.provider.as_str() {
+			PAYMENT_PROVIDER_MIDTRANS => self.verify_midtrans_notification(body),
+			PAYMENT_PROVIDER_XENDIT => {
+				self.verify_xendit_notification(headers, body)
+			}
+			_ => self.verify_mock_notification(headers, body),
+		}
+	}
+
+	fn verify_midtrans_notification(
+		&self,
+		body: &[u8],
+	) -> Result<PaymentNotification, PaymentError> {
+		let notification: MidtransNotification = serde_json::from_slice(body)
+			.map_err(|err| PaymentError::InvalidNotification(err.to_string()))?;
+
+		let expected = to_hex(&Sha512::digest(format!(
+			"{}{}{}{}",
+			notification.order_id,
+			notification.status_code,
+			notification.gross_amount,
+			self.server_key
+		)));
+		if !constant_time_eq(
+			expected.as_bytes(),
+			notification.signature_key.to_lowercase().as_bytes(),
+		) {
+			return Err(PaymentError::InvalidSignature);
+		}
+
+		let status = match notification.transaction_status.as_str() {
+			"capture"
+				if notification.fraud_status.as_deref() == Some("challenge") =>
+			{
+				PaymentStatus::Pending
+			}
+			"capture" | "settlement" => PaymentStatus::Paid,
+			"pending" => PaymentStatus::Pending,
+			"deny" | "cancel" | "failure" => PaymentStatus::Failed,
+			"expire" => PaymentStatus::Expired,
+			other => {
+				return Err(PaymentError::InvalidNotification(format!(
+					"Unknown transaction status '{}'",
+					other
+				)))
+			}
+		};
+
+		Ok(PaymentNotification {
+			order_id: notification.order_id,
+			reference: notification.transaction_id,
+			status,
+			amount: notification
+				.gross_amount
+				.parse::<f64>()
+				.ok()
+				.map(|amount| amount.round() as i64),
+		})
+	}
+
+	fn verify_xendit_notification(
+		&self,
+		headers: &HeaderMap,
+		body: &[u8],
+	) -> Result<PaymentNotification, PaymentError> {
+		let token = headers
+			.get(XENDIT_CALLBACK_TOKEN_HEADER)
+			.and_then(|value| value.to_str().ok())
+			.unwrap_or_default();
+		if !constant_time_eq(token.as_bytes(), self.webhook_secret.as_bytes()) {
+			return Err(PaymentError::InvalidSignature);
+		}
+
+		let notification: XenditNotification = serde_json::from_slice(body)
+			.map_err(|err| PaymentError::InvalidNotification(err.to_string()))?;
+
+		let status = match notification.status.to_uppercase().as_str() {
+			"PAID" | "SETTLED" => PaymentStatus::Paid,
+			"PENDING" => PaymentStatus::Pending,
+			"EXPIRED" => PaymentStatus::Expired,
+			"FAILED" => PaymentStatus::Failed,
+			other => {
+				return Err(PaymentError::InvalidNotification(format!(
+					"Unknown invoice status '{}'",
+					other
+				)))
+			}
+		};
+
+		Ok(PaymentNotification {
+			order_id: notification.external_id,
+			reference: notification.id,
+			status,
+			amount: notification.amount.map(|amount| amount.round() as i64),
+		})
+	}
+
+	fn verify_mock_notification(
+		&self,
+		headers: &HeaderMap,
+		body: &[u8],
+	) -> Result<PaymentNotification, PaymentError> {
+		let signature = headers
+			.get(MOCK_SIGNATURE_HEADER)
+			.and_then(|value| value.to_str().ok())
+			.unwrap_or_default();
+		if !constant_time_eq(self.sign_mock(body).as_bytes(), signature.as_bytes()) {
+			return Err(PaymentError::InvalidSignature);
+		}
+
+		let notification: MockNotification = serde_json::from_slice(body)
+			.map_err(|err| PaymentError::InvalidNotification(err.to_string()))?;
+
+		Ok(PaymentNotification {
+			order_id: notification.order_id,
+			reference: notification.reference,
+			status: notification.status,
+			amount: Some(notification.amount),
+		})
+	}
+
+	/// What the mock gateway would POST to the webhook: the JSON body and its
+	/// signature header value.
+	pub fn mock_notification(
+		&self,
+		order_id: &str,
+		reference: Option<String>,
+		status: PaymentStatus,
+		amount: i64,
+	) -> Result<(Vec<u8>, String), PaymentError> {
+		if !self.is_mock() {
+			return Err(PaymentError::NotConfigured);
+		}
+
+		let body = serde_json::to_vec(&MockNotification {
+			order_id: order_id.to_string(),
+			reference,
+			status,
+			amount,
+		})
+		.map_err(|err| PaymentError::InvalidNotification(err.to_string()))?;
+		let signature = self.sign_mock(&body);
+
+		Ok((body, signature))
+	}
+
+	fn sign_mock(&self, body: &[u8]) -> String {
+		let mut mac = HmacSha256::new_from_slice(self.webhook_secret.as_bytes())
+			.expect("HMAC can take key of any size");
+		mac.update(body);
+		to_hex(&mac.finalize().into_bytes())
+	}
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
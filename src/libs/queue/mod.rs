@@ -0,0 +1,270 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use log::{error, info, warn};
+use sea_orm::{
+	sea_query::{OnConflict, Query},
+	ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, DbErr,
+	EntityTrait, QueryFilter, Set, Statement,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+	get_db,
+	schemas::{
+		JobsActiveModel, JobsEntity, JobsModel, RolesColumn, RolesEntity,
+		TestSessionsEntity, TestsEntity, UsersColumn, UsersEntity,
+	},
+	send_email,
+	sessions::export_session_results,
+	tests::score_test_answer,
+	Config, EmailTemplate, SoftDelete,
+};
+
+pub const JOB_STATUS_PENDING: &str = "pending";
+pub const JOB_STATUS_RUNNING: &str = "running";
+pub const JOB_STATUS_COMPLETED: &str = "completed";
+pub const JOB_STATUS_FAILED: &str = "failed";
+
+const JOB_DEFAULT_MAX_ATTEMPTS: i32 = 5;
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const JOB_BACKOFF_BASE_SECS: i64 = 10;
+const JOB_BACKOFF_MAX_SECS: i64 = 3600;
+const PURGE_SOFT_DELETED_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Claims one due job, also picking up jobs whose worker died mid-run.
+const CLAIM_JOB_SQL: &str = r#"
+UPDATE app_jobs
+SET status = 'running', locked_at = now(), attempts = attempts + 1, updated_at = now()
+WHERE id = (
+	SELECT id FROM app_jobs
+	WHERE (status = 'pending' AND run_at <= now())
+		OR (status = 'running' AND locked_at < now() - interval '10 minutes')
+	ORDER BY run_at
+	LIMIT 1
+	FOR UPDATE SKIP LOCKED
+)
+RETURNING *
+"#;
+
+/// Work that runs outside the request cycle. New kinds of background work
+/// are added as variants here and handled in `Job::run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
+pub enum Job {
+	SendEmail {
+		to: String,
+		template: EmailTemplate,
+	},
+	PurgeSoftDeleted,
+	/// Scores a submitted answer once it is stored.
+	ComputeResult {
+		answer_id: Uuid,
+	},
+	/// Writes a session's results to a CSV file and notifies whoever asked.
+	ExportSessionResults {
+		session_id: Uuid,
+		requested_by: Uuid,
+	},
+}
+
+impl Job {
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Job::SendEmail { .. } => "send_email",
+			Job::PurgeSoftDeleted => "purge_soft_deleted",
+			Job::ComputeResult { .. } => "compute_result",
+			Job::ExportSessionResults { .. } => "export_session_results",
+		}
+	}
+
+	async fn run(self, db: &DatabaseConnection) -> Result<(), String> {
+		match self {
+			Job::SendEmail { to, template } => {
+				send_email(&to, template).await.map_err(|err| err.to_string())
+			}
+			Job::PurgeSoftDeleted => {
+				purge_soft_deleted(db).await.map_err(|err| err.to_string())
+			}
+			Job::ComputeResult { answer_id } => score_test_answer(db, answer_id)
+				.await
+				.map_err(|err| err.to_string()),
+			Job::ExportSessionResults {
+				session_id,
+				requested_by,
+			} => export_session_results(db, session_id, requested_by).await,
+		}
+	}
+}
+
+fn new_job_model(job: &Job) -> Result<JobsActiveModel, DbErr> {
+	let payload =
+		serde_json::to_value(job).map_err(|err| DbErr::Custom(err.to_string()))?;
+
+	Ok(JobsActiveModel {
+		id: Set(Uuid::new_v4()),
+		kind: Set(job.kind().to_string()),
+		payload: Set(payload),
+		status: Set(JOB_STATUS_PENDING.to_string()),
+		attempts: Set(0),
+		max_attempts: Set(JOB_DEFAULT_MAX_ATTEMPTS),
+		run_at: Set(Utc::now()),
+		locked_at: Set(None),
+		last_error: Set(None),
+		created_at: Set(Some(Utc::now())),
+		updated_at: Set(Some(Utc::now())),
+	})
+}
+
+pub async fn enqueue_job(
+	db: &DatabaseConnection,
+	job: Job,
+) -> Result<Uuid, DbErr> {
+	let job = new_job_model(&job)?.insert(db).await?;
+
+	Ok(job.id)
+}
+
+/// Enqueues a job unless one of the same kind is already pending or
+/// running. Only kinds backed by a partial unique index on `app_jobs`
+/// are deduplicated; returns whether a job was added.
+async fn enqueue_unique_job(
+	db: &DatabaseConnection,
+	job: Job,
+) -> Result<bool, DbErr> {
+	let inserted = JobsEntity::insert(new_job_model(&job)?)
+		.on_conflict(OnConflict::new().do_nothing().to_owned())
+		.exec_without_returning(db)
+		.await?;
+
+	Ok(inserted > 0)
+}
+
+pub fn spawn_job_worker() {
+	tokio::spawn(async move {
+		let db = get_db().await;
+		let mut interval = tokio::time::interval(PURGE_SOFT_DELETED_INTERVAL);
+
+		loop {
+			interval.tick().await;
+			if let Err(err) = enqueue_unique_job(&db, Job::PurgeSoftDeleted).await {
+				error!("Failed to schedule soft-delete purge: {}", err);
+			}
+		}
+	});
+
+	tokio::spawn(async move {
+		let db = get_db().await;
+		info!("Background job worker started");
+
+		loop {
+			match claim_next_job(&db).await {
+				Ok(Some(job)) => process_job(&db, job).await,
+				Ok(None) => tokio::time::sleep(JOB_POLL_INTERVAL).await,
+				Err(err) => {
+					error!("Failed to claim background job: {}", err);
+					tokio::time::sleep(JOB_POLL_INTERVAL).await;
+				}
+			}
+		}
+	});
+}
+
+async fn claim_next_job(
+	db: &DatabaseConnection,
+) -> Result<Option<JobsModel>, DbErr> {
+	JobsEntity::find()
+		.from_raw_sql(Statement::from_string(DbBackend::Postgres, CLAIM_JOB_SQL))
+		.one(db)
+		.await
+}
+
+async fn process_job(db: &DatabaseConnection, job: JobsModel) {
+	let result = match serde_json::from_value::<Job>(job.payload.clone()) {
+		Ok(payload) => payload.run(db).await,
+		Err(err) => Err(format!("Invalid job payload: {}", err)),
+	};
+
+	let attempts = job.attempts;
+	let max_attempts = job.max_attempts;
+	let job_id = job.id;
+	let kind = job.kind.clone();
+
+	let mut active_job: JobsActiveModel = job.into();
+	active_job.locked_at = Set(None);
+	active_job.updated_at = Set(Some(Utc::now()));
+
+	match result {
+		Ok(_) => {
+			active_job.status = Set(JOB_STATUS_COMPLETED.to_string());
+			active_job.last_error = Set(None);
+		}
+		Err(err) if attempts >= max_attempts => {
+			error!("Job {} ({}) failed permanently: {}", job_id, kind, err);
+			active_job.status = Set(JOB_STATUS_FAILED.to_string());
+			active_job.last_error = Set(Some(err));
+		}
+		Err(err) => {
+			warn!("Job {} ({}) failed, will retry: {}", job_id, kind, err);
+			active_job.status = Set(JOB_STATUS_PENDING.to_string());
+			active_job.run_at = Set(Utc::now() + job_backoff(attempts));
+			active_job.last_error = Set(Some(err));
+		}
+	}
+
+	if let Err(err) = active_job.update(db).await {
+		error!("Failed to update job {}: {}", job_id, err);
+	}
+}
+
+/// Hard-deletes rows whose soft delete is older than the retention period.
+/// Users go first so that roles they referenced become purgeable; a role
+/// that is still referenced by any user is kept, since removing it would
+/// cascade into those accounts.
+async fn purge_soft_deleted(db: &DatabaseConnection) -> Result<(), DbErr> {
+	let retention = Config::new().soft_delete_retention_days;
+	let cutoff = Utc::now() - ChronoDuration::days(retention);
+
+	let users = UsersEntity::delete_many()
+		.filter(UsersEntity::deleted_before(cutoff))
+		.exec(db)
+		.await?;
+	let tests = TestsEntity::delete_many()
+		.filter(TestsEntity::deleted_before(cutoff))
+		.exec(db)
+		.await?;
+	let sessions = TestSessionsEntity::delete_many()
+		.filter(TestSessionsEntity::deleted_before(cutoff))
+		.exec(db)
+		.await?;
+	let roles = RolesEntity::delete_many()
+		.filter(RolesEntity::deleted_before(cutoff))
+		.filter(
+			RolesColumn::Id.not_in_subquery(
+				Query::select()
+					.column(UsersColumn::RoleId)
+					.from(UsersEntity)
+					.to_owned(),
+			),
+		)
+		.exec(db)
+		.await?;
+
+	info!(
+		"Purged soft-deleted rows: {} users, {} tests, {} sessions, {} roles",
+		users.rows_affected,
+		tests.rows_affected,
+		sessions.rows_affected,
+		roles.rows_affected
+	);
+
+	Ok(())
+}
+
+fn job_backoff(attempts: i32) -> ChronoDuration {
+	let exponent = attempts.clamp(1, 16) as u32 - 1;
+	let secs = JOB_BACKOFF_BASE_SECS
+		.saturating_mul(2i64.pow(exponent))
+		.min(JOB_BACKOFF_MAX_SECS);
+	ChronoDuration::seconds(secs)
+}
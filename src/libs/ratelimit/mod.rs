@@ -0,0 +1,457 @@
+use axum::{
+	body::Body,
+	extract::{ConnectInfo, Request},
+	http::{Extensions, HeaderMap, HeaderValue, StatusCode},
+	response::Response,
+};
+use futures::future::BoxFuture;
+use log::error;
+use std::{
+	collections::HashMap,
+	net::{IpAddr, SocketAddr},
+	sync::{Arc, Mutex, OnceLock},
+	task::{Context, Poll},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tower::{Layer, Service};
+
+use crate::{common_response, decode_access_token, Config};
+
+/// How often idle buckets are swept out of the in-memory backend.
+const MEMORY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Idle redis connections kept for reuse by the redis backend.
+const REDIS_IDLE_CONNECTIONS: usize = 16;
+
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * refill_per_ms)
+local allowed = 0
+if tokens >= 1 then
+	tokens = tokens - 1
+	allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / refill_per_ms))
+return { allowed, tostring(tokens) }
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKey {
+	User,
+	Ip,
+	UserOrIp,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+	pub name: &'static str,
+	pub capacity: u32,
+	pub period: Duration,
+	pub key: RateLimitKey,
+}
+
+impl RateLimitPolicy {
+	pub fn per_minute(name: &'static str, capacity: u32) -> Self {
+		Self {
+			name,
+			capacity,
+			period: Duration::from_secs(60),
+			key: RateLimitKey::UserOrIp,
+		}
+	}
+
+	pub fn per_hour(name: &'static str, capacity: u32) -> Self {
+		Self {
+			name,
+			capacity,
+			period: Duration::from_secs(3600),
+			key: RateLimitKey::UserOrIp,
+		}
+	}
+
+	pub fn keyed_by(mut self, key: RateLimitKey) -> Self {
+		self.key = key;
+		self
+	}
+
+	fn refill_per_sec(&self) -> f64 {
+		self.capacity as f64 / self.period.as_secs_f64()
+	}
+
+	fn identity(&self, req: &Request<Body>) -> String {
+		let user = match self.key {
+			RateLimitKey::Ip => None,
+			_ => user_identity(req.headers()),
+		};
+
+		match user {
+			Some(email) => format!("rate_limit:{}:user:{}", self.name, email),
+			None => format!("rate_limit:{}:ip:{}", self.name, ip_identity(req)),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitDecision {
+	pub allowed: bool,
+	pub remaining: f64,
+}
+
+impl RateLimitDecision {
+	fn apply_headers(&self, headers: &mut HeaderMap, policy: &RateLimitPolicy) {
+		let refill = policy.refill_per_sec();
+		let remaining = self.remaining.max(0.0);
+		let reset =
+			((policy.capacity as f64 - remaining).max(0.0) / refill).ceil() as u64;
+
+		headers.insert("RateLimit-Limit", HeaderValue::from(policy.capacity));
+		headers.insert("RateLimit-Remaining", HeaderValue::from(remaining as u64));
+		headers.insert("RateLimit-Reset", HeaderValue::from(reset));
+
+		if !self.allowed {
+			let retry_after = ((1.0 - remaining) / refill).ceil().max(1.0) as u64;
+			headers.insert("Retry-After", HeaderValue::from(retry_after));
+		}
+	}
+}
+
+pub trait RateLimitBackend: Send + Sync {
+	fn check(&self, policy: &RateLimitPolicy, key: &str) -> RateLimitDecision;
+}
+
+struct MemoryBucket {
+	tokens: f64,
+	last: Instant,
+	period: Duration,
+}
+
+struct MemoryBuckets {
+	buckets: HashMap<String, MemoryBucket>,
+	last_sweep: Instant,
+}
+
+pub struct MemoryRateLimitBackend {
+	state: Mutex<MemoryBuckets>,
+}
+
+impl Default for MemoryRateLimitBackend {
+	fn default() -> Self {
+		Self {
+			state: Mutex::new(MemoryBuckets {
+				buckets: HashMap::new(),
+				last_sweep: Instant::now(),
+			}),
+		}
+	}
+}
+
+impl RateLimitBackend for MemoryRateLimitBackend {
+	fn check(&self, policy: &RateLimitPolicy, key: &str) -> RateLimitDecision {
+		let capacity = policy.capacity as f64;
+		let now = Instant::now();
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+		// A bucket idle for a full period has refilled, so it can be dropped.
+		// Sweeping on an interval keeps each check independent of how many
+		// clients are tracked.
+		if now.duration_since(state.last_sweep) >= MEMORY_SWEEP_INTERVAL {
+			state
+				.buckets
+				.retain(|_, bucket| now.duration_since(bucket.last) < bucket.period);
+			state.last_sweep = now;
+		}
+
+		let bucket = state
+			.buckets
+			.entry(key.to_string())
+			.or_insert(MemoryBucket {
+				tokens: capacity,
+				last: now,
+				period: policy.period,
+			});
+		let elapsed = now.duration_since(bucket.last).as_secs_f64();
+		bucket.tokens =
+			(bucket.tokens + elapsed * policy.refill_per_sec()).min(capacity);
+		bucket.last = now;
+
+		let allowed = bucket.tokens >= 1.0;
+		if allowed {
+			bucket.tokens -= 1.0;
+		}
+
+		RateLimitDecision {
+			allowed,
+			remaining: bucket.tokens,
+		}
+	}
+}
+
+pub struct RedisRateLimitBackend {
+	client: redis::Client,
+	script: redis::Script,
+	idle: Mutex<Vec<redis::Connection>>,
+}
+
+impl RedisRateLimitBackend {
+	pub fn new(redis_hostname: &str) -> Result<Self, redis::RedisError> {
+		let client = redis::Client::open(format!("redis://{}", redis_hostname))?;
+		Ok(Self {
+			client,
+			script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+			idle: Mutex::new(Vec::new()),
+		})
+	}
+
+	fn connection(&self) -> redis::RedisResult<redis::Connection> {
+		let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+		match idle {
+			Some(conn) => Ok(conn),
+			None => self.client.get_connection(),
+		}
+	}
+
+	/// Returns a healthy connection to the pool; broken ones are dropped so
+	/// the next check reconnects.
+	fn release(&self, conn: redis::Connection) {
+		let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+		if idle.len() < REDIS_IDLE_CONNECTIONS {
+			idle.push(conn);
+		}
+	}
+}
+
+impl RateLimitBackend for RedisRateLimitBackend {
+	fn check(&self, policy: &RateLimitPolicy, key: &str) -> RateLimitDecision {
+		let now_ms = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis() as u64;
+
+		let result = self.connection().and_then(|mut conn| {
+			let result = self
+				.script
+				.key(key)
+				.arg(policy.capacity)
+				.arg(policy.refill_per_sec() / 1000.0)
+				.arg(now_ms)
+				.invoke::<(i64, String)>(&mut conn);
+			if result.is_ok() {
+				self.release(conn);
+			}
+			result
+		});
+
+		match result {
+			Ok((allowed, tokens)) => RateLimitDecision {
+				allowed: allowed == 1,
+				remaining: tokens.parse().unwrap_or(0.0),
+			},
+			Err(err) => {
+				// Fail open: an unavailable limiter must not take the API down.
+				error!("Rate limiter redis error: {}", err);
+				RateLimitDecision {
+					allowed: true,
+					remaining: policy.capacity as f64,
+				}
+			}
+		}
+	}
+}
+
+static RATE_LIMIT_BACKEND: OnceLock<Arc<dyn RateLimitBackend>> = OnceLock::new();
+
+pub fn rate_limit_backend() -> Arc<dyn RateLimitBackend> {
+	RATE_LIMIT_BACKEND
+		.get_or_init(|| {
+			let config = Config::new();
+			match config.rate_limit_backend.as_str() {
+				"memory" => Arc::new(MemoryRateLimitBackend::default()),
+				_ => match RedisRateLimitBackend::new(&config.redis_hostname) {
+					Ok(backend) => Arc::new(backend),
+					Err(err) => {
+						error!("Invalid redis URL for rate limiter: {}", err);
+						Arc::new(MemoryRateLimitBackend::default())
+					}
+				},
+			}
+		})
+		.clone()
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+	policy: RateLimitPolicy,
+	backend: Arc<dyn RateLimitBackend>,
+}
+
+impl RateLimitLayer {
+	pub fn new(policy: RateLimitPolicy) -> Self {
+		Self {
+			policy,
+			backend: rate_limit_backend(),
+		}
+	}
+
+	pub fn with_backend(
+		policy: RateLimitPolicy,
+		backend: Arc<dyn RateLimitBackend>,
+	) -> Self {
+		Self { policy, backend }
+	}
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+	type Service = RateLimitService<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		RateLimitService {
+			inner,
+			policy: self.policy.clone(),
+			backend: self.backend.clone(),
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+	inner: S,
+	policy: RateLimitPolicy,
+	backend: Arc<dyn RateLimitBackend>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+	S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = Response;
+	type Error = S::Error;
+	type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	fn call(&mut self, req: Request<Body>) -> Self::Future {
+		let clone = self.inner.clone();
+		let mut inner = std::mem::replace(&mut self.inner, clone);
+		let policy = self.policy.clone();
+		let backend = self.backend.clone();
+
+		Box::pin(async move {
+			let key = policy.identity(&req);
+			let check_policy = policy.clone();
+			let decision = tokio::task::spawn_blocking(move || {
+				backend.check(&check_policy, &key)
+			})
+			.await
+			.unwrap_or(RateLimitDecision {
+				allowed: true,
+				remaining: policy.capacity as f64,
+			});
+
+			if !decision.allowed {
+				let mut response = common_response(
+					StatusCode::TOO_MANY_REQUESTS,
+					"Too many requests, please try again later",
+				);
+				decision.apply_headers(response.headers_mut(), &policy);
+				return Ok(response);
+			}
+
+			let mut response = inner.call(req).await?;
+			decision.apply_headers(response.headers_mut(), &policy);
+			Ok(response)
+		})
+	}
+}
+
+fn user_identity(headers: &HeaderMap) -> Option<String> {
+	let auth_header = headers.get("Authorization")?.to_str().ok()?;
+	let token = auth_header.split_whitespace().nth(1)?;
+	decode_access_token(token)
+		.ok()
+		.map(|data| data.claims.email)
+}
+
+fn ip_identity(req: &Request<Body>) -> String {
+	client_ip(req.headers(), req.extensions())
+}
+
+static TRUSTED_PROXIES: OnceLock<Vec<IpAddr>> = OnceLock::new();
+
+/// Peers allowed to report the client address, from the comma separated
+/// `TRUSTED_PROXIES`.
+fn trusted_proxies() -> &'static [IpAddr] {
+	TRUSTED_PROXIES.get_or_init(|| {
+		Config::new()
+			.trusted_proxies
+			.split(',')
+			.map(str::trim)
+			.filter(|proxy| !proxy.is_empty())
+			.filter_map(|proxy| match proxy.parse() {
+				Ok(ip) => Some(ip),
+				Err(_) => {
+					error!("Ignoring invalid trusted proxy address: {}", proxy);
+					None
+				}
+			})
+			.collect()
+	})
+}
+
+/// Client address. `X-Forwarded-For` and `X-Real-IP` are only honoured when
+/// the socket peer is a trusted proxy, otherwise any client could rotate
+/// them to dodge IP-keyed limits.
+pub fn client_ip(headers: &HeaderMap, extensions: &Extensions) -> String {
+	let Some(peer) = extensions
+		.get::<ConnectInfo<SocketAddr>>()
+		.map(|ConnectInfo(addr)| addr.ip())
+	else {
+		return "unknown".to_string();
+	};
+
+	let proxies = trusted_proxies();
+	if !proxies.contains(&peer) {
+		return peer.to_string();
+	}
+
+	// Each proxy appends the address it received from, so the nearest hop
+	// that is not one of ours is the client.
+	if let Some(forwarded) = headers
+		.get("X-Forwarded-For")
+		.and_then(|value| value.to_str().ok())
+	{
+		let mut client = None;
+		for hop in forwarded.rsplit(',') {
+			match hop.trim().parse::<IpAddr>() {
+				Ok(ip) => {
+					client = Some(ip);
+					if !proxies.contains(&ip) {
+						break;
+					}
+				}
+				Err(_) => break,
+			}
+		}
+		if let Some(client) = client {
+			return client.to_string();
+		}
+	}
+
+	if let Some(real_ip) = headers
+		.get("X-Real-IP")
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.trim().parse::<IpAddr>().ok())
+	{
+		return real_ip.to_string();
+	}
+
+	peer.to_string()
+}
@@ -0,0 +1,250 @@
+use log::{error, info, warn};
+use sea_orm::{DbBackend, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+	sync::{mpsc, OnceLock},
+	time::Duration,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{get_db, Config};
+
+pub const REALTIME_EVENT_TIME_WARNING: &str = "time_warning";
+pub const REALTIME_EVENT_SESSION_EXTENDED: &str = "session_extended";
+pub const REALTIME_EVENT_RESULTS_PUBLISHED: &str = "results_published";
+pub const REALTIME_EVENT_ATTEMPT_PROGRESS: &str = "attempt_progress";
+pub const REALTIME_EVENT_ATTEMPT_AUTO_SUBMITTED: &str = "attempt_auto_submitted";
+
+/// Topic for live attempt progress, watched from the admin console.
+pub const REALTIME_TOPIC_ATTEMPTS: &str = "attempts";
+
+const REALTIME_CHANNEL: &str = "realtime:events";
+const REALTIME_BUFFER: usize = 1024;
+const REALTIME_RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const TIME_WARNING_INTERVAL: Duration = Duration::from_secs(15);
+const TIME_WARNING_TTL_SECS: u64 = 600;
+
+// Scheduled tests of open sessions that end within five minutes.
+const ENDING_SOON_SQL: &str = r#"
+SELECT st.id, st.session_id, st.test_id, st.end_date
+FROM app_sessions_has_tests st
+JOIN app_test_sessions s ON s.id = st.session_id
+WHERE s.deleted_at IS NULL AND s.is_active
+	AND st.end_date > now() AND st.end_date <= now() + interval '5 minutes'
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct EndingSoonRow {
+	id: Uuid,
+	session_id: Uuid,
+	test_id: Uuid,
+	end_date: chrono::DateTime<chrono::Utc>,
+}
+
+/// An event pushed to connected clients. Every API instance publishes to
+/// Redis and relays what it receives there to its own streams, so a client
+/// gets the event whichever instance it is connected to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RealtimeEvent {
+	pub topic: String,
+	pub event: String,
+	pub data: Value,
+}
+
+impl RealtimeEvent {
+	pub fn new(topic: String, event: &str, data: Value) -> Self {
+		Self {
+			topic,
+			event: event.to_string(),
+			data,
+		}
+	}
+}
+
+pub fn user_topic(user_id: Uuid) -> String {
+	format!("user:{}", user_id)
+}
+
+pub fn session_topic(session_id: Uuid) -> String {
+	format!("session:{}", session_id)
+}
+
+fn realtime_sender() -> &'static broadcast::Sender<RealtimeEvent> {
+	static SENDER: OnceLock<broadcast::Sender<RealtimeEvent>> = OnceLock::new();
+	SENDER.get_or_init(|| broadcast::channel(REALTIME_BUFFER).0)
+}
+
+/// Queue drained by the publisher thread, set once the worker is running.
+fn publish_queue() -> &'static OnceLock<mpsc::SyncSender<String>> {
+	static QUEUE: OnceLock<mpsc::SyncSender<String>> = OnceLock::new();
+	&QUEUE
+}
+
+fn redis_client() -> redis::RedisResult<redis::Client> {
+	redis::Client::open(format!("redis://{}", Config::new().redis_hostname))
+}
+
+/// Events relayed to this instance from now on.
+pub fn subscribe_events() -> broadcast::Receiver<RealtimeEvent> {
+	realtime_sender().subscribe()
+}
+
+/// Publishes `event` to every API instance. Delivery is best effort: the
+/// event is queued for the realtime worker, and a full queue or a Redis
+/// failure is logged without failing the request that caused it.
+pub fn publish_event(event: RealtimeEvent) {
+	let payload = match serde_json::to_string(&event) {
+		Ok(payload) => payload,
+		Err(err) => {
+			error!("Failed to encode realtime event: {}", err);
+			return;
+		}
+	};
+
+	let queue = match publish_queue().get() {
+		Some(queue) => queue,
+		None => {
+			warn!("Realtime worker is not running, dropping {}", event.event);
+			return;
+		}
+	};
+
+	if let Err(err) = queue.try_send(payload) {
+		error!("Failed to queue realtime event: {}", err);
+	}
+}
+
+/// Publishes queued events over one Redis connection, relays Redis events
+/// to this instance's streams and sends the five minute warning for
+/// scheduled tests.
+pub fn spawn_realtime_worker() {
+	let (sender, receiver) = mpsc::sync_channel(REALTIME_BUFFER);
+	if publish_queue().set(sender).is_err() {
+		warn!("Realtime worker is already running");
+		return;
+	}
+
+	std::thread::spawn(move || loop {
+		match publish_events(&receiver) {
+			Ok(()) => break,
+			Err(err) => warn!("Realtime publisher disconnected: {}", err),
+		}
+		std::thread::sleep(REALTIME_RECONNECT_DELAY);
+	});
+
+	std::thread::spawn(|| loop {
+		if let Err(err) = relay_events() {
+			warn!("Realtime relay disconnected: {}", err);
+		}
+		std::thread::sleep(REALTIME_RECONNECT_DELAY);
+	});
+
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(TIME_WARNING_INTERVAL);
+
+		loop {
+			interval.tick().await;
+			publish_time_warnings().await;
+		}
+	});
+}
+
+/// Returns once every sender is gone; a Redis error drops the event being
+/// published and hands back to the caller to reconnect.
+fn publish_events(events: &mpsc::Receiver<String>) -> redis::RedisResult<()> {
+	let mut conn = redis_client()?.get_connection()?;
+
+	for payload in events {
+		redis::cmd("PUBLISH")
+			.arg(REALTIME_CHANNEL)
+			.arg(payload)
+			.query::<()>(&mut conn)?;
+	}
+
+	Ok(())
+}
+
+fn relay_events() -> redis::RedisResult<()> {
+	let mut conn = redis_client()?.get_connection()?;
+	let mut pubsub = conn.as_pubsub();
+	pubsub.subscribe(REALTIME_CHANNEL)?;
+	info!("Realtime relay subscribed to {}", REALTIME_CHANNEL);
+
+	loop {
+		let payload: String = pubsub.get_message()?.get_payload()?;
+		match serde_json::from_str::<RealtimeEvent>(&payload) {
+			// Sending only fails when no stream is connected.
+			Ok(event) => {
+				let _ = realtime_sender().send(event);
+			}
+			Err(err) => warn!("Ignoring malformed realtime event: {}", err),
+		}
+	}
+}
+
+async fn publish_time_warnings() {
+	let db = get_db().await;
+
+	let rows = match EndingSoonRow::find_by_statement(Statement::from_string(
+		DbBackend::Postgres,
+		ENDING_SOON_SQL,
+	))
+	.all(&db)
+	.await
+	{
+		Ok(rows) => rows,
+		Err(err) => {
+			error!("Failed to load tests ending soon: {}", err);
+			return;
+		}
+	};
+
+	if rows.is_empty() {
+		return;
+	}
+
+	let mut conn = match redis_client().and_then(|client| client.get_connection()) {
+		Ok(conn) => conn,
+		Err(err) => {
+			error!("Failed to connect to redis for time warnings: {}", err);
+			return;
+		}
+	};
+
+	for row in rows {
+		// Every instance runs this check; the first to claim the key sends
+		// the warning. The end date is part of the key so an extension
+		// warns again before the new deadline.
+		let key = format!(
+			"realtime:time_warning:{}:{}",
+			row.id,
+			row.end_date.timestamp()
+		);
+		let claimed = redis::cmd("SET")
+			.arg(&key)
+			.arg(1)
+			.arg("NX")
+			.arg("EX")
+			.arg(TIME_WARNING_TTL_SECS)
+			.query::<Option<String>>(&mut conn);
+
+		match claimed {
+			Ok(Some(_)) => publish_event(RealtimeEvent::new(
+				session_topic(row.session_id),
+				REALTIME_EVENT_TIME_WARNING,
+				json!({
+					"session_id": row.session_id,
+					"test_id": row.test_id,
+					"end_date": row.end_date.to_string(),
+					"seconds_left": (row.end_date - chrono::Utc::now())
+						.num_seconds()
+						.max(0),
+				}),
+			)),
+			Ok(None) => {}
+			Err(err) => error!("Failed to claim time warning: {}", err),
+		}
+	}
+}
@@ -0,0 +1,390 @@
+use axum::{http::StatusCode, response::Response};
+use chrono::{DateTime, NaiveDate, Utc};
+use log::warn;
+use sea_orm::{
+	sea_query::{Expr, NullOrdering, Query, SimpleExpr},
+	Condition, ConnectionTrait, EntityTrait, ModelTrait, Order, PaginatorTrait,
+	QueryFilter, QueryOrder, QuerySelect, Select, Value,
+};
+use uuid::Uuid;
+
+use crate::{common_response, MetaRequestDto, MetaResponseDto};
+
+const DEFAULT_PER_PAGE: u64 = 10;
+const MAX_PER_PAGE: u64 = 100;
+
+/// How a filter value is parsed before it is bound to its column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldKind {
+	Text,
+	Uuid,
+	Bool,
+	DateTime,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterOp {
+	Eq,
+	In,
+	Gte,
+	Lte,
+}
+
+/// Fields a list endpoint exposes through `filters` and `sort_by`. Anything
+/// else is rejected, so clients can never filter or sort on columns the
+/// endpoint did not opt into.
+pub struct ListSpec<E: EntityTrait> {
+	pub filters: &'static [(&'static str, E::Column, FieldKind)],
+	pub sorts: &'static [(&'static str, E::Column)],
+	pub default_sort: &'static str,
+	/// Unique column used to break sort ties and to anchor cursors.
+	pub id: E::Column,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldFilter {
+	pub field: String,
+	pub op: FilterOp,
+	pub values: Vec<String>,
+	/// Read from `filter_by`/`filter`, which older clients send for fields
+	/// the endpoint never supported, so an unknown field is skipped.
+	pub legacy: bool,
+}
+
+/// Validated form of `MetaRequestDto` shared by the list endpoints.
+#[derive(Clone, Debug)]
+pub struct ListParams {
+	pub page: u64,
+	pub per_page: u64,
+	pub cursor: Option<Uuid>,
+	pub search: String,
+	pub sort_by: Option<String>,
+	pub order: Order,
+	pub filters: Vec<FieldFilter>,
+}
+
+fn bad_request(message: &str) -> Response {
+	common_response(StatusCode::BAD_REQUEST, message)
+}
+
+fn parse_op(op: &str) -> Option<FilterOp> {
+	match op {
+		"eq" => Some(FilterOp::Eq),
+		"in" => Some(FilterOp::In),
+		"gte" => Some(FilterOp::Gte),
+		"lte" => Some(FilterOp::Lte),
+		_ => None,
+	}
+}
+
+/// Accepts RFC 3339 timestamps or plain `YYYY-MM-DD` dates. A plain date
+/// used as an upper bound covers the whole day.
+fn parse_datetime(value: &str, op: FilterOp) -> Option<DateTime<Utc>> {
+	if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+		return Some(datetime.with_timezone(&Utc));
+	}
+
+	let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+	let datetime = match op {
+		FilterOp::Lte => date.and_hms_micro_opt(23, 59, 59, 999_999)?,
+		_ => date.and_hms_opt(0, 0, 0)?,
+	};
+	Some(datetime.and_utc())
+}
+
+fn parse_value(
+	field: &str,
+	kind: FieldKind,
+	op: FilterOp,
+	value: &str,
+) -> Result<Value, Response> {
+	let parsed = match kind {
+		FieldKind::Text => Some(Value::from(value.to_string())),
+		FieldKind::Uuid => Uuid::parse_str(value).ok().map(Value::from),
+		FieldKind::Bool => value.parse::<bool>().ok().map(Value::from),
+		FieldKind::DateTime => parse_datetime(value, op).map(Value::from),
+	};
+
+	parsed.ok_or_else(|| bad_request(&format!("Invalid {} value: {}", field, value)))
+}
+
+impl FieldFilter {
+	/// Parsed values for a filter the endpoint applies itself, such as one
+	/// that needs a join.
+	pub fn parse_values(&self, kind: FieldKind) -> Result<Vec<Value>, Response> {
+		self.values
+			.iter()
+			.map(|value| parse_value(&self.field, kind, self.op, value))
+			.collect()
+	}
+}
+
+impl ListParams {
+	/// `filters` is a `;` separated list of `field:op:value` where `op` is
+	/// `eq`, `in` (values separated by `,`), `gte` or `lte`. The legacy
+	/// `filter_by`/`filter` pair is read as an `eq` filter, and ignored when the
+	/// endpoint does not support the field.
+	pub fn parse(params: MetaRequestDto) -> Result<Self, Response> {
+		let cursor = match params.cursor.filter(|cursor| !cursor.is_empty()) {
+			Some(cursor) => match Uuid::parse_str(&cursor) {
+				Ok(cursor) => Some(cursor),
+				Err(_) => return Err(bad_request("Invalid cursor")),
+			},
+			None => None,
+		};
+
+		let order = match params.order.map(|order| order.to_lowercase()).as_deref() {
+			None | Some("") | Some("desc") => Order::Desc,
+			Some("asc") => Order::Asc,
+			Some(_) => return Err(bad_request("Order must be asc or desc")),
+		};
+
+		let mut filters = Vec::new();
+		for filter in params.filters.unwrap_or_default().split(';') {
+			let filter = filter.trim();
+			if filter.is_empty() {
+				continue;
+			}
+
+			let mut parts = filter.splitn(3, ':');
+			let field = parts.next().unwrap_or_default().trim().to_lowercase();
+			let op = parts
+				.next()
+				.and_then(|op| parse_op(&op.trim().to_lowercase()));
+			let value = parts.next().unwrap_or_default().trim();
+			let op = match op {
+				Some(op) if !field.is_empty() && !value.is_empty() => op,
+				_ => {
+					return Err(bad_request(&format!(
+						"Invalid filter {}, expected field:op:value",
+						filter
+					)))
+				}
+			};
+
+			let values = match op {
+				FilterOp::In => value
+					.split(',')
+					.map(|value| value.trim().to_string())
+					.filter(|value| !value.is_empty())
+					.collect(),
+				_ => vec![value.to_string()],
+			};
+			filters.push(FieldFilter {
+				field,
+				op,
+				values,
+				legacy: false,
+			});
+		}
+
+		let filter = params.filter.unwrap_or_default();
+		let filter_by = params.filter_by.unwrap_or_default().to_lowercase();
+		if !filter.is_empty() && !filter_by.is_empty() {
+			filters.push(FieldFilter {
+				field: filter_by,
+				op: FilterOp::Eq,
+				values: vec![filter],
+				legacy: true,
+			});
+		}
+
+		Ok(Self {
+			page: params.page.unwrap_or(1).max(1),
+			per_page: params
+				.per_page
+				.unwrap_or(DEFAULT_PER_PAGE)
+				.clamp(1, MAX_PER_PAGE),
+			cursor,
+			search: params.search.unwrap_or_default().trim().to_string(),
+			sort_by: params
+				.sort_by
+				.map(|sort_by| sort_by.to_lowercase())
+				.filter(|sort_by| !sort_by.is_empty()),
+			order,
+			filters,
+		})
+	}
+
+	/// Removes the filters on `field` so the endpoint can apply them itself.
+	pub fn take_filters(&mut self, field: &str) -> Vec<FieldFilter> {
+		let (taken, rest) = std::mem::take(&mut self.filters)
+			.into_iter()
+			.partition(|filter| filter.field == field);
+		self.filters = rest;
+		taken
+	}
+
+	/// Applies the filters and sort to `query` and fetches one page, by
+	/// offset or, when a cursor is given, by keyset after the cursor row.
+	/// `rank` orders search results when no `sort_by` is requested.
+	pub async fn fetch<E, C>(
+		&self,
+		db: &C,
+		mut query: Select<E>,
+		spec: &ListSpec<E>,
+		rank: Option<SimpleExpr>,
+	) -> Result<(Vec<E::Model>, MetaResponseDto), Response>
+	where
+		E: EntityTrait,
+		E::Model: Sync,
+		C: ConnectionTrait,
+	{
+		for filter in &self.filters {
+			let Some((_, column, kind)) = spec
+				.filters
+				.iter()
+				.find(|(name, _, _)| *name == filter.field)
+			else {
+				if filter.legacy {
+					warn!("Ignoring unsupported filter_by {}", filter.field);
+					continue;
+				}
+				return Err(bad_request(&format!(
+					"Cannot filter by {}",
+					filter.field
+				)));
+			};
+
+			if matches!(filter.op, FilterOp::Gte | FilterOp::Lte)
+				&& *kind != FieldKind::DateTime
+			{
+				return Err(bad_request(&format!(
+					"{} only supports eq and in",
+					filter.field
+				)));
+			}
+
+			let mut values = filter.parse_values(*kind)?;
+			let column = Expr::col((E::default(), *column));
+			query = query.filter(match filter.op {
+				FilterOp::In => column.is_in(values),
+				FilterOp::Eq => column.eq(values.remove(0)),
+				FilterOp::Gte => column.gte(values.remove(0)),
+				FilterOp::Lte => column.lte(values.remove(0)),
+			});
+		}
+
+		let sort: SimpleExpr = match (&self.sort_by, rank) {
+			(None, Some(rank)) => rank,
+			(sort_by, _) => {
+				let sort_by = sort_by.as_deref().unwrap_or(spec.default_sort);
+				match spec.sorts.iter().find(|(name, _)| *name == sort_by) {
+					Some((_, column)) => Expr::col((E::default(), *column)).into(),
+					None => {
+						return Err(bad_request(&format!(
+							"Cannot sort by {}",
+							sort_by
+						)))
+					}
+				}
+			}
+		};
+
+		let total = query
+			.clone()
+			.paginate(db, self.per_page)
+			.num_items()
+			.await
+			.map_err(|err| {
+				common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+			})?;
+
+		// Rows without a sort value come last in either direction.
+		let id: SimpleExpr = Expr::col((E::default(), spec.id)).into();
+		query = query
+			.order_by_with_nulls(
+				sort.clone(),
+				self.order.clone(),
+				NullOrdering::Last,
+			)
+			.order_by(id.clone(), self.order.clone());
+
+		let rows = match self.cursor {
+			Some(cursor) => {
+				let anchor = |expr: SimpleExpr| {
+					let select = Query::select()
+						.expr(expr)
+						.from(E::default())
+						.and_where(Expr::expr(id.clone()).eq(cursor))
+						.to_owned();
+					SimpleExpr::SubQuery(
+						None,
+						Box::new(select.into_sub_query_statement()),
+					)
+				};
+				let after = |position: Expr, anchor: Expr| match self.order {
+					Order::Asc => position.gt(anchor),
+					_ => position.lt(anchor),
+				};
+				let anchor_sort = anchor(sort.clone());
+				let anchor_id = anchor(id.clone());
+
+				// Rows strictly after the cursor row in (sort, id) order. A
+				// tuple holding NULL never compares, so the rows without a
+				// sort value are matched on their own.
+				let position = Condition::any()
+					.add(
+						Condition::all()
+							.add(Expr::expr(anchor_sort.clone()).is_not_null())
+							.add(
+								Condition::any()
+									.add(Expr::expr(sort.clone()).is_null())
+									.add(after(
+										Expr::tuple([sort.clone(), id.clone()]),
+										Expr::tuple([
+											anchor_sort.clone(),
+											anchor_id.clone(),
+										]),
+									)),
+							),
+					)
+					.add(
+						Condition::all()
+							.add(Expr::expr(anchor_sort).is_null())
+							.add(Expr::expr(sort).is_null())
+							.add(after(Expr::expr(id), Expr::expr(anchor_id))),
+					);
+
+				query
+					.filter(position)
+					.limit(self.per_page + 1)
+					.all(db)
+					.await
+			}
+			None => {
+				query
+					.paginate(db, self.per_page)
+					.fetch_page(self.page - 1)
+					.await
+			}
+		};
+		let mut rows = rows.map_err(|err| {
+			common_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())
+		})?;
+
+		let has_more = match self.cursor {
+			Some(_) => {
+				let has_more = rows.len() as u64 > self.per_page;
+				rows.truncate(self.per_page as usize);
+				has_more
+			}
+			None => self.page * self.per_page < total,
+		};
+		let next_cursor = rows.last().filter(|_| has_more).and_then(|row| match row
+			.get(spec.id)
+		{
+			Value::Uuid(Some(id)) => Some(id.to_string()),
+			_ => None,
+		});
+
+		let meta = MetaResponseDto {
+			page: self.cursor.is_none().then_some(self.page),
+			per_page: Some(self.per_page),
+			total: Some(total),
+			next_cursor,
+		};
+
+		Ok((rows, meta))
+	}
+}
@@ -1,7 +1,13 @@
 pub mod init;
+pub mod list_query;
 pub mod schemas;
+pub mod search;
 pub mod seeders;
+pub mod soft_delete;
 
 pub use init::*;
+pub use list_query::*;
 pub use schemas::*;
+pub use search::*;
 pub use seeders::*;
+pub use soft_delete::*;
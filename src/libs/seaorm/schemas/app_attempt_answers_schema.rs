@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{app_options_schema, app_questions_schema, app_test_attempts_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_attempt_answers")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub attempt_id: Uuid,
+	pub question_id: Uuid,
+	pub option_id: Uuid,
+	pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_test_attempts_schema::Entity",
+		from = "Column::AttemptId",
+		to = "app_test_attempts_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Attempt,
+	#[sea_orm(
+		belongs_to = "app_questions_schema::Entity",
+		from = "Column::QuestionId",
+		to = "app_questions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Question,
+	#[sea_orm(
+		belongs_to = "app_options_schema::Entity",
+		from = "Column::OptionId",
+		to = "app_options_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Option,
+}
+
+impl Related<app_test_attempts_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Attempt.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
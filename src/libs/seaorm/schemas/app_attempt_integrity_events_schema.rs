@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::app_test_attempts_schema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_attempt_integrity_events")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub attempt_id: Uuid,
+	pub kind: String,
+	pub weight: i32,
+	pub detail: Option<Json>,
+	pub occurred_at: DateTimeUtc,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_test_attempts_schema::Entity",
+		from = "Column::AttemptId",
+		to = "app_test_attempts_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Attempt,
+}
+
+impl Related<app_test_attempts_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Attempt.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
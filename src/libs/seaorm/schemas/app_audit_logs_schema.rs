@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_audit_logs")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub actor_id: Option<Uuid>,
+	pub actor_email: Option<String>,
+	pub action: String,
+	pub entity_type: String,
+	pub entity_id: Option<String>,
+	pub before: Option<Json>,
+	pub after: Option<Json>,
+	pub changes: Option<Json>,
+	pub ip_address: Option<String>,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,64 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_orders_schema, app_test_sessions_schema, app_users_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_entitlements")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub session_id: Uuid,
+	pub order_id: Option<Uuid>,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+	#[sea_orm(
+		belongs_to = "app_test_sessions_schema::Entity",
+		from = "Column::SessionId",
+		to = "app_test_sessions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Session,
+	#[sea_orm(
+		belongs_to = "app_orders_schema::Entity",
+		from = "Column::OrderId",
+		to = "app_orders_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "SetNull"
+	)]
+	Order,
+}
+
+impl Related<app_users_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::User.def()
+	}
+}
+
+impl Related<app_test_sessions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Session.def()
+	}
+}
+
+impl Related<app_orders_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Order.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
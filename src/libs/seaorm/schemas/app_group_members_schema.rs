@@ -0,0 +1,50 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_groups_schema, app_users_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_group_members")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub group_id: Uuid,
+	pub user_id: Uuid,
+	pub role: String,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_groups_schema::Entity",
+		from = "Column::GroupId",
+		to = "app_groups_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Group,
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+}
+
+impl Related<app_groups_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Group.def()
+	}
+}
+
+impl Related<app_users_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::User.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
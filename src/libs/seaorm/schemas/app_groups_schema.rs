@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_group_members_schema, app_session_groups_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_groups")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub name: String,
+	pub description: Option<String>,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(has_many = "app_group_members_schema::Entity")]
+	Members,
+	#[sea_orm(has_many = "app_session_groups_schema::Entity")]
+	Sessions,
+}
+
+impl Related<app_group_members_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Members.def()
+	}
+}
+
+impl Related<app_session_groups_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Sessions.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_jobs")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub kind: String,
+	pub payload: Json,
+	pub status: String,
+	pub attempts: i32,
+	pub max_attempts: i32,
+	pub run_at: DateTimeUtc,
+	pub locked_at: Option<DateTimeUtc>,
+	pub last_error: Option<String>,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
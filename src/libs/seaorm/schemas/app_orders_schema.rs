@@ -0,0 +1,59 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_packages_schema, app_users_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_orders")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub package_id: Uuid,
+	pub amount: i64,
+	pub discount_amount: i64,
+	pub voucher_code: Option<String>,
+	pub status: String,
+	pub provider: String,
+	pub provider_reference: Option<String>,
+	pub payment_url: Option<String>,
+	pub paid_at: Option<DateTimeUtc>,
+	pub expires_at: DateTimeUtc,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+	#[sea_orm(
+		belongs_to = "app_packages_schema::Entity",
+		from = "Column::PackageId",
+		to = "app_packages_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Restrict"
+	)]
+	Package,
+}
+
+impl Related<app_users_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::User.def()
+	}
+}
+
+impl Related<app_packages_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Package.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_packages_schema, app_test_sessions_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_package_sessions")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub package_id: Uuid,
+	pub session_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_packages_schema::Entity",
+		from = "Column::PackageId",
+		to = "app_packages_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Package,
+	#[sea_orm(
+		belongs_to = "app_test_sessions_schema::Entity",
+		from = "Column::SessionId",
+		to = "app_test_sessions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Session,
+}
+
+impl Related<app_packages_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Package.def()
+	}
+}
+
+impl Related<app_test_sessions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Session.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_orders_schema, app_package_sessions_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_packages")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub name: String,
+	pub description: Option<String>,
+	pub price: i64,
+	pub is_active: bool,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(has_many = "app_package_sessions_schema::Entity")]
+	Sessions,
+	#[sea_orm(has_many = "app_orders_schema::Entity")]
+	Orders,
+}
+
+impl Related<app_package_sessions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Sessions.def()
+	}
+}
+
+impl Related<app_orders_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Orders.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -6,25 +6,27 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
 	#[sea_orm(primary_key)]
 	pub id: Uuid,
-	pub test_id: Uuid,
 	pub question: String,
 	pub image_url: Option<String>,
 	pub discussion: String,
 	pub discussion_image_url: Option<String>,
+	pub topic: Option<String>,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
+	pub author_id: Option<Uuid>,
+	pub version: i32,
+	pub previous_version_id: Option<Uuid>,
+	pub archived_at: Option<DateTimeUtc>,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
 	#[sea_orm(has_many = "super::app_options_schema::Entity")]
 	Options,
-	#[sea_orm(
-		belongs_to = "super::app_tests_schema::Entity",
-		from = "Column::TestId",
-		to = "super::app_tests_schema::Column::Id",
-		on_update = "Cascade",
-		on_delete = "Cascade"
-	)]
-	Test,
+	#[sea_orm(has_many = "super::app_tests_has_questions_schema::Entity")]
+	TestsHasQuestions,
 }
 
 impl Related<super::app_options_schema::Entity> for Entity {
@@ -33,9 +35,23 @@ impl Related<super::app_options_schema::Entity> for Entity {
 	}
 }
 
+impl Related<super::app_tests_has_questions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::TestsHasQuestions.def()
+	}
+}
+
 impl Related<super::app_tests_schema::Entity> for Entity {
 	fn to() -> RelationDef {
-		Relation::Test.def()
+		super::app_tests_has_questions_schema::Relation::Test.def()
+	}
+
+	fn via() -> Option<RelationDef> {
+		Some(
+			super::app_tests_has_questions_schema::Relation::Question
+				.def()
+				.rev(),
+		)
 	}
 }
 
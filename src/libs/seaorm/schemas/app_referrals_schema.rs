@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_referrals")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub referrer_id: Uuid,
+	pub referred_id: Uuid,
+	pub referral_code: String,
+	pub status: String,
+	pub reward_points: i32,
+	pub activated_at: Option<DateTimeUtc>,
+	pub converted_at: Option<DateTimeUtc>,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "super::app_users_schema::Entity",
+		from = "Column::ReferrerId",
+		to = "super::app_users_schema::Column::Id"
+	)]
+	Referrer,
+	#[sea_orm(
+		belongs_to = "super::app_users_schema::Entity",
+		from = "Column::ReferredId",
+		to = "super::app_users_schema::Column::Id"
+	)]
+	Referred,
+}
+
+// A referral points at two users; resolve the referred student by default.
+impl Related<super::app_users_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Referred.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
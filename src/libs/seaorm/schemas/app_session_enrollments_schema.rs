@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_test_sessions_schema, app_users_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_session_enrollments")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_test_sessions_schema::Entity",
+		from = "Column::SessionId",
+		to = "app_test_sessions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Session,
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+}
+
+impl Related<app_test_sessions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Session.def()
+	}
+}
+
+impl Related<app_users_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::User.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_groups_schema, app_test_sessions_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_session_groups")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub group_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_test_sessions_schema::Entity",
+		from = "Column::SessionId",
+		to = "app_test_sessions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Session,
+	#[sea_orm(
+		belongs_to = "app_groups_schema::Entity",
+		from = "Column::GroupId",
+		to = "app_groups_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Group,
+}
+
+impl Related<app_test_sessions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Session.def()
+	}
+}
+
+impl Related<app_groups_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Group.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_questions_schema, app_test_attempts_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_test_attempt_questions")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub attempt_id: Uuid,
+	pub question_id: Uuid,
+	pub position: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_test_attempts_schema::Entity",
+		from = "Column::AttemptId",
+		to = "app_test_attempts_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Attempt,
+	#[sea_orm(
+		belongs_to = "app_questions_schema::Entity",
+		from = "Column::QuestionId",
+		to = "app_questions_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "NoAction"
+	)]
+	Question,
+}
+
+impl Related<app_test_attempts_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Attempt.def()
+	}
+}
+
+impl Related<app_questions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Question.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_test_attempt_questions_schema, app_tests_schema, app_users_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_test_attempts")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub test_id: Uuid,
+	pub user_id: Uuid,
+	pub session_id: Option<Uuid>,
+	pub created_at: DateTimeUtc,
+	pub auto_submitted_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_tests_schema::Entity",
+		from = "Column::TestId",
+		to = "app_tests_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Test,
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+	#[sea_orm(has_many = "app_test_attempt_questions_schema::Entity")]
+	Questions,
+}
+
+impl Related<app_tests_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Test.def()
+	}
+}
+
+impl Related<app_test_attempt_questions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Questions.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
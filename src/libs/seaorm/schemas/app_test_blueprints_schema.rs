@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::app_tests_schema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_test_blueprints")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub test_id: Uuid,
+	pub topic: String,
+	pub subtopic: Option<String>,
+	pub difficulty: Option<String>,
+	pub question_count: i32,
+	pub position: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_tests_schema::Entity",
+		from = "Column::TestId",
+		to = "app_tests_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Test,
+}
+
+impl Related<app_tests_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Test.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
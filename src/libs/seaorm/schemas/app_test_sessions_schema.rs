@@ -13,8 +13,12 @@ pub struct Model {
 	pub description: String,
 	pub category: String,
 	pub student_type: Option<String>,
+	pub results_release: String,
+	pub results_published_at: Option<DateTimeUtc>,
+	pub enrollment: String,
 	pub created_at: Option<DateTimeUtc>,
 	pub updated_at: Option<DateTimeUtc>,
+	pub deleted_at: Option<DateTimeUtc>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
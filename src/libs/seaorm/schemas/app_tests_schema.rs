@@ -11,8 +11,10 @@ pub struct Model {
 	#[sea_orm(primary_key)]
 	pub id: Uuid,
 	pub test_name: String,
+	pub max_violations: Option<i32>,
 	pub created_at: Option<DateTimeUtc>,
 	pub updated_at: Option<DateTimeUtc>,
+	pub deleted_at: Option<DateTimeUtc>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -22,3 +24,9 @@ impl Related<app_sessions_has_tests_schema::Entity> for Entity {
 		app_sessions_has_tests_schema::Relation::Test.def()
 	}
 }
+
+impl Related<super::app_tests_has_questions_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		super::app_tests_has_questions_schema::Relation::Test.def().rev()
+	}
+}
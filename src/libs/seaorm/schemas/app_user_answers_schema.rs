@@ -9,6 +9,11 @@ pub struct Model {
     pub id: Uuid,
     pub user_id: Uuid,
     pub test_id: Uuid,
+    pub attempt_id: Option<Uuid>,
+    pub session_id: Option<Uuid>,
+    pub created_at: Option<DateTimeUtc>,
+    pub score: Option<f64>,
+    pub scored_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
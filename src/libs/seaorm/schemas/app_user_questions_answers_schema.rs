@@ -27,7 +27,7 @@ pub enum Relation {
         from = "Column::QuestionId",
         to = "crate::app_questions_schema::Column::Id",
         on_update = "Cascade",
-        on_delete = "Cascade"
+        on_delete = "NoAction"
     )]
     Question,
     #[sea_orm(
@@ -35,7 +35,7 @@ pub enum Relation {
         from = "Column::OptionId",
         to = "crate::app_options_schema::Column::Id",
         on_update = "Cascade",
-        on_delete = "Cascade"
+        on_delete = "NoAction"
     )]
     Option,
 }
@@ -23,8 +23,12 @@ pub struct Model {
 	pub is_active: bool,
 	pub is_deleted: bool,
 	pub is_profile_completed: bool,
+	pub totp_secret: Option<String>,
+	pub totp_enabled_at: Option<DateTimeUtc>,
+	pub totp_recovery_codes: Option<Json>,
 	pub created_at: Option<DateTimeUtc>,
 	pub updated_at: Option<DateTimeUtc>,
+	pub deleted_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_packages_schema, app_vouchers_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_voucher_packages")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub voucher_id: Uuid,
+	pub package_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_vouchers_schema::Entity",
+		from = "Column::VoucherId",
+		to = "app_vouchers_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Voucher,
+	#[sea_orm(
+		belongs_to = "app_packages_schema::Entity",
+		from = "Column::PackageId",
+		to = "app_packages_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Package,
+}
+
+impl Related<app_vouchers_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Voucher.def()
+	}
+}
+
+impl Related<app_packages_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Package.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
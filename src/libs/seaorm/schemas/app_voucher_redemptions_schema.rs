@@ -0,0 +1,61 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{app_orders_schema, app_users_schema, app_vouchers_schema};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_voucher_redemptions")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	pub voucher_id: Option<Uuid>,
+	#[sea_orm(unique)]
+	pub order_id: Uuid,
+	pub user_id: Uuid,
+	pub code: String,
+	pub discount: i64,
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "app_vouchers_schema::Entity",
+		from = "Column::VoucherId",
+		to = "app_vouchers_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Restrict"
+	)]
+	Voucher,
+	#[sea_orm(
+		belongs_to = "app_orders_schema::Entity",
+		from = "Column::OrderId",
+		to = "app_orders_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	Order,
+	#[sea_orm(
+		belongs_to = "app_users_schema::Entity",
+		from = "Column::UserId",
+		to = "app_users_schema::Column::Id",
+		on_update = "Cascade",
+		on_delete = "Cascade"
+	)]
+	User,
+}
+
+impl Related<app_vouchers_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Voucher.def()
+	}
+}
+
+impl Related<app_orders_schema::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Order.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_vouchers")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: Uuid,
+	#[sea_orm(unique)]
+	pub code: String,
+	pub description: Option<String>,
+	pub discount_type: String,
+	pub discount_value: i64,
+	pub max_discount: Option<i64>,
+	pub max_uses: Option<i32>,
+	pub max_uses_per_user: Option<i32>,
+	pub starts_at: Option<DateTimeUtc>,
+	pub ends_at: Option<DateTimeUtc>,
+	pub is_active: bool,
+	pub created_at: Option<DateTimeUtc>,
+	pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
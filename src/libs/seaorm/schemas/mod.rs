@@ -1,69 +1,201 @@
+pub mod app_attempt_answers_schema;
+pub mod app_attempt_integrity_events_schema;
+pub mod app_audit_logs_schema;
+pub mod app_entitlements_schema;
+pub mod app_group_members_schema;
+pub mod app_groups_schema;
+pub mod app_jobs_schema;
+pub mod app_notifications_schema;
 pub mod app_options_schema;
+pub mod app_orders_schema;
+pub mod app_package_sessions_schema;
+pub mod app_packages_schema;
 pub mod app_permissions_schema;
 pub mod app_questions_schema;
+pub mod app_referrals_schema;
 pub mod app_roles_permissions_schema;
 pub mod app_roles_schema;
+pub mod app_session_enrollments_schema;
+pub mod app_session_groups_schema;
 pub mod app_sessions_has_tests_schema;
+pub mod app_test_attempt_questions_schema;
+pub mod app_test_attempts_schema;
+pub mod app_test_blueprints_schema;
 pub mod app_test_sessions_schema;
+pub mod app_tests_has_questions_schema;
 pub mod app_tests_schema;
 pub mod app_user_answers_schema;
+pub mod app_user_identities_schema;
 pub mod app_user_questions_answers_schema;
 pub mod app_users_schema;
+pub mod app_voucher_packages_schema;
+pub mod app_voucher_redemptions_schema;
+pub mod app_vouchers_schema;
 
+pub use app_attempt_answers_schema::Column as AttemptAnswersColumn;
+pub use app_attempt_integrity_events_schema::Column as AttemptIntegrityEventsColumn;
+pub use app_audit_logs_schema::Column as AuditLogsColumn;
+pub use app_entitlements_schema::Column as EntitlementsColumn;
+pub use app_group_members_schema::Column as GroupMembersColumn;
+pub use app_groups_schema::Column as GroupsColumn;
+pub use app_jobs_schema::Column as JobsColumn;
+pub use app_notifications_schema::Column as NotificationsColumn;
 pub use app_options_schema::Column as OptionsColumn;
+pub use app_orders_schema::Column as OrdersColumn;
+pub use app_package_sessions_schema::Column as PackageSessionsColumn;
+pub use app_packages_schema::Column as PackagesColumn;
 pub use app_permissions_schema::Column as PermissionsColumn;
 pub use app_questions_schema::Column as QuestionsColumn;
+pub use app_referrals_schema::Column as ReferralsColumn;
 pub use app_roles_permissions_schema::Column as RolesPermissionsColumn;
 pub use app_roles_schema::Column as RolesColumn;
+pub use app_session_enrollments_schema::Column as SessionEnrollmentsColumn;
+pub use app_session_groups_schema::Column as SessionGroupsColumn;
 pub use app_sessions_has_tests_schema::Column as SessionsHasTestsColumn;
+pub use app_test_attempt_questions_schema::Column as TestAttemptQuestionsColumn;
+pub use app_test_attempts_schema::Column as TestAttemptsColumn;
+pub use app_test_blueprints_schema::Column as TestBlueprintsColumn;
 pub use app_test_sessions_schema::Column as TestSessionsColumn;
+pub use app_tests_has_questions_schema::Column as TestsHasQuestionsColumn;
 pub use app_tests_schema::Column as TestsColumn;
 pub use app_user_answers_schema::Column as TestAnswersColumn;
+pub use app_user_identities_schema::Column as UserIdentitiesColumn;
 pub use app_user_questions_answers_schema::Column as TestQuestionsAnswersColumn;
 pub use app_users_schema::Column as UsersColumn;
+pub use app_voucher_packages_schema::Column as VoucherPackagesColumn;
+pub use app_voucher_redemptions_schema::Column as VoucherRedemptionsColumn;
+pub use app_vouchers_schema::Column as VouchersColumn;
 
+pub use app_attempt_answers_schema::Relation as AttemptAnswersRelation;
+pub use app_attempt_integrity_events_schema::Relation as AttemptIntegrityEventsRelation;
+pub use app_audit_logs_schema::Relation as AuditLogsRelation;
+pub use app_entitlements_schema::Relation as EntitlementsRelation;
+pub use app_group_members_schema::Relation as GroupMembersRelation;
+pub use app_groups_schema::Relation as GroupsRelation;
+pub use app_jobs_schema::Relation as JobsRelation;
+pub use app_notifications_schema::Relation as NotificationsRelation;
 pub use app_options_schema::Relation as OptionsRelation;
+pub use app_orders_schema::Relation as OrdersRelation;
+pub use app_package_sessions_schema::Relation as PackageSessionsRelation;
+pub use app_packages_schema::Relation as PackagesRelation;
 pub use app_permissions_schema::Relation as PermissionsRelation;
 pub use app_questions_schema::Relation as QuestionsRelation;
+pub use app_referrals_schema::Relation as ReferralsRelation;
 pub use app_roles_permissions_schema::Relation as RolesPermissionsRelation;
 pub use app_roles_schema::Relation as RolesRelation;
+pub use app_session_enrollments_schema::Relation as SessionEnrollmentsRelation;
+pub use app_session_groups_schema::Relation as SessionGroupsRelation;
 pub use app_sessions_has_tests_schema::Relation as SessionsHasTestsRelation;
+pub use app_test_attempt_questions_schema::Relation as TestAttemptQuestionsRelation;
+pub use app_test_attempts_schema::Relation as TestAttemptsRelation;
+pub use app_test_blueprints_schema::Relation as TestBlueprintsRelation;
+pub use app_tests_has_questions_schema::Relation as TestsHasQuestionsRelation;
 pub use app_user_answers_schema::Relation as TestAnswersRelation;
+pub use app_user_identities_schema::Relation as UserIdentitiesRelation;
 pub use app_user_questions_answers_schema::Relation as TestQuestionsAnswersRelation;
 pub use app_users_schema::Relation as UsersRelation;
+pub use app_voucher_packages_schema::Relation as VoucherPackagesRelation;
+pub use app_voucher_redemptions_schema::Relation as VoucherRedemptionsRelation;
+pub use app_vouchers_schema::Relation as VouchersRelation;
 
+pub use app_attempt_answers_schema::Entity as AttemptAnswersEntity;
+pub use app_attempt_integrity_events_schema::Entity as AttemptIntegrityEventsEntity;
+pub use app_audit_logs_schema::Entity as AuditLogsEntity;
+pub use app_entitlements_schema::Entity as EntitlementsEntity;
+pub use app_group_members_schema::Entity as GroupMembersEntity;
+pub use app_groups_schema::Entity as GroupsEntity;
+pub use app_jobs_schema::Entity as JobsEntity;
+pub use app_notifications_schema::Entity as NotificationsEntity;
 pub use app_options_schema::Entity as OptionsEntity;
+pub use app_orders_schema::Entity as OrdersEntity;
+pub use app_package_sessions_schema::Entity as PackageSessionsEntity;
+pub use app_packages_schema::Entity as PackagesEntity;
 pub use app_permissions_schema::Entity as PermissionsEntity;
 pub use app_questions_schema::Entity as QuestionsEntity;
+pub use app_referrals_schema::Entity as ReferralsEntity;
 pub use app_roles_permissions_schema::Entity as RolesPermissionsEntity;
 pub use app_roles_schema::Entity as RolesEntity;
+pub use app_session_enrollments_schema::Entity as SessionEnrollmentsEntity;
+pub use app_session_groups_schema::Entity as SessionGroupsEntity;
 pub use app_sessions_has_tests_schema::Entity as SessionsHasTestsEntity;
+pub use app_test_attempt_questions_schema::Entity as TestAttemptQuestionsEntity;
+pub use app_test_attempts_schema::Entity as TestAttemptsEntity;
+pub use app_test_blueprints_schema::Entity as TestBlueprintsEntity;
 pub use app_test_sessions_schema::Entity as TestSessionsEntity;
+pub use app_tests_has_questions_schema::Entity as TestsHasQuestionsEntity;
 pub use app_tests_schema::Entity as TestsEntity;
 pub use app_user_answers_schema::Entity as TestAnswersEntity;
+pub use app_user_identities_schema::Entity as UserIdentitiesEntity;
 pub use app_user_questions_answers_schema::Entity as TestQuestionsAnswersEntity;
 pub use app_users_schema::Entity as UsersEntity;
+pub use app_voucher_packages_schema::Entity as VoucherPackagesEntity;
+pub use app_voucher_redemptions_schema::Entity as VoucherRedemptionsEntity;
+pub use app_vouchers_schema::Entity as VouchersEntity;
 
+pub use app_attempt_answers_schema::Model as AttemptAnswersModel;
+pub use app_attempt_integrity_events_schema::Model as AttemptIntegrityEventsModel;
+pub use app_audit_logs_schema::Model as AuditLogsModel;
+pub use app_entitlements_schema::Model as EntitlementsModel;
+pub use app_group_members_schema::Model as GroupMembersModel;
+pub use app_groups_schema::Model as GroupsModel;
+pub use app_jobs_schema::Model as JobsModel;
+pub use app_notifications_schema::Model as NotificationsModel;
 pub use app_options_schema::Model as OptionsModel;
+pub use app_orders_schema::Model as OrdersModel;
+pub use app_package_sessions_schema::Model as PackageSessionsModel;
+pub use app_packages_schema::Model as PackagesModel;
 pub use app_permissions_schema::Model as PermissionsModel;
 pub use app_questions_schema::Model as QuestionsModel;
+pub use app_referrals_schema::Model as ReferralsModel;
 pub use app_roles_permissions_schema::Model as RolesPermissionsModel;
 pub use app_roles_schema::Model as RolesModel;
+pub use app_session_enrollments_schema::Model as SessionEnrollmentsModel;
+pub use app_session_groups_schema::Model as SessionGroupsModel;
 pub use app_sessions_has_tests_schema::Model as SessionsHasTestsModel;
+pub use app_test_attempt_questions_schema::Model as TestAttemptQuestionsModel;
+pub use app_test_attempts_schema::Model as TestAttemptsModel;
+pub use app_test_blueprints_schema::Model as TestBlueprintsModel;
 pub use app_test_sessions_schema::Model as TestSessionsModel;
+pub use app_tests_has_questions_schema::Model as TestsHasQuestionsModel;
 pub use app_tests_schema::Model as TestsModel;
 pub use app_user_answers_schema::Model as TestAnswersModel;
+pub use app_user_identities_schema::Model as UserIdentitiesModel;
 pub use app_user_questions_answers_schema::Model as TestQuestionsAnswersModel;
 pub use app_users_schema::Model as UsersModel;
+pub use app_voucher_packages_schema::Model as VoucherPackagesModel;
+pub use app_voucher_redemptions_schema::Model as VoucherRedemptionsModel;
+pub use app_vouchers_schema::Model as VouchersModel;
 
+pub use app_attempt_answers_schema::ActiveModel as AttemptAnswersActiveModel;
+pub use app_attempt_integrity_events_schema::ActiveModel as AttemptIntegrityEventsActiveModel;
+pub use app_audit_logs_schema::ActiveModel as AuditLogsActiveModel;
+pub use app_entitlements_schema::ActiveModel as EntitlementsActiveModel;
+pub use app_group_members_schema::ActiveModel as GroupMembersActiveModel;
+pub use app_groups_schema::ActiveModel as GroupsActiveModel;
+pub use app_jobs_schema::ActiveModel as JobsActiveModel;
+pub use app_notifications_schema::ActiveModel as NotificationsActiveModel;
 pub use app_options_schema::ActiveModel as OptionsActiveModel;
+pub use app_orders_schema::ActiveModel as OrdersActiveModel;
+pub use app_package_sessions_schema::ActiveModel as PackageSessionsActiveModel;
+pub use app_packages_schema::ActiveModel as PackagesActiveModel;
 pub use app_permissions_schema::ActiveModel as PermissionsActiveModel;
 pub use app_questions_schema::ActiveModel as QuestionsActiveModel;
+pub use app_referrals_schema::ActiveModel as ReferralsActiveModel;
 pub use app_roles_permissions_schema::ActiveModel as RolesPermissionsActiveModel;
 pub use app_roles_schema::ActiveModel as RolesActiveModel;
+pub use app_session_enrollments_schema::ActiveModel as SessionEnrollmentsActiveModel;
+pub use app_session_groups_schema::ActiveModel as SessionGroupsActiveModel;
 pub use app_sessions_has_tests_schema::ActiveModel as SessionsHasTestsActiveModel;
+pub use app_test_attempt_questions_schema::ActiveModel as TestAttemptQuestionsActiveModel;
+pub use app_test_attempts_schema::ActiveModel as TestAttemptsActiveModel;
+pub use app_test_blueprints_schema::ActiveModel as TestBlueprintsActiveModel;
 pub use app_test_sessions_schema::ActiveModel as TestSessionsActiveModel;
+pub use app_tests_has_questions_schema::ActiveModel as TestsHasQuestionsActiveModel;
 pub use app_tests_schema::ActiveModel as TestsActiveModel;
 pub use app_user_answers_schema::ActiveModel as TestAnswersActiveModel;
+pub use app_user_identities_schema::ActiveModel as UserIdentitiesActiveModel;
 pub use app_user_questions_answers_schema::ActiveModel as TestQuestionsAnswersActiveModel;
 pub use app_users_schema::ActiveModel as UsersActiveModel;
+pub use app_voucher_packages_schema::ActiveModel as VoucherPackagesActiveModel;
+pub use app_voucher_redemptions_schema::ActiveModel as VoucherRedemptionsActiveModel;
+pub use app_vouchers_schema::ActiveModel as VouchersActiveModel;
@@ -0,0 +1,74 @@
+use sea_orm::{
+	sea_query::{Expr, SimpleExpr},
+	EntityTrait,
+};
+
+use crate::schemas::{QuestionsEntity, TestsEntity, UsersEntity};
+
+/// Text search configuration the `search_vector` columns are built with.
+///
+/// It is copied from `SEARCH_LANGUAGE` only when the full-text search
+/// migration runs, so changing that variable afterwards does nothing until
+/// `app_search` is dropped and recreated and the `search_vector` columns are
+/// regenerated.
+pub const SEARCH_CONFIG: &str = "app_search";
+
+/// Picks the tsquery function and argument for `search`. Plain words must
+/// all match and the last one matches as a prefix, so `budi san` finds
+/// "Budi Santoso" while it is still being typed. Anything using web search
+/// syntax goes to `websearch_to_tsquery` as is.
+fn search_tsquery(search: &str) -> (&'static str, String) {
+	let uses_syntax = search.contains('"')
+		|| search
+			.split_whitespace()
+			.any(|word| word.starts_with('-') || word.eq_ignore_ascii_case("or"));
+	// Punctuation is dropped the same way emails are split when indexed.
+	let words: Vec<&str> = search
+		.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.collect();
+
+	if uses_syntax || words.is_empty() {
+		return ("websearch_to_tsquery", search.to_string());
+	}
+
+	("to_tsquery", format!("{}:*", words.join(" & ")))
+}
+
+/// Ranked Postgres full-text search over an entity's generated
+/// `search_vector` column. `search` matches its words by prefix, or uses web
+/// search syntax: quoted phrases, `or` and `-word` to exclude.
+pub trait FullTextSearch: EntityTrait {
+	fn search_matches(search: &str) -> SimpleExpr {
+		let (function, query) = search_tsquery(search);
+		Expr::cust_with_values(
+			format!(
+				r#""{}"."search_vector" @@ {}('{}', $1)"#,
+				Self::default().table_name(),
+				function,
+				SEARCH_CONFIG
+			),
+			[query],
+		)
+	}
+
+	/// Relevance of a row to `search`, for ordering matches best first.
+	fn search_rank(search: &str) -> SimpleExpr {
+		let (function, query) = search_tsquery(search);
+		Expr::cust_with_values(
+			format!(
+				r#"ts_rank("{}"."search_vector", {}('{}', $1))"#,
+				Self::default().table_name(),
+				function,
+				SEARCH_CONFIG
+			),
+			[query],
+		)
+	}
+}
+
+impl FullTextSearch for TestsEntity {}
+
+impl FullTextSearch for QuestionsEntity {}
+
+impl FullTextSearch for UsersEntity {}
@@ -10,10 +10,14 @@ use super::schemas::{
 	app_questions_schema as questions,
 	app_roles_permissions_schema as roles_permissions, app_roles_schema as roles,
 	app_sessions_has_tests_schema as sessions_has_tests,
-	app_test_sessions_schema as test_sessions, app_tests_schema as tests,
-	app_users_schema as users,
+	app_test_sessions_schema as test_sessions,
+	app_tests_has_questions_schema as tests_has_questions,
+	app_tests_schema as tests, app_users_schema as users,
+};
+use crate::{
+	hash_password, PermissionsEnum, RolesEnum, RESULTS_RELEASE_IMMEDIATELY,
+	SESSION_ENROLLMENT_OPEN,
 };
-use crate::{hash_password, PermissionsEnum, RolesEnum};
 
 pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 	println!("Seeding Permissions....");
@@ -44,6 +48,38 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 		PermissionsEnum::CreateTests,
 		PermissionsEnum::UpdateTests,
 		PermissionsEnum::DeleteTests,
+		PermissionsEnum::ReadTestAnalytics,
+		PermissionsEnum::ReadListQuestions,
+		PermissionsEnum::ReadDetailQuestions,
+		PermissionsEnum::CreateQuestions,
+		PermissionsEnum::UpdateQuestions,
+		PermissionsEnum::DeleteQuestions,
+		PermissionsEnum::ReadListJobs,
+		PermissionsEnum::ReadDetailJobs,
+		PermissionsEnum::UpdateJobs,
+		PermissionsEnum::DeleteJobs,
+		PermissionsEnum::ReadListReferrals,
+		PermissionsEnum::ReadListAuditLogs,
+		PermissionsEnum::ReadDashboardStats,
+		PermissionsEnum::ReadListGroups,
+		PermissionsEnum::ReadDetailGroups,
+		PermissionsEnum::CreateGroups,
+		PermissionsEnum::UpdateGroups,
+		PermissionsEnum::DeleteGroups,
+		PermissionsEnum::ReadListPackages,
+		PermissionsEnum::ReadDetailPackages,
+		PermissionsEnum::CreatePackages,
+		PermissionsEnum::UpdatePackages,
+		PermissionsEnum::DeletePackages,
+		PermissionsEnum::ReadListOrders,
+		PermissionsEnum::ReadListVouchers,
+		PermissionsEnum::ReadDetailVouchers,
+		PermissionsEnum::CreateVouchers,
+		PermissionsEnum::UpdateVouchers,
+		PermissionsEnum::DeleteVouchers,
+		PermissionsEnum::BroadcastNotifications,
+		PermissionsEnum::MonitorAttempts,
+		PermissionsEnum::ExportSessionResults,
 	]
 	.iter()
 	{
@@ -86,8 +122,10 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 		let role_model = roles::ActiveModel {
 			id: Set(role_id),
 			name: Set(role_str.clone()),
+			require_two_factor: Set(false),
 			created_at: Set(Some(Utc::now())),
 			updated_at: Set(Some(Utc::now())),
+			deleted_at: Set(None),
 		};
 		roles::Entity::insert(role_model).exec(db).await?;
 		println!("Inserted role '{}'.", role_str);
@@ -101,11 +139,12 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 		.expect("Admin role should exist")
 		.1;
 	for (_, perm_id) in permission_ids.iter() {
-		if let Some(_) = roles_permissions::Entity::find()
+		if roles_permissions::Entity::find()
 			.filter(roles_permissions::Column::RoleId.eq(admin_role_id))
 			.filter(roles_permissions::Column::PermissionId.eq(*perm_id))
 			.one(db)
 			.await?
+			.is_some()
 		{
 			println!(
                 "Role-Permission mapping for admin and permission {} already exists. Skipping.",
@@ -145,8 +184,12 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			student_type: Set(Some("tni".to_string())),
 			description: Set("Lorem ipsum".to_string()),
 			is_active: Set(false),
+			results_release: Set(RESULTS_RELEASE_IMMEDIATELY.to_string()),
+			results_published_at: Set(None),
+			enrollment: Set(SESSION_ENROLLMENT_OPEN.to_string()),
 			created_at: Set(Some(Utc::now())),
 			updated_at: Set(Some(Utc::now())),
+			deleted_at: Set(None),
 		};
 		test_sessions::Entity::insert(new_session).exec(db).await?;
 		println!("Inserted session '{}'.", session_name);
@@ -189,11 +232,12 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			"Linking test '{}' with session '{}'",
 			test_name, session.session_name
 		);
-		if let Some(_) = sessions_has_tests::Entity::find()
+		if sessions_has_tests::Entity::find()
 			.filter(sessions_has_tests::Column::SessionId.eq(session.id))
 			.filter(sessions_has_tests::Column::TestId.eq(test_entity.id))
 			.one(db)
 			.await?
+			.is_some()
 		{
 			println!(
 				"Link between session '{}' and test '{}' already exists. Skipping.",
@@ -221,7 +265,6 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 				format!("{} - Question {}", test_name, question_number);
 			let question_entity = if let Some(existing) = questions::Entity::find()
 				.filter(questions::Column::Question.eq(&question_text))
-				.filter(questions::Column::TestId.eq(test_entity.id))
 				.one(db)
 				.await?
 			{
@@ -231,13 +274,21 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 				let question_id = Uuid::new_v4();
 				let new_question = questions::ActiveModel {
 					id: Set(question_id),
-					test_id: Set(test_entity.id),
 					question: Set(question_text.clone()),
 					discussion: Set("Auto-generated question".to_owned()),
 					image_url: Set(Some("https://example.com/image.jpg".to_owned())),
 					discussion_image_url: Set(Some(
 						"https://example.com/image.jpg".to_owned(),
 					)),
+					topic: Set(None),
+					subtopic: Set(None),
+					difficulty: Set(None),
+					author_id: Set(None),
+					version: Set(1),
+					previous_version_id: Set(None),
+					archived_at: Set(None),
+					created_at: Set(Some(Utc::now())),
+					updated_at: Set(Some(Utc::now())),
 				};
 				questions::Entity::insert(new_question).exec(db).await?;
 				println!("Inserted question '{}'.", question_text);
@@ -247,13 +298,32 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 					.expect("Question should be inserted")
 			};
 
+			if tests_has_questions::Entity::find()
+				.filter(tests_has_questions::Column::TestId.eq(test_entity.id))
+				.filter(
+					tests_has_questions::Column::QuestionId.eq(question_entity.id),
+				)
+				.one(db)
+				.await?
+				.is_none()
+			{
+				let link = tests_has_questions::ActiveModel {
+					id: Set(Uuid::new_v4()),
+					test_id: Set(test_entity.id),
+					question_id: Set(question_entity.id),
+					position: Set(question_number - 1),
+				};
+				tests_has_questions::Entity::insert(link).exec(db).await?;
+			}
+
 			for option_number in 1..=4 {
 				let option_text = format!("Option {}", option_number);
-				if let Some(_) = options::Entity::find()
+				if options::Entity::find()
 					.filter(options::Column::QuestionId.eq(question_entity.id))
 					.filter(options::Column::Label.eq(&option_text))
 					.one(db)
 					.await?
+					.is_some()
 				{
 					println!(
 						"Option '{}' for question '{}' already exists. Skipping.",
@@ -279,10 +349,11 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 
 	println!("Seeding Admin User....");
 	let admin_email = "admin@example.com";
-	if let Some(_) = users::Entity::find()
+	if users::Entity::find()
 		.filter(users::Column::Email.eq(admin_email))
 		.one(db)
 		.await?
+		.is_some()
 	{
 		println!(
 			"Admin user with email '{}' already exists. Skipping.",
@@ -298,7 +369,7 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			email: Set(admin_email.to_owned()),
 			email_verified: Set(Some(Utc::now())),
 			referral_code: Set(Some("REF123".to_owned())),
-			referred_by: Set(Some("REF999".to_owned())),
+			referred_by: Set(None),
 			phone_number: Set("1234567890".to_owned()),
 			password: Set(hashed_password.unwrap()),
 			avatar: Set(None),
@@ -310,8 +381,12 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			is_active: Set(true),
 			is_deleted: Set(false),
 			is_profile_completed: Set(true),
+			totp_secret: Set(None),
+			totp_enabled_at: Set(None),
+			totp_recovery_codes: Set(None),
 			created_at: Set(Some(Utc::now())),
 			updated_at: Set(Some(Utc::now())),
+			deleted_at: Set(None),
 		};
 		users::Entity::insert(user).exec(db).await?;
 		println!("Inserted admin user with email '{}'.", admin_email);
@@ -321,10 +396,11 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 	let mut rng = rand::thread_rng();
 	for i in 1..=100 {
 		let email = format!("user{}@example.com", i);
-		if let Some(_) = users::Entity::find()
+		if users::Entity::find()
 			.filter(users::Column::Email.eq(email.clone()))
 			.one(db)
 			.await?
+			.is_some()
 		{
 			println!("User with email '{}' already exists. Skipping.", email);
 			continue;
@@ -339,7 +415,7 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			email: Set(email.clone()),
 			email_verified: Set(Some(Utc::now())),
 			referral_code: Set(Some(format!("REF{}", i))),
-			referred_by: Set(Some("REF999".to_owned())),
+			referred_by: Set(Some("REF123".to_owned())),
 			phone_number: Set(format!("123456789{}", i)),
 			password: Set(hashed_password.unwrap()),
 			avatar: Set(None),
@@ -351,8 +427,12 @@ pub async fn run_seeds(db: &DatabaseConnection) -> Result<(), DbErr> {
 			is_active: Set(true),
 			is_deleted: Set(false),
 			is_profile_completed: Set(true),
+			totp_secret: Set(None),
+			totp_enabled_at: Set(None),
+			totp_recovery_codes: Set(None),
 			created_at: Set(Some(Utc::now())),
 			updated_at: Set(Some(Utc::now())),
+			deleted_at: Set(None),
 		};
 		users::Entity::insert(user).exec(db).await?;
 		println!("Inserted random user with email '{}'.", email);
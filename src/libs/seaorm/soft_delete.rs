@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+	sea_query::{Expr, SimpleExpr},
+	ColumnTrait, EntityTrait, QueryFilter, Select,
+};
+
+use crate::schemas::{
+	RolesColumn, RolesEntity, TestSessionsColumn, TestSessionsEntity, TestsColumn,
+	TestsEntity, UsersColumn, UsersEntity,
+};
+
+/// Query scopes for entities that are soft-deleted through `deleted_at`.
+/// Read paths should start from `find_active` so deleted rows never leak
+/// into listings, lookups or authorization.
+pub trait SoftDelete: EntityTrait {
+	fn deleted_at_column() -> Self::Column;
+
+	fn find_active() -> Select<Self> {
+		Self::find().filter(Self::not_deleted())
+	}
+
+	fn find_deleted() -> Select<Self> {
+		Self::find().filter(Self::deleted_at_column().is_not_null())
+	}
+
+	fn deleted_before(cutoff: DateTime<Utc>) -> SimpleExpr {
+		Self::deleted_at_column().lt(cutoff)
+	}
+
+	/// Condition for queries that join this entity from another one.
+	fn not_deleted() -> SimpleExpr {
+		Expr::col((Self::default(), Self::deleted_at_column())).is_null()
+	}
+}
+
+impl SoftDelete for UsersEntity {
+	fn deleted_at_column() -> Self::Column {
+		UsersColumn::DeletedAt
+	}
+}
+
+impl SoftDelete for TestsEntity {
+	fn deleted_at_column() -> Self::Column {
+		TestsColumn::DeletedAt
+	}
+}
+
+impl SoftDelete for TestSessionsEntity {
+	fn deleted_at_column() -> Self::Column {
+		TestSessionsColumn::DeletedAt
+	}
+}
+
+impl SoftDelete for RolesEntity {
+	fn deleted_at_column() -> Self::Column {
+		RolesColumn::DeletedAt
+	}
+}
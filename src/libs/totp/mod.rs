@@ -0,0 +1,128 @@
+use hmac::{Hmac, Mac};
+use rand::{distributions::Alphanumeric, thread_rng, Rng, RngCore};
+use sha1::Sha1;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha1 = Hmac<Sha1>;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const TOTP_STEP_SECS: u64 = 30;
+const TOTP_DIGITS: u32 = 6;
+const TOTP_ALLOWED_SKEW: i64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub fn generate_totp_secret() -> String {
+	let mut bytes = [0u8; 20];
+	thread_rng().fill_bytes(&mut bytes);
+	base32_encode(&bytes)
+}
+
+pub fn totp_provisioning_uri(secret: &str, account: &str, issuer: &str) -> String {
+	format!(
+		"otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+		urlencoding::encode(issuer),
+		urlencoding::encode(account),
+		secret,
+		urlencoding::encode(issuer),
+		TOTP_DIGITS,
+		TOTP_STEP_SECS,
+	)
+}
+
+/// Returns the time step the code matched, so callers can reject replays.
+pub fn verify_totp(secret: &str, code: &str) -> Option<u64> {
+	let code = code.trim();
+	if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit())
+	{
+		return None;
+	}
+
+	let key = base32_decode(secret)?;
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.ok()?
+		.as_secs();
+	let current_step = (now / TOTP_STEP_SECS) as i64;
+
+	(-TOTP_ALLOWED_SKEW..=TOTP_ALLOWED_SKEW)
+		.map(|offset| (current_step + offset) as u64)
+		.find(|step| {
+			let expected = format!(
+				"{:0width$}",
+				hotp(&key, *step),
+				width = TOTP_DIGITS as usize
+			);
+			expected == code
+		})
+}
+
+pub fn generate_recovery_codes() -> Vec<String> {
+	(0..RECOVERY_CODE_COUNT)
+		.map(|_| {
+			let raw: String = thread_rng()
+				.sample_iter(&Alphanumeric)
+				.take(10)
+				.map(|c| char::from(c).to_ascii_uppercase())
+				.collect();
+			format!("{}-{}", &raw[..5], &raw[5..])
+		})
+		.collect()
+}
+
+fn hotp(key: &[u8], counter: u64) -> u32 {
+	let mut mac =
+		HmacSha1::new_from_slice(key).expect("HMAC accepts keys of any length");
+	mac.update(&counter.to_be_bytes());
+	let digest = mac.finalize().into_bytes();
+
+	let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+	let binary = ((digest[offset] as u32 & 0x7f) << 24)
+		| ((digest[offset + 1] as u32) << 16)
+		| ((digest[offset + 2] as u32) << 8)
+		| (digest[offset + 3] as u32);
+
+	binary % 10u32.pow(TOTP_DIGITS)
+}
+
+fn base32_encode(data: &[u8]) -> String {
+	let mut output = String::new();
+	let mut buffer: u32 = 0;
+	let mut bits = 0;
+
+	for byte in data {
+		buffer = (buffer << 8) | *byte as u32;
+		bits += 8;
+		while bits >= 5 {
+			let index = ((buffer >> (bits - 5)) & 0x1f) as usize;
+			output.push(BASE32_ALPHABET[index] as char);
+			bits -= 5;
+		}
+	}
+
+	if bits > 0 {
+		let index = ((buffer << (5 - bits)) & 0x1f) as usize;
+		output.push(BASE32_ALPHABET[index] as char);
+	}
+
+	output
+}
+
+fn base32_decode(input: &str) -> Option<Vec<u8>> {
+	let mut output = Vec::new();
+	let mut buffer: u32 = 0;
+	let mut bits = 0;
+
+	for c in input.trim_end_matches('=').chars() {
+		let value = BASE32_ALPHABET
+			.iter()
+			.position(|a| *a as char == c.to_ascii_uppercase())? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			output.push(((buffer >> (bits - 8)) & 0xff) as u8);
+			bits -= 8;
+		}
+	}
+
+	Some(output)
+}
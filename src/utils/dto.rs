@@ -16,6 +16,12 @@ pub struct MetaRequestDto {
 	pub order: Option<String>,
 	pub filter: Option<String>,
 	pub filter_by: Option<String>,
+	/// `;` separated `field:op:value` filters, where `op` is `eq`, `in`
+	/// (values separated by `,`), `gte` or `lte`, e.g.
+	/// `role_id:eq:<uuid>;created_at:gte:2025-01-01`.
+	pub filters: Option<String>,
+	/// `next_cursor` of the previous page; replaces `page` when given.
+	pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
@@ -23,4 +29,6 @@ pub struct MetaResponseDto {
 	pub page: Option<u64>,
 	pub per_page: Option<u64>,
 	pub total: Option<u64>,
+	/// Pass as `cursor` to fetch the page after this one.
+	pub next_cursor: Option<String>,
 }
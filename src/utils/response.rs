@@ -20,13 +20,6 @@ pub struct ResponseSuccessListDto<T: Serialize> {
 	pub meta: Option<MetaResponseDto>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct ResponseErrorDto {
-	status: String,
-	message: String,
-	version: String,
-}
-
 pub fn success_response<T: Serialize>(params: ResponseSuccessDto<T>) -> Response {
 	let version = get_version().unwrap();
 	(